@@ -9,8 +9,26 @@ if ! command -v wasm-pack &> /dev/null; then
     curl https://rustwasm.github.io/wasm-pack/installer/init.sh -sSf | sh
 fi
 
-# Build the WASM package
-wasm-pack build --target web --out-dir pkg --release
+# SIMD is enabled in .cargo/config.toml; SCALAR=1 builds without it for
+# browsers that lack wasm SIMD, and combines with THREADS=1
+SIMD="+simd128"
+if [ "$SCALAR" = "1" ]; then
+    echo "Building scalar (no SIMD) variant..."
+    SIMD="-simd128"
+    export RUSTFLAGS="-C target-feature=$SIMD"
+fi
+
+# THREADS=1 builds the multithreaded variant (the "threads" feature), which
+# needs nightly Rust to rebuild std with atomics
+if [ "$THREADS" = "1" ]; then
+    echo "Building multithreaded variant..."
+    export RUSTFLAGS="-C target-feature=+atomics,+bulk-memory,+mutable-globals,$SIMD"
+    rustup run nightly wasm-pack build --target web --out-dir pkg --release -- \
+        --features threads -Z build-std=panic_abort,std
+else
+    # Build the WASM package
+    wasm-pack build --target web --out-dir pkg --release
+fi
 
 # Copy the generated files to the appropriate directory
 mkdir -p ../utils/wasm
@@ -0,0 +1,141 @@
+// Synodic periods, close approaches and distance time series between pairs of
+// bodies, e.g. the next Mars close approach to Earth
+
+use wasm_bindgen::prelude::*;
+use js_sys::Float64Array;
+use serde::{Deserialize, Serialize};
+
+use crate::errors::{check_julian_date, ErrorCode, SimulationError, MAX_JULIAN_DATE};
+use crate::lagrange::body_state_and_mass;
+use crate::resonances::mean_motion;
+use crate::search::golden_section_min;
+use crate::{ecliptic_position, find_orbital_elements, Precision, Vec3};
+
+// Samples per synodic period when bracketing a minimum, and the bounds on the
+// sampling step (days)
+const SAMPLES_PER_SYNODIC_PERIOD: f64 = 200.0;
+const MIN_STEP: f64 = 0.01;
+const MAX_STEP: f64 = 5.0;
+
+// Refinement tolerance (days)
+const TIME_TOLERANCE: f64 = 1e-5;
+
+// The closest approach of two bodies in space
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[wasm_bindgen]
+pub struct CloseApproach {
+    body_a: String,
+    body_b: String,
+    julian_date: f64,
+    distance: f64,
+}
+
+#[wasm_bindgen]
+impl CloseApproach {
+    #[wasm_bindgen(getter)]
+    pub fn body_a(&self) -> String {
+        self.body_a.clone()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn body_b(&self) -> String {
+        self.body_b.clone()
+    }
+
+    // Julian date of minimum distance
+    #[wasm_bindgen(getter)]
+    pub fn julian_date(&self) -> f64 {
+        self.julian_date
+    }
+
+    // Minimum distance (AU)
+    #[wasm_bindgen(getter)]
+    pub fn distance(&self) -> f64 {
+        self.distance
+    }
+}
+
+// Heliocentric ecliptic position (AU) of the Sun, the Moon or any body with
+// orbital elements
+fn heliocentric_position(name: &str, julian_date: f64) -> Option<Vec3> {
+    body_state_and_mass(name, julian_date)
+        .map(|(state, _)| state.position)
+        .or_else(|| find_orbital_elements(name, julian_date, Precision::High).map(|elements| ecliptic_position(&elements, julian_date)))
+}
+
+// Distance (AU) between two bodies
+fn separation(body_a: &str, body_b: &str, julian_date: f64) -> Option<f64> {
+    Some(heliocentric_position(body_a, julian_date)?.distance(&heliocentric_position(body_b, julian_date)?))
+}
+
+// Interval (days) between successive alignments of two bodies orbiting the
+// Sun, from their mean motions
+#[wasm_bindgen]
+pub fn synodic_period(body_a: &str, body_b: &str) -> Result<f64, JsError> {
+    let difference = (mean_motion(body_a)? - mean_motion(body_b)?).abs();
+    if difference == 0.0 {
+        return Err(SimulationError::invalid_argument(&format!(
+            "{} and {} share a mean motion and never realign",
+            body_a, body_b
+        ))
+        .into());
+    }
+    Ok(360.0 / difference)
+}
+
+// The first minimum of the distance between two bodies after a date. Minima
+// recur about once a synodic period, which sets how finely the distance is
+// sampled before golden-section refinement.
+#[wasm_bindgen]
+pub fn next_closest_approach(body_a: &str, body_b: &str, after_jd: f64) -> Result<CloseApproach, JsError> {
+    check_julian_date(after_jd)?;
+    let step = (synodic_period(body_a, body_b)? / SAMPLES_PER_SYNODIC_PERIOD).clamp(MIN_STEP, MAX_STEP);
+    for name in [body_a, body_b] {
+        heliocentric_position(name, after_jd).ok_or_else(|| SimulationError::unknown_body(name))?;
+    }
+    let distance = |jd: f64| separation(body_a, body_b, jd).unwrap_or(f64::INFINITY);
+
+    let (mut previous, mut current) = (distance(after_jd - step), distance(after_jd));
+    let mut jd = after_jd;
+    while jd + step <= MAX_JULIAN_DATE {
+        let next = distance(jd + step);
+        if current < previous && current <= next {
+            let time = golden_section_min(distance, jd - step, jd + step, TIME_TOLERANCE).max(after_jd);
+            return Ok(CloseApproach {
+                body_a: body_a.to_string(),
+                body_b: body_b.to_string(),
+                julian_date: time,
+                distance: distance(time),
+            });
+        }
+        (previous, current) = (current, next);
+        jd += step;
+    }
+    Err(SimulationError::new(
+        ErrorCode::EpochOutOfRange,
+        format!("No close approach of {} and {} before Julian date {}", body_a, body_b, MAX_JULIAN_DATE),
+    )
+    .into())
+}
+
+// Distance (AU) between two bodies at `steps` evenly spaced epochs from
+// jd_start to jd_end inclusive, for distance-versus-time charts
+#[wasm_bindgen]
+pub fn distance_series(
+    body_a: &str,
+    body_b: &str,
+    jd_start: f64,
+    jd_end: f64,
+    steps: u32,
+) -> Result<Float64Array, JsError> {
+    check_julian_date(jd_start)?;
+    check_julian_date(jd_end)?;
+    for name in [body_a, body_b] {
+        heliocentric_position(name, jd_start).ok_or_else(|| SimulationError::unknown_body(name))?;
+    }
+    let step = if steps > 1 { (jd_end - jd_start) / (steps - 1) as f64 } else { 0.0 };
+    let distances: Vec<f64> = (0..steps)
+        .map(|i| separation(body_a, body_b, jd_start + step * i as f64).unwrap_or(f64::NAN))
+        .collect();
+    Ok(Float64Array::from(distances.as_slice()))
+}
@@ -0,0 +1,115 @@
+use wasm_bindgen::prelude::*;
+
+use crate::errors::check_julian_date;
+use crate::{build_planet_data, OrbitalElements, PhysicalData, PlanetData};
+
+// Named asteroid orbital elements, approximate osculating values referred to J2000.0
+pub(crate) static MINOR_PLANET_ELEMENTS: &[(&str, OrbitalElements)] = &[
+    ("Pallas", OrbitalElements {
+        a: 2.773000, e: 0.230200, i: 34.837, omega: 173.085, w: 310.049, m0: 352.950, n: 0.213442,
+    }),
+    ("Juno", OrbitalElements {
+        a: 2.668600, e: 0.256900, i: 12.982, omega: 169.871, w: 248.410, m0: 32.150, n: 0.226089,
+    }),
+    ("Vesta", OrbitalElements {
+        a: 2.361500, e: 0.088700, i: 7.134, omega: 103.851, w: 150.728, m0: 181.400, n: 0.271595,
+    }),
+    ("Astraea", OrbitalElements {
+        a: 2.574300, e: 0.190900, i: 5.367, omega: 141.596, w: 358.908, m0: 285.600, n: 0.238625,
+    }),
+    ("Hebe", OrbitalElements {
+        a: 2.425100, e: 0.201800, i: 14.737, omega: 138.656, w: 239.766, m0: 52.300, n: 0.260981,
+    }),
+    ("Iris", OrbitalElements {
+        a: 2.385700, e: 0.229700, i: 5.523, omega: 259.564, w: 145.271, m0: 116.900, n: 0.267473,
+    }),
+    ("Flora", OrbitalElements {
+        a: 2.201400, e: 0.156600, i: 5.888, omega: 110.889, w: 285.613, m0: 204.500, n: 0.301756,
+    }),
+    ("Metis", OrbitalElements {
+        a: 2.386300, e: 0.123600, i: 5.576, omega: 68.934, w: 6.412, m0: 330.200, n: 0.267372,
+    }),
+    ("Hygiea", OrbitalElements {
+        a: 3.141700, e: 0.112500, i: 3.832, omega: 283.203, w: 312.315, m0: 48.700, n: 0.176993,
+    }),
+    ("Eunomia", OrbitalElements {
+        a: 2.643600, e: 0.186600, i: 11.753, omega: 292.936, w: 98.622, m0: 151.100, n: 0.229303,
+    }),
+    ("Psyche", OrbitalElements {
+        a: 2.924400, e: 0.134000, i: 3.097, omega: 150.038, w: 229.004, m0: 96.300, n: 0.197083,
+    }),
+    ("Euphrosyne", OrbitalElements {
+        a: 3.155300, e: 0.221600, i: 26.275, omega: 31.107, w: 61.489, m0: 262.400, n: 0.175850,
+    }),
+    ("Interamnia", OrbitalElements {
+        a: 3.056200, e: 0.155300, i: 17.312, omega: 280.282, w: 94.927, m0: 13.800, n: 0.184472,
+    }),
+    ("Davida", OrbitalElements {
+        a: 3.164900, e: 0.188400, i: 15.939, omega: 107.626, w: 338.020, m0: 228.600, n: 0.175051,
+    }),
+    ("Sylvia", OrbitalElements {
+        a: 3.489300, e: 0.092000, i: 10.880, omega: 73.016, w: 263.557, m0: 304.100, n: 0.151216,
+    }),
+    ("Eros", OrbitalElements {
+        a: 1.457900, e: 0.222900, i: 10.829, omega: 304.322, w: 178.817, m0: 208.100, n: 0.559903,
+    }),
+    ("Ida", OrbitalElements {
+        a: 2.861700, e: 0.043200, i: 1.132, omega: 324.002, w: 110.048, m0: 71.500, n: 0.203595,
+    }),
+    ("Gaspra", OrbitalElements {
+        a: 2.209800, e: 0.173700, i: 4.102, omega: 253.214, w: 129.532, m0: 313.600, n: 0.300037,
+    }),
+    ("Lutetia", OrbitalElements {
+        a: 2.435200, e: 0.164500, i: 3.064, omega: 80.867, w: 249.997, m0: 185.200, n: 0.259359,
+    }),
+    ("Itokawa", OrbitalElements {
+        a: 1.324100, e: 0.280100, i: 1.621, omega: 69.081, w: 162.818, m0: 110.700, n: 0.646879,
+    }),
+    ("Bennu", OrbitalElements {
+        a: 1.126400, e: 0.203700, i: 6.035, omega: 2.061, w: 66.223, m0: 101.700, n: 0.824451,
+    }),
+    ("Apophis", OrbitalElements {
+        a: 0.922400, e: 0.191400, i: 3.339, omega: 204.043, w: 126.601, m0: 215.500, n: 1.112564,
+    }),
+    ("Ryugu", OrbitalElements {
+        a: 1.189600, e: 0.190200, i: 5.884, omega: 251.589, w: 211.435, m0: 21.500, n: 0.759631,
+    }),
+];
+
+// Named asteroid physical data
+static MINOR_PLANET_DATA: &[PhysicalData] = &[
+    ("Pallas", 0.0402, "#a39e95", 2.773, 84.0, 7.81, 1686.6, 164.0, 0, 0.000034, 2.89),
+    ("Juno", 0.0199, "#b3a58f", 2.669, 51.0, 7.21, 1592.3, 163.0, 0, 0.0000045, 3.2),
+    ("Vesta", 0.0412, "#c2bba8", 2.362, 29.0, 5.34, 1325.5, 210.0, 0, 0.000043, 3.46),
+    ("Astraea", 0.0083, "#a8a092", 2.574, 0.0, 16.8, 1508.6, 165.0, 0, 0.00000044, 3.0),
+    ("Hebe", 0.0146, "#b5a792", 2.425, 0.0, 7.27, 1379.4, 170.0, 0, 0.0000021, 3.8),
+    ("Iris", 0.0157, "#b8ab96", 2.386, 0.0, 7.14, 1345.9, 171.0, 0, 0.0000023, 2.7),
+    ("Flora", 0.0110, "#ad9f8a", 2.201, 0.0, 12.8, 1193.0, 180.0, 0, 0.0000014, 3.0),
+    ("Metis", 0.0137, "#aaa08f", 2.386, 0.0, 5.08, 1346.4, 170.0, 0, 0.0000020, 3.0),
+    ("Hygiea", 0.0341, "#6f6a63", 3.1417, 0.0, 13.83, 2033.9, 164.0, 0, 0.000015, 2.06),
+    ("Eunomia", 0.0212, "#b0a591", 2.644, 0.0, 6.08, 1569.9, 170.0, 0, 0.0000051, 3.5),
+    ("Psyche", 0.0177, "#9fa0a3", 2.924, 95.0, 4.20, 1826.6, 160.0, 0, 0.0000038, 3.9),
+    ("Euphrosyne", 0.0210, "#65615b", 3.155, 0.0, 5.53, 2047.2, 150.0, 1, 0.0000028, 1.67),
+    ("Interamnia", 0.0261, "#6b665f", 3.056, 0.0, 8.73, 1951.5, 160.0, 0, 0.0000064, 2.0),
+    ("Davida", 0.0234, "#68635c", 3.165, 0.0, 5.13, 2056.5, 160.0, 0, 0.0000045, 2.0),
+    ("Sylvia", 0.0213, "#5e5a54", 3.489, 0.0, 5.18, 2380.7, 140.0, 2, 0.0000025, 1.3),
+    ("Eros", 0.00132, "#b39b7d", 1.458, 89.0, 5.27, 643.0, 227.0, 0, 0.0000000011, 2.67),
+    ("Ida", 0.00246, "#a69a88", 2.862, 0.0, 4.63, 1768.2, 200.0, 1, 0.0000000070, 2.6),
+    ("Gaspra", 0.00096, "#a89b87", 2.210, 0.0, 7.04, 1199.8, 181.0, 0, 0.00000000043, 2.7),
+    ("Lutetia", 0.0077, "#8f8a83", 2.435, 96.0, 8.17, 1388.0, 170.0, 0, 0.00000028, 3.4),
+    ("Itokawa", 0.000026, "#9e9080", 1.324, 178.0, 12.13, 556.5, 240.0, 0, 0.0000000000000059, 1.9),
+    ("Bennu", 0.000038, "#3f3c39", 1.126, 178.0, 4.30, 436.6, 270.0, 0, 0.000000000000012, 1.19),
+    ("Apophis", 0.000027, "#9b8f80", 0.922, 0.0, 30.56, 323.6, 270.0, 0, 0.000000000000010, 3.2),
+    ("Ryugu", 0.000070, "#3a3735", 1.190, 172.0, 7.63, 473.9, 250.0, 0, 0.000000000000075, 1.19),
+];
+
+// Calculate positions of named asteroids so they can be labeled individually
+#[wasm_bindgen]
+pub fn minor_planet_positions(julian_date: f64) -> Result<Vec<PlanetData>, JsError> {
+    check_julian_date(julian_date)?;
+    Ok(MINOR_PLANET_ELEMENTS
+        .iter()
+        .zip(MINOR_PLANET_DATA.iter())
+        .map(|((name, elements), data)| build_planet_data(name, elements, julian_date, data))
+        .collect())
+}
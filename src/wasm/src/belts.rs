@@ -0,0 +1,266 @@
+use wasm_bindgen::prelude::*;
+use js_sys::Float32Array;
+
+use crate::batch::OrbitBatch;
+use crate::culling::{CameraView, VisibleSet};
+use crate::errors::{check_julian_date, SimulationError};
+use crate::random::Rng;
+use crate::{
+    deg_to_rad, orbit_point, planet_elements, solve_kepler, to_scene, OrbitalElements,
+    Precision, Vec3, PLANET_DATA, PLANET_ELEMENTS, SUN_EARTH_MASS_RATIO,
+};
+
+// Kirkwood gaps: semi-major axis (AU) of Jupiter mean-motion resonances and
+// the half-width (AU) cleared around each
+static KIRKWOOD_GAPS: &[(f64, f64)] = &[
+    (2.065, 0.015), // 4:1
+    (2.502, 0.020), // 3:1
+    (2.825, 0.015), // 5:2
+    (2.958, 0.012), // 7:3
+    (3.279, 0.025), // 2:1
+];
+
+// Main belt semi-major axis range (AU)
+const BELT_INNER: f64 = 2.1;
+const BELT_OUTER: f64 = 3.3;
+
+// Draw a main-belt semi-major axis, rejecting values inside the Kirkwood gaps
+fn belt_semi_major_axis(rng: &mut Rng) -> f64 {
+    loop {
+        let a = rng.range(BELT_INNER, BELT_OUTER);
+        if KIRKWOOD_GAPS.iter().all(|(center, half_width)| (a - center).abs() > *half_width) {
+            return a;
+        }
+    }
+}
+
+// Write scene positions into a packed f32 buffer
+fn pack_scene(positions: &[Vec3]) -> Float32Array {
+    let mut buffer = Vec::with_capacity(positions.len() * 3);
+    for &ecliptic in positions {
+        let position = to_scene(ecliptic);
+        buffer.extend_from_slice(&[position.x as f32, position.y as f32, position.z as f32]);
+    }
+    Float32Array::from(buffer.as_slice())
+}
+
+// Write the scene positions of a set of orbits into a packed f32 buffer
+pub(crate) fn pack_positions(orbits: &[OrbitalElements], julian_date: f64) -> Float32Array {
+    pack_scene(&OrbitBatch::new(orbits).positions(julian_date))
+}
+
+// Generate main-belt asteroid orbits deterministically from a seed
+pub(crate) fn asteroid_belt_orbits(count: u32, seed: u32) -> Vec<OrbitalElements> {
+    let mut rng = Rng::new(seed as u64);
+    (0..count)
+        .map(|_| {
+            OrbitalElements::from_semi_major_axis(
+                belt_semi_major_axis(&mut rng),
+                rng.rayleigh(0.1).min(0.4),
+                rng.rayleigh(7.0).min(35.0),
+                rng.range(0.0, 360.0),
+                rng.range(0.0, 360.0),
+                rng.range(0.0, 360.0),
+            )
+        })
+        .collect()
+}
+
+// Generate an asteroid belt and return positions at a Julian date as packed
+// [x, y, z, ...] f32 scene coordinates for an instanced particle system
+#[wasm_bindgen]
+pub fn asteroid_belt(count: u32, seed: u32, julian_date: f64) -> Result<Float32Array, JsError> {
+    check_julian_date(julian_date)?;
+    Ok(pack_positions(&asteroid_belt_orbits(count, seed), julian_date))
+}
+
+// A generated belt kept between frames, so that each frame only propagates the
+// orbits instead of regenerating them
+#[wasm_bindgen]
+pub struct BeltParticles {
+    orbits: OrbitBatch,
+}
+
+#[wasm_bindgen]
+impl BeltParticles {
+    // Main-belt asteroids, the same population as asteroid_belt
+    pub fn asteroid_belt(count: u32, seed: u32) -> BeltParticles {
+        BeltParticles { orbits: OrbitBatch::new(&asteroid_belt_orbits(count, seed)) }
+    }
+
+    // Trans-Neptunian objects, the same population as kuiper_belt
+    pub fn kuiper_belt(count: u32, seed: u32) -> BeltParticles {
+        BeltParticles { orbits: OrbitBatch::new(&kuiper_belt_orbits(count, seed)) }
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn count(&self) -> usize {
+        self.orbits.len()
+    }
+
+    // Packed [x, y, z, ...] f32 scene positions at a Julian date
+    pub fn positions(&self, julian_date: f64) -> Result<Float32Array, JsError> {
+        check_julian_date(julian_date)?;
+        Ok(pack_scene(&self.orbits.positions(julian_date)))
+    }
+
+    // The particles a camera can see at a Julian date, treating each as a
+    // sphere of particle_radius scene units. When the belt's bounding sphere
+    // is off-screen nothing is propagated.
+    pub fn visible(&self, julian_date: f64, camera: &CameraView, particle_radius: f64) -> Result<VisibleSet, JsError> {
+        check_julian_date(julian_date)?;
+        let extent = to_scene(Vec3::new(self.orbits.max_aphelion(), 0.0, 0.0)).length();
+        if !camera.is_visible(&Vec3::new(0.0, 0.0, 0.0), extent + particle_radius) {
+            return Ok(VisibleSet::default());
+        }
+        let positions = self.scene_positions(julian_date);
+        Ok(VisibleSet::cull(camera, positions.into_iter().map(|position| (position, particle_radius))))
+    }
+
+    // Packed scene positions of one particle at `steps` evenly spaced dates
+    // from start to end inclusive, for a trail; fails for an index out of range
+    pub fn track(&self, index: usize, start_date: f64, end_date: f64, steps: u32) -> Result<Float32Array, JsError> {
+        check_julian_date(start_date)?;
+        check_julian_date(end_date)?;
+        if index >= self.orbits.len() {
+            let message = format!("No particle {} in a belt of {}", index, self.orbits.len());
+            return Err(SimulationError::invalid_argument(&message).into());
+        }
+        let step = (end_date - start_date) / (steps.max(2) - 1) as f64;
+        let dates: Vec<f64> = (0..steps).map(|k| start_date + step * k as f64).collect();
+        Ok(pack_scene(&self.orbits.track(index, &dates)))
+    }
+}
+
+impl BeltParticles {
+    pub(crate) fn scene_positions(&self, julian_date: f64) -> Vec<Vec3> {
+        self.orbits.positions(julian_date).into_iter().map(to_scene).collect()
+    }
+}
+
+// Trans-Neptunian population fractions: cold classical, hot classical,
+// 3:2 resonant (plutinos), remainder scattered disk
+const COLD_CLASSICAL_FRACTION: f64 = 0.35;
+const HOT_CLASSICAL_FRACTION: f64 = 0.25;
+const PLUTINO_FRACTION: f64 = 0.2;
+
+// Semi-major axis of the 3:2 Neptune mean-motion resonance (AU)
+const PLUTINO_SEMI_MAJOR_AXIS: f64 = 39.4;
+
+// Draw (a, e, i) for one trans-Neptunian object
+fn trans_neptunian_shape(rng: &mut Rng) -> (f64, f64, f64) {
+    let population = rng.next_f64();
+    if population < COLD_CLASSICAL_FRACTION {
+        // Dynamically cold, low-inclination core between 42 and 47 AU
+        (rng.range(42.0, 47.0), rng.rayleigh(0.04).min(0.12), rng.rayleigh(2.0).min(10.0))
+    } else if population < COLD_CLASSICAL_FRACTION + HOT_CLASSICAL_FRACTION {
+        (rng.range(40.0, 48.0), rng.rayleigh(0.08).min(0.2), rng.rayleigh(12.0).min(40.0))
+    } else if population < COLD_CLASSICAL_FRACTION + HOT_CLASSICAL_FRACTION + PLUTINO_FRACTION {
+        (
+            PLUTINO_SEMI_MAJOR_AXIS + rng.range(-0.2, 0.2),
+            rng.range(0.05, 0.3),
+            rng.rayleigh(10.0).min(40.0),
+        )
+    } else {
+        // Scattered disk: perihelion near Neptune, aphelion far beyond
+        let perihelion = rng.range(30.0, 38.0);
+        // Log-uniform semi-major axis between 50 and 200 AU
+        let a = 50.0 * 4.0_f64.powf(rng.next_f64());
+        (a, 1.0 - perihelion / a, rng.rayleigh(15.0).min(60.0))
+    }
+}
+
+// Generate Kuiper belt and scattered disk orbits deterministically from a seed
+pub(crate) fn kuiper_belt_orbits(count: u32, seed: u32) -> Vec<OrbitalElements> {
+    let mut rng = Rng::new(seed as u64);
+    (0..count)
+        .map(|_| {
+            let (a, e, i) = trans_neptunian_shape(&mut rng);
+            OrbitalElements::from_semi_major_axis(
+                a,
+                e,
+                i,
+                rng.range(0.0, 360.0),
+                rng.range(0.0, 360.0),
+                rng.range(0.0, 360.0),
+            )
+        })
+        .collect()
+}
+
+// Generate the trans-Neptunian population (classical belt, plutinos and
+// scattered disk) and return packed [x, y, z, ...] f32 scene positions
+#[wasm_bindgen]
+pub fn kuiper_belt(count: u32, seed: u32, julian_date: f64) -> Result<Float32Array, JsError> {
+    check_julian_date(julian_date)?;
+    Ok(pack_positions(&kuiper_belt_orbits(count, seed), julian_date))
+}
+
+// A Trojan's fixed shape and its tadpole libration about L4 or L5
+struct Trojan {
+    // +60° for L4 (leading), -60° for L5 (trailing)
+    offset: f64,
+    // Libration amplitude (degrees) and phase (radians)
+    amplitude: f64,
+    phase: f64,
+    a_scale: f64,
+    e: f64,
+    i: f64,
+    omega: f64,
+    perihelion_longitude: f64,
+}
+
+// Generate Trojans for both swarms, alternating leading and trailing
+fn trojan_population(count: u32, seed: u32) -> Vec<Trojan> {
+    let mut rng = Rng::new(seed as u64);
+    (0..count)
+        .map(|index| Trojan {
+            offset: if index % 2 == 0 { 60.0 } else { -60.0 },
+            amplitude: rng.rayleigh(10.0).min(35.0),
+            phase: rng.range(0.0, 2.0 * std::f64::consts::PI),
+            a_scale: 1.0 + rng.range(-0.005, 0.005),
+            e: rng.rayleigh(0.05).min(0.2),
+            i: rng.rayleigh(10.0).min(40.0),
+            omega: rng.range(0.0, 360.0),
+            perihelion_longitude: rng.range(0.0, 360.0),
+        })
+        .collect()
+}
+
+// Generate Trojan swarms librating about a planet's L4 and L5 points (e.g.
+// "Jupiter", "Neptune" or "Mars") and return packed [x, y, z, ...] f32 scene
+// positions at a Julian date; fails for an unknown planet. Even indices are
+// in the leading swarm, odd indices in the trailing one.
+#[wasm_bindgen]
+pub fn trojan_swarms(planet: &str, count: u32, seed: u32, julian_date: f64) -> Result<Float32Array, JsError> {
+    check_julian_date(julian_date)?;
+    let index = PLANET_ELEMENTS
+        .iter()
+        .position(|(name, _)| *name == planet)
+        .ok_or_else(|| SimulationError::unknown_body(planet))?;
+    let elements = planet_elements(index, julian_date, Precision::Standard);
+    let days = julian_date - 2451545.0;
+    let mean_longitude = elements.omega + elements.w + elements.m0 + elements.n * days;
+
+    // Small-amplitude tadpole libration period, T_lib = T / sqrt(27 mu / 4)
+    let mu = PLANET_DATA[index].9 / SUN_EARTH_MASS_RATIO;
+    let libration_period = 360.0 / elements.n / (27.0 * mu / 4.0).sqrt();
+    let libration_angle = 2.0 * std::f64::consts::PI * days / libration_period;
+
+    let mut buffer = Vec::with_capacity(count as usize * 3);
+    for trojan in trojan_population(count, seed) {
+        let longitude = mean_longitude + trojan.offset + trojan.amplitude * (libration_angle + trojan.phase).sin();
+        let orbit = OrbitalElements::from_semi_major_axis(
+            elements.a * trojan.a_scale,
+            trojan.e,
+            trojan.i,
+            trojan.omega,
+            trojan.perihelion_longitude - trojan.omega,
+            0.0,
+        );
+        let mean_anomaly = deg_to_rad(longitude - trojan.perihelion_longitude);
+        let position = to_scene(orbit_point(&orbit, solve_kepler(mean_anomaly, trojan.e)));
+        buffer.extend_from_slice(&[position.x as f32, position.y as f32, position.z as f32]);
+    }
+    Ok(Float32Array::from(buffer.as_slice()))
+}
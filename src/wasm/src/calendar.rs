@@ -0,0 +1,256 @@
+// Calendar date conversions
+//
+// Years use astronomical numbering (year 0 is 1 BCE, year -43 is 44 BCE) and
+// all arithmetic is integer, so any finite Julian date within about two
+// billion years converts, well beyond the range of JavaScript's Date.
+
+use wasm_bindgen::prelude::*;
+use js_sys::Int32Array;
+
+use crate::errors::{check_finite_date, ErrorCode, SimulationError};
+
+// Day number of the first Gregorian date, 1582-10-15 (the day after Julian 1582-10-04)
+const GREGORIAN_REFORM: i64 = 2299161;
+
+const MILLISECONDS_PER_DAY: i64 = 86_400_000;
+
+// Largest magnitude of a Julian date whose year still fits a CalendarDate
+const MAX_CALENDAR_JULIAN_DATE: f64 = 7.8e11;
+
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CalendarSystem {
+    // Proleptic Gregorian calendar for all dates
+    Gregorian = 0,
+    // Proleptic Julian calendar for all dates
+    Julian = 1,
+    // Julian calendar before the 1582 reform and Gregorian from 1582-10-15 on,
+    // as historical dates are usually recorded
+    Historical = 2,
+}
+
+// Julian day number (the day beginning at noon) of a calendar date. Months
+// outside 1-12 and days past the end of a month roll over.
+fn day_number(year: i64, month: i64, day: i64, calendar: CalendarSystem) -> i64 {
+    let year = year + (month - 1).div_euclid(12);
+    let month = (month - 1).rem_euclid(12) + 1;
+    // Count from March so the leap day falls at the end of the year
+    let a = (14 - month) / 12;
+    let y = year + 4800 - a;
+    let m = month + 12 * a - 3;
+    let days = day + (153 * m + 2) / 5 + 365 * y + y.div_euclid(4);
+    let gregorian = days - y.div_euclid(100) + y.div_euclid(400) - 32045;
+    match calendar {
+        CalendarSystem::Gregorian => gregorian,
+        CalendarSystem::Julian => days - 32083,
+        CalendarSystem::Historical if gregorian >= GREGORIAN_REFORM => gregorian,
+        CalendarSystem::Historical => days - 32083,
+    }
+}
+
+// Calendar date (year, month, day) of a Julian day number
+fn date_from_day_number(number: i64, calendar: CalendarSystem) -> (i64, i64, i64) {
+    let gregorian = match calendar {
+        CalendarSystem::Gregorian => true,
+        CalendarSystem::Julian => false,
+        CalendarSystem::Historical => number >= GREGORIAN_REFORM,
+    };
+    let (b, c) = if gregorian {
+        let a = number + 32044;
+        let b = (4 * a + 3).div_euclid(146097);
+        (b, a - (146097 * b).div_euclid(4))
+    } else {
+        (0, number + 32082)
+    };
+    let d = (4 * c + 3).div_euclid(1461);
+    let e = c - (1461 * d).div_euclid(4);
+    let m = (5 * e + 2) / 153;
+    let day = e - (153 * m + 2) / 5 + 1;
+    let month = m + 3 - 12 * (m / 10);
+    let year = 100 * b + d - 4800 + m / 10;
+    (year, month, day)
+}
+
+// Reject non-finite dates, and dates so remote their year overflows
+fn check_calendar_date(julian_date: f64) -> Result<(), SimulationError> {
+    check_finite_date(julian_date)?;
+    if julian_date.abs() > MAX_CALENDAR_JULIAN_DATE {
+        return Err(SimulationError::new(
+            ErrorCode::EpochOutOfRange,
+            format!("Julian date {} is beyond the calendar's {} day range", julian_date, MAX_CALENDAR_JULIAN_DATE),
+        ));
+    }
+    Ok(())
+}
+
+// Julian date at 0h UT of a Gregorian calendar date; day may be fractional
+pub(crate) fn calendar_to_julian_date(year: i32, month: u32, day: f64) -> f64 {
+    julian_date_from_calendar(year, month as i32, day, CalendarSystem::Gregorian)
+}
+
+// A calendar date and time of day
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CalendarDate {
+    year: i32,
+    month: u32,
+    day: u32,
+    hour: u32,
+    minute: u32,
+    second: f64,
+    day_of_year: u32,
+    julian_date: f64,
+    calendar: CalendarSystem,
+}
+
+#[wasm_bindgen]
+impl CalendarDate {
+    // Astronomical year: 0 is 1 BCE
+    #[wasm_bindgen(getter)]
+    pub fn year(&self) -> i32 {
+        self.year
+    }
+
+    // Month, 1-12
+    #[wasm_bindgen(getter)]
+    pub fn month(&self) -> u32 {
+        self.month
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn day(&self) -> u32 {
+        self.day
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn hour(&self) -> u32 {
+        self.hour
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn minute(&self) -> u32 {
+        self.minute
+    }
+
+    // Seconds including the fraction, to the millisecond
+    #[wasm_bindgen(getter)]
+    pub fn second(&self) -> f64 {
+        self.second
+    }
+
+    // Day of the week, 0 = Sunday as in JavaScript's Date.getDay
+    #[wasm_bindgen(getter)]
+    pub fn day_of_week(&self) -> u32 {
+        weekday(self.julian_date)
+    }
+
+    // Day of the year, 1 on January 1
+    #[wasm_bindgen(getter)]
+    pub fn day_of_year(&self) -> u32 {
+        self.day_of_year
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn julian_date(&self) -> f64 {
+        self.julian_date
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn calendar(&self) -> CalendarSystem {
+        self.calendar
+    }
+
+    // "BCE"/"CE" era label and the year within it (44 BCE for year -43)
+    #[wasm_bindgen(getter)]
+    pub fn era(&self) -> String {
+        if self.year > 0 {
+            format!("{} CE", self.year)
+        } else {
+            format!("{} BCE", 1 - self.year)
+        }
+    }
+
+    // ISO 8601 representation, with a signed six-digit year outside 0000-9999
+    pub fn to_iso_string(&self) -> String {
+        let year = if (0..=9999).contains(&self.year) {
+            format!("{:04}", self.year)
+        } else {
+            format!("{}{:06}", if self.year < 0 { '-' } else { '+' }, self.year.unsigned_abs())
+        };
+        format!(
+            "{}-{:02}-{:02}T{:02}:{:02}:{:06.3}",
+            year, self.month, self.day, self.hour, self.minute, self.second
+        )
+    }
+}
+
+// Julian date of a calendar date in the given calendar; day may be fractional
+// (1.5 is noon on the 1st). Years are astronomical, so 44 BCE is year -43.
+#[wasm_bindgen]
+pub fn julian_date_from_calendar(year: i32, month: i32, day: f64, calendar: CalendarSystem) -> f64 {
+    let whole = day.floor();
+    day_number(year as i64, month as i64, whole as i64, calendar) as f64 - 0.5 + (day - whole)
+}
+
+// Calendar date and time of a Julian date in the given calendar, to the millisecond
+#[wasm_bindgen]
+pub fn calendar_from_julian_date(julian_date: f64, calendar: CalendarSystem) -> Result<CalendarDate, JsError> {
+    check_calendar_date(julian_date)?;
+    // Civil days start at midnight, half a day before the Julian day number
+    let shifted = julian_date + 0.5;
+    let mut number = shifted.floor() as i64;
+    let mut milliseconds = ((shifted - shifted.floor()) * MILLISECONDS_PER_DAY as f64).round() as i64;
+    if milliseconds >= MILLISECONDS_PER_DAY {
+        number += 1;
+        milliseconds -= MILLISECONDS_PER_DAY;
+    }
+    let (year, month, day) = date_from_day_number(number, calendar);
+    let day_of_year = number - day_number(year, 1, 1, calendar) + 1;
+    Ok(CalendarDate {
+        year: year as i32,
+        month: month as u32,
+        day: day as u32,
+        hour: (milliseconds / 3_600_000) as u32,
+        minute: (milliseconds / 60_000 % 60) as u32,
+        second: (milliseconds % 60_000) as f64 / 1000.0,
+        day_of_year: day_of_year as u32,
+        julian_date,
+        calendar,
+    })
+}
+
+// Day of the week at a Julian date (UT), 0 = Sunday through 6 = Saturday. The
+// weekday cycle is the same in every calendar.
+#[wasm_bindgen]
+pub fn day_of_week(julian_date: f64) -> Result<u32, JsError> {
+    check_finite_date(julian_date)?;
+    Ok(weekday(julian_date))
+}
+
+// Day of the week, 0 = Sunday, for a date already known to be valid
+fn weekday(julian_date: f64) -> u32 {
+    ((julian_date + 1.5).floor() as i64).rem_euclid(7) as u32
+}
+
+// ISO 8601 week date of a Julian date as [year, week, weekday], with weekday
+// 1 = Monday through 7 = Sunday. Weeks belong to the year holding their
+// Thursday, so early January can fall in the previous year's last week.
+#[wasm_bindgen]
+pub fn iso_week(julian_date: f64) -> Result<Int32Array, JsError> {
+    check_calendar_date(julian_date)?;
+    let number = (julian_date + 0.5).floor() as i64;
+    let weekday = number.rem_euclid(7) + 1;
+    let thursday = number - weekday + 4;
+    let (year, _, _) = date_from_day_number(thursday, CalendarSystem::Gregorian);
+    let week = (thursday - day_number(year, 1, 1, CalendarSystem::Gregorian)) / 7 + 1;
+    Ok(Int32Array::from(&[year as i32, week as i32, weekday as i32][..]))
+}
+
+// Whether a year has a February 29 in the given calendar (the reform year
+// 1582 counts as Julian in the historical calendar)
+#[wasm_bindgen]
+pub fn is_leap_year(year: i32, calendar: CalendarSystem) -> bool {
+    let year = year as i64;
+    let march_first = day_number(year, 3, 1, calendar);
+    march_first - day_number(year, 2, 28, calendar) == 2
+}
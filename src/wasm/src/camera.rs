@@ -0,0 +1,168 @@
+// Keyframed camera paths for guided tours: positions and look-at points follow
+// Catmull-Rom splines, orientation is slerped between keyframes and the
+// simulation epoch is interpolated alongside
+
+use wasm_bindgen::prelude::*;
+use js_sys::Float64Array;
+
+use crate::errors::{check_julian_date, SimulationError};
+use crate::math::Quat;
+use crate::Vec3;
+
+#[derive(Debug, Clone, Copy)]
+struct Keyframe {
+    time: f64,
+    position: Vec3,
+    look_at: Vec3,
+    julian_date: f64,
+    quaternion: Quat,
+}
+
+// Catmull-Rom tangent at a keyframe for uneven keyframe spacing, one-sided at
+// the ends of the path
+fn tangent(keyframes: &[Keyframe], i: usize, point: fn(&Keyframe) -> Vec3) -> Vec3 {
+    let before = &keyframes[i.saturating_sub(1)];
+    let after = &keyframes[(i + 1).min(keyframes.len() - 1)];
+    let span = after.time - before.time;
+    if span <= 0.0 {
+        return Vec3::new(0.0, 0.0, 0.0);
+    }
+    point(after).sub(&point(before)).scale(1.0 / span)
+}
+
+// Cubic Hermite interpolation between keyframes i and i + 1 at fraction u
+fn hermite(keyframes: &[Keyframe], i: usize, u: f64, point: fn(&Keyframe) -> Vec3) -> Vec3 {
+    let (a, b) = (&keyframes[i], &keyframes[i + 1]);
+    let span = b.time - a.time;
+    let (u2, u3) = (u * u, u * u * u);
+    point(a)
+        .scale(2.0 * u3 - 3.0 * u2 + 1.0)
+        .add(&tangent(keyframes, i, point).scale((u3 - 2.0 * u2 + u) * span))
+        .add(&point(b).scale(-2.0 * u3 + 3.0 * u2))
+        .add(&tangent(keyframes, i + 1, point).scale((u3 - u2) * span))
+}
+
+// Camera state at a moment of a tour
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy)]
+pub struct CameraTransform {
+    position: Vec3,
+    look_at: Vec3,
+    quaternion: Quat,
+    julian_date: f64,
+}
+
+#[wasm_bindgen]
+impl CameraTransform {
+    #[wasm_bindgen(getter)]
+    pub fn position(&self) -> Vec3 {
+        self.position
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn look_at(&self) -> Vec3 {
+        self.look_at
+    }
+
+    // Camera rotation [x, y, z, w], ready to copy into a Three.js camera
+    #[wasm_bindgen(getter)]
+    pub fn quaternion(&self) -> Float64Array {
+        self.quaternion.to_array()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn rotation(&self) -> Quat {
+        self.quaternion
+    }
+
+    // Simulation epoch to show at this moment
+    #[wasm_bindgen(getter)]
+    pub fn julian_date(&self) -> f64 {
+        self.julian_date
+    }
+}
+
+// A camera tour through keyframes placed on a timeline in seconds
+#[wasm_bindgen]
+#[derive(Debug, Clone, Default)]
+pub struct CameraPath {
+    keyframes: Vec<Keyframe>,
+}
+
+#[wasm_bindgen]
+impl CameraPath {
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> CameraPath {
+        CameraPath::default()
+    }
+
+    // Add a keyframe at a tour time (seconds): camera position and look-at
+    // point in scene units, and the simulation epoch shown there. Keyframes may
+    // be added in any order.
+    pub fn add_keyframe(&mut self, time: f64, position: &Vec3, look_at: &Vec3, julian_date: f64) -> Result<(), JsError> {
+        if !time.is_finite() {
+            return Err(SimulationError::invalid_argument(&format!("Invalid keyframe time {}", time)).into());
+        }
+        check_julian_date(julian_date)?;
+        let index = self.keyframes.partition_point(|keyframe| keyframe.time <= time);
+        self.keyframes.insert(
+            index,
+            Keyframe {
+                time,
+                position: *position,
+                look_at: *look_at,
+                julian_date,
+                quaternion: Quat::look_at(position, look_at, &Vec3::new(0.0, 1.0, 0.0)),
+            },
+        );
+        Ok(())
+    }
+
+    pub fn clear(&mut self) {
+        self.keyframes.clear();
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn keyframe_count(&self) -> usize {
+        self.keyframes.len()
+    }
+
+    // Tour time of the last keyframe (seconds)
+    #[wasm_bindgen(getter)]
+    pub fn duration(&self) -> f64 {
+        self.keyframes.last().map_or(0.0, |keyframe| keyframe.time)
+    }
+
+    // Camera transform at a tour time (seconds), held at the first or last
+    // keyframe outside the path. None for an empty path or a non-finite time.
+    pub fn evaluate(&self, time: f64) -> Option<CameraTransform> {
+        if !time.is_finite() {
+            return None;
+        }
+        let first = self.keyframes.first()?;
+        let last = self.keyframes.last()?;
+        let hold = |keyframe: &Keyframe| CameraTransform {
+            position: keyframe.position,
+            look_at: keyframe.look_at,
+            quaternion: keyframe.quaternion,
+            julian_date: keyframe.julian_date,
+        };
+        if time <= first.time || self.keyframes.len() == 1 {
+            return Some(hold(first));
+        }
+        if time >= last.time {
+            return Some(hold(last));
+        }
+
+        let i = self.keyframes.partition_point(|keyframe| keyframe.time <= time) - 1;
+        let (a, b) = (&self.keyframes[i], &self.keyframes[i + 1]);
+        let u = (time - a.time) / (b.time - a.time);
+        Some(CameraTransform {
+            position: hermite(&self.keyframes, i, u, |keyframe| keyframe.position),
+            look_at: hermite(&self.keyframes, i, u, |keyframe| keyframe.look_at),
+            quaternion: a.quaternion.slerp(&b.quaternion, u),
+            // Linear so the epoch never runs backwards between keyframes
+            julian_date: a.julian_date + (b.julian_date - a.julian_date) * u,
+        })
+    }
+}
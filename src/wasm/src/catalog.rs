@@ -0,0 +1,132 @@
+// Static body metadata, fetched once at startup rather than with every frame's
+// positions
+
+use wasm_bindgen::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{PhysicalData, DWARF_PLANET_DATA, PLANET_DATA};
+
+// Category of a catalog body
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BodyKind {
+    Planet = 0,
+    DwarfPlanet = 1,
+}
+
+// Physical data of a planet or dwarf planet, without position
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[wasm_bindgen]
+pub struct BodyInfo {
+    name: String,
+    kind: BodyKind,
+    radius: f64,
+    color: String,
+    orbit_radius: f64,
+    axial_tilt: f64,
+    day_length: f64,
+    year_length: f64,
+    temperature: f64,
+    moons: u32,
+    mass: f64,
+    density: f64,
+}
+
+#[wasm_bindgen]
+impl BodyInfo {
+    #[wasm_bindgen(getter)]
+    pub fn name(&self) -> String {
+        self.name.clone()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn kind(&self) -> BodyKind {
+        self.kind
+    }
+
+    // Equatorial radius (Earth radii)
+    #[wasm_bindgen(getter)]
+    pub fn radius(&self) -> f64 {
+        self.radius
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn color(&self) -> String {
+        self.color.clone()
+    }
+
+    // Mean orbital distance (AU)
+    #[wasm_bindgen(getter)]
+    pub fn orbit_radius(&self) -> f64 {
+        self.orbit_radius
+    }
+
+    // Obliquity (degrees)
+    #[wasm_bindgen(getter)]
+    pub fn axial_tilt(&self) -> f64 {
+        self.axial_tilt
+    }
+
+    // Rotation period (hours)
+    #[wasm_bindgen(getter)]
+    pub fn day_length(&self) -> f64 {
+        self.day_length
+    }
+
+    // Orbital period (days)
+    #[wasm_bindgen(getter)]
+    pub fn year_length(&self) -> f64 {
+        self.year_length
+    }
+
+    // Mean surface or cloud-top temperature (K)
+    #[wasm_bindgen(getter)]
+    pub fn temperature(&self) -> f64 {
+        self.temperature
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn moons(&self) -> u32 {
+        self.moons
+    }
+
+    // Mass (Earth masses)
+    #[wasm_bindgen(getter)]
+    pub fn mass(&self) -> f64 {
+        self.mass
+    }
+
+    // Mean density (g/cm³)
+    #[wasm_bindgen(getter)]
+    pub fn density(&self) -> f64 {
+        self.density
+    }
+}
+
+fn body_info(data: &PhysicalData, kind: BodyKind) -> BodyInfo {
+    let &(name, radius, color, orbit_radius, axial_tilt, day_length, year_length, temperature, moons, mass, density) =
+        data;
+    BodyInfo {
+        name: name.to_string(),
+        kind,
+        radius,
+        color: color.to_string(),
+        orbit_radius,
+        axial_tilt,
+        day_length,
+        year_length,
+        temperature,
+        moons,
+        mass,
+        density,
+    }
+}
+
+// List the built-in planets and dwarf planets with their static metadata, in
+// the order of planet_positions followed by dwarf_planet_positions
+#[wasm_bindgen]
+pub fn bodies() -> Vec<BodyInfo> {
+    let planets = PLANET_DATA.iter().map(|data| body_info(data, BodyKind::Planet));
+    let dwarfs = DWARF_PLANET_DATA.iter().map(|data| body_info(data, BodyKind::DwarfPlanet));
+    planets.chain(dwarfs).collect()
+}
@@ -0,0 +1,83 @@
+// Chebyshev interpolation of body states over fixed time windows: each window
+// is fitted once from a handful of exact states, after which every frame inside
+// it costs a short polynomial evaluation instead of a Kepler solve
+
+use crate::state::StateVector;
+use crate::Vec3;
+
+// Exact states sampled per window, one more than the polynomial degree
+const NODES: usize = 12;
+
+// Position and velocity series over one window
+#[derive(Debug, Clone)]
+struct Segment {
+    start: f64,
+    // Six series: position x, y, z then velocity x, y, z
+    coefficients: [[f64; NODES]; 6],
+}
+
+impl Segment {
+    fn fit(start: f64, length: f64, state: &impl Fn(f64) -> StateVector) -> Segment {
+        let half = 0.5 * length;
+        let samples: [[f64; 6]; NODES] = std::array::from_fn(|k| {
+            let x = (std::f64::consts::PI * (k as f64 + 0.5) / NODES as f64).cos();
+            let s = state(start + half * (1.0 + x));
+            [s.position.x, s.position.y, s.position.z, s.velocity.x, s.velocity.y, s.velocity.z]
+        });
+        let coefficients = std::array::from_fn(|component| {
+            std::array::from_fn(|j| {
+                let sum = (0..NODES).fold(0.0, |sum, k| {
+                    let angle = std::f64::consts::PI * j as f64 * (k as f64 + 0.5) / NODES as f64;
+                    sum + samples[k][component] * angle.cos()
+                });
+                2.0 * sum / NODES as f64
+            })
+        });
+        Segment { start, coefficients }
+    }
+
+    // Clenshaw summation of each series at x in [-1, 1]
+    fn evaluate(&self, x: f64) -> StateVector {
+        let value: [f64; 6] = std::array::from_fn(|component| {
+            let c = &self.coefficients[component];
+            let (mut b1, mut b2) = (0.0, 0.0);
+            for &coefficient in c[1..].iter().rev() {
+                (b1, b2) = (2.0 * x * b1 - b2 + coefficient, b1);
+            }
+            x * b1 - b2 + 0.5 * c[0]
+        });
+        StateVector::new(Vec3::new(value[0], value[1], value[2]), Vec3::new(value[3], value[4], value[5]))
+    }
+}
+
+// Per-slot cache of the window most recently used, for a fixed set of bodies
+#[derive(Debug, Clone)]
+pub(crate) struct StateCache {
+    windows: Vec<f64>,
+    segments: Vec<Option<Segment>>,
+}
+
+impl StateCache {
+    // One slot per window length (days); shorter windows follow faster bodies
+    pub(crate) fn new(windows: Vec<f64>) -> StateCache {
+        let segments = vec![None; windows.len()];
+        StateCache { windows, segments }
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.segments.iter_mut().for_each(|segment| *segment = None);
+    }
+
+    // Interpolated state of a slot at a Julian date, refitting from the exact
+    // state function when the date leaves the cached window. Windows are
+    // aligned on J2000.0 so scrubbing back and forth lands on the same fits.
+    pub(crate) fn state(&mut self, slot: usize, julian_date: f64, exact: impl Fn(f64) -> StateVector) -> StateVector {
+        let length = self.windows[slot];
+        let start = 2451545.0 + ((julian_date - 2451545.0) / length).floor() * length;
+        let segment = match &mut self.segments[slot] {
+            Some(segment) if segment.start == start => segment,
+            cached => cached.insert(Segment::fit(start, length, &exact)),
+        };
+        segment.evaluate(2.0 * (julian_date - start) / length - 1.0)
+    }
+}
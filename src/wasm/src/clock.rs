@@ -0,0 +1,164 @@
+// Simulation clock: the epoch shown on screen, advanced by real frame times
+// scaled by a rate multiplier
+
+use wasm_bindgen::prelude::*;
+
+use crate::errors::check_julian_date;
+use crate::time::{Time, TimeScale};
+
+const MILLISECONDS_PER_DAY: f64 = 86_400_000.0;
+
+// Longest real interval a single tick advances, so a frame after the tab was
+// hidden doesn't jump the simulation by minutes times the rate
+const MAX_TICK_MS: f64 = 250.0;
+
+// Drives the animation loop from a single tick per frame. The epoch is a TT
+// Julian date held as whole days plus a fraction, so tiny frame increments
+// accumulate without rounding away against a seven-digit day count and the
+// same sequence of ticks always lands on the same date.
+#[wasm_bindgen]
+#[derive(Debug, Clone)]
+pub struct SimulationClock {
+    day: f64,
+    fraction: f64,
+    start: f64,
+    rate: f64,
+    paused: bool,
+    frame_step: f64,
+}
+
+#[wasm_bindgen]
+impl SimulationClock {
+    // A running clock at a Julian date (TT), advancing in real time
+    #[wasm_bindgen(constructor)]
+    pub fn new(julian_date: f64) -> Result<SimulationClock, JsError> {
+        check_julian_date(julian_date)?;
+        Ok(SimulationClock::starting_at(julian_date))
+    }
+
+    // Advance by a real frame time (milliseconds) times the rate and return the
+    // new Julian date. Paused clocks stay put.
+    pub fn tick(&mut self, real_dt_ms: f64) -> f64 {
+        if !self.paused && real_dt_ms.is_finite() && real_dt_ms > 0.0 {
+            self.advance(real_dt_ms.min(MAX_TICK_MS) * self.rate / MILLISECONDS_PER_DAY);
+        }
+        self.julian_date()
+    }
+
+    // Advance by a number of frame steps (negative steps back), whether paused or
+    // not, and return the new Julian date
+    pub fn step(&mut self, frames: i32) -> f64 {
+        self.advance(frames as f64 * self.frame_step);
+        self.julian_date()
+    }
+
+    pub fn pause(&mut self) {
+        self.paused = true;
+    }
+
+    pub fn resume(&mut self) {
+        self.paused = false;
+    }
+
+    // Return to the epoch the clock was created with
+    pub fn reset(&mut self) {
+        self.jump_to(self.start);
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn julian_date(&self) -> f64 {
+        self.day + self.fraction
+    }
+
+    // Jump to a Julian date (TT)
+    #[wasm_bindgen(setter)]
+    pub fn set_julian_date(&mut self, julian_date: f64) -> Result<(), JsError> {
+        check_julian_date(julian_date)?;
+        self.jump_to(julian_date);
+        Ok(())
+    }
+
+    // The current epoch with its conversions to other time scales
+    #[wasm_bindgen(getter)]
+    pub fn time(&self) -> Time {
+        Time::in_scale(self.julian_date(), TimeScale::Tt)
+    }
+
+    // Simulated seconds per real second; negative runs backwards
+    #[wasm_bindgen(getter)]
+    pub fn rate(&self) -> f64 {
+        self.rate
+    }
+
+    #[wasm_bindgen(setter)]
+    pub fn set_rate(&mut self, rate: f64) {
+        if rate.is_finite() {
+            self.rate = rate;
+        }
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn paused(&self) -> bool {
+        self.paused
+    }
+
+    #[wasm_bindgen(setter)]
+    pub fn set_paused(&mut self, paused: bool) {
+        self.paused = paused;
+    }
+
+    // Days advanced by each frame step
+    #[wasm_bindgen(getter)]
+    pub fn frame_step(&self) -> f64 {
+        self.frame_step
+    }
+
+    #[wasm_bindgen(setter)]
+    pub fn set_frame_step(&mut self, days: f64) {
+        if days.is_finite() {
+            self.frame_step = days;
+        }
+    }
+}
+
+impl SimulationClock {
+    // A running clock at a Julian date already known to be valid
+    pub(crate) fn starting_at(julian_date: f64) -> SimulationClock {
+        let mut clock = SimulationClock {
+            day: 0.0,
+            fraction: 0.0,
+            start: julian_date,
+            rate: 1.0,
+            paused: false,
+            frame_step: 1.0,
+        };
+        clock.jump_to(julian_date);
+        clock
+    }
+
+    // A clock set to an epoch given exactly as whole days plus a fraction
+    pub(crate) fn from_parts(day: f64, fraction: f64) -> SimulationClock {
+        let mut clock = SimulationClock::starting_at(day + fraction);
+        clock.day = day;
+        clock.fraction = fraction;
+        clock
+    }
+
+    // The epoch as whole days and the fraction of a day, as held internally
+    pub(crate) fn parts(&self) -> (f64, f64) {
+        (self.day, self.fraction)
+    }
+
+    pub(crate) fn jump_to(&mut self, julian_date: f64) {
+        self.day = julian_date.floor();
+        self.fraction = julian_date - self.day;
+    }
+
+    fn advance(&mut self, days: f64) {
+        self.fraction += days;
+        // Keep the fraction within [0, 1) by carrying whole days
+        let carry = self.fraction.floor();
+        self.day += carry;
+        self.fraction -= carry;
+    }
+}
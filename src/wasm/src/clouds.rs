@@ -0,0 +1,252 @@
+// Animated cloud textures for the giant planets: zonal bands sheared by
+// latitude-dependent winds, curl-noise turbulence and long-lived vortices
+
+use wasm_bindgen::prelude::*;
+use js_sys::Uint8Array;
+
+use crate::deg_to_rad;
+use crate::errors::check_julian_date;
+use crate::noise::SimplexNoise;
+use crate::terrain::{pixel_latitude, pixel_longitude, unit_vector};
+
+// Largest texture edge generated
+const MAX_TEXTURE_SIZE: u32 = 4096;
+
+// Turbulence is advected by the winds for this many days before fading into a
+// fresh noise pattern, so features stretch without shearing into streaks
+const FLOW_CYCLE_DAYS: f64 = 20.0;
+
+// A long-lived vortex: latitude and longitude at J2000.0 (degrees), longitude
+// drift (degrees/day), half-extent in longitude and latitude (degrees), color
+// and swirl (radians of rotation at the core; positive is counter-clockwise)
+type Storm = (f64, f64, f64, f64, f64, &'static str, f64);
+
+struct CloudProfile {
+    planet: &'static str,
+    // Band colors at latitudes (degrees), south to north
+    bands: &'static [(f64, &'static str)],
+    // Peak zonal wind (degrees/day) and the number of alternating jets pole to pole
+    wind: f64,
+    jets: f64,
+    // Latitude displacement of the curl-noise turbulence (degrees)
+    turbulence: f64,
+    // Contrast of the fine cloud texture within each band
+    detail: f64,
+    storms: &'static [Storm],
+}
+
+static JUPITER_BANDS: &[(f64, &str)] = &[
+    (-90.0, "#8a8478"),
+    (-60.0, "#a39a88"),
+    (-40.0, "#c9b79c"),
+    (-30.0, "#b3946f"),
+    (-22.0, "#e8dcc4"),
+    (-15.0, "#a8754f"),
+    (-8.0, "#c48e5f"),
+    (-4.0, "#efe6d2"),
+    (4.0, "#efe6d2"),
+    (8.0, "#b98050"),
+    (17.0, "#9c6a45"),
+    (21.0, "#ece0c8"),
+    (27.0, "#a9825e"),
+    (33.0, "#ddd0b8"),
+    (45.0, "#b8a68a"),
+    (60.0, "#a39a88"),
+    (90.0, "#8a8478"),
+];
+
+static SATURN_BANDS: &[(f64, &str)] = &[
+    (-90.0, "#9a8f74"),
+    (-60.0, "#c8b58a"),
+    (-30.0, "#dcc999"),
+    (-15.0, "#e3d2a6"),
+    (0.0, "#eadbb2"),
+    (15.0, "#e0cc9c"),
+    (30.0, "#d6c08e"),
+    (60.0, "#b7a47c"),
+    (78.0, "#8e9ea6"),
+    (90.0, "#7e8f98"),
+];
+
+static URANUS_BANDS: &[(f64, &str)] = &[
+    (-90.0, "#a8dbe0"),
+    (-45.0, "#9fd6dd"),
+    (0.0, "#94cfd8"),
+    (45.0, "#9fd6dd"),
+    (90.0, "#b3e2e6"),
+];
+
+static NEPTUNE_BANDS: &[(f64, &str)] = &[
+    (-90.0, "#3857a8"),
+    (-60.0, "#4565b8"),
+    (-25.0, "#3b5fb5"),
+    (-20.0, "#5a7fd0"),
+    (0.0, "#3f62b8"),
+    (30.0, "#4a6cc0"),
+    (90.0, "#3857a8"),
+];
+
+// The Great Red Spot and Oval BA, both anticyclones in the southern hemisphere
+static JUPITER_STORMS: &[Storm] = &[
+    (-22.4, -60.0, 0.05, 11.0, 6.0, "#c0563a", 2.5),
+    (-33.0, 150.0, -0.4, 4.0, 3.0, "#d9a08a", 1.5),
+];
+
+static CLOUD_PROFILES: &[CloudProfile] = &[
+    CloudProfile {
+        planet: "Jupiter",
+        bands: JUPITER_BANDS,
+        wind: 6.0,
+        jets: 12.0,
+        turbulence: 2.5,
+        detail: 0.12,
+        storms: JUPITER_STORMS,
+    },
+    CloudProfile {
+        planet: "Saturn",
+        bands: SATURN_BANDS,
+        wind: 4.0,
+        jets: 8.0,
+        turbulence: 1.5,
+        detail: 0.06,
+        storms: &[],
+    },
+    CloudProfile {
+        planet: "Uranus",
+        bands: URANUS_BANDS,
+        wind: 1.5,
+        jets: 3.0,
+        turbulence: 0.5,
+        detail: 0.02,
+        storms: &[],
+    },
+    CloudProfile {
+        planet: "Neptune",
+        bands: NEPTUNE_BANDS,
+        wind: 3.0,
+        jets: 3.0,
+        turbulence: 1.5,
+        detail: 0.08,
+        storms: &[],
+    },
+];
+
+// Parse a #rrggbb color into 0-1 components
+fn hex_rgb(color: &str) -> [f64; 3] {
+    let channel = |i: usize| u8::from_str_radix(&color[1 + 2 * i..3 + 2 * i], 16).unwrap_or(0) as f64 / 255.0;
+    [channel(0), channel(1), channel(2)]
+}
+
+fn mix(a: [f64; 3], b: [f64; 3], t: f64) -> [f64; 3] {
+    std::array::from_fn(|i| a[i] + (b[i] - a[i]) * t)
+}
+
+// Band color at a latitude (degrees), interpolated between the profile's stops
+fn band_color(bands: &[(f64, &str)], latitude: f64) -> [f64; 3] {
+    let upper = bands.iter().position(|band| band.0 >= latitude).unwrap_or(bands.len() - 1).max(1);
+    let (lat_a, color_a) = bands[upper - 1];
+    let (lat_b, color_b) = bands[upper];
+    let t = ((latitude - lat_a) / (lat_b - lat_a)).clamp(0.0, 1.0);
+    mix(hex_rgb(color_a), hex_rgb(color_b), t)
+}
+
+// Curl of a noise potential on the sphere, as (longitude, latitude)
+// displacements in degrees. Divergence-free, so it swirls rather than bunches.
+fn curl(noise: &SimplexNoise, longitude: f64, latitude: f64, phase: f64) -> (f64, f64) {
+    let step = 0.01;
+    let potential = |lon: f64, lat: f64| {
+        let [x, y, z] = unit_vector(lon, lat).map(|c| c * 6.0);
+        noise.fbm(x, y, z + phase, 3)
+    };
+    let d_lat = (potential(longitude, latitude + step) - potential(longitude, latitude - step)) / (2.0 * step);
+    let d_lon = (potential(longitude + step, latitude) - potential(longitude - step, latitude)) / (2.0 * step);
+    (d_lat / latitude.cos().max(0.05), -d_lon / latitude.cos().max(0.05))
+}
+
+// Swirl the sampling point around any storm it falls in and return the storm's
+// color weight
+fn apply_storms(profile: &CloudProfile, days: f64, longitude: &mut f64, latitude: &mut f64) -> Option<([f64; 3], f64)> {
+    for &(storm_lat, storm_lon, drift, half_lon, half_lat, color, swirl) in profile.storms {
+        let center_lon = storm_lon + drift * days;
+        let dx = ((*longitude - center_lon + 180.0).rem_euclid(360.0) - 180.0) / half_lon;
+        let dy = (*latitude - storm_lat) / half_lat;
+        let r2 = dx * dx + dy * dy;
+        if r2 >= 4.0 {
+            continue;
+        }
+        let angle = swirl * (-r2).exp();
+        let (sin, cos) = angle.sin_cos();
+        *longitude = center_lon + (dx * cos - dy * sin) * half_lon;
+        *latitude = storm_lat + (dx * sin + dy * cos) * half_lat;
+        // Storm color is strongest at the core
+        let weight = (-(r2 * 1.5).powi(2)).exp();
+        return Some((hex_rgb(color), weight));
+    }
+    None
+}
+
+// Generate an equirectangular RGBA cloud texture for "Jupiter", "Saturn",
+// "Uranus" or "Neptune" at a Julian date (row 0 north, column 0 at longitude
+// -180° in the planet's rotating frame). Zonal jets shear the turbulence
+// continuously and Jupiter's Great Red Spot and Oval BA drift in longitude, so
+// regenerating at later dates animates the clouds. Returns None for other bodies
+// or an empty size.
+#[wasm_bindgen]
+pub fn gas_giant_texture(planet: &str, width: u32, height: u32, julian_date: f64, seed: u32) -> Result<Option<Uint8Array>, JsError> {
+    check_julian_date(julian_date)?;
+    Ok(render_gas_giant(planet, width, height, julian_date, seed))
+}
+
+// Cloud texture at a date already known to be valid
+fn render_gas_giant(planet: &str, width: u32, height: u32, julian_date: f64, seed: u32) -> Option<Uint8Array> {
+    let profile = CLOUD_PROFILES.iter().find(|profile| profile.planet == planet)?;
+    if width == 0 || height == 0 {
+        return None;
+    }
+    let (width, height) = (width.min(MAX_TEXTURE_SIZE) as usize, height.min(MAX_TEXTURE_SIZE) as usize);
+    let noise = SimplexNoise::new(seed as u64);
+    let days = julian_date - 2451545.0;
+
+    let mut pixels = Vec::with_capacity(width * height * 4);
+    for row in 0..height {
+        let row_latitude = pixel_latitude(row, height).to_degrees();
+        // Alternating prograde and retrograde jets
+        let wind = profile.wind * (deg_to_rad(row_latitude) * profile.jets).cos();
+        for column in 0..width {
+            let mut longitude = pixel_longitude(column, width).to_degrees();
+            let mut latitude = row_latitude;
+            let storm = apply_storms(profile, days, &mut longitude, &mut latitude);
+
+            // Two advected noise layers half a cycle apart, cross-faded so each
+            // is invisible when it resets
+            let mut warped_latitude = 0.0;
+            let mut texture = 0.0;
+            for layer in 0..2 {
+                let cycle = days / FLOW_CYCLE_DAYS + layer as f64 * 0.5;
+                let (epoch, age) = (cycle.floor(), cycle.rem_euclid(1.0));
+                let weight = 1.0 - (2.0 * age - 1.0).abs();
+                let phase = epoch * 17.31 + layer as f64 * 5.7;
+                let lon = deg_to_rad(longitude - wind * age * FLOW_CYCLE_DAYS);
+                let lat = deg_to_rad(latitude);
+
+                let (d_lon, d_lat) = curl(&noise, lon, lat, phase);
+                let lat_warp = lat + deg_to_rad(profile.turbulence) * d_lat;
+                let lon_warp = lon + deg_to_rad(profile.turbulence) * d_lon;
+                warped_latitude += weight * lat_warp.to_degrees();
+
+                // Fine texture stretched along the bands
+                let [x, y, z] = unit_vector(lon_warp, lat_warp);
+                texture += weight * noise.fbm(x * 8.0, y * 8.0, z * 32.0 + phase, 4);
+            }
+
+            let mut color = band_color(profile.bands, warped_latitude.clamp(-90.0, 90.0));
+            color = color.map(|c| c * (1.0 + profile.detail * texture));
+            if let Some((storm_color, weight)) = storm {
+                color = mix(color, storm_color, weight);
+            }
+            let [r, g, b] = color.map(|c| (c.clamp(0.0, 1.0) * 255.0).round() as u8);
+            pixels.extend_from_slice(&[r, g, b, 255]);
+        }
+    }
+    Some(Uint8Array::from(pixels.as_slice()))
+}
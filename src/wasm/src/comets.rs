@@ -0,0 +1,282 @@
+use wasm_bindgen::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::errors::check_julian_date;
+use crate::state::StateVector;
+use crate::scene::{scene_axes, scene_length, scene_velocity};
+use crate::{deg_to_rad, orbital_to_reference, to_scene, Vec3, GM_SUN};
+
+// Perihelion-referenced conic elements valid for elliptical, parabolic and
+// hyperbolic orbits: perihelion distance (AU), eccentricity, inclination,
+// ascending node and argument of perihelion (degrees), time of perihelion (JD)
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[wasm_bindgen]
+pub struct CometElements {
+    q: f64,
+    e: f64,
+    i: f64,
+    omega: f64,
+    w: f64,
+    perihelion_time: f64,
+}
+
+#[wasm_bindgen]
+impl CometElements {
+    #[wasm_bindgen(constructor)]
+    pub fn new(q: f64, e: f64, i: f64, omega: f64, w: f64, perihelion_time: f64) -> CometElements {
+        CometElements { q, e, i, omega, w, perihelion_time }
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn q(&self) -> f64 {
+        self.q
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn e(&self) -> f64 {
+        self.e
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn i(&self) -> f64 {
+        self.i
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn omega(&self) -> f64 {
+        self.omega
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn w(&self) -> f64 {
+        self.w
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn perihelion_time(&self) -> f64 {
+        self.perihelion_time
+    }
+}
+
+// Comet position, velocity and tail geometry in scene coordinates
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[wasm_bindgen]
+pub struct CometData {
+    name: String,
+    position: Vec3,
+    velocity: Vec3,
+    true_position: Vec3,
+    distance: f64,
+    ion_tail_direction: Vec3,
+    dust_tail_direction: Vec3,
+    tail_length: f64,
+    tail_brightness: f64,
+}
+
+#[wasm_bindgen]
+impl CometData {
+    #[wasm_bindgen(getter)]
+    pub fn name(&self) -> String {
+        self.name.clone()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn position(&self) -> Vec3 {
+        self.position
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn velocity(&self) -> Vec3 {
+        self.velocity
+    }
+
+    // Heliocentric ecliptic J2000 position (AU), unaffected by the scene configuration
+    #[wasm_bindgen(getter)]
+    pub fn true_position(&self) -> Vec3 {
+        self.true_position
+    }
+
+    // Heliocentric distance (AU)
+    #[wasm_bindgen(getter)]
+    pub fn distance(&self) -> f64 {
+        self.distance
+    }
+
+    // Unit vector of the plasma tail, pointing straight away from the Sun
+    #[wasm_bindgen(getter)]
+    pub fn ion_tail_direction(&self) -> Vec3 {
+        self.ion_tail_direction
+    }
+
+    // Unit vector of the dust tail, lagging behind the orbital motion
+    #[wasm_bindgen(getter)]
+    pub fn dust_tail_direction(&self) -> Vec3 {
+        self.dust_tail_direction
+    }
+
+    // Tail length in scene units
+    #[wasm_bindgen(getter)]
+    pub fn tail_length(&self) -> f64 {
+        self.tail_length
+    }
+
+    // Relative tail brightness, 1 at 1 AU and 0 beyond the activity limit
+    #[wasm_bindgen(getter)]
+    pub fn tail_brightness(&self) -> f64 {
+        self.tail_brightness
+    }
+}
+
+// Famous comets and interstellar objects, osculating elements near perihelion
+pub(crate) static COMET_ELEMENTS: &[(&str, CometElements)] = &[
+    ("Halley", CometElements {
+        q: 0.585978, e: 0.967143, i: 162.262, omega: 58.420, w: 111.333, perihelion_time: 2446470.959,
+    }),
+    ("Encke", CometElements {
+        q: 0.330200, e: 0.848300, i: 11.780, omega: 334.570, w: 186.540, perihelion_time: 2457822.590,
+    }),
+    ("Swift-Tuttle", CometElements {
+        q: 0.959500, e: 0.963200, i: 113.450, omega: 139.380, w: 152.980, perihelion_time: 2448968.820,
+    }),
+    ("Hale-Bopp", CometElements {
+        q: 0.914100, e: 0.995086, i: 89.430, omega: 282.470, w: 130.590, perihelion_time: 2450539.638,
+    }),
+    ("NEOWISE", CometElements {
+        q: 0.294710, e: 0.999180, i: 128.940, omega: 61.010, w: 37.280, perihelion_time: 2459034.180,
+    }),
+    ("'Oumuamua", CometElements {
+        q: 0.255340, e: 1.201130, i: 122.740, omega: 24.600, w: 241.810, perihelion_time: 2458006.010,
+    }),
+    ("Borisov", CometElements {
+        q: 2.006600, e: 3.356500, i: 44.050, omega: 308.150, w: 209.120, perihelion_time: 2458826.050,
+    }),
+];
+
+// Heliocentric distance (AU) beyond which water ice no longer sublimates and
+// the coma and tails fade out
+const ACTIVITY_LIMIT: f64 = 5.0;
+
+// Visible tail length at 1 AU (AU), scaling with the inverse square of distance
+const TAIL_LENGTH_AT_1AU: f64 = 0.3;
+
+// Longest tail the model will produce (AU)
+const MAX_TAIL_LENGTH: f64 = 1.5;
+
+// How strongly dust grains trail the orbital motion relative to radiation pressure
+const DUST_LAG: f64 = 0.4;
+
+// Brightness exponent of the standard total-magnitude law (r^-n)
+const ACTIVITY_EXPONENT: f64 = 4.0;
+
+// Tail directions (ion, dust), length (AU) and brightness from a comet's
+// heliocentric ecliptic state
+fn tail_model(state: &StateVector) -> (Vec3, Vec3, f64, f64) {
+    let r = state.position.length();
+    let anti_solar = state.position.scale(1.0 / r);
+    let speed = state.velocity.length();
+    let dust = anti_solar.sub(&state.velocity.scale(DUST_LAG / speed));
+    let dust = dust.scale(1.0 / dust.length());
+
+    // Fade linearly to zero over the outer fifth of the activity range
+    let window = ((ACTIVITY_LIMIT - r) / (0.2 * ACTIVITY_LIMIT)).clamp(0.0, 1.0);
+    let length = (TAIL_LENGTH_AT_1AU / (r * r)).min(MAX_TAIL_LENGTH) * window;
+    let brightness = r.powf(-ACTIVITY_EXPONENT) * window;
+
+    (anti_solar, dust, length, brightness)
+}
+
+// Stumpff functions C(z) and S(z) for the universal-variable Kepler equation
+fn stumpff(z: f64) -> (f64, f64) {
+    if z > 1e-6 {
+        let s = z.sqrt();
+        ((1.0 - s.cos()) / z, (s - s.sin()) / (s * z))
+    } else if z < -1e-6 {
+        let s = (-z).sqrt();
+        ((s.cosh() - 1.0) / -z, (s.sinh() - s) / (s * -z))
+    } else {
+        // Series expansion around the parabolic case
+        (1.0 / 2.0 - z / 24.0 + z * z / 720.0, 1.0 / 6.0 - z / 120.0 + z * z / 5040.0)
+    }
+}
+
+// Solve Barker's equation for a parabola, returning D = tan(ν/2)
+fn solve_barker(q: f64, dt: f64) -> f64 {
+    let w = 3.0 * (GM_SUN / (2.0 * q * q * q)).sqrt() * dt;
+    let y = (w / 2.0 + (w * w / 4.0 + 1.0).sqrt()).cbrt();
+    y - 1.0 / y
+}
+
+// Calculate the ecliptic state of any conic orbit at a Julian date using
+// universal variables, seeded with the parabolic (Barker) solution
+pub(crate) fn comet_state_at(elements: &CometElements, julian_date: f64) -> StateVector {
+    let q = elements.q;
+    let dt = julian_date - elements.perihelion_time;
+    let sqrt_mu = GM_SUN.sqrt();
+    let alpha = (1.0 - elements.e) / q; // reciprocal semi-major axis
+    let perihelion_speed = (GM_SUN * (1.0 + elements.e) / q).sqrt();
+
+    // Universal Kepler equation starting at perihelion (radial velocity zero):
+    // sqrt(mu) dt = (1 - alpha q) chi³ S(z) + q chi, with z = alpha chi²
+    let mut chi = (2.0 * q).sqrt() * solve_barker(q, dt);
+    for _ in 0..50 {
+        let z = alpha * chi * chi;
+        let (c, s) = stumpff(z);
+        let f = (1.0 - alpha * q) * chi * chi * chi * s + q * chi - sqrt_mu * dt;
+        let r = (1.0 - alpha * q) * chi * chi * c + q;
+        let delta = f / r;
+        chi -= delta;
+        if delta.abs() < 1e-12 * chi.abs().max(1.0) {
+            break;
+        }
+    }
+
+    let z = alpha * chi * chi;
+    let (c, s) = stumpff(z);
+    let r = (1.0 - alpha * q) * chi * chi * c + q;
+
+    // Lagrange coefficients relative to the perihelion state
+    let f = 1.0 - chi * chi / q * c;
+    let g = dt - chi * chi * chi * s / sqrt_mu;
+    let f_dot = sqrt_mu / (r * q) * (alpha * chi * chi * chi * s - chi);
+    let g_dot = 1.0 - chi * chi / r * c;
+
+    let omega = deg_to_rad(elements.omega);
+    let w = deg_to_rad(elements.w);
+    let i = deg_to_rad(elements.i);
+
+    StateVector {
+        position: orbital_to_reference(f * q, g * perihelion_speed, omega, w, i),
+        velocity: orbital_to_reference(f_dot * q, g_dot * perihelion_speed, omega, w, i),
+    }
+}
+
+// Calculate the heliocentric ecliptic state of comet elements at a Julian date
+#[wasm_bindgen]
+pub fn comet_state(elements: &CometElements, julian_date: f64) -> Result<StateVector, JsError> {
+    check_julian_date(julian_date)?;
+    Ok(comet_state_at(elements, julian_date))
+}
+
+// Calculate positions of the built-in comets and interstellar objects
+#[wasm_bindgen]
+pub fn comet_positions(julian_date: f64) -> Result<Vec<CometData>, JsError> {
+    check_julian_date(julian_date)?;
+    Ok(COMET_ELEMENTS
+        .iter()
+        .map(|(name, elements)| {
+            let state = comet_state_at(elements, julian_date);
+            let (ion, dust, length, brightness) = tail_model(&state);
+            CometData {
+                name: name.to_string(),
+                position: to_scene(state.position),
+                velocity: scene_velocity(&state.position, &state.velocity),
+                true_position: state.position,
+                distance: state.position.length(),
+                ion_tail_direction: scene_axes(&ion),
+                dust_tail_direction: scene_axes(&dust),
+                tail_length: scene_length(length),
+                tail_brightness: brightness,
+            }
+        })
+        .collect())
+}
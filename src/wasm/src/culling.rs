@@ -0,0 +1,147 @@
+// Per-frame visibility culling in scene coordinates: bodies and particles
+// outside the camera frustum, or smaller on screen than a pixel threshold, are
+// dropped before the renderer touches them. Whole groups (a belt, a moon
+// system) are tested by a bounding sphere first, so off-screen groups are
+// never propagated at all.
+
+use wasm_bindgen::prelude::*;
+use js_sys::{Float32Array, Uint32Array};
+
+use crate::errors::SimulationError;
+use crate::math::Mat4;
+use crate::Vec3;
+
+// Default smallest projected radius (pixels) still drawn
+const DEFAULT_MIN_PIXELS: f64 = 0.5;
+
+// The camera for one frame
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy)]
+pub struct CameraView {
+    position: Vec3,
+    // Frustum planes (a, b, c, d) with unit inward normals: left, right,
+    // bottom, top, near, far
+    planes: [[f64; 4]; 6],
+    // Pixels per unit of radius at unit distance
+    pixel_scale: f64,
+    min_pixels: f64,
+}
+
+#[wasm_bindgen]
+impl CameraView {
+    // From the camera position, its view-projection matrix
+    // (camera.projectionMatrix × camera.matrixWorldInverse in Three.js), the
+    // vertical field of view (degrees, as PerspectiveCamera.fov) and the
+    // viewport height in pixels
+    #[wasm_bindgen(constructor)]
+    pub fn new(position: &Vec3, view_projection: &Mat4, fov_y: f64, viewport_height: f64) -> CameraView {
+        let m = view_projection.as_array();
+        let row = |i: usize| [m[i], m[4 + i], m[8 + i], m[12 + i]];
+        let (x, y, z, w) = (row(0), row(1), row(2), row(3));
+        let plane = |sign: f64, r: [f64; 4]| {
+            let p: [f64; 4] = std::array::from_fn(|k| w[k] + sign * r[k]);
+            let length = (p[0] * p[0] + p[1] * p[1] + p[2] * p[2]).sqrt();
+            p.map(|value| value / length)
+        };
+        CameraView {
+            position: *position,
+            planes: [plane(1.0, x), plane(-1.0, x), plane(1.0, y), plane(-1.0, y), plane(1.0, z), plane(-1.0, z)],
+            pixel_scale: 0.5 * viewport_height / (0.5 * fov_y.to_radians()).tan(),
+            min_pixels: DEFAULT_MIN_PIXELS,
+        }
+    }
+
+    // Smallest projected radius (pixels) counted as visible
+    #[wasm_bindgen(getter)]
+    pub fn min_pixels(&self) -> f64 {
+        self.min_pixels
+    }
+
+    #[wasm_bindgen(setter)]
+    pub fn set_min_pixels(&mut self, min_pixels: f64) {
+        self.min_pixels = min_pixels.max(0.0);
+    }
+
+    // Approximate projected radius (pixels) of a sphere
+    pub fn pixel_radius(&self, center: &Vec3, radius: f64) -> f64 {
+        let distance = center.distance(&self.position);
+        if distance <= radius {
+            return f64::INFINITY;
+        }
+        radius * self.pixel_scale / distance
+    }
+
+    // Whether any of a sphere lies inside the frustum and it projects to at
+    // least min_pixels
+    pub fn is_visible(&self, center: &Vec3, radius: f64) -> bool {
+        self.in_frustum(center, radius) && self.pixel_radius(center, radius) >= self.min_pixels
+    }
+}
+
+impl CameraView {
+    fn in_frustum(&self, center: &Vec3, radius: f64) -> bool {
+        self.planes
+            .iter()
+            .all(|p| p[0] * center.x + p[1] * center.y + p[2] * center.z + p[3] >= -radius)
+    }
+}
+
+// The visible subset of a list: source indices in order, and their scene
+// positions packed [x, y, z, ...]
+#[wasm_bindgen]
+#[derive(Debug, Clone, Default)]
+pub struct VisibleSet {
+    indices: Vec<u32>,
+    positions: Vec<f32>,
+}
+
+#[wasm_bindgen]
+impl VisibleSet {
+    #[wasm_bindgen(getter)]
+    pub fn count(&self) -> usize {
+        self.indices.len()
+    }
+
+    pub fn indices(&self) -> Uint32Array {
+        Uint32Array::from(self.indices.as_slice())
+    }
+
+    pub fn positions(&self) -> Float32Array {
+        Float32Array::from(self.positions.as_slice())
+    }
+}
+
+impl VisibleSet {
+    // Keep the (position, radius) spheres the camera can see
+    pub(crate) fn cull(camera: &CameraView, spheres: impl Iterator<Item = (Vec3, f64)>) -> VisibleSet {
+        let mut visible = VisibleSet::default();
+        for (index, (position, radius)) in spheres.enumerate() {
+            if camera.is_visible(&position, radius) {
+                visible.indices.push(index as u32);
+                visible.positions.extend_from_slice(&[position.x as f32, position.y as f32, position.z as f32]);
+            }
+        }
+        visible
+    }
+}
+
+// Cull arbitrary spheres: scene positions packed [x, y, z, ...] with one
+// radius each, or a single radius shared by all
+#[wasm_bindgen]
+pub fn cull_spheres(camera: &CameraView, positions: &[f64], radii: &[f64]) -> Result<VisibleSet, JsError> {
+    let count = positions.len() / 3;
+    if !positions.len().is_multiple_of(3) || (radii.len() != 1 && radii.len() != count) {
+        return Err(SimulationError::invalid_argument(&format!(
+            "Expected packed positions and 1 or {} radii, got {} values and {} radii",
+            count,
+            positions.len(),
+            radii.len()
+        ))
+        .into());
+    }
+    let spheres = positions
+        .chunks_exact(3)
+        .enumerate()
+        .map(|(index, p)| (Vec3::new(p[0], p[1], p[2]), radii[index.min(radii.len() - 1)]));
+    Ok(VisibleSet::cull(camera, spheres))
+}
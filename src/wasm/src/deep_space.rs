@@ -0,0 +1,556 @@
+// Deep-space terms of SDP4 for satellites with periods of 225 minutes or more
+//
+// Lunar and solar gravity add secular rates and long-period periodics to the
+// mean elements, and orbits near the 12-hour and 24-hour commensurabilities
+// with Earth's rotation pick up geopotential resonances, integrated
+// numerically from epoch. This follows dscom, dsinit, dspace and dpper of
+// Vallado et al. (2006).
+
+use std::f64::consts::PI;
+
+use crate::observer::sidereal_time;
+
+const TWO_PI: f64 = 2.0 * PI;
+
+// Eccentricities, mean motions (radians/minute) and perturbation strengths of
+// the Sun and Moon as seen from Earth
+const SOLAR_ECCENTRICITY: f64 = 0.01675;
+const LUNAR_ECCENTRICITY: f64 = 0.05490;
+const SOLAR_MOTION: f64 = 1.19459e-5;
+const LUNAR_MOTION: f64 = 1.5835218e-4;
+const SOLAR_STRENGTH: f64 = 2.9864797e-6;
+const LUNAR_STRENGTH: f64 = 4.7968065e-7;
+
+// Earth's rotation rate (radians/minute)
+const EARTH_ROTATION: f64 = 4.3752690880113e-3;
+
+// Resonance integrator step (minutes) and half its square
+const STEP: f64 = 720.0;
+const HALF_STEP_SQUARED: f64 = 259200.0;
+
+// Inclinations this close to 0° or 180° take no lunar-solar node rate
+const EQUATORIAL_LIMIT: f64 = 5.2359877e-2;
+
+// Mean elements (radians, radians/minute) handed between SGP4 and the
+// deep-space terms
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct MeanElements {
+    pub(crate) eccentricity: f64,
+    pub(crate) inclination: f64,
+    pub(crate) node: f64,
+    pub(crate) perigee: f64,
+    pub(crate) mean_anomaly: f64,
+    pub(crate) mean_motion: f64,
+}
+
+// Orientation of a perturbing body's orbit relative to the satellite's, with
+// the coefficients its secular rates and periodics are built from
+#[derive(Debug, Clone, Copy)]
+struct Perturbation {
+    s1: f64,
+    s2: f64,
+    s3: f64,
+    s4: f64,
+    s5: f64,
+    s6: f64,
+    s7: f64,
+    z1: f64,
+    z2: f64,
+    z3: f64,
+    z11: f64,
+    z12: f64,
+    z13: f64,
+    z21: f64,
+    z22: f64,
+    z23: f64,
+    z31: f64,
+    z32: f64,
+    z33: f64,
+}
+
+impl Perturbation {
+    // Cosines and sines of the body's argument of perigee, inclination and
+    // node on the equator, and its strength
+    fn new(body: [(f64, f64); 3], strength: f64, elements: &MeanElements) -> Perturbation {
+        let [(zcosg, zsing), (zcosi, zsini), (zcosh, zsinh)] = body;
+        let (sinim, cosim) = elements.inclination.sin_cos();
+        let (sinomm, cosomm) = elements.perigee.sin_cos();
+        let em = elements.eccentricity;
+        let emsq = em * em;
+        let betasq = 1.0 - emsq;
+        let rtemsq = betasq.sqrt();
+
+        let a1 = zcosg * zcosh + zsing * zcosi * zsinh;
+        let a3 = -zsing * zcosh + zcosg * zcosi * zsinh;
+        let a7 = -zcosg * zsinh + zsing * zcosi * zcosh;
+        let a8 = zsing * zsini;
+        let a9 = zsing * zsinh + zcosg * zcosi * zcosh;
+        let a10 = zcosg * zsini;
+        let a2 = cosim * a7 + sinim * a8;
+        let a4 = cosim * a9 + sinim * a10;
+        let a5 = -sinim * a7 + cosim * a8;
+        let a6 = -sinim * a9 + cosim * a10;
+
+        let x1 = a1 * cosomm + a2 * sinomm;
+        let x2 = a3 * cosomm + a4 * sinomm;
+        let x3 = -a1 * sinomm + a2 * cosomm;
+        let x4 = -a3 * sinomm + a4 * cosomm;
+        let x5 = a5 * sinomm;
+        let x6 = a6 * sinomm;
+        let x7 = a5 * cosomm;
+        let x8 = a6 * cosomm;
+
+        let z31 = 12.0 * x1 * x1 - 3.0 * x3 * x3;
+        let z32 = 24.0 * x1 * x2 - 6.0 * x3 * x4;
+        let z33 = 12.0 * x2 * x2 - 3.0 * x4 * x4;
+        let z1 = 3.0 * (a1 * a1 + a2 * a2) + z31 * emsq;
+        let z2 = 6.0 * (a1 * a3 + a2 * a4) + z32 * emsq;
+        let z3 = 3.0 * (a3 * a3 + a4 * a4) + z33 * emsq;
+        let z11 = -6.0 * a1 * a5 + emsq * (-24.0 * x1 * x7 - 6.0 * x3 * x5);
+        let z12 = -6.0 * (a1 * a6 + a3 * a5) + emsq * (-24.0 * (x2 * x7 + x1 * x8) - 6.0 * (x3 * x6 + x4 * x5));
+        let z13 = -6.0 * a3 * a6 + emsq * (-24.0 * x2 * x8 - 6.0 * x4 * x6);
+        let z21 = 6.0 * a2 * a5 + emsq * (24.0 * x1 * x5 - 6.0 * x3 * x7);
+        let z22 = 6.0 * (a4 * a5 + a2 * a6) + emsq * (24.0 * (x2 * x5 + x1 * x6) - 6.0 * (x4 * x7 + x3 * x8));
+        let z23 = 6.0 * a4 * a6 + emsq * (24.0 * x2 * x6 - 6.0 * x4 * x8);
+
+        let s3 = strength / elements.mean_motion;
+        let s4 = s3 * rtemsq;
+        Perturbation {
+            s1: -15.0 * em * s4,
+            s2: -0.5 * s3 / rtemsq,
+            s3,
+            s4,
+            s5: x1 * x3 + x2 * x4,
+            s6: x2 * x3 + x1 * x4,
+            s7: x2 * x4 - x1 * x3,
+            z1: 2.0 * z1 + betasq * z31,
+            z2: 2.0 * z2 + betasq * z32,
+            z3: 2.0 * z3 + betasq * z33,
+            z11,
+            z12,
+            z13,
+            z21,
+            z22,
+            z23,
+            z31,
+            z32,
+            z33,
+        }
+    }
+
+    // Secular rates of eccentricity, inclination, mean anomaly, perigee and
+    // node for a body of the given mean motion
+    fn rates(&self, motion: f64, elements: &MeanElements) -> [f64; 5] {
+        let emsq = elements.eccentricity * elements.eccentricity;
+        let (sinim, cosim) = elements.inclination.sin_cos();
+        let inclination = elements.inclination;
+        let mut node = -motion * self.s2 * (self.z21 + self.z23);
+        if !(EQUATORIAL_LIMIT..=PI - EQUATORIAL_LIMIT).contains(&inclination) {
+            node = 0.0;
+        }
+        if sinim != 0.0 {
+            node /= sinim;
+        }
+        [
+            self.s1 * motion * self.s5,
+            self.s2 * motion * (self.z11 + self.z13),
+            -motion * self.s3 * (self.z1 + self.z3 - 14.0 - 6.0 * emsq),
+            self.s4 * motion * (self.z31 + self.z33 - 6.0) - cosim * node,
+            node,
+        ]
+    }
+
+    // Long-period periodic coefficients for a body of the given orbital
+    // eccentricity and mean anomaly at epoch
+    fn periodics(&self, eccentricity: f64, anomaly: f64, motion: f64, elements: &MeanElements) -> Periodics {
+        let emsq = elements.eccentricity * elements.eccentricity;
+        Periodics {
+            e2: 2.0 * self.s1 * self.s6,
+            e3: 2.0 * self.s1 * self.s7,
+            i2: 2.0 * self.s2 * self.z12,
+            i3: 2.0 * self.s2 * (self.z13 - self.z11),
+            l2: -2.0 * self.s3 * self.z2,
+            l3: -2.0 * self.s3 * (self.z3 - self.z1),
+            l4: -2.0 * self.s3 * (-21.0 - 9.0 * emsq) * eccentricity,
+            gh2: 2.0 * self.s4 * self.z32,
+            gh3: 2.0 * self.s4 * (self.z33 - self.z31),
+            gh4: -18.0 * self.s4 * eccentricity,
+            h2: -2.0 * self.s2 * self.z22,
+            h3: -2.0 * self.s2 * (self.z23 - self.z21),
+            eccentricity,
+            anomaly,
+            motion,
+        }
+    }
+}
+
+// Long-period periodics of one perturbing body
+#[derive(Debug, Clone, Copy)]
+struct Periodics {
+    e2: f64,
+    e3: f64,
+    i2: f64,
+    i3: f64,
+    l2: f64,
+    l3: f64,
+    l4: f64,
+    gh2: f64,
+    gh3: f64,
+    gh4: f64,
+    h2: f64,
+    h3: f64,
+    eccentricity: f64,
+    anomaly: f64,
+    motion: f64,
+}
+
+impl Periodics {
+    // Offsets of eccentricity, inclination, mean longitude, perigee (times
+    // sin i, before the node is removed) and node (times sin i) after t minutes
+    fn at(&self, t: f64) -> [f64; 5] {
+        let zm = self.anomaly + self.motion * t;
+        let zf = zm + 2.0 * self.eccentricity * zm.sin();
+        let (sinzf, coszf) = zf.sin_cos();
+        let f2 = 0.5 * sinzf * sinzf - 0.25;
+        let f3 = -0.5 * sinzf * coszf;
+        [
+            self.e2 * f2 + self.e3 * f3,
+            self.i2 * f2 + self.i3 * f3,
+            self.l2 * f2 + self.l3 * f3 + self.l4 * sinzf,
+            self.gh2 * f2 + self.gh3 * f3 + self.gh4 * sinzf,
+            self.h2 * f2 + self.h3 * f3,
+        ]
+    }
+}
+
+// Geopotential resonance coefficients
+#[derive(Debug, Clone, Copy)]
+enum ResonanceTerms {
+    // Geosynchronous (24-hour) orbits
+    Synchronous([f64; 3]),
+    // Eccentric 12-hour orbits such as Molniya
+    HalfDay([f64; 10]),
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Resonance {
+    terms: ResonanceTerms,
+    // Resonant angle at epoch and its rate less the mean motion
+    xlamo: f64,
+    xfact: f64,
+}
+
+impl Resonance {
+    // Rate of the mean motion and its derivative, for the resonant angle xli
+    // and mean motion xni at `atime` minutes after epoch
+    fn derivatives(&self, xli: f64, xni: f64, perigee: f64) -> (f64, f64) {
+        let xldot = xni + self.xfact;
+        let (xndt, xnddt) = match self.terms {
+            ResonanceTerms::Synchronous([del1, del2, del3]) => {
+                const FASX2: f64 = 0.13130908;
+                const FASX4: f64 = 2.8843198;
+                const FASX6: f64 = 0.37448087;
+                (
+                    del1 * (xli - FASX2).sin() + del2 * (2.0 * (xli - FASX4)).sin() + del3 * (3.0 * (xli - FASX6)).sin(),
+                    del1 * (xli - FASX2).cos()
+                        + 2.0 * del2 * (2.0 * (xli - FASX4)).cos()
+                        + 3.0 * del3 * (3.0 * (xli - FASX6)).cos(),
+                )
+            }
+            ResonanceTerms::HalfDay([d2201, d2211, d3210, d3222, d4410, d4422, d5220, d5232, d5421, d5433]) => {
+                const G22: f64 = 5.7686396;
+                const G32: f64 = 0.95240898;
+                const G44: f64 = 1.8014998;
+                const G52: f64 = 1.0508330;
+                const G54: f64 = 4.4108898;
+                let (xomi, x2li) = (perigee, 2.0 * xli);
+                let x2omi = 2.0 * xomi;
+                (
+                    d2201 * (x2omi + xli - G22).sin()
+                        + d2211 * (xli - G22).sin()
+                        + d3210 * (xomi + xli - G32).sin()
+                        + d3222 * (-xomi + xli - G32).sin()
+                        + d4410 * (x2omi + x2li - G44).sin()
+                        + d4422 * (x2li - G44).sin()
+                        + d5220 * (xomi + xli - G52).sin()
+                        + d5232 * (-xomi + xli - G52).sin()
+                        + d5421 * (xomi + x2li - G54).sin()
+                        + d5433 * (-xomi + x2li - G54).sin(),
+                    d2201 * (x2omi + xli - G22).cos()
+                        + d2211 * (xli - G22).cos()
+                        + d3210 * (xomi + xli - G32).cos()
+                        + d3222 * (-xomi + xli - G32).cos()
+                        + d5220 * (xomi + xli - G52).cos()
+                        + d5232 * (-xomi + xli - G52).cos()
+                        + 2.0
+                            * (d4410 * (x2omi + x2li - G44).cos()
+                                + d4422 * (x2li - G44).cos()
+                                + d5421 * (xomi + x2li - G54).cos()
+                                + d5433 * (-xomi + x2li - G54).cos()),
+                )
+            }
+        };
+        (xndt, xnddt * xldot)
+    }
+}
+
+// Deep-space state fixed at epoch
+#[derive(Debug, Clone)]
+pub(crate) struct DeepSpace {
+    solar: Periodics,
+    lunar: Periodics,
+    // Lunar-solar secular rates of eccentricity, inclination, mean anomaly,
+    // perigee and node (per minute)
+    rates: [f64; 5],
+    resonance: Option<Resonance>,
+    // Greenwich sidereal angle at epoch (radians)
+    sidereal: f64,
+    mean_motion: f64,
+    perigee: f64,
+    perigee_rate: f64,
+}
+
+impl DeepSpace {
+    // Initialise from the epoch (Julian date), the mean elements with the
+    // un-Kozaied mean motion, the SGP4 secular rates of mean anomaly, perigee
+    // and node, and the gravitational constant in Earth radii and minutes
+    pub(crate) fn new(epoch: f64, elements: &MeanElements, secular: [f64; 3], xke: f64) -> DeepSpace {
+        let [mdot, argpdot, nodedot] = secular;
+        let (snodm, cnodm) = elements.node.sin_cos();
+        let (sinim, cosim) = elements.inclination.sin_cos();
+        let emsq = elements.eccentricity * elements.eccentricity;
+
+        // Lunar orbit at epoch, from days since 1900 January 0.5
+        let day = epoch - 2415020.0;
+        let xnodce = (4.5236020 - 9.2422029e-4 * day).rem_euclid(TWO_PI);
+        let (stem, ctem) = xnodce.sin_cos();
+        let zcosil = 0.91375164 - 0.03568096 * ctem;
+        let zsinil = (1.0 - zcosil * zcosil).sqrt();
+        let zsinhl = 0.089683511 * stem / zsinil;
+        let zcoshl = (1.0 - zsinhl * zsinhl).sqrt();
+        let gam = 5.8351514 + 0.0019443680 * day;
+        let zx = (0.39785416 * stem / zsinil).atan2(zcoshl * ctem + 0.91744867 * zsinhl * stem);
+        let (zsingl, zcosgl) = (gam + zx - xnodce).sin_cos();
+        let zmol = (4.7199672 + 0.22997150 * day - gam).rem_euclid(TWO_PI);
+        let zmos = (6.2565837 + 0.017201977 * day).rem_euclid(TWO_PI);
+
+        let solar = Perturbation::new(
+            [(0.1945905, -0.98088458), (0.91744867, 0.39785416), (cnodm, snodm)],
+            SOLAR_STRENGTH,
+            elements,
+        );
+        let lunar = Perturbation::new(
+            [
+                (zcosgl, zsingl),
+                (zcosil, zsinil),
+                (zcoshl * cnodm + zsinhl * snodm, snodm * zcoshl - cnodm * zsinhl),
+            ],
+            LUNAR_STRENGTH,
+            elements,
+        );
+
+        let solar_rates = solar.rates(SOLAR_MOTION, elements);
+        let lunar_rates = lunar.rates(LUNAR_MOTION, elements);
+        let rates: [f64; 5] = std::array::from_fn(|k| solar_rates[k] + lunar_rates[k]);
+        let [_, _, dmdt, domdt, dnodt] = rates;
+
+        // Resonances of 24-hour orbits and of eccentric 12-hour ones
+        let sidereal = sidereal_time(epoch).to_radians();
+        let nm = elements.mean_motion;
+        let em = elements.eccentricity;
+        let aonv = (nm / xke).powf(2.0 / 3.0);
+        let mo = elements.mean_anomaly;
+        let nodeo = elements.node;
+        let resonance = if nm > 0.0034906585 && nm < 0.0052359877 {
+            const Q22: f64 = 1.7891679e-6;
+            const Q31: f64 = 2.1460748e-6;
+            const Q33: f64 = 2.2123015e-7;
+            let g200 = 1.0 + emsq * (-2.5 + 0.8125 * emsq);
+            let g310 = 1.0 + 2.0 * emsq;
+            let g300 = 1.0 + emsq * (-6.0 + 6.60937 * emsq);
+            let f220 = 0.75 * (1.0 + cosim) * (1.0 + cosim);
+            let f311 = 0.9375 * sinim * sinim * (1.0 + 3.0 * cosim) - 0.75 * (1.0 + cosim);
+            let f330 = 1.875 * (1.0 + cosim).powi(3);
+            let del1 = 3.0 * nm * nm * aonv * aonv;
+            Some(Resonance {
+                terms: ResonanceTerms::Synchronous([
+                    del1 * f311 * g310 * Q31 * aonv,
+                    2.0 * del1 * f220 * g200 * Q22,
+                    3.0 * del1 * f330 * g300 * Q33 * aonv,
+                ]),
+                xlamo: (mo + nodeo + elements.perigee - sidereal).rem_euclid(TWO_PI),
+                xfact: mdot + argpdot + nodedot - EARTH_ROTATION + dmdt + domdt + dnodt - nm,
+            })
+        } else if (8.26e-3..=9.24e-3).contains(&nm) && em >= 0.5 {
+            const ROOT22: f64 = 1.7891679e-6;
+            const ROOT32: f64 = 3.7393792e-7;
+            const ROOT44: f64 = 7.3636953e-9;
+            const ROOT52: f64 = 1.1428639e-7;
+            const ROOT54: f64 = 2.1765803e-9;
+            let cosisq = cosim * cosim;
+            let eoc = em * emsq;
+            let g201 = -0.306 - (em - 0.64) * 0.440;
+            let (g211, g310, g322, g410, g422, g520) = if em <= 0.65 {
+                (
+                    3.616 - 13.2470 * em + 16.2900 * emsq,
+                    -19.302 + 117.3900 * em - 228.4190 * emsq + 156.5910 * eoc,
+                    -18.9068 + 109.7927 * em - 214.6334 * emsq + 146.5816 * eoc,
+                    -41.122 + 242.6940 * em - 471.0940 * emsq + 313.9530 * eoc,
+                    -146.407 + 841.8800 * em - 1629.014 * emsq + 1083.4350 * eoc,
+                    -532.114 + 3017.977 * em - 5740.032 * emsq + 3708.2760 * eoc,
+                )
+            } else {
+                (
+                    -72.099 + 331.819 * em - 508.738 * emsq + 266.724 * eoc,
+                    -346.844 + 1582.851 * em - 2415.925 * emsq + 1246.113 * eoc,
+                    -342.585 + 1554.908 * em - 2366.899 * emsq + 1215.972 * eoc,
+                    -1052.797 + 4758.686 * em - 7193.992 * emsq + 3651.957 * eoc,
+                    -3581.690 + 16178.110 * em - 24462.770 * emsq + 12422.520 * eoc,
+                    if em > 0.715 {
+                        -5149.66 + 29936.92 * em - 54087.36 * emsq + 31324.56 * eoc
+                    } else {
+                        1464.74 - 4664.75 * em + 3763.64 * emsq
+                    },
+                )
+            };
+            let (g533, g521, g532) = if em < 0.7 {
+                (
+                    -919.22770 + 4988.6100 * em - 9064.7700 * emsq + 5542.21 * eoc,
+                    -822.71072 + 4568.6173 * em - 8491.4146 * emsq + 5337.524 * eoc,
+                    -853.66600 + 4690.2500 * em - 8624.7700 * emsq + 5341.4 * eoc,
+                )
+            } else {
+                (
+                    -37995.780 + 161616.52 * em - 229838.20 * emsq + 109377.94 * eoc,
+                    -51752.104 + 218913.95 * em - 309468.16 * emsq + 146349.42 * eoc,
+                    -40023.880 + 170470.89 * em - 242699.48 * emsq + 115605.82 * eoc,
+                )
+            };
+
+            let sini2 = sinim * sinim;
+            let f220 = 0.75 * (1.0 + 2.0 * cosim + cosisq);
+            let f221 = 1.5 * sini2;
+            let f321 = 1.875 * sinim * (1.0 - 2.0 * cosim - 3.0 * cosisq);
+            let f322 = -1.875 * sinim * (1.0 + 2.0 * cosim - 3.0 * cosisq);
+            let f441 = 35.0 * sini2 * f220;
+            let f442 = 39.3750 * sini2 * sini2;
+            let f522 = 9.84375
+                * sinim
+                * (sini2 * (1.0 - 2.0 * cosim - 5.0 * cosisq) + 0.33333333 * (-2.0 + 4.0 * cosim + 6.0 * cosisq));
+            let f523 = sinim
+                * (4.92187512 * sini2 * (-2.0 - 4.0 * cosim + 10.0 * cosisq)
+                    + 6.56250012 * (1.0 + 2.0 * cosim - 3.0 * cosisq));
+            let f542 = 29.53125 * sinim * (2.0 - 8.0 * cosim + cosisq * (-12.0 + 8.0 * cosim + 10.0 * cosisq));
+            let f543 = 29.53125 * sinim * (-2.0 - 8.0 * cosim + cosisq * (12.0 + 8.0 * cosim - 10.0 * cosisq));
+
+            let temp1 = 3.0 * nm * nm * aonv * aonv;
+            let temp2 = temp1 * aonv;
+            let temp3 = temp2 * aonv;
+            let temp4 = temp3 * aonv;
+            Some(Resonance {
+                terms: ResonanceTerms::HalfDay([
+                    temp1 * ROOT22 * f220 * g201,
+                    temp1 * ROOT22 * f221 * g211,
+                    temp2 * ROOT32 * f321 * g310,
+                    temp2 * ROOT32 * f322 * g322,
+                    2.0 * temp3 * ROOT44 * f441 * g410,
+                    2.0 * temp3 * ROOT44 * f442 * g422,
+                    temp4 * ROOT52 * f522 * g520,
+                    temp4 * ROOT52 * f523 * g532,
+                    2.0 * temp4 * ROOT54 * f542 * g521,
+                    2.0 * temp4 * ROOT54 * f543 * g533,
+                ]),
+                xlamo: (mo + 2.0 * nodeo - 2.0 * sidereal).rem_euclid(TWO_PI),
+                xfact: mdot + dmdt + 2.0 * (nodedot + dnodt - EARTH_ROTATION) - nm,
+            })
+        } else {
+            None
+        };
+
+        DeepSpace {
+            solar: solar.periodics(SOLAR_ECCENTRICITY, zmos, SOLAR_MOTION, elements),
+            lunar: lunar.periodics(LUNAR_ECCENTRICITY, zmol, LUNAR_MOTION, elements),
+            rates,
+            resonance,
+            sidereal,
+            mean_motion: nm,
+            perigee: elements.perigee,
+            perigee_rate: argpdot,
+        }
+    }
+
+    // Add the lunar-solar secular rates to mean elements t minutes after
+    // epoch and, for resonant orbits, integrate the mean motion and anomaly
+    pub(crate) fn secular(&self, t: f64, elements: &mut MeanElements) {
+        let [dedt, didt, dmdt, domdt, dnodt] = self.rates;
+        elements.eccentricity += dedt * t;
+        elements.inclination += didt * t;
+        elements.perigee += domdt * t;
+        elements.node += dnodt * t;
+        elements.mean_anomaly += dmdt * t;
+        let Some(resonance) = &self.resonance else { return };
+
+        // Euler-Maclaurin steps from epoch, then a Taylor step to t
+        let step = if t > 0.0 { STEP } else { -STEP };
+        let (mut atime, mut xli, mut xni) = (0.0, resonance.xlamo, self.mean_motion);
+        let (xndt, xnddt) = loop {
+            let (xndt, xnddt) = resonance.derivatives(xli, xni, self.perigee + self.perigee_rate * atime);
+            if (t - atime).abs() < STEP {
+                break (xndt, xnddt);
+            }
+            xli += (xni + resonance.xfact) * step + xndt * HALF_STEP_SQUARED;
+            xni += xndt * step + xnddt * HALF_STEP_SQUARED;
+            atime += step;
+        };
+        let ft = t - atime;
+        let xldot = xni + resonance.xfact;
+        let xl = xli + xldot * ft + xndt * ft * ft * 0.5;
+        let theta = (self.sidereal + t * EARTH_ROTATION).rem_euclid(TWO_PI);
+        elements.mean_anomaly = match resonance.terms {
+            ResonanceTerms::Synchronous(_) => xl - elements.node - elements.perigee + theta,
+            ResonanceTerms::HalfDay(_) => xl - 2.0 * elements.node + 2.0 * theta,
+        };
+        elements.mean_motion = xni + xndt * ft + xnddt * ft * ft * 0.5;
+    }
+
+    // Apply the lunar-solar long-period periodics t minutes after epoch,
+    // or None if they push the eccentricity out of range
+    pub(crate) fn periodics(&self, t: f64, elements: &MeanElements) -> Option<MeanElements> {
+        let solar = self.solar.at(t);
+        let lunar = self.lunar.at(t);
+        let [pe, pinc, pl, pgh, ph] = std::array::from_fn(|k| solar[k] + lunar[k]);
+
+        let mut perturbed = *elements;
+        perturbed.eccentricity += pe;
+        perturbed.inclination += pinc;
+        let (sinip, cosip) = perturbed.inclination.sin_cos();
+        if perturbed.inclination >= 0.2 {
+            let ph = ph / sinip;
+            perturbed.perigee += pgh - cosip * ph;
+            perturbed.node += ph;
+            perturbed.mean_anomaly += pl;
+        } else {
+            // Lyddane's modification for low inclinations, where the node is
+            // poorly defined
+            let (sinop, cosop) = perturbed.node.sin_cos();
+            let alfdp = sinip * sinop + ph * cosop + pinc * cosip * sinop;
+            let betdp = sinip * cosop - ph * sinop + pinc * cosip * cosop;
+            let node = perturbed.node.rem_euclid(TWO_PI);
+            let xls = perturbed.mean_anomaly + perturbed.perigee + cosip * node + pl + pgh - pinc * node * sinip;
+            let mut new_node = alfdp.atan2(betdp).rem_euclid(TWO_PI);
+            if (node - new_node).abs() > PI {
+                new_node += if new_node < node { TWO_PI } else { -TWO_PI };
+            }
+            perturbed.mean_anomaly += pl;
+            perturbed.node = new_node;
+            perturbed.perigee = xls - perturbed.mean_anomaly - cosip * new_node;
+        }
+
+        if perturbed.inclination < 0.0 {
+            perturbed.inclination = -perturbed.inclination;
+            perturbed.node += PI;
+            perturbed.perigee -= PI;
+        }
+        (0.0..=1.0).contains(&perturbed.eccentricity).then_some(perturbed)
+    }
+}
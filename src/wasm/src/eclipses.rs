@@ -0,0 +1,255 @@
+use wasm_bindgen::prelude::*;
+use serde::{Deserialize, Serialize};
+use js_sys::Float64Array;
+
+use crate::errors::check_date_range;
+use crate::observer::geocentric_ecliptic;
+use crate::precession::{apparent_sidereal_time, ecliptic_to_true_equator};
+use crate::search::{find_minima, golden_section_min};
+use crate::units::{EARTH_RADIUS_KM, SOLAR_RADIUS_KM};
+use crate::{Vec3, AU_KM};
+
+// Radius of the Moon (km)
+const MOON_RADIUS: f64 = 1737.4;
+
+// Enlargement of Earth's shadow by its atmosphere (Danjon)
+const SHADOW_ENLARGEMENT: f64 = 1.02;
+
+// Time step (days) between ground track samples, and half-width of the sampled window
+const TRACK_STEP: f64 = 2.0 / 1440.0;
+const TRACK_WINDOW: f64 = 0.2;
+
+// Longest range (days) searched in one call, a century
+const MAX_SEARCH_SPAN: f64 = 36525.0;
+
+// Kind of eclipse
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum EclipseKind {
+    SolarPartial = 0,
+    SolarAnnular = 1,
+    SolarTotal = 2,
+    LunarPenumbral = 3,
+    LunarPartial = 4,
+    LunarTotal = 5,
+}
+
+// A predicted solar or lunar eclipse
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[wasm_bindgen]
+pub struct Eclipse {
+    kind: EclipseKind,
+    maximum: f64,
+    magnitude: f64,
+    gamma: f64,
+    ground_track: Vec<f64>,
+}
+
+#[wasm_bindgen]
+impl Eclipse {
+    #[wasm_bindgen(getter)]
+    pub fn kind(&self) -> EclipseKind {
+        self.kind
+    }
+
+    // Julian date of greatest eclipse
+    #[wasm_bindgen(getter)]
+    pub fn maximum(&self) -> f64 {
+        self.maximum
+    }
+
+    // Fraction of the Sun's diameter covered (solar), or umbral magnitude
+    // (penumbral magnitude for penumbral lunar eclipses)
+    #[wasm_bindgen(getter)]
+    pub fn magnitude(&self) -> f64 {
+        self.magnitude
+    }
+
+    // Least distance of the shadow axis from Earth's center (solar) or of the
+    // Moon from the shadow axis (lunar), in Earth radii
+    #[wasm_bindgen(getter)]
+    pub fn gamma(&self) -> f64 {
+        self.gamma
+    }
+
+    // Central line of a solar eclipse as packed [julian_date, lat, lon, ...]
+    // in degrees; empty for lunar and non-central eclipses
+    #[wasm_bindgen(getter)]
+    pub fn ground_track(&self) -> Float64Array {
+        Float64Array::from(self.ground_track.as_slice())
+    }
+}
+
+// Geocentric ecliptic positions (km) of the Sun and Moon
+fn sun_and_moon(julian_date: f64) -> (Vec3, Vec3) {
+    let sun = geocentric_ecliptic("Sun", julian_date).unwrap_or(Vec3::new(1.0, 0.0, 0.0));
+    let moon = geocentric_ecliptic("Moon", julian_date).unwrap_or(Vec3::new(0.0, 0.0, 0.0));
+    (sun.scale(AU_KM), moon.scale(AU_KM))
+}
+
+// Angle between two vectors (radians)
+fn separation(a: &Vec3, b: &Vec3) -> f64 {
+    (a.dot(b) / (a.length() * b.length())).clamp(-1.0, 1.0).acos()
+}
+
+// Shadow axis of the Moon: (closest point to Earth's center, unit direction)
+fn lunar_shadow_axis(sun: &Vec3, moon: &Vec3) -> (Vec3, Vec3) {
+    let axis = moon.sub(sun);
+    let direction = axis.scale(1.0 / axis.length());
+    let along = -moon.dot(&direction);
+    (moon.add(&direction.scale(along)), direction)
+}
+
+// Point (km) where the Moon's shadow axis first meets Earth's surface, if it does
+fn axis_surface_point(sun: &Vec3, moon: &Vec3) -> Option<Vec3> {
+    let (closest, direction) = lunar_shadow_axis(sun, moon);
+    let miss = closest.length();
+    if miss >= EARTH_RADIUS_KM {
+        return None;
+    }
+
+    // Step back from the closest point to the sunward intersection with the sphere
+    Some(closest.sub(&direction.scale((EARTH_RADIUS_KM * EARTH_RADIUS_KM - miss * miss).sqrt())))
+}
+
+// Geographic latitude and longitude (degrees) of the sub-shadow point
+fn shadow_ground_point(julian_date: f64) -> Option<(f64, f64)> {
+    let (sun, moon) = sun_and_moon(julian_date);
+    let surface = axis_surface_point(&sun, &moon)?;
+    let equatorial = ecliptic_to_true_equator(&surface, julian_date);
+    let lat = (equatorial.z / equatorial.length()).asin().to_degrees();
+    let lon = (equatorial.y.atan2(equatorial.x).to_degrees() - apparent_sidereal_time(julian_date) + 180.0)
+        .rem_euclid(360.0)
+        - 180.0;
+    Some((lat, lon))
+}
+
+// Classify the solar eclipse, if any, at a new moon
+fn solar_eclipse(new_moon: f64) -> Option<Eclipse> {
+    let miss_distance = |jd: f64| {
+        let (sun, moon) = sun_and_moon(jd);
+        lunar_shadow_axis(&sun, &moon).0.length()
+    };
+    let maximum = golden_section_min(miss_distance, new_moon - 0.3, new_moon + 0.3, 1e-5);
+
+    let (sun, moon) = sun_and_moon(maximum);
+    let (closest, _) = lunar_shadow_axis(&sun, &moon);
+    let miss = closest.length();
+    let sun_moon = moon.sub(&sun).length();
+    let beyond_moon = closest.sub(&moon).length();
+
+    // Shadow radii in the plane through Earth's center; negative umbra means antumbra
+    let umbra = MOON_RADIUS - beyond_moon * (SOLAR_RADIUS_KM - MOON_RADIUS) / sun_moon;
+    let penumbra = MOON_RADIUS + beyond_moon * (SOLAR_RADIUS_KM + MOON_RADIUS) / sun_moon;
+    if miss > EARTH_RADIUS_KM + penumbra {
+        return None;
+    }
+
+    let surface = axis_surface_point(&sun, &moon);
+    let central = surface.is_some();
+    let (kind, magnitude) = match surface {
+        Some(ground) => {
+            // Ratio of apparent diameters seen from the sub-shadow point
+            let ratio = (MOON_RADIUS / moon.sub(&ground).length()) / (SOLAR_RADIUS_KM / sun.sub(&ground).length());
+            if umbra > 0.0 {
+                (EclipseKind::SolarTotal, ratio)
+            } else {
+                (EclipseKind::SolarAnnular, ratio)
+            }
+        }
+        None => (EclipseKind::SolarPartial, (EARTH_RADIUS_KM + penumbra - miss) / (penumbra - umbra)),
+    };
+
+    let mut ground_track = Vec::new();
+    if central {
+        let mut jd = maximum - TRACK_WINDOW;
+        while jd <= maximum + TRACK_WINDOW {
+            if let Some((lat, lon)) = shadow_ground_point(jd) {
+                ground_track.extend_from_slice(&[jd, lat, lon]);
+            }
+            jd += TRACK_STEP;
+        }
+    }
+
+    Some(Eclipse {
+        kind,
+        maximum,
+        magnitude,
+        gamma: miss / EARTH_RADIUS_KM,
+        ground_track,
+    })
+}
+
+// Classify the lunar eclipse, if any, at a full moon
+fn lunar_eclipse(full_moon: f64) -> Option<Eclipse> {
+    // Distance of the Moon from the axis of Earth's shadow, and along it
+    let shadow_offsets = |jd: f64| {
+        let (sun, moon) = sun_and_moon(jd);
+        let direction = sun.scale(-1.0 / sun.length());
+        let along = moon.dot(&direction);
+        (moon.sub(&direction.scale(along)).length(), along, sun.length())
+    };
+    let maximum = golden_section_min(|jd| shadow_offsets(jd).0, full_moon - 0.3, full_moon + 0.3, 1e-5);
+    let (miss, along, sun_distance) = shadow_offsets(maximum);
+
+    let umbra = EARTH_RADIUS_KM * SHADOW_ENLARGEMENT - along * (SOLAR_RADIUS_KM - EARTH_RADIUS_KM) / sun_distance;
+    let penumbra = EARTH_RADIUS_KM * SHADOW_ENLARGEMENT + along * (SOLAR_RADIUS_KM + EARTH_RADIUS_KM) / sun_distance;
+    let umbral_magnitude = (umbra + MOON_RADIUS - miss) / (2.0 * MOON_RADIUS);
+    let penumbral_magnitude = (penumbra + MOON_RADIUS - miss) / (2.0 * MOON_RADIUS);
+
+    let (kind, magnitude) = if umbral_magnitude >= 1.0 {
+        (EclipseKind::LunarTotal, umbral_magnitude)
+    } else if umbral_magnitude > 0.0 {
+        (EclipseKind::LunarPartial, umbral_magnitude)
+    } else if penumbral_magnitude > 0.0 {
+        (EclipseKind::LunarPenumbral, penumbral_magnitude)
+    } else {
+        return None;
+    };
+
+    Some(Eclipse {
+        kind,
+        maximum,
+        magnitude,
+        gamma: miss / EARTH_RADIUS_KM,
+        ground_track: Vec::new(),
+    })
+}
+
+// Search for solar and lunar eclipses between two Julian dates, in time order
+#[wasm_bindgen]
+pub fn eclipses_between(jd_start: f64, jd_end: f64) -> Result<Vec<Eclipse>, JsError> {
+    check_date_range(jd_start, jd_end, MAX_SEARCH_SPAN)?;
+
+    // Syzygies are the minima of Sun-Moon separation (new moon) and of
+    // anti-Sun-Moon separation (full moon)
+    let new_moons = find_minima(
+        |jd| {
+            let (sun, moon) = sun_and_moon(jd);
+            separation(&sun, &moon)
+        },
+        jd_start,
+        jd_end,
+        1.0,
+        1e-4,
+    );
+    let full_moons = find_minima(
+        |jd| {
+            let (sun, moon) = sun_and_moon(jd);
+            separation(&sun.scale(-1.0), &moon)
+        },
+        jd_start,
+        jd_end,
+        1.0,
+        1e-4,
+    );
+
+    let mut eclipses: Vec<Eclipse> = new_moons
+        .into_iter()
+        .filter_map(solar_eclipse)
+        .chain(full_moons.into_iter().filter_map(lunar_eclipse))
+        .filter(|eclipse| eclipse.maximum >= jd_start && eclipse.maximum <= jd_end)
+        .collect();
+    eclipses.sort_by(|a, b| a.maximum.total_cmp(&b.maximum));
+    Ok(eclipses)
+}
@@ -0,0 +1,106 @@
+// Errors returned to JavaScript by fallible entry points. Each message starts
+// with a machine-readable code, e.g. "UNKNOWN_BODY: Unknown body 'Vulcan'",
+// so callers can branch on err.message.split(':')[0].
+
+use wasm_bindgen::prelude::*;
+use std::fmt;
+
+// Julian dates bounding the long-term planetary elements, 3000 BC to AD 3000
+pub(crate) const MIN_JULIAN_DATE: f64 = 625673.5;
+pub(crate) const MAX_JULIAN_DATE: f64 = 2817152.5;
+
+// Category of an error
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorCode {
+    UnknownBody = 0,
+    InvalidDate = 1,
+    EpochOutOfRange = 2,
+    InvalidArgument = 3,
+}
+
+impl ErrorCode {
+    // Prefix of the error message
+    fn as_str(self) -> &'static str {
+        match self {
+            ErrorCode::UnknownBody => "UNKNOWN_BODY",
+            ErrorCode::InvalidDate => "INVALID_DATE",
+            ErrorCode::EpochOutOfRange => "EPOCH_OUT_OF_RANGE",
+            ErrorCode::InvalidArgument => "INVALID_ARGUMENT",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct SimulationError {
+    code: ErrorCode,
+    message: String,
+}
+
+impl SimulationError {
+    pub(crate) fn new(code: ErrorCode, message: String) -> SimulationError {
+        SimulationError { code, message }
+    }
+
+    pub(crate) fn unknown_body(name: &str) -> SimulationError {
+        SimulationError::new(ErrorCode::UnknownBody, format!("Unknown body '{}'", name))
+    }
+
+    pub(crate) fn invalid_argument(message: &str) -> SimulationError {
+        SimulationError::new(ErrorCode::InvalidArgument, message.to_string())
+    }
+}
+
+impl fmt::Display for SimulationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.code.as_str(), self.message)
+    }
+}
+
+impl std::error::Error for SimulationError {}
+
+// Reject NaN and infinite dates, for calculations valid at any epoch such as
+// calendars, time scales and long-term precession
+pub(crate) fn check_finite_date(julian_date: f64) -> Result<(), SimulationError> {
+    if !julian_date.is_finite() {
+        return Err(SimulationError::new(ErrorCode::InvalidDate, format!("Invalid Julian date {}", julian_date)));
+    }
+    Ok(())
+}
+
+// Reject NaN and infinite dates, and dates outside the range the ephemeris
+// covers
+pub(crate) fn check_julian_date(julian_date: f64) -> Result<(), SimulationError> {
+    check_finite_date(julian_date)?;
+    if !(MIN_JULIAN_DATE..=MAX_JULIAN_DATE).contains(&julian_date) {
+        return Err(SimulationError::new(
+            ErrorCode::EpochOutOfRange,
+            format!(
+                "Julian date {} is outside the supported range {} to {} (3000 BC to AD 3000)",
+                julian_date, MIN_JULIAN_DATE, MAX_JULIAN_DATE
+            ),
+        ));
+    }
+    Ok(())
+}
+
+// Validate both ends of a search range, which must run forwards and span at
+// most max_span days, so a mistyped end date cannot allocate without limit
+pub(crate) fn check_date_range(start: f64, end: f64, max_span: f64) -> Result<(), SimulationError> {
+    check_julian_date(start)?;
+    check_julian_date(end)?;
+    if start > end {
+        return Err(SimulationError::invalid_argument(&format!(
+            "Range start {} is after its end {}",
+            start, end
+        )));
+    }
+    if end - start > max_span {
+        return Err(SimulationError::invalid_argument(&format!(
+            "Range of {} days exceeds the limit of {} days",
+            end - start,
+            max_span
+        )));
+    }
+    Ok(())
+}
@@ -0,0 +1,284 @@
+use wasm_bindgen::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::errors::check_date_range;
+use crate::observer::{geocentric_ecliptic, planet_state};
+use crate::search::{golden_section_min, newton_extremum, newton_root};
+use crate::{Vec3, PLANET_DATA, PLANET_ELEMENTS};
+
+// Bodies considered for conjunctions as seen from Earth
+static SKY_BODIES: &[&str] = &["Moon", "Mercury", "Venus", "Mars", "Jupiter", "Saturn", "Uranus", "Neptune"];
+
+// Sampling interval (days) for event searches and refinement tolerance (days)
+const SAMPLE_STEP: f64 = 1.0;
+const TIME_TOLERANCE: f64 = 1e-5;
+
+// Longest range (days) searched in one call, a century
+const MAX_SEARCH_SPAN: f64 = 36525.0;
+
+// A close approach of two bodies on the sky
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[wasm_bindgen]
+pub struct Conjunction {
+    body_a: String,
+    body_b: String,
+    julian_date: f64,
+    separation: f64,
+}
+
+#[wasm_bindgen]
+impl Conjunction {
+    #[wasm_bindgen(getter)]
+    pub fn body_a(&self) -> String {
+        self.body_a.clone()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn body_b(&self) -> String {
+        self.body_b.clone()
+    }
+
+    // Julian date of minimum separation
+    #[wasm_bindgen(getter)]
+    pub fn julian_date(&self) -> f64 {
+        self.julian_date
+    }
+
+    // Minimum angular separation (degrees)
+    #[wasm_bindgen(getter)]
+    pub fn separation(&self) -> f64 {
+        self.separation
+    }
+}
+
+// Angle between two directions (degrees)
+pub(crate) fn angle_between(a: &Vec3, b: &Vec3) -> f64 {
+    (a.dot(b) / (a.length() * b.length())).clamp(-1.0, 1.0).acos().to_degrees()
+}
+
+// Geocentric angular separation (degrees) of two bodies
+pub(crate) fn sky_separation(body_a: &str, body_b: &str, julian_date: f64) -> f64 {
+    match (geocentric_ecliptic(body_a, julian_date), geocentric_ecliptic(body_b, julian_date)) {
+        (Some(a), Some(b)) => angle_between(&a, &b),
+        _ => 180.0,
+    }
+}
+
+// Find close approaches between pairs of planets, or a planet and the Moon,
+// as seen from Earth, sorted by date
+#[wasm_bindgen]
+pub fn find_conjunctions(jd_start: f64, jd_end: f64, max_separation_deg: f64) -> Result<Vec<Conjunction>, JsError> {
+    check_date_range(jd_start, jd_end, MAX_SEARCH_SPAN)?;
+
+    // Sample every body's direction once, then scan each pair for minima
+    let epochs = sample_epochs(jd_start, jd_end, SAMPLE_STEP);
+    let directions: Vec<Vec<Vec3>> = SKY_BODIES
+        .iter()
+        .map(|body| {
+            epochs
+                .iter()
+                .map(|&jd| geocentric_ecliptic(body, jd).unwrap_or(Vec3::new(1.0, 0.0, 0.0)))
+                .collect()
+        })
+        .collect();
+
+    let mut conjunctions = Vec::new();
+    for a in 0..SKY_BODIES.len() {
+        for b in (a + 1)..SKY_BODIES.len() {
+            let separations: Vec<f64> = (0..epochs.len())
+                .map(|k| angle_between(&directions[a][k], &directions[b][k]))
+                .collect();
+
+            for (window, values) in epochs.windows(3).zip(separations.windows(3)) {
+                if values[1] > values[0] || values[1] >= values[2] {
+                    continue;
+                }
+                if values[1] > max_separation_deg + 2.0 {
+                    continue;
+                }
+
+                let (body_a, body_b) = (SKY_BODIES[a], SKY_BODIES[b]);
+                let time = golden_section_min(
+                    |jd| sky_separation(body_a, body_b, jd),
+                    window[0],
+                    window[2],
+                    TIME_TOLERANCE,
+                );
+                let separation = sky_separation(body_a, body_b, time);
+                if separation <= max_separation_deg && time >= jd_start && time <= jd_end {
+                    conjunctions.push(Conjunction {
+                        body_a: body_a.to_string(),
+                        body_b: body_b.to_string(),
+                        julian_date: time,
+                        separation,
+                    });
+                }
+            }
+        }
+    }
+
+    conjunctions.sort_by(|x, y| x.julian_date.total_cmp(&y.julian_date));
+    Ok(conjunctions)
+}
+
+// Kind of calendar event
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum EventKind {
+    Opposition = 0,
+    GreatestEasternElongation = 1,
+    GreatestWesternElongation = 2,
+    Perihelion = 3,
+    Aphelion = 4,
+}
+
+// An astronomical event for a body
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[wasm_bindgen]
+pub struct AstronomicalEvent {
+    kind: EventKind,
+    body: String,
+    julian_date: f64,
+    value: f64,
+}
+
+#[wasm_bindgen]
+impl AstronomicalEvent {
+    #[wasm_bindgen(getter)]
+    pub fn kind(&self) -> EventKind {
+        self.kind
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn body(&self) -> String {
+        self.body.clone()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn julian_date(&self) -> f64 {
+        self.julian_date
+    }
+
+    // Elongation from the Sun (degrees) for oppositions and elongations,
+    // heliocentric distance (AU) for perihelion and aphelion
+    #[wasm_bindgen(getter)]
+    pub fn value(&self) -> f64 {
+        self.value
+    }
+}
+
+// Geocentric ecliptic longitude (degrees)
+fn ecliptic_longitude(body: &str, julian_date: f64) -> f64 {
+    geocentric_ecliptic(body, julian_date)
+        .map_or(0.0, |v| v.y.atan2(v.x).to_degrees())
+}
+
+// Longitude of a body east of the Sun, wrapped to (-180, 180]
+fn longitude_from_sun(body: &str, julian_date: f64) -> f64 {
+    let difference = (ecliptic_longitude(body, julian_date) - ecliptic_longitude("Sun", julian_date)).rem_euclid(360.0);
+    if difference > 180.0 { difference - 360.0 } else { difference }
+}
+
+// Evenly spaced sample epochs covering [start, end] with one step of margin
+fn sample_epochs(start: f64, end: f64, step: f64) -> Vec<f64> {
+    let mut epochs = Vec::new();
+    let mut jd = start - step;
+    while jd <= end + step {
+        epochs.push(jd);
+        jd += step;
+    }
+    epochs
+}
+
+// Times of oppositions of a superior planet, when it is 180 degrees from the Sun
+fn oppositions(body: &str, start: f64, end: f64, events: &mut Vec<AstronomicalEvent>) {
+    let offset = |jd: f64| {
+        let difference = longitude_from_sun(body, jd);
+        if difference > 0.0 { difference - 180.0 } else { difference + 180.0 }
+    };
+    let epochs = sample_epochs(start, end, SAMPLE_STEP);
+    for pair in epochs.windows(2) {
+        let (f0, f1) = (offset(pair[0]), offset(pair[1]));
+        // A true crossing rather than the wrap where the planet passes the Sun
+        if (f0 < 0.0) != (f1 < 0.0) && f0.abs() < 90.0 && f1.abs() < 90.0 {
+            let time = newton_root(offset, pair[0], pair[1], TIME_TOLERANCE);
+            events.push(AstronomicalEvent {
+                kind: EventKind::Opposition,
+                body: body.to_string(),
+                julian_date: time,
+                value: longitude_from_sun(body, time).abs(),
+            });
+        }
+    }
+}
+
+// Times of greatest elongation of an inferior planet, east and west
+fn greatest_elongations(body: &str, start: f64, end: f64, events: &mut Vec<AstronomicalEvent>) {
+    let elongation = |jd: f64| sky_separation(body, "Sun", jd);
+    let epochs = sample_epochs(start, end, SAMPLE_STEP);
+    let values: Vec<f64> = epochs.iter().map(|&jd| elongation(jd)).collect();
+    for (window, value) in epochs.windows(3).zip(values.windows(3)) {
+        if value[1] > value[0] && value[1] >= value[2] {
+            let time = newton_extremum(elongation, window[0], window[2], TIME_TOLERANCE);
+            let kind = if longitude_from_sun(body, time) > 0.0 {
+                EventKind::GreatestEasternElongation
+            } else {
+                EventKind::GreatestWesternElongation
+            };
+            events.push(AstronomicalEvent {
+                kind,
+                body: body.to_string(),
+                julian_date: time,
+                value: elongation(time),
+            });
+        }
+    }
+}
+
+// Times of perihelion and aphelion passages of a planet
+fn apsides(index: usize, start: f64, end: f64, events: &mut Vec<AstronomicalEvent>) {
+    let distance = |jd: f64| planet_state(index, jd).position.length();
+    // About 200 samples per orbit, and never coarser than the search step
+    let step = (PLANET_DATA[index].6 / 200.0).max(SAMPLE_STEP);
+    let epochs = sample_epochs(start, end, step);
+    let values: Vec<f64> = epochs.iter().map(|&jd| distance(jd)).collect();
+    for (window, value) in epochs.windows(3).zip(values.windows(3)) {
+        let kind = if value[1] < value[0] && value[1] <= value[2] {
+            EventKind::Perihelion
+        } else if value[1] > value[0] && value[1] >= value[2] {
+            EventKind::Aphelion
+        } else {
+            continue;
+        };
+        let time = newton_extremum(distance, window[0], window[2], TIME_TOLERANCE);
+        events.push(AstronomicalEvent {
+            kind,
+            body: PLANET_ELEMENTS[index].0.to_string(),
+            julian_date: time,
+            value: distance(time),
+        });
+    }
+}
+
+// List oppositions of the superior planets, greatest elongations of Mercury
+// and Venus, and perihelion/aphelion passages of every planet, sorted by date
+#[wasm_bindgen]
+pub fn find_events(jd_start: f64, jd_end: f64) -> Result<Vec<AstronomicalEvent>, JsError> {
+    check_date_range(jd_start, jd_end, MAX_SEARCH_SPAN)?;
+
+    let mut events = Vec::new();
+
+    for body in ["Mars", "Jupiter", "Saturn", "Uranus", "Neptune"] {
+        oppositions(body, jd_start, jd_end, &mut events);
+    }
+    for body in ["Mercury", "Venus"] {
+        greatest_elongations(body, jd_start, jd_end, &mut events);
+    }
+    for index in 0..PLANET_ELEMENTS.len() {
+        apsides(index, jd_start, jd_end, &mut events);
+    }
+
+    events.retain(|event| event.julian_date >= jd_start && event.julian_date <= jd_end);
+    events.sort_by(|a, b| a.julian_date.total_cmp(&b.julian_date));
+    Ok(events)
+}
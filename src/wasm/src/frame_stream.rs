@@ -0,0 +1,118 @@
+// A fixed ring of frames in WASM memory that the renderer reads in place.
+// Each slot holds one frame of a SolarSystem, laid out as
+//   [julian date, x, y, z per body, qx, qy, qz, qw per body]
+// in SolarSystem buffer order. The slots are allocated once and never move,
+// so JS builds views from the pointers:
+//
+//     const frame = new Float64Array(wasm.memory.buffer, stream.latest_ptr(), stream.frame_len);
+//
+// Rebuild a view only after the WASM memory grows (its buffer is replaced);
+// otherwise views stay valid across writes. With more than one slot a frame
+// can be filled while the previous one is still being drawn.
+
+use wasm_bindgen::prelude::*;
+
+use crate::rotation::body_orientation;
+use crate::solar_system::{body_names, SolarSystem};
+use crate::{DWARF_PLANET_ELEMENTS, PLANET_ELEMENTS};
+
+#[wasm_bindgen]
+pub struct FrameStream {
+    slots: Vec<f64>,
+    slot_count: usize,
+    body_count: usize,
+    // Slot holding the latest complete frame, and frames written so far
+    latest: usize,
+    sequence: u32,
+}
+
+#[wasm_bindgen]
+impl FrameStream {
+    // A ring of slot_count frames (at least one; three for triple buffering)
+    #[wasm_bindgen(constructor)]
+    pub fn new(slot_count: usize) -> FrameStream {
+        let slot_count = slot_count.max(1);
+        let body_count = PLANET_ELEMENTS.len() + DWARF_PLANET_ELEMENTS.len();
+        FrameStream {
+            slots: vec![0.0; slot_count * frame_len(body_count)],
+            slot_count,
+            body_count,
+            latest: 0,
+            sequence: 0,
+        }
+    }
+
+    // Write a simulation's current frame into the next slot, which becomes
+    // the latest, and return its sequence number. Nothing is allocated.
+    pub fn write(&mut self, system: &SolarSystem) -> u32 {
+        let (frame_len, body_count) = (self.frame_len(), self.body_count);
+        let next = if self.sequence == 0 { 0 } else { (self.latest + 1) % self.slot_count };
+        let slot = &mut self.slots[next * frame_len..(next + 1) * frame_len];
+        let julian_date = system.julian_date();
+
+        slot[0] = julian_date;
+        slot[1..1 + body_count * 3].copy_from_slice(system.scene_positions());
+        let orientations = slot[1 + body_count * 3..].chunks_exact_mut(4);
+        for (quaternion, name) in orientations.zip(body_names()) {
+            quaternion.copy_from_slice(&body_orientation(name, julian_date));
+        }
+
+        self.latest = next;
+        self.sequence = self.sequence.wrapping_add(1);
+        self.sequence
+    }
+
+    // Address in WASM memory of the latest frame
+    pub fn latest_ptr(&self) -> *const f64 {
+        self.slot_ptr(self.latest)
+    }
+
+    // Address of a slot, for renderers that manage the ring themselves
+    pub fn slot_ptr(&self, slot: usize) -> *const f64 {
+        let offset = (slot % self.slot_count) * self.frame_len();
+        self.slots[offset..].as_ptr()
+    }
+
+    // Slot holding the latest frame
+    #[wasm_bindgen(getter)]
+    pub fn latest_slot(&self) -> usize {
+        self.latest
+    }
+
+    // Frames written so far; 0 until the first write
+    #[wasm_bindgen(getter)]
+    pub fn sequence(&self) -> u32 {
+        self.sequence
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn slot_count(&self) -> usize {
+        self.slot_count
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn body_count(&self) -> usize {
+        self.body_count
+    }
+
+    // f64 values per frame
+    #[wasm_bindgen(getter)]
+    pub fn frame_len(&self) -> usize {
+        frame_len(self.body_count)
+    }
+
+    // Index within a frame of the first position and the first quaternion
+    #[wasm_bindgen(getter)]
+    pub fn positions_offset(&self) -> usize {
+        1
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn orientations_offset(&self) -> usize {
+        1 + self.body_count * 3
+    }
+}
+
+fn frame_len(body_count: usize) -> usize {
+    1 + body_count * 7
+}
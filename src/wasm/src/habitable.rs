@@ -0,0 +1,90 @@
+// Circumstellar habitable zone from the Kopparapu et al. (2014) climate-model
+// fits: each boundary is the stellar flux, relative to Earth's, at which a
+// water-rich Earth-mass planet crosses a climate limit
+
+use wasm_bindgen::prelude::*;
+use serde::{Deserialize, Serialize};
+use js_sys::Float64Array;
+
+use crate::scene::to_scene;
+use crate::Vec3;
+
+// Effective temperature (K) the fits are centred on, and the range they cover
+const REFERENCE_TEMPERATURE: f64 = 5780.0;
+const MIN_TEMPERATURE: f64 = 2600.0;
+const MAX_TEMPERATURE: f64 = 7200.0;
+
+// Effective flux at the reference temperature and polynomial coefficients in
+// (T - 5780): recent Venus, runaway greenhouse, maximum greenhouse, early Mars
+static FLUX_LIMITS: [(f64, f64, f64, f64, f64); 4] = [
+    (1.776, 2.136e-4, 2.533e-8, -1.332e-11, -3.097e-15),
+    (1.107, 1.332e-4, 1.580e-8, -8.308e-12, -1.931e-15),
+    (0.356, 6.171e-5, 1.698e-9, -3.198e-12, -5.575e-16),
+    (0.320, 5.547e-5, 1.526e-9, -2.874e-12, -5.011e-16),
+];
+
+// Habitable zone boundaries (AU)
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[wasm_bindgen]
+pub struct HabitableZone {
+    optimistic_inner: f64,
+    conservative_inner: f64,
+    conservative_outer: f64,
+    optimistic_outer: f64,
+}
+
+#[wasm_bindgen]
+impl HabitableZone {
+    // Recent Venus limit, where Venus may last have had surface water
+    #[wasm_bindgen(getter)]
+    pub fn optimistic_inner(&self) -> f64 {
+        self.optimistic_inner
+    }
+
+    // Runaway greenhouse limit
+    #[wasm_bindgen(getter)]
+    pub fn conservative_inner(&self) -> f64 {
+        self.conservative_inner
+    }
+
+    // Maximum greenhouse limit, beyond which CO2 clouds cool more than they warm
+    #[wasm_bindgen(getter)]
+    pub fn conservative_outer(&self) -> f64 {
+        self.conservative_outer
+    }
+
+    // Early Mars limit, where Mars may once have had surface water
+    #[wasm_bindgen(getter)]
+    pub fn optimistic_outer(&self) -> f64 {
+        self.optimistic_outer
+    }
+
+    // The four boundaries in scene units, innermost first, for the inner and
+    // outer radii of annulus meshes
+    pub fn scene_radii(&self) -> Float64Array {
+        let radii = [self.optimistic_inner, self.conservative_inner, self.conservative_outer, self.optimistic_outer]
+            .map(|radius| to_scene(Vec3::new(radius, 0.0, 0.0)).length());
+        Float64Array::from(radii.as_slice())
+    }
+}
+
+impl HabitableZone {
+    // Boundaries for a star's luminosity (solar luminosities) and effective
+    // temperature (K, clamped to the range of the fits)
+    pub(crate) fn for_star(luminosity: f64, effective_temperature: f64) -> HabitableZone {
+        let t = effective_temperature.clamp(MIN_TEMPERATURE, MAX_TEMPERATURE) - REFERENCE_TEMPERATURE;
+        let [optimistic_inner, conservative_inner, conservative_outer, optimistic_outer] =
+            FLUX_LIMITS.map(|(flux, a, b, c, d)| {
+                let flux = flux + t * (a + t * (b + t * (c + t * d)));
+                (luminosity.max(0.0) / flux).sqrt()
+            });
+        HabitableZone { optimistic_inner, conservative_inner, conservative_outer, optimistic_outer }
+    }
+}
+
+// Habitable zone of a Sun-like star of the given luminosity (solar
+// luminosities); StarData.habitable_zone also accounts for the star's color
+#[wasm_bindgen]
+pub fn habitable_zone(star_luminosity: f64) -> HabitableZone {
+    HabitableZone::for_star(star_luminosity, REFERENCE_TEMPERATURE)
+}
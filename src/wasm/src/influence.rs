@@ -0,0 +1,24 @@
+// Hill sphere and sphere-of-influence radii, shared by the body data, the
+// derived properties and the patched-conic propagator so all agree
+
+use crate::{PLANET_DATA, PLANET_ELEMENTS, SUN_EARTH_MASS_RATIO};
+
+// Hill sphere radius of a body at a distance from its star, in the unit of
+// the distance, where mass_ratio is the body's mass over the star's. Within
+// it the body's gravity beats the star's tide; moons are only stable for long
+// inside about half of it.
+pub(crate) fn hill_radius(distance: f64, mass_ratio: f64) -> f64 {
+    distance * (mass_ratio / 3.0).cbrt()
+}
+
+// Laplace sphere-of-influence radius a·(m/M)^(2/5), in the unit of the
+// semi-major axis, where patched conics hand a spacecraft over between the
+// body and the star
+pub(crate) fn sphere_of_influence(semi_major_axis: f64, mass_ratio: f64) -> f64 {
+    semi_major_axis * mass_ratio.powf(0.4)
+}
+
+// Sphere of influence of a planet by index (AU)
+pub(crate) fn planet_sphere_of_influence(index: usize) -> f64 {
+    sphere_of_influence(PLANET_ELEMENTS[index].1.a, PLANET_DATA[index].9 / SUN_EARTH_MASS_RATIO)
+}
@@ -0,0 +1,274 @@
+// Phenomena of Jupiter's Galilean moons: eclipses in Jupiter's shadow,
+// occultations behind its disk, transits and shadow transits across it, and
+// mutual eclipses and occultations of one moon by another. Events are timed
+// by the moons' centers for phenomena involving Jupiter and by first and last
+// contact of the disks for mutual events, as seen from Earth's center (light
+// time included).
+
+use wasm_bindgen::prelude::*;
+use serde::{Deserialize, Serialize};
+use js_sys::Float64Array;
+
+use crate::errors::{check_date_range, check_julian_date};
+use crate::moons::moon_positions;
+use crate::observer::{light_time_corrected, planet_state, EARTH_INDEX};
+use crate::rotation::equator_axes;
+use crate::scene::scene_axes;
+use crate::search::bisect;
+use crate::Vec3;
+
+// Index of Jupiter in PLANET_ELEMENTS
+const JUPITER_INDEX: usize = 4;
+
+// Jupiter's equatorial and polar radii (km); moon positions are in
+// equatorial radii
+const JUPITER_EQUATORIAL_RADIUS: f64 = 71492.0;
+const JUPITER_POLAR_RADIUS: f64 = 66854.0;
+
+// Sampling interval (days), short enough to catch brief mutual events, and
+// refinement tolerance (days)
+const SAMPLE_STEP: f64 = 1.0 / 1440.0;
+const TIME_TOLERANCE: f64 = 1e-6;
+
+// Longest range (days) searched in one call; minute sampling makes a year
+// about half a million samples
+const MAX_SEARCH_SPAN: f64 = 366.0;
+
+// Kind of Galilean moon event
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum JovianEventKind {
+    Eclipse = 0,
+    Occultation = 1,
+    Transit = 2,
+    ShadowTransit = 3,
+    MutualEclipse = 4,
+    MutualOccultation = 5,
+}
+
+// An event of a Galilean moon, from its start to its end
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[wasm_bindgen]
+pub struct JovianEvent {
+    kind: JovianEventKind,
+    moon: String,
+    other: Option<String>,
+    start: f64,
+    end: f64,
+}
+
+#[wasm_bindgen]
+impl JovianEvent {
+    #[wasm_bindgen(getter)]
+    pub fn kind(&self) -> JovianEventKind {
+        self.kind
+    }
+
+    // The moon eclipsed, occulted or transiting, or whose shadow transits
+    #[wasm_bindgen(getter)]
+    pub fn moon(&self) -> String {
+        self.moon.clone()
+    }
+
+    // The moon casting the shadow or in front, for mutual events
+    #[wasm_bindgen(getter)]
+    pub fn other(&self) -> Option<String> {
+        self.other.clone()
+    }
+
+    // Julian date the event begins, as seen from Earth
+    #[wasm_bindgen(getter)]
+    pub fn start(&self) -> f64 {
+        self.start
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn end(&self) -> f64 {
+        self.end
+    }
+}
+
+// The Jovian system at one moment: moon positions and radii in Jupiter
+// equatorial radii, ecliptic axes, in moon_positions order, and unit
+// directions from Jupiter to the Sun, to Earth and along its rotation axis
+struct SystemGeometry {
+    names: Vec<String>,
+    moons: Vec<(Vec3, f64)>,
+    sun: Vec3,
+    earth: Vec3,
+    pole: Vec3,
+}
+
+// The system as seen from Earth at a Julian date, at the moment the light left
+// Jupiter
+fn system_geometry(julian_date: f64) -> Option<SystemGeometry> {
+    let earth = planet_state(EARTH_INDEX, julian_date).position;
+    let (jupiter, light_time) = light_time_corrected(JUPITER_INDEX, julian_date, &earth);
+    let moons = moon_positions("Jupiter", julian_date - light_time).ok()?;
+    Some(SystemGeometry {
+        names: moons.iter().map(|moon| moon.name()).collect(),
+        moons: moons
+            .iter()
+            .map(|moon| (scene_axes(&moon.position()), moon.radius() / JUPITER_EQUATORIAL_RADIUS))
+            .collect(),
+        sun: jupiter.position.scale(-1.0).normalize(),
+        earth: earth.sub(&jupiter.position).normalize(),
+        pole: jupiter_pole(julian_date - light_time),
+    })
+}
+
+// Jupiter's IAU rotation axis (ecliptic axes, matching moon positions)
+fn jupiter_pole(julian_date: f64) -> Vec3 {
+    equator_axes("Jupiter", julian_date).map_or(Vec3::new(0.0, 0.0, 1.0), |[_, _, pole]| pole)
+}
+
+// Stretch along the pole by a factor, turning Jupiter's spheroid into the unit
+// sphere (factor > 1) or back (factor < 1)
+fn stretch(vector: &Vec3, pole: &Vec3, factor: f64) -> Vec3 {
+    vector.add(&pole.scale((factor - 1.0) * vector.dot(pole)))
+}
+
+const OBLATENESS_STRETCH: f64 = JUPITER_EQUATORIAL_RADIUS / JUPITER_POLAR_RADIUS;
+
+// Distance of a point along a direction from Jupiter's center, and from the
+// line through the center in that direction, with the disk's limb at 1
+fn disk_offsets(point: &Vec3, direction: &Vec3, pole: &Vec3) -> (f64, f64) {
+    let point = stretch(point, pole, OBLATENESS_STRETCH);
+    let direction = stretch(direction, pole, OBLATENESS_STRETCH).normalize();
+    let along = point.dot(&direction);
+    (along, point.sub(&direction.scale(along)).length())
+}
+
+// Whether a point on the given side (+1 toward the direction, -1 away) lies
+// within the disk: negative while it does
+fn disk_margin(point: &Vec3, direction: &Vec3, pole: &Vec3, side: f64) -> f64 {
+    let (along, miss) = disk_offsets(point, direction, pole);
+    if along * side > 0.0 { miss - 1.0 } else { f64::INFINITY }
+}
+
+// Whether moon `behind` is covered by moon `front` looking back along a
+// direction: negative while the disks overlap
+fn mutual_margin(behind: &(Vec3, f64), front: &(Vec3, f64), direction: &Vec3) -> f64 {
+    let offset = behind.0.sub(&front.0);
+    let along = offset.dot(direction);
+    if along >= 0.0 {
+        return f64::INFINITY;
+    }
+    offset.sub(&direction.scale(along)).length() - (behind.1 + front.1)
+}
+
+// Every tracked event: (kind, moon, other moon)
+fn event_list(count: usize) -> Vec<(JovianEventKind, usize, Option<usize>)> {
+    let mut list = Vec::new();
+    for moon in 0..count {
+        for kind in [
+            JovianEventKind::Eclipse,
+            JovianEventKind::Occultation,
+            JovianEventKind::Transit,
+            JovianEventKind::ShadowTransit,
+        ] {
+            list.push((kind, moon, None));
+        }
+        for other in (0..count).filter(|&other| other != moon) {
+            list.push((JovianEventKind::MutualEclipse, moon, Some(other)));
+            list.push((JovianEventKind::MutualOccultation, moon, Some(other)));
+        }
+    }
+    list
+}
+
+// Negative while an event is in progress
+fn event_margin(geometry: &SystemGeometry, (kind, moon, other): (JovianEventKind, usize, Option<usize>)) -> f64 {
+    let (position, _) = geometry.moons[moon];
+    match (kind, other) {
+        (JovianEventKind::Eclipse, _) => disk_margin(&position, &geometry.sun, &geometry.pole, -1.0),
+        (JovianEventKind::Occultation, _) => disk_margin(&position, &geometry.earth, &geometry.pole, -1.0),
+        (JovianEventKind::Transit, _) => disk_margin(&position, &geometry.earth, &geometry.pole, 1.0),
+        (JovianEventKind::ShadowTransit, _) => disk_margin(&position, &geometry.sun, &geometry.pole, 1.0),
+        (JovianEventKind::MutualEclipse, Some(other)) => {
+            mutual_margin(&geometry.moons[moon], &geometry.moons[other], &geometry.sun)
+        }
+        (JovianEventKind::MutualOccultation, Some(other)) => {
+            mutual_margin(&geometry.moons[moon], &geometry.moons[other], &geometry.earth)
+        }
+        _ => f64::INFINITY,
+    }
+}
+
+// Events of the Galilean moons beginning and ending between two Julian dates,
+// in order of start
+#[wasm_bindgen]
+pub fn jovian_events(jd_start: f64, jd_end: f64) -> Result<Vec<JovianEvent>, JsError> {
+    check_date_range(jd_start, jd_end, MAX_SEARCH_SPAN)?;
+    let Some(first) = system_geometry(jd_start) else {
+        return Ok(Vec::new());
+    };
+    let names = first.names.clone();
+    let list = event_list(names.len());
+    let margin = |event, jd: f64| system_geometry(jd).map_or(f64::INFINITY, |geometry| event_margin(&geometry, event));
+
+    // Start of each event in progress, None before the first sample so events
+    // already under way at jd_start are skipped
+    let mut started: Vec<Option<f64>> = vec![None; list.len()];
+    let mut inside: Vec<bool> = list.iter().map(|&event| event_margin(&first, event) < 0.0).collect();
+    let mut events = Vec::new();
+    let mut jd = jd_start;
+    while jd < jd_end {
+        let next = (jd + SAMPLE_STEP).min(jd_end);
+        let Some(geometry) = system_geometry(next) else {
+            break;
+        };
+        for (k, &event) in list.iter().enumerate() {
+            let now_inside = event_margin(&geometry, event) < 0.0;
+            if now_inside == inside[k] {
+                continue;
+            }
+            let contact = bisect(|t| margin(event, t), jd, next, TIME_TOLERANCE);
+            if now_inside {
+                started[k] = Some(contact);
+            } else if let Some(start) = started[k].take() {
+                let (kind, moon, other) = event;
+                events.push(JovianEvent {
+                    kind,
+                    moon: names[moon].clone(),
+                    other: other.map(|other| names[other].clone()),
+                    start,
+                    end: contact,
+                });
+            }
+            inside[k] = now_inside;
+        }
+        jd = next;
+    }
+
+    events.sort_by(|a, b| a.start.total_cmp(&b.start));
+    Ok(events)
+}
+
+// Where each Galilean moon's shadow falls on Jupiter at a Julian date, packed
+// [x, y, z, ...] in moon_positions order and the same planetocentric scene
+// frame (Jupiter equatorial radii), NaN for shadows missing the disk. Uses the
+// same instant as moon_positions, for drawing shadow spots beside the moons.
+#[wasm_bindgen]
+pub fn jovian_shadow_spots(julian_date: f64) -> Result<Float64Array, JsError> {
+    check_julian_date(julian_date)?;
+    let moons = moon_positions("Jupiter", julian_date)?;
+    let sun = planet_state(JUPITER_INDEX, julian_date).position.scale(-1.0).normalize();
+    let pole = jupiter_pole(julian_date);
+    let mut spots = Vec::with_capacity(moons.len() * 3);
+    for moon in &moons {
+        let position = scene_axes(&moon.position());
+        let (along, miss) = disk_offsets(&position, &sun, &pole);
+        if along <= 0.0 || miss >= 1.0 {
+            spots.extend_from_slice(&[f64::NAN; 3]);
+            continue;
+        }
+        // Follow the shadow axis from the moon to the sunlit surface of the
+        // unit sphere, then undo the stretch
+        let direction = stretch(&sun, &pole, OBLATENESS_STRETCH).normalize();
+        let surface = stretch(&position, &pole, OBLATENESS_STRETCH).sub(&direction.scale(along - (1.0 - miss * miss).sqrt()));
+        let spot = scene_axes(&stretch(&surface, &pole, 1.0 / OBLATENESS_STRETCH));
+        spots.extend_from_slice(&[spot.x, spot.y, spot.z]);
+    }
+    Ok(Float64Array::from(spots.as_slice()))
+}
@@ -0,0 +1,85 @@
+// Screen placement of HUD labels: bodies projected through the renderer's own
+// view-projection matrix, with a flag for labels whose body is hidden behind
+// the Sun or another body's display sphere
+
+use wasm_bindgen::prelude::*;
+use js_sys::{Float64Array, Uint8Array};
+
+use crate::math::Mat4;
+use crate::Vec3;
+
+// Projected label anchors for a list of bodies, in the order given
+#[wasm_bindgen]
+#[derive(Debug, Clone, Default)]
+pub struct LabelLayout {
+    screen: Vec<f64>,
+    depth: Vec<f64>,
+    on_screen: Vec<u8>,
+    occluded: Vec<u8>,
+}
+
+#[wasm_bindgen]
+impl LabelLayout {
+    // Pixel coordinates packed [x, y, ...], origin at the top left, as for CSS
+    pub fn screen(&self) -> Float64Array {
+        Float64Array::from(self.screen.as_slice())
+    }
+
+    // Depth-buffer value in [0, 1] (0 at the near plane), for ordering labels
+    pub fn depth(&self) -> Float64Array {
+        Float64Array::from(self.depth.as_slice())
+    }
+
+    // 1 where the body is in front of the camera and inside the viewport
+    pub fn on_screen(&self) -> Uint8Array {
+        Uint8Array::from(self.on_screen.as_slice())
+    }
+
+    // 1 where the line of sight to the body's center crosses the Sun or a
+    // nearer body
+    pub fn occluded(&self) -> Uint8Array {
+        Uint8Array::from(self.occluded.as_slice())
+    }
+}
+
+impl LabelLayout {
+    // Lay out labels for bodies given as (center, display radius) in scene
+    // coordinates; occluders are the bodies themselves plus any extra spheres
+    pub(crate) fn project(
+        bodies: &[(Vec3, f64)],
+        extra_occluders: &[(Vec3, f64)],
+        view_projection: &Mat4,
+        camera_position: &Vec3,
+        viewport_width: f64,
+        viewport_height: f64,
+    ) -> LabelLayout {
+        let m = view_projection.as_array();
+        let mut layout = LabelLayout::default();
+        for (index, (center, _)) in bodies.iter().enumerate() {
+            let clip: [f64; 4] =
+                std::array::from_fn(|row| m[row] * center.x + m[4 + row] * center.y + m[8 + row] * center.z + m[12 + row]);
+            let (x, y, z) = (clip[0] / clip[3], clip[1] / clip[3], clip[2] / clip[3]);
+            let in_view = clip[3] > 0.0 && x.abs() <= 1.0 && y.abs() <= 1.0 && z.abs() <= 1.0;
+            layout.screen.extend_from_slice(&[(x + 1.0) * 0.5 * viewport_width, (1.0 - y) * 0.5 * viewport_height]);
+            layout.depth.push(z * 0.5 + 0.5);
+            layout.on_screen.push(in_view as u8);
+
+            let sight = center.sub(camera_position);
+            let distance = sight.length();
+            let occluders = bodies.iter().enumerate().filter(|(other, _)| *other != index).map(|(_, sphere)| sphere);
+            let hidden = occluders.chain(extra_occluders).any(|(occluder, radius)| {
+                blocks_sight(camera_position, &sight.scale(1.0 / distance), distance, occluder, *radius)
+            });
+            layout.occluded.push(hidden as u8);
+        }
+        layout
+    }
+}
+
+// Whether a sphere crosses the line of sight before it reaches the target
+fn blocks_sight(eye: &Vec3, direction: &Vec3, target_distance: f64, center: &Vec3, radius: f64) -> bool {
+    let offset = center.sub(eye);
+    let along = offset.dot(direction);
+    let miss_squared = offset.dot(&offset) - along * along;
+    along > 0.0 && along < target_distance && miss_squared <= radius * radius
+}
@@ -0,0 +1,106 @@
+use wasm_bindgen::prelude::*;
+
+use crate::errors::{check_julian_date, SimulationError};
+use crate::moons::lunar_geocentric_ecliptic;
+use crate::observer::{planet_state, EARTH_INDEX};
+use crate::search::bisect;
+use crate::state::{elements_to_state, StateVector};
+use crate::{
+    find_orbital_elements, to_scene, Precision, Vec3, AU_KM, DWARF_PLANET_DATA, PLANET_DATA, PLANET_ELEMENTS,
+    SUN_EARTH_MASS_RATIO,
+};
+
+// Moon's mass in Earth masses
+const MOON_EARTH_MASS_RATIO: f64 = 0.0123000371;
+
+// Heliocentric ecliptic state (AU, AU/day) and mass (Earth masses) of the Sun,
+// a planet, a dwarf planet or the Moon
+pub(crate) fn body_state_and_mass(name: &str, julian_date: f64) -> Option<(StateVector, f64)> {
+    if name == "Sun" {
+        let origin = Vec3::new(0.0, 0.0, 0.0);
+        return Some((StateVector::new(origin, origin), SUN_EARTH_MASS_RATIO));
+    }
+    if name == "Moon" {
+        let earth = planet_state(EARTH_INDEX, julian_date);
+        // Geocentric velocity by central difference over about a quarter hour
+        let h = 0.01;
+        let velocity = lunar_geocentric(julian_date + h).sub(&lunar_geocentric(julian_date - h)).scale(0.5 / h);
+        let state = StateVector::new(
+            earth.position.add(&lunar_geocentric(julian_date)),
+            earth.velocity.add(&velocity),
+        );
+        return Some((state, MOON_EARTH_MASS_RATIO));
+    }
+    if let Some(index) = PLANET_ELEMENTS.iter().position(|(n, _)| *n == name) {
+        return Some((planet_state(index, julian_date), PLANET_DATA[index].9));
+    }
+    let data = DWARF_PLANET_DATA.iter().find(|data| data.0 == name)?;
+    let elements = find_orbital_elements(name, julian_date, Precision::High)?;
+    Some((elements_to_state(&elements, julian_date), data.9))
+}
+
+// Geocentric ecliptic position of the Moon (AU)
+fn lunar_geocentric(julian_date: f64) -> Vec3 {
+    lunar_geocentric_ecliptic(julian_date).scale(1.0 / AU_KM)
+}
+
+// Positions (AU) of L1-L5 for a secondary orbiting a primary, in the same frame
+// as the input states. L4 leads the secondary by 60° and L5 trails it.
+pub(crate) fn lagrange_positions(
+    primary: &StateVector,
+    primary_mass: f64,
+    secondary: &StateVector,
+    secondary_mass: f64,
+) -> [Vec3; 5] {
+    let mu = secondary_mass / (primary_mass + secondary_mass);
+    let separation = secondary.position.sub(&primary.position);
+    let distance = separation.length();
+
+    // Rotating frame: x toward the secondary, y along its motion, z along the
+    // orbital angular momentum
+    let x_axis = separation.scale(1.0 / distance);
+    let normal = separation.cross(&secondary.velocity.sub(&primary.velocity));
+    let z_axis = normal.scale(1.0 / normal.length());
+    let y_axis = z_axis.cross(&x_axis);
+    let barycenter = primary.position.add(&separation.scale(mu));
+    let point = |x: f64, y: f64| barycenter.add(&x_axis.scale(x * distance)).add(&y_axis.scale(y * distance));
+
+    // Net x-acceleration in the co-rotating frame, in units where the
+    // separation is 1, the primary sits at -mu and the secondary at 1 - mu
+    let force = |x: f64| {
+        let d1 = x + mu;
+        let d2 = x - 1.0 + mu;
+        x - (1.0 - mu) * d1 / d1.abs().powi(3) - mu * d2 / d2.abs().powi(3)
+    };
+    // The force diverges at each body, so each collinear point is bracketed
+    // between a body and the next singularity or a distant bound
+    let (tolerance, epsilon) = (1e-12, 1e-9);
+    let l1 = bisect(force, -mu + epsilon, 1.0 - mu - epsilon, tolerance);
+    let l2 = bisect(force, 1.0 - mu + epsilon, 2.0, tolerance);
+    let l3 = bisect(force, -2.0, -mu - epsilon, tolerance);
+    let half_root_three = 3f64.sqrt() / 2.0;
+
+    [
+        point(l1, 0.0),
+        point(l2, 0.0),
+        point(l3, 0.0),
+        point(0.5 - mu, half_root_three),
+        point(0.5 - mu, -half_root_three),
+    ]
+}
+
+// Calculate the five Lagrange points of a primary-secondary pair (e.g. "Sun"
+// and "Earth", "Earth" and "Moon", "Sun" and "Jupiter"), returned in order L1-L5
+// as heliocentric scene positions. Fails for unknown or identical bodies.
+#[wasm_bindgen]
+pub fn lagrange_points(primary: &str, secondary: &str, julian_date: f64) -> Result<Vec<Vec3>, JsError> {
+    check_julian_date(julian_date)?;
+    let state = |body: &str| body_state_and_mass(body, julian_date).ok_or_else(|| SimulationError::unknown_body(body));
+    let (primary_state, primary_mass) = state(primary)?;
+    let (secondary_state, secondary_mass) = state(secondary)?;
+    if primary == secondary {
+        return Err(SimulationError::invalid_argument("The primary and secondary must differ").into());
+    }
+    let points = lagrange_positions(&primary_state, primary_mass, &secondary_state, secondary_mass);
+    Ok(points.iter().map(|point| to_scene(*point)).collect())
+}
@@ -0,0 +1,197 @@
+// Lambert's problem and universal-variable Kepler propagation, valid for
+// elliptic, parabolic and hyperbolic arcs alike
+
+use wasm_bindgen::prelude::*;
+use std::f64::consts::PI;
+
+use crate::search::golden_section_min;
+use crate::state::StateVector;
+use crate::Vec3;
+
+const MAX_ITERATIONS: usize = 200;
+const TOLERANCE: f64 = 1e-12;
+
+// Stumpff functions C(z) and S(z), with series near zero where the closed
+// forms lose precision
+fn stumpff(z: f64) -> (f64, f64) {
+    if z > 1e-6 {
+        let s = z.sqrt();
+        ((1.0 - s.cos()) / z, (s - s.sin()) / (s * z))
+    } else if z < -1e-6 {
+        let s = (-z).sqrt();
+        ((s.cosh() - 1.0) / -z, (s.sinh() - s) / (s * -z))
+    } else {
+        (1.0 / 2.0 - z / 24.0 + z * z / 720.0, 1.0 / 6.0 - z / 120.0 + z * z / 5040.0)
+    }
+}
+
+// Transfer geometry from r1 to r2 shared by every branch of Lambert's problem,
+// with y(z) and the flight time in the universal variable z
+struct Geometry {
+    r1n: f64,
+    r2n: f64,
+    a: f64,
+}
+
+impl Geometry {
+    fn new(r1: &Vec3, r2: &Vec3, tof: f64, mu: f64) -> Option<Geometry> {
+        let (r1n, r2n) = (r1.length(), r2.length());
+        if !(tof > 0.0 && mu > 0.0 && r1n > 0.0 && r2n > 0.0) {
+            return None;
+        }
+        let cos_dnu = (r1.dot(r2) / (r1n * r2n)).clamp(-1.0, 1.0);
+        let dnu = if r1.cross(r2).z >= 0.0 { cos_dnu.acos() } else { 2.0 * PI - cos_dnu.acos() };
+        let a = dnu.sin() * (r1n * r2n / (1.0 - cos_dnu)).sqrt();
+        if !a.is_finite() || a.abs() < 1e-14 {
+            return None;
+        }
+        Some(Geometry { r1n, r2n, a })
+    }
+
+    fn y(&self, z: f64) -> f64 {
+        let (c, s) = stumpff(z);
+        self.r1n + self.r2n + self.a * (z * s - 1.0) / c.sqrt()
+    }
+
+    // Flight time at z; infinite where negative y makes the arc infeasible
+    fn time(&self, z: f64, mu: f64) -> f64 {
+        let y = self.y(z);
+        if y < 0.0 {
+            return f64::INFINITY;
+        }
+        let (c, s) = stumpff(z);
+        ((y / c).powf(1.5) * s + self.a * y.sqrt()) / mu.sqrt()
+    }
+
+    // Bisect for the z in [low, high] reaching tof, given whether flight time
+    // rises or falls across the interval. Negative y lies toward low z, below
+    // the shortest feasible time.
+    fn solve(&self, tof: f64, mu: f64, mut low: f64, mut high: f64, rising: bool) -> Option<f64> {
+        for _ in 0..MAX_ITERATIONS {
+            let z = 0.5 * (low + high);
+            if self.y(z) < 0.0 || (self.time(z, mu) < tof) == rising {
+                low = z;
+            } else {
+                high = z;
+            }
+            if (high - low) < TOLERANCE * (1.0 + z.abs()) {
+                return (((self.time(z, mu) - tof) / tof).abs() <= 1e-6).then_some(z);
+            }
+        }
+        None
+    }
+
+    // Velocities at both ends from the Lagrange coefficients at z
+    fn velocities(&self, r1: &Vec3, r2: &Vec3, z: f64, mu: f64) -> (Vec3, Vec3) {
+        let yz = self.y(z);
+        let f = 1.0 - yz / self.r1n;
+        let g = self.a * (yz / mu).sqrt();
+        let g_dot = 1.0 - yz / self.r2n;
+        let v1 = r2.sub(&r1.scale(f)).scale(1.0 / g);
+        let v2 = r2.scale(g_dot).sub(r1).scale(1.0 / g);
+        (v1, v2)
+    }
+}
+
+// Velocities at both ends of the prograde (counter-clockwise about +z)
+// single-revolution arc from r1 to r2 taking tof, for gravitational parameter
+// mu in consistent units. None when the geometry is degenerate (the positions
+// are collinear) or the solver fails to converge.
+pub(crate) fn solve_lambert(r1: &Vec3, r2: &Vec3, tof: f64, mu: f64) -> Option<(Vec3, Vec3)> {
+    let geometry = Geometry::new(r1, r2, tof, mu)?;
+    // Flight time grows monotonically with z on a single revolution, so bisect
+    // between a deep hyperbola and the one-revolution limit
+    let z = geometry.solve(tof, mu, -4.0 * PI * PI, 4.0 * PI * PI, true)?;
+    Some(geometry.velocities(r1, r2, z, mu))
+}
+
+// Velocities at both ends of a prograde arc from r1 to r2 that completes
+// `revolutions` whole orbits on the way, as for a spacecraft returning to the
+// planet it left. Beyond a single revolution flight time has a minimum within
+// the revolution's range of z, with one ellipse either side of it; `upper`
+// picks the one at larger z. None when tof is shorter than the minimum.
+pub(crate) fn solve_lambert_revolutions(r1: &Vec3, r2: &Vec3, tof: f64, mu: f64, revolutions: u32, upper: bool) -> Option<(Vec3, Vec3)> {
+    if revolutions == 0 {
+        return solve_lambert(r1, r2, tof, mu);
+    }
+    let geometry = Geometry::new(r1, r2, tof, mu)?;
+    // Flight time diverges at both ends, where the ellipse degenerates
+    let n = revolutions as f64;
+    let low = (2.0 * PI * n).powi(2) * (1.0 + TOLERANCE);
+    let high = (2.0 * PI * (n + 1.0)).powi(2) * (1.0 - TOLERANCE);
+    let z_min = golden_section_min(|z| geometry.time(z, mu), low, high, TOLERANCE * high);
+    if geometry.time(z_min, mu) > tof {
+        return None;
+    }
+    let z = if upper {
+        geometry.solve(tof, mu, z_min, high, true)?
+    } else {
+        geometry.solve(tof, mu, low, z_min, false)?
+    };
+    Some(geometry.velocities(r1, r2, z, mu))
+}
+
+// Propagate a state along its two-body conic for dt (any sign), using the
+// universal anomaly so the same code handles bound and escape orbits
+pub(crate) fn propagate_universal(state: &StateVector, dt: f64, mu: f64) -> StateVector {
+    let (r0, v0) = (&state.position, &state.velocity);
+    let r0n = r0.length();
+    let sqrt_mu = mu.sqrt();
+    let radial = r0.dot(v0) / sqrt_mu;
+    let alpha = 2.0 / r0n - v0.dot(v0) / mu;
+
+    // Newton iteration on Kepler's equation in the universal anomaly chi
+    let mut chi = sqrt_mu * alpha.abs().max(1e-3 / r0n) * dt;
+    for _ in 0..MAX_ITERATIONS {
+        let z = alpha * chi * chi;
+        let (c, s) = stumpff(z);
+        let f = radial * chi * chi * c + (1.0 - alpha * r0n) * chi * chi * chi * s + r0n * chi - sqrt_mu * dt;
+        let df = radial * chi * (1.0 - z * s) + (1.0 - alpha * r0n) * chi * chi * c + r0n;
+        let step = f / df;
+        chi -= step;
+        if step.abs() < TOLERANCE * (1.0 + chi.abs()) {
+            break;
+        }
+    }
+
+    let z = alpha * chi * chi;
+    let (c, s) = stumpff(z);
+    let f = 1.0 - chi * chi / r0n * c;
+    let g = dt - chi * chi * chi * s / sqrt_mu;
+    let position = r0.scale(f).add(&v0.scale(g));
+    let rn = position.length();
+    let f_dot = sqrt_mu / (rn * r0n) * chi * (z * s - 1.0);
+    let g_dot = 1.0 - chi * chi / rn * c;
+    StateVector::new(position, r0.scale(f_dot).add(&v0.scale(g_dot)))
+}
+
+// Velocities at both ends of a Lambert arc
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy)]
+pub struct LambertSolution {
+    departure_velocity: Vec3,
+    arrival_velocity: Vec3,
+}
+
+#[wasm_bindgen]
+impl LambertSolution {
+    #[wasm_bindgen(getter)]
+    pub fn departure_velocity(&self) -> Vec3 {
+        self.departure_velocity
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn arrival_velocity(&self) -> Vec3 {
+        self.arrival_velocity
+    }
+}
+
+// Solve Lambert's problem: the prograde single-revolution orbit from position
+// r1 to r2 in time tof around a body with gravitational parameter mu (for
+// example AU, days and GM_SUN in AU³/day²). Returns None for collinear
+// positions or impossible inputs.
+#[wasm_bindgen]
+pub fn lambert(r1: &Vec3, r2: &Vec3, tof: f64, mu: f64) -> Option<LambertSolution> {
+    let (departure_velocity, arrival_velocity) = solve_lambert(r1, r2, tof, mu)?;
+    Some(LambertSolution { departure_velocity, arrival_velocity })
+}
@@ -1,6 +1,161 @@
 use wasm_bindgen::prelude::*;
 use serde::{Deserialize, Serialize};
+use js_sys::Float64Array;
 use std::f64::consts::PI;
+use errors::{check_julian_date, SimulationError};
+use scene::{scene_radius, scene_velocity, to_scene};
+use std::ops::{Add, AddAssign, Div, Mul, MulAssign, Neg, Sub, SubAssign};
+
+mod approaches;
+mod asteroids;
+mod batch;
+mod belts;
+mod calendar;
+mod camera;
+mod catalog;
+mod chebyshev;
+mod clock;
+mod clouds;
+mod comets;
+mod culling;
+mod deep_space;
+mod eclipses;
+mod errors;
+mod events;
+mod frame_stream;
+mod habitable;
+mod influence;
+mod jovian;
+mod labels;
+mod lagrange;
+mod lambert;
+mod math;
+mod mesh;
+mod missions;
+mod moons;
+mod mpc;
+mod nbody;
+mod noise;
+mod observer;
+mod orbit_sampling;
+mod parallel;
+mod phases;
+mod picking;
+mod potential;
+mod precession;
+mod presets;
+mod properties;
+mod propagator;
+mod random;
+mod registry;
+mod resonances;
+mod rings;
+mod rise_set;
+mod rotation;
+mod satellites;
+mod scene;
+mod search;
+mod seasons;
+mod session;
+mod shadows;
+mod snapshot;
+mod solar_system;
+mod spacecraft;
+mod spatial;
+mod spk;
+mod stars;
+mod state;
+mod system;
+mod system_graph;
+mod terminator;
+mod terrain;
+mod thermal;
+mod tides;
+mod time;
+mod trails;
+mod transfer;
+mod transits;
+mod tree;
+mod units;
+mod validity;
+
+pub use approaches::{distance_series, next_closest_approach, synodic_period, CloseApproach};
+pub use asteroids::minor_planet_positions;
+pub use belts::{asteroid_belt, kuiper_belt, trojan_swarms, BeltParticles};
+pub use calendar::{
+    calendar_from_julian_date, day_of_week, is_leap_year, iso_week, julian_date_from_calendar, CalendarDate,
+    CalendarSystem,
+};
+pub use camera::{CameraPath, CameraTransform};
+pub use catalog::{bodies, BodyInfo, BodyKind};
+pub use clock::SimulationClock;
+pub use clouds::gas_giant_texture;
+pub use comets::{comet_positions, comet_state, CometData, CometElements};
+pub use culling::{cull_spheres, CameraView, VisibleSet};
+pub use eclipses::{eclipses_between, Eclipse, EclipseKind};
+pub use errors::ErrorCode;
+pub use events::{find_conjunctions, find_events, AstronomicalEvent, Conjunction, EventKind};
+pub use frame_stream::FrameStream;
+pub use habitable::{habitable_zone, HabitableZone};
+pub use jovian::{jovian_events, jovian_shadow_spots, JovianEvent, JovianEventKind};
+pub use labels::LabelLayout;
+pub use lagrange::lagrange_points;
+pub use lambert::{lambert, LambertSolution};
+pub use math::{Mat4, Quat};
+pub use mesh::{generate_sphere_mesh, SphereMesh};
+pub use missions::{mission_names, mission_position};
+pub use moons::{moon_position, moon_positions, visible_moons, MoonData};
+pub use nbody::{Integrator, NBodySimulator};
+pub use observer::{
+    altaz, angular_separation, apparent_planet_positions, earth_rotation_angle, equatorial_coordinates,
+    greenwich_sidereal_time, EquatorialCoordinates, HorizontalCoordinates, ObserverLocation,
+};
+pub use orbit_sampling::{comet_orbit_path, orbit_path_adaptive};
+#[cfg(feature = "threads")]
+pub use parallel::init_thread_pool;
+pub use parallel::{set_threads_enabled, threads_enabled};
+pub use phases::{apparent_data, moon_phase, ApparentData, MoonPhase};
+pub use potential::{potential_grid, PotentialGrid};
+pub use precession::{greenwich_apparent_sidereal_time, precess_equatorial};
+pub use presets::{load_preset_system, preset_system_names};
+pub use properties::{derived_properties, DerivedProperties};
+pub use propagator::{propagate_state, PropagatorConfig};
+pub use registry::{clear_registered_bodies, register_body_from_mpc, registered_body_positions};
+pub use resonances::{find_all_resonances, find_resonances, Resonance};
+pub use rings::{ring_geometry, ring_particles, RingFeature, RingGeometry};
+pub use rise_set::{rise_set_times, RiseSetTimes};
+pub use rotation::{planet_orientations, planet_rotation, RotationState};
+pub use satellites::Satellite;
+pub use scene::{reset_scene_config, scene_config, set_scene_config, SceneConfig};
+pub use seasons::{seasons, Seasons};
+pub use session::{SessionPlayer, SessionRecorder};
+pub use shadows::{shadow_cones, ShadowCone};
+pub use solar_system::{SimulationConfig, SolarSystem};
+pub use spacecraft::{
+    flyby, gravity_assist_route, Flyby, GravityAssistRoute, ManeuverNode, SoiTransition, Spacecraft,
+};
+pub use spatial::{RayHit, SpatialIndex};
+pub use spk::{load_spk_kernel, spk_kernel_loaded, unload_spk_kernel};
+pub use stars::{
+    bright_star_complete_magnitude, bright_stars, constellation_lines, generate_starfield, BrightStar, Constellation,
+    Starfield,
+};
+pub use state::{elements_from_state, state_from_elements, StateVector};
+pub use system::{
+    load_system_from_json, reset_system, system_positions, system_star, StarData, ValidationError,
+};
+pub use system_graph::SystemGraph;
+pub use terminator::{terminator, Terminator};
+pub use terrain::{planet_height_map, HeightMap};
+pub use thermal::{solar_flux_at, thermal_state, ThermalState};
+pub use tides::{moon_tides, roche_limit, MoonTide};
+pub use time::{convert_time, Time, TimeScale};
+pub use trails::TrailRecorder;
+pub use transfer::{hohmann_transfer, lambert_transfer, porkchop, HohmannTransfer, LambertTransfer, PorkchopPlot};
+pub use transits::{transits_between, Transit};
+pub use tree::{body_tree, BodyNode};
+pub use units::{convert_distance, convert_mass, DistanceUnit, MassUnit};
+pub use validity::{ephemeris_validity, ephemeris_validity_range, EphemerisValidity};
 
 // Import the `console.log` function from the `console` module
 #[wasm_bindgen]
@@ -59,6 +214,155 @@ impl Vec3 {
     pub fn length(&self) -> f64 {
         (self.x * self.x + self.y * self.y + self.z * self.z).sqrt()
     }
+
+    pub fn add(&self, other: &Vec3) -> Vec3 {
+        Vec3::new(self.x + other.x, self.y + other.y, self.z + other.z)
+    }
+
+    pub fn sub(&self, other: &Vec3) -> Vec3 {
+        Vec3::new(self.x - other.x, self.y - other.y, self.z - other.z)
+    }
+
+    pub fn scale(&self, factor: f64) -> Vec3 {
+        Vec3::new(self.x * factor, self.y * factor, self.z * factor)
+    }
+
+    pub fn dot(&self, other: &Vec3) -> f64 {
+        self.x * other.x + self.y * other.y + self.z * other.z
+    }
+
+    pub fn cross(&self, other: &Vec3) -> Vec3 {
+        Vec3::new(
+            self.y * other.z - self.z * other.y,
+            self.z * other.x - self.x * other.z,
+            self.x * other.y - self.y * other.x,
+        )
+    }
+
+    // Unit vector in the same direction; the zero vector stays zero
+    pub fn normalize(&self) -> Vec3 {
+        let length = self.length();
+        if length > 0.0 {
+            self.scale(1.0 / length)
+        } else {
+            *self
+        }
+    }
+
+    // Linear interpolation: self at t = 0, other at t = 1
+    pub fn lerp(&self, other: &Vec3, t: f64) -> Vec3 {
+        self.add(&other.sub(self).scale(t))
+    }
+
+    pub fn distance(&self, other: &Vec3) -> f64 {
+        self.sub(other).length()
+    }
+
+    // Angle between two vectors (radians, 0 to π), stable for nearly parallel
+    // vectors where acos of the dot product loses precision
+    pub fn angle_to(&self, other: &Vec3) -> f64 {
+        self.cross(other).length().atan2(self.dot(other))
+    }
+}
+
+// Operator forms of the vector methods for Rust callers
+impl Add for Vec3 {
+    type Output = Vec3;
+
+    fn add(self, other: Vec3) -> Vec3 {
+        Vec3::add(&self, &other)
+    }
+}
+
+impl Sub for Vec3 {
+    type Output = Vec3;
+
+    fn sub(self, other: Vec3) -> Vec3 {
+        Vec3::sub(&self, &other)
+    }
+}
+
+impl Add<&Vec3> for Vec3 {
+    type Output = Vec3;
+
+    fn add(self, other: &Vec3) -> Vec3 {
+        Vec3::add(&self, other)
+    }
+}
+
+impl Sub<&Vec3> for Vec3 {
+    type Output = Vec3;
+
+    fn sub(self, other: &Vec3) -> Vec3 {
+        Vec3::sub(&self, other)
+    }
+}
+
+impl Neg for Vec3 {
+    type Output = Vec3;
+
+    fn neg(self) -> Vec3 {
+        self.scale(-1.0)
+    }
+}
+
+impl Mul<f64> for Vec3 {
+    type Output = Vec3;
+
+    fn mul(self, factor: f64) -> Vec3 {
+        self.scale(factor)
+    }
+}
+
+impl Mul<Vec3> for f64 {
+    type Output = Vec3;
+
+    fn mul(self, vector: Vec3) -> Vec3 {
+        vector.scale(self)
+    }
+}
+
+impl Div<f64> for Vec3 {
+    type Output = Vec3;
+
+    fn div(self, divisor: f64) -> Vec3 {
+        self.scale(1.0 / divisor)
+    }
+}
+
+impl AddAssign for Vec3 {
+    fn add_assign(&mut self, other: Vec3) {
+        *self = Vec3::add(self, &other);
+    }
+}
+
+impl SubAssign for Vec3 {
+    fn sub_assign(&mut self, other: Vec3) {
+        *self = Vec3::sub(self, &other);
+    }
+}
+
+impl MulAssign<f64> for Vec3 {
+    fn mul_assign(&mut self, factor: f64) {
+        *self = self.scale(factor);
+    }
+}
+
+// Ephemeris precision: fixed J2000.0 elements, or elements with secular rates
+// for accurate positions centuries away from J2000.0
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Precision {
+    Standard = 0,
+    High = 1,
+}
+
+// Origin of output coordinates: the Sun's center, or the solar system barycenter
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReferenceFrame {
+    Heliocentric = 0,
+    Barycentric = 1,
 }
 
 // Planet data structure
@@ -67,7 +371,11 @@ impl Vec3 {
 pub struct PlanetData {
     name: String,
     position: Vec3,
+    velocity: Vec3,
+    true_position: Vec3,
+    true_velocity: Vec3,
     radius: f64,
+    true_radius: f64,
     color: String,
     orbit_radius: f64,
     orbit_speed: f64,
@@ -78,6 +386,8 @@ pub struct PlanetData {
     moons: u32,
     mass: f64,
     density: f64,
+    hill_radius: f64,
+    sphere_of_influence: f64,
 }
 
 #[wasm_bindgen]
@@ -92,11 +402,43 @@ impl PlanetData {
         self.position
     }
 
+    // Heliocentric velocity in scene units per day, same axes as position
+    #[wasm_bindgen(getter)]
+    pub fn velocity(&self) -> Vec3 {
+        self.velocity
+    }
+
+    // Ecliptic J2000 position (AU) relative to the same origin as position,
+    // unaffected by the scene configuration
+    #[wasm_bindgen(getter)]
+    pub fn true_position(&self) -> Vec3 {
+        self.true_position
+    }
+
+    // Ecliptic J2000 velocity (AU/day)
+    #[wasm_bindgen(getter)]
+    pub fn true_velocity(&self) -> Vec3 {
+        self.true_velocity
+    }
+
+    // True distance from the origin (AU), for distance readouts
+    #[wasm_bindgen(getter)]
+    pub fn distance(&self) -> f64 {
+        self.true_position.length()
+    }
+
+    // Radius in Earth radii, times the scene radius scale
     #[wasm_bindgen(getter)]
     pub fn radius(&self) -> f64 {
         self.radius
     }
 
+    // Radius in Earth radii
+    #[wasm_bindgen(getter)]
+    pub fn true_radius(&self) -> f64 {
+        self.true_radius
+    }
+
     #[wasm_bindgen(getter)]
     pub fn color(&self) -> String {
         self.color.clone()
@@ -127,6 +469,7 @@ impl PlanetData {
         self.year_length
     }
 
+    // Surface or cloud-top temperature at the current distance from the Sun (K)
     #[wasm_bindgen(getter)]
     pub fn temperature(&self) -> f64 {
         self.temperature
@@ -146,11 +489,24 @@ impl PlanetData {
     pub fn density(&self) -> f64 {
         self.density
     }
+
+    // Radius of the Hill sphere at the current distance from the Sun (AU)
+    #[wasm_bindgen(getter)]
+    pub fn hill_radius(&self) -> f64 {
+        self.hill_radius
+    }
+
+    // Radius of the sphere of influence used for patched-conic handovers (AU)
+    #[wasm_bindgen(getter)]
+    pub fn sphere_of_influence(&self) -> f64 {
+        self.sphere_of_influence
+    }
 }
 
-// Orbital elements structure for VSOP87-based calculations
+// Orbital elements structure for Keplerian calculations
 #[derive(Debug, Clone, Copy)]
-struct OrbitalElements {
+#[wasm_bindgen]
+pub struct OrbitalElements {
     a: f64,      // Semi-major axis (AU)
     e: f64,      // Eccentricity
     i: f64,      // Inclination (degrees)
@@ -160,6 +516,67 @@ struct OrbitalElements {
     n: f64,      // Mean motion (degrees/day)
 }
 
+#[wasm_bindgen]
+impl OrbitalElements {
+    // Create heliocentric elements referred to the J2000.0 epoch; angles in degrees
+    #[wasm_bindgen(constructor)]
+    pub fn new(a: f64, e: f64, i: f64, omega: f64, w: f64, m0: f64, n: f64) -> OrbitalElements {
+        OrbitalElements { a, e, i, omega, w, m0, n }
+    }
+
+    // Create elements with the mean motion derived from Kepler's third law
+    pub fn from_semi_major_axis(a: f64, e: f64, i: f64, omega: f64, w: f64, m0: f64) -> OrbitalElements {
+        OrbitalElements::new(a, e, i, omega, w, m0, GAUSSIAN_MEAN_MOTION / a.powf(1.5))
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn a(&self) -> f64 {
+        self.a
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn e(&self) -> f64 {
+        self.e
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn i(&self) -> f64 {
+        self.i
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn omega(&self) -> f64 {
+        self.omega
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn w(&self) -> f64 {
+        self.w
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn m0(&self) -> f64 {
+        self.m0
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn n(&self) -> f64 {
+        self.n
+    }
+}
+
+// Mean motion (degrees/day) of a massless body at 1 AU from the Sun
+const GAUSSIAN_MEAN_MOTION: f64 = 0.9856076686;
+
+// Heliocentric gravitational parameter (AU³/day²), the Gaussian constant squared
+const GM_SUN: f64 = 2.959122082855911e-4;
+
+// Astronomical unit (km)
+const AU_KM: f64 = 149597870.7;
+
+// Ratio of the Sun's mass to Earth's mass
+const SUN_EARTH_MASS_RATIO: f64 = 332946.0487;
+
 // Planet orbital elements at J2000.0 epoch
 static PLANET_ELEMENTS: &[(&str, OrbitalElements)] = &[
     ("Mercury", OrbitalElements {
@@ -169,7 +586,7 @@ static PLANET_ELEMENTS: &[(&str, OrbitalElements)] = &[
         a: 0.723332, e: 0.006773, i: 3.394, omega: 76.678, w: 54.884, m0: 50.115, n: 1.6021,
     }),
     ("Earth", OrbitalElements {
-        a: 1.000001, e: 0.016709, i: 0.000, omega: 0.000, w: 102.937, m0: 100.464, n: 0.9856,
+        a: 1.000001, e: 0.016709, i: 0.000, omega: 0.000, w: 102.937, m0: 357.527, n: 0.9856,
     }),
     ("Mars", OrbitalElements {
         a: 1.523679, e: 0.093941, i: 1.849, omega: 49.558, w: 286.502, m0: 19.373, n: 0.5240,
@@ -188,8 +605,76 @@ static PLANET_ELEMENTS: &[(&str, OrbitalElements)] = &[
     }),
 ];
 
+// Secular variation of orbital elements: J2000.0 value and rate per Julian century
+#[derive(Debug, Clone, Copy)]
+struct SecularElements {
+    a: (f64, f64),      // Semi-major axis (AU, AU/century)
+    e: (f64, f64),      // Eccentricity
+    i: (f64, f64),      // Inclination (degrees)
+    l: (f64, f64),      // Mean longitude (degrees)
+    varpi: (f64, f64),  // Longitude of perihelion (degrees)
+    omega: (f64, f64),  // Longitude of ascending node (degrees)
+    b: f64,             // Mean anomaly correction terms for the giant planets
+    c: f64,
+    s: f64,
+    f: f64,
+}
+
+// Keplerian elements with secular rates, valid 3000 BC to 3000 AD (Standish, JPL)
+static PLANET_SECULAR_ELEMENTS: &[SecularElements] = &[
+    SecularElements {
+        a: (0.38709843, 0.0), e: (0.20563661, 0.00002123), i: (7.00559432, -0.00590158),
+        l: (252.25166724, 149472.67486623), varpi: (77.45771895, 0.15940013),
+        omega: (48.33961819, -0.12214182), b: 0.0, c: 0.0, s: 0.0, f: 0.0,
+    },
+    SecularElements {
+        a: (0.72332102, -0.00000026), e: (0.00676399, -0.00005107), i: (3.39777545, 0.00043494),
+        l: (181.97970850, 58517.81560260), varpi: (131.76755713, 0.05679648),
+        omega: (76.67261496, -0.27274174), b: 0.0, c: 0.0, s: 0.0, f: 0.0,
+    },
+    SecularElements {
+        a: (1.00000018, -0.00000003), e: (0.01673163, -0.00003661), i: (-0.00054346, -0.01337178),
+        l: (100.46691572, 35999.37306329), varpi: (102.93005885, 0.31795260),
+        omega: (-5.11260389, -0.24123856), b: 0.0, c: 0.0, s: 0.0, f: 0.0,
+    },
+    SecularElements {
+        a: (1.52371243, 0.00000097), e: (0.09336511, 0.00009149), i: (1.85181869, -0.00724757),
+        l: (-4.56813164, 19140.29934243), varpi: (-23.91744784, 0.45223625),
+        omega: (49.71320984, -0.26852431), b: 0.0, c: 0.0, s: 0.0, f: 0.0,
+    },
+    SecularElements {
+        a: (5.20248019, -0.00002864), e: (0.04853590, 0.00018026), i: (1.29861416, -0.00322699),
+        l: (34.33479152, 3034.90371757), varpi: (14.27495244, 0.18199196),
+        omega: (100.29282654, 0.13024619),
+        b: -0.00012452, c: 0.06064060, s: -0.35635438, f: 38.35125000,
+    },
+    SecularElements {
+        a: (9.54149883, -0.00003065), e: (0.05550825, -0.00032044), i: (2.49424102, 0.00451969),
+        l: (50.07571329, 1222.11494724), varpi: (92.86136063, 0.54179478),
+        omega: (113.63998702, -0.25015002),
+        b: 0.00025899, c: -0.13434469, s: 0.87320147, f: 38.35125000,
+    },
+    SecularElements {
+        a: (19.18797948, -0.00020455), e: (0.04685740, -0.00001550), i: (0.77298127, -0.00180155),
+        l: (314.20276625, 428.49512595), varpi: (172.43404441, 0.09266985),
+        omega: (73.96250215, 0.05739699),
+        b: 0.00058331, c: -0.97731848, s: 0.17689245, f: 7.67025000,
+    },
+    SecularElements {
+        a: (30.06952752, 0.00006447), e: (0.00895439, 0.00000818), i: (1.77005520, 0.00022400),
+        l: (304.22289287, 218.46515314), varpi: (46.68158724, 0.01009938),
+        omega: (131.78635853, -0.00606302),
+        b: -0.00041348, c: 0.68346318, s: -0.10162547, f: 7.67025000,
+    },
+];
+
+// Physical data row: name, radius (Earth radii), color, orbit radius (AU),
+// axial tilt (degrees), day length (hours), year length (days), temperature (K),
+// moons, mass (Earth masses), density (g/cm³)
+type PhysicalData = (&'static str, f64, &'static str, f64, f64, f64, f64, f64, u32, f64, f64);
+
 // Planet physical data
-static PLANET_DATA: &[(&str, f64, &str, f64, f64, f64, f64, f64, u32, f64, f64)] = &[
+static PLANET_DATA: &[PhysicalData] = &[
     ("Mercury", 0.383, "#8c7853", 0.387, 0.034, 1407.6, 87.97, 340.0, 0, 0.055, 5.427),
     ("Venus", 0.949, "#ffc649", 0.723, 177.4, 5832.5, 224.7, 737.0, 0, 0.815, 5.243),
     ("Earth", 1.0, "#6b93d6", 1.0, 23.4, 24.0, 365.25, 288.0, 1, 1.0, 5.514),
@@ -200,6 +685,34 @@ static PLANET_DATA: &[(&str, f64, &str, f64, f64, f64, f64, f64, u32, f64, f64)]
     ("Neptune", 3.88, "#4b70dd", 30.104, 28.3, 16.1, 59800.0, 72.0, 14, 17.1, 1.638),
 ];
 
+// Dwarf planet orbital elements, approximate osculating values referred to J2000.0
+static DWARF_PLANET_ELEMENTS: &[(&str, OrbitalElements)] = &[
+    ("Ceres", OrbitalElements {
+        a: 2.767500, e: 0.078500, i: 10.587, omega: 80.255, w: 73.424, m0: 6.900, n: 0.21408,
+    }),
+    ("Pluto", OrbitalElements {
+        a: 39.482117, e: 0.248827, i: 17.140, omega: 110.299, w: 113.834, m0: 14.530, n: 0.003975,
+    }),
+    ("Haumea", OrbitalElements {
+        a: 43.218000, e: 0.191000, i: 28.210, omega: 122.167, w: 239.041, m0: 191.600, n: 0.003469,
+    }),
+    ("Makemake", OrbitalElements {
+        a: 45.715000, e: 0.159000, i: 28.983, omega: 79.620, w: 294.834, m0: 141.100, n: 0.003189,
+    }),
+    ("Eris", OrbitalElements {
+        a: 67.864000, e: 0.436100, i: 44.040, omega: 35.951, w: 151.639, m0: 192.800, n: 0.001766,
+    }),
+];
+
+// Dwarf planet physical data
+static DWARF_PLANET_DATA: &[PhysicalData] = &[
+    ("Ceres", 0.074, "#9d9a94", 2.768, 4.0, 9.07, 1681.6, 168.0, 0, 0.00016, 2.162),
+    ("Pluto", 0.186, "#d9b48f", 39.482, 122.5, 153.3, 90560.0, 44.0, 5, 0.0022, 1.854),
+    ("Haumea", 0.123, "#ece8e1", 43.218, 126.0, 3.92, 103774.0, 32.0, 2, 0.00067, 1.885),
+    ("Makemake", 0.112, "#c98e6c", 45.715, 0.0, 22.83, 112897.0, 40.0, 1, 0.00052, 1.7),
+    ("Eris", 0.182, "#e4e4e4", 67.864, 0.0, 378.9, 203830.0, 30.0, 1, 0.0028, 2.43),
+];
+
 // Convert degrees to radians
 fn deg_to_rad(degrees: f64) -> f64 {
     degrees * PI / 180.0
@@ -218,8 +731,29 @@ fn solve_kepler(mean_anomaly: f64, eccentricity: f64) -> f64 {
     e
 }
 
-// Calculate planet position from orbital elements
-fn calculate_planet_position(elements: &OrbitalElements, julian_date: f64) -> Vec3 {
+// Rotate a position in the orbital plane into the reference frame given by
+// the longitude of ascending node, argument of periapsis and inclination (radians)
+fn orbital_to_reference(x_orb: f64, y_orb: f64, omega: f64, w: f64, i: f64) -> Vec3 {
+    let cos_omega = omega.cos();
+    let sin_omega = omega.sin();
+    let cos_w = w.cos();
+    let sin_w = w.sin();
+    let cos_i = i.cos();
+    let sin_i = i.sin();
+    
+    let x = (cos_omega * cos_w - sin_omega * sin_w * cos_i) * x_orb
+        + (-cos_omega * sin_w - sin_omega * cos_w * cos_i) * y_orb;
+    
+    let y = (sin_omega * cos_w + cos_omega * sin_w * cos_i) * x_orb
+        + (-sin_omega * sin_w + cos_omega * cos_w * cos_i) * y_orb;
+    
+    let z = (sin_w * sin_i) * x_orb + (cos_w * sin_i) * y_orb;
+    
+    Vec3::new(x, y, z)
+}
+
+// Calculate heliocentric ecliptic position (AU) from orbital elements
+fn ecliptic_position(elements: &OrbitalElements, julian_date: f64) -> Vec3 {
     let days_since_epoch = julian_date - 2451545.0; // J2000.0 epoch
     
     // Calculate mean anomaly
@@ -240,57 +774,292 @@ fn calculate_planet_position(elements: &OrbitalElements, julian_date: f64) -> Ve
     let y_orb = r * true_anomaly.sin();
     
     // Convert to ecliptic coordinates
-    let cos_omega = deg_to_rad(elements.omega).cos();
-    let sin_omega = deg_to_rad(elements.omega).sin();
-    let cos_w = deg_to_rad(elements.w).cos();
-    let sin_w = deg_to_rad(elements.w).sin();
-    let cos_i = deg_to_rad(elements.i).cos();
-    let sin_i = deg_to_rad(elements.i).sin();
-    
-    let x = (cos_omega * cos_w - sin_omega * sin_w * cos_i) * x_orb
-        + (-cos_omega * sin_w - sin_omega * cos_w * cos_i) * y_orb;
-    
-    let y = (sin_omega * cos_w + cos_omega * sin_w * cos_i) * x_orb
-        + (-sin_omega * sin_w + cos_omega * cos_w * cos_i) * y_orb;
+    orbital_to_reference(
+        x_orb,
+        y_orb,
+        deg_to_rad(elements.omega),
+        deg_to_rad(elements.w),
+        deg_to_rad(elements.i),
+    )
+}
+
+// Calculate the ecliptic point (AU) on an orbit at a given eccentric anomaly (radians)
+fn orbit_point(elements: &OrbitalElements, eccentric_anomaly: f64) -> Vec3 {
+    let x_orb = elements.a * (eccentric_anomaly.cos() - elements.e);
+    let y_orb = elements.a * (1.0 - elements.e * elements.e).sqrt() * eccentric_anomaly.sin();
     
-    let z = (sin_w * sin_i) * x_orb + (cos_w * sin_i) * y_orb;
+    orbital_to_reference(
+        x_orb,
+        y_orb,
+        deg_to_rad(elements.omega),
+        deg_to_rad(elements.w),
+        deg_to_rad(elements.i),
+    )
+}
+
+// Calculate planet position from orbital elements
+fn calculate_planet_position(elements: &OrbitalElements, julian_date: f64) -> Vec3 {
+    to_scene(ecliptic_position(elements, julian_date))
+}
+
+// Evaluate secular elements at a Julian date. The mean anomaly is referred back
+// to J2000.0 along the current mean motion so the result plugs into the
+// fixed-epoch propagation unchanged.
+fn secular_elements(secular: &SecularElements, julian_date: f64) -> OrbitalElements {
+    let t = (julian_date - 2451545.0) / 36525.0; // Julian centuries since J2000.0
+    let at = |(value, rate): (f64, f64)| value + rate * t;
+
+    let l = at(secular.l);
+    let varpi = at(secular.varpi);
+    let omega = at(secular.omega);
+    let mean_anomaly = l - varpi
+        + secular.b * t * t
+        + secular.c * deg_to_rad(secular.f * t).cos()
+        + secular.s * deg_to_rad(secular.f * t).sin();
+    let n = secular.l.1 / 36525.0;
+
+    OrbitalElements {
+        a: at(secular.a),
+        e: at(secular.e),
+        i: at(secular.i),
+        omega,
+        w: varpi - omega,
+        m0: mean_anomaly - n * (julian_date - 2451545.0),
+        n,
+    }
+}
+
+// Planet orbital elements for the requested precision
+fn planet_elements(index: usize, julian_date: f64, precision: Precision) -> OrbitalElements {
+    match precision {
+        Precision::Standard => PLANET_ELEMENTS[index].1,
+        Precision::High => secular_elements(&PLANET_SECULAR_ELEMENTS[index], julian_date),
+    }
+}
+
+// Heliocentric ecliptic state of a planet by index. High precision uses a loaded
+// SPK kernel when one covers the date, falling back to the secular elements.
+fn planet_state_with_precision(index: usize, julian_date: f64, precision: Precision) -> StateVector {
+    if precision == Precision::High {
+        if let Some(state) = spk::planet_state(index, julian_date) {
+            return state;
+        }
+    }
+    state::elements_to_state(&planet_elements(index, julian_date, precision), julian_date)
+}
+
+// Look up a planet, dwarf planet, named asteroid or registered body by name and return its elements at a Julian date.
+// Precision applies to the planets, whose elements differ between the two ephemerides.
+fn find_orbital_elements(name: &str, julian_date: f64, precision: Precision) -> Option<OrbitalElements> {
+    if let Some(index) = PLANET_ELEMENTS.iter().position(|(n, _)| *n == name) {
+        return Some(planet_elements(index, julian_date, precision));
+    }
+    DWARF_PLANET_ELEMENTS
+        .iter()
+        .chain(asteroids::MINOR_PLANET_ELEMENTS.iter())
+        .find(|(n, _)| *n == name)
+        .map(|(_, elements)| *elements)
+        .or_else(|| registry::registered_elements(name))
+}
+
+// Assemble planet data from orbital elements and a row of the physical data table
+fn build_planet_data(
+    name: &str,
+    elements: &OrbitalElements,
+    julian_date: f64,
+    data: &PhysicalData,
+) -> PlanetData {
+    planet_data_from_state(name, &state::elements_to_state(elements, julian_date), data)
+}
+
+// Assemble planet data from a heliocentric ecliptic state and a row of physical data
+fn planet_data_from_state(name: &str, state: &state::StateVector, data: &PhysicalData) -> PlanetData {
+    let (_, radius, color, orbit_radius, axial_tilt, day_length, year_length, _, moons, mass, density) = *data;
     
-    // Scale for visualization
-    let scale = 2.0;
-    Vec3::new(x * scale, z * scale, y * scale)
+    PlanetData {
+        name: name.to_string(),
+        position: to_scene(state.position),
+        velocity: scene_velocity(&state.position, &state.velocity),
+        true_position: state.position,
+        true_velocity: state.velocity,
+        radius: scene_radius(radius),
+        true_radius: radius,
+        color: color.to_string(),
+        orbit_radius,
+        orbit_speed: 365.25 / year_length, // Speed relative to Earth
+        axial_tilt,
+        day_length,
+        year_length,
+        temperature: thermal::temperature(name, data, state.position.length()),
+        moons,
+        mass,
+        density,
+        hill_radius: influence::hill_radius(state.position.length(), mass / SUN_EARTH_MASS_RATIO),
+        sphere_of_influence: influence::sphere_of_influence(orbit_radius, mass / SUN_EARTH_MASS_RATIO),
+    }
 }
 
 // Main function to calculate all planet positions
 #[wasm_bindgen]
-pub fn planet_positions(julian_date: f64) -> Vec<PlanetData> {
+pub fn planet_positions(julian_date: f64) -> Result<Vec<PlanetData>, JsError> {
+    planet_positions_with_precision(julian_date, Precision::Standard)
+}
+
+// Calculate all planet positions as plain JavaScript objects rather than
+// class handles, so they can be spread into UI state without .free() calls
+#[wasm_bindgen]
+pub fn planet_positions_json(julian_date: f64) -> Result<JsValue, JsError> {
+    Ok(serde_wasm_bindgen::to_value(&planet_positions(julian_date)?)?)
+}
+
+// Calculate all planet positions with the chosen ephemeris precision
+#[wasm_bindgen]
+pub fn planet_positions_with_precision(julian_date: f64, precision: Precision) -> Result<Vec<PlanetData>, JsError> {
+    planet_positions_in_frame(julian_date, precision, ReferenceFrame::Heliocentric)
+}
+
+// Calculate all planet positions and velocities relative to the chosen origin
+#[wasm_bindgen]
+pub fn planet_positions_in_frame(
+    julian_date: f64,
+    precision: Precision,
+    frame: ReferenceFrame,
+) -> Result<Vec<PlanetData>, JsError> {
     set_panic_hook();
+    check_julian_date(julian_date)?;
     
+    let origin = frame_origin(julian_date, precision, frame);
     let mut planets = Vec::new();
     
-    for (i, (name, elements)) in PLANET_ELEMENTS.iter().enumerate() {
+    for (i, (name, _)) in PLANET_ELEMENTS.iter().enumerate() {
+        let state = planet_state_with_precision(i, julian_date, precision);
+        let relative = StateVector::new(state.position.sub(&origin.position), state.velocity.sub(&origin.velocity));
+        planets.push(planet_data_from_state(name, &relative, &PLANET_DATA[i]));
+    }
+    
+    Ok(planets)
+}
+
+// Calculate a single planet or dwarf planet, matching the corresponding entry
+// of planet_positions or dwarf_planet_positions, for callers tracking one body
+#[wasm_bindgen]
+pub fn planet_position(name: &str, julian_date: f64) -> Result<PlanetData, JsError> {
+    check_julian_date(julian_date)?;
+    if let Some(index) = PLANET_ELEMENTS.iter().position(|(n, _)| *n == name) {
+        let state = planet_state_with_precision(index, julian_date, Precision::Standard);
+        return Ok(planet_data_from_state(name, &state, &PLANET_DATA[index]));
+    }
+    DWARF_PLANET_ELEMENTS
+        .iter()
+        .zip(DWARF_PLANET_DATA.iter())
+        .find(|((n, _), _)| *n == name)
+        .map(|((_, elements), data)| build_planet_data(name, elements, julian_date, data))
+        .ok_or_else(|| SimulationError::unknown_body(name).into())
+}
+
+// Calculate the Sun's position in scene coordinates; in the barycentric frame
+// this traces the solar wobble driven mostly by Jupiter and Saturn
+#[wasm_bindgen]
+pub fn sun_position(julian_date: f64, precision: Precision, frame: ReferenceFrame) -> Result<Vec3, JsError> {
+    check_julian_date(julian_date)?;
+    Ok(to_scene(frame_origin(julian_date, precision, frame).position.scale(-1.0)))
+}
+
+// Heliocentric state of the solar system barycenter (AU, AU/day)
+fn barycenter_state(julian_date: f64, precision: Precision) -> StateVector {
+    let mut total_mass = SUN_EARTH_MASS_RATIO;
+    let mut position = Vec3::new(0.0, 0.0, 0.0);
+    let mut velocity = Vec3::new(0.0, 0.0, 0.0);
+    
+    for (i, data) in PLANET_DATA.iter().enumerate() {
+        let mass = data.9;
+        let state = planet_state_with_precision(i, julian_date, precision);
+        position = position.add(&state.position.scale(mass));
+        velocity = velocity.add(&state.velocity.scale(mass));
+        total_mass += mass;
+    }
+    
+    StateVector::new(position.scale(1.0 / total_mass), velocity.scale(1.0 / total_mass))
+}
+
+// Heliocentric state of the origin of a reference frame
+fn frame_origin(julian_date: f64, precision: Precision, frame: ReferenceFrame) -> StateVector {
+    match frame {
+        ReferenceFrame::Heliocentric => StateVector::new(Vec3::new(0.0, 0.0, 0.0), Vec3::new(0.0, 0.0, 0.0)),
+        ReferenceFrame::Barycentric => barycenter_state(julian_date, precision),
+    }
+}
+
+// Calculate all planet positions as a packed [x, y, z, ...] array in
+// PLANET_ELEMENTS order, for writing straight into render buffers
+#[wasm_bindgen]
+pub fn planet_positions_flat(julian_date: f64) -> Result<Float64Array, JsError> {
+    check_julian_date(julian_date)?;
+    let mut buffer = Vec::with_capacity(PLANET_ELEMENTS.len() * 3);
+    push_planet_positions(&mut buffer, julian_date);
+    Ok(Float64Array::from(buffer.as_slice()))
+}
+
+// Calculate planet positions at `steps` evenly spaced epochs from jd_start to
+// jd_end inclusive, packed epoch-major: [epoch][planet][x, y, z]
+#[wasm_bindgen]
+pub fn planet_positions_range(jd_start: f64, jd_end: f64, steps: u32) -> Result<Float64Array, JsError> {
+    check_julian_date(jd_start)?;
+    check_julian_date(jd_end)?;
+    let mut buffer = Vec::with_capacity(steps as usize * PLANET_ELEMENTS.len() * 3);
+    let step = if steps > 1 { (jd_end - jd_start) / (steps - 1) as f64 } else { 0.0 };
+    
+    for i in 0..steps {
+        push_planet_positions(&mut buffer, jd_start + step * i as f64);
+    }
+    
+    Ok(Float64Array::from(buffer.as_slice()))
+}
+
+// Append the scene position of every planet to a packed buffer
+fn push_planet_positions(buffer: &mut Vec<f64>, julian_date: f64) {
+    for (_, elements) in PLANET_ELEMENTS.iter() {
         let position = calculate_planet_position(elements, julian_date);
-        let (_, radius, color, orbit_radius, axial_tilt, day_length, year_length, temperature, moons, mass, density) = PLANET_DATA[i];
-        
-        let planet = PlanetData {
-            name: name.to_string(),
-            position,
-            radius,
-            color: color.to_string(),
-            orbit_radius,
-            orbit_speed: 365.25 / year_length, // Speed relative to Earth
-            axial_tilt,
-            day_length,
-            year_length,
-            temperature,
-            moons,
-            mass,
-            density,
-        };
-        
-        planets.push(planet);
+        buffer.extend_from_slice(&[position.x, position.y, position.z]);
+    }
+}
+
+// Sample a body's orbit at julian_date as a closed polyline of segments + 1
+// scene-space vertices packed [x, y, z, ...], including inclination and node.
+// Pass the precision the planets are drawn with so each lies on its orbit.
+#[wasm_bindgen]
+pub fn orbit_path(planet_name: &str, julian_date: f64, segments: u32, precision: Precision) -> Result<Float64Array, JsError> {
+    check_julian_date(julian_date)?;
+    let elements =
+        find_orbital_elements(planet_name, julian_date, precision).ok_or_else(|| SimulationError::unknown_body(planet_name))?;
+    let segments = segments.max(3);
+    let mut buffer = Vec::with_capacity((segments as usize + 1) * 3);
+    
+    for i in 0..=segments {
+        let eccentric_anomaly = 2.0 * PI * i as f64 / segments as f64;
+        let vertex = to_scene(orbit_point(&elements, eccentric_anomaly));
+        buffer.extend_from_slice(&[vertex.x, vertex.y, vertex.z]);
     }
     
-    planets
+    Ok(Float64Array::from(buffer.as_slice()))
+}
+
+// Propagate user-supplied orbital elements to a Julian date, in scene coordinates
+#[wasm_bindgen]
+pub fn propagate_elements(elements: &OrbitalElements, julian_date: f64) -> Result<Vec3, JsError> {
+    check_julian_date(julian_date)?;
+    Ok(calculate_planet_position(elements, julian_date))
+}
+
+// Calculate positions of Ceres, Pluto, Haumea, Makemake and Eris
+#[wasm_bindgen]
+pub fn dwarf_planet_positions(julian_date: f64) -> Result<Vec<PlanetData>, JsError> {
+    check_julian_date(julian_date)?;
+    Ok(DWARF_PLANET_ELEMENTS
+        .iter()
+        .zip(DWARF_PLANET_DATA.iter())
+        .map(|((name, elements), data)| build_planet_data(name, elements, julian_date, data))
+        .collect())
 }
 
 // Initialize the WASM module
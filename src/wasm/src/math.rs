@@ -0,0 +1,268 @@
+// Quaternion and 4x4 matrix types for orientations and transforms, in the
+// Three.js conventions: quaternions are [x, y, z, w] and matrices are stored
+// column-major
+
+use wasm_bindgen::prelude::*;
+use serde::{Deserialize, Serialize};
+use js_sys::Float64Array;
+use std::ops::Mul;
+
+use crate::rotation::quaternion_from_axes;
+use crate::Vec3;
+
+// Rotation quaternion
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[wasm_bindgen]
+pub struct Quat {
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+    pub w: f64,
+}
+
+#[wasm_bindgen]
+impl Quat {
+    #[wasm_bindgen(constructor)]
+    pub fn new(x: f64, y: f64, z: f64, w: f64) -> Quat {
+        Quat { x, y, z, w }
+    }
+
+    pub fn identity() -> Quat {
+        Quat::new(0.0, 0.0, 0.0, 1.0)
+    }
+
+    // Rotation by an angle (radians) about an axis, right-handed
+    pub fn from_axis_angle(axis: &Vec3, angle: f64) -> Quat {
+        let (sin, cos) = (0.5 * angle).sin_cos();
+        let axis = axis.normalize().scale(sin);
+        Quat::new(axis.x, axis.y, axis.z, cos)
+    }
+
+    // Orientation of an object at eye facing target, with its -Z axis forward
+    // and +Y as close to up as possible, as for a Three.js camera
+    pub fn look_at(eye: &Vec3, target: &Vec3, up: &Vec3) -> Quat {
+        let z = eye.sub(target);
+        if z.length() == 0.0 {
+            return Quat::identity();
+        }
+        let z = z.normalize();
+        let mut up = up.normalize();
+        // Pick a perpendicular up when looking straight along it
+        if up.cross(&z).length() < 1e-6 {
+            let axis = if up.x.abs() < 0.9 { Vec3::new(1.0, 0.0, 0.0) } else { Vec3::new(0.0, 1.0, 0.0) };
+            up = axis.cross(&up).normalize();
+        }
+        let x = up.cross(&z).normalize();
+        let y = z.cross(&x);
+        Quat::from(quaternion_from_axes(&x, &y, &z))
+    }
+
+    // Composition: the rotation other followed by self
+    pub fn multiply(&self, other: &Quat) -> Quat {
+        Quat::new(
+            self.w * other.x + self.x * other.w + self.y * other.z - self.z * other.y,
+            self.w * other.y - self.x * other.z + self.y * other.w + self.z * other.x,
+            self.w * other.z + self.x * other.y - self.y * other.x + self.z * other.w,
+            self.w * other.w - self.x * other.x - self.y * other.y - self.z * other.z,
+        )
+    }
+
+    // Inverse rotation of a unit quaternion
+    pub fn conjugate(&self) -> Quat {
+        Quat::new(-self.x, -self.y, -self.z, self.w)
+    }
+
+    pub fn length(&self) -> f64 {
+        (self.x * self.x + self.y * self.y + self.z * self.z + self.w * self.w).sqrt()
+    }
+
+    // Unit quaternion for the same rotation; a zero quaternion becomes the identity
+    pub fn normalize(&self) -> Quat {
+        let length = self.length();
+        if length > 0.0 {
+            Quat::new(self.x / length, self.y / length, self.z / length, self.w / length)
+        } else {
+            Quat::identity()
+        }
+    }
+
+    // Apply the rotation to a vector
+    pub fn rotate(&self, v: &Vec3) -> Vec3 {
+        let axis = Vec3::new(self.x, self.y, self.z);
+        let t = axis.cross(v).scale(2.0);
+        v.add(&t.scale(self.w)).add(&axis.cross(&t))
+    }
+
+    // Spherical linear interpolation along the shorter arc
+    pub fn slerp(&self, other: &Quat, t: f64) -> Quat {
+        let mut dot = self.x * other.x + self.y * other.y + self.z * other.z + self.w * other.w;
+        let mut b = *other;
+        if dot < 0.0 {
+            dot = -dot;
+            b = Quat::new(-b.x, -b.y, -b.z, -b.w);
+        }
+        let (wa, wb) = if dot > 0.9995 {
+            (1.0 - t, t)
+        } else {
+            let angle = dot.acos();
+            let sin = angle.sin();
+            (((1.0 - t) * angle).sin() / sin, (t * angle).sin() / sin)
+        };
+        Quat::new(
+            wa * self.x + wb * b.x,
+            wa * self.y + wb * b.y,
+            wa * self.z + wb * b.z,
+            wa * self.w + wb * b.w,
+        )
+        .normalize()
+    }
+
+    // Components [x, y, z, w], ready to copy into a Three.js quaternion
+    pub fn to_array(&self) -> Float64Array {
+        Float64Array::from(&self.components()[..])
+    }
+
+    pub fn to_matrix(&self) -> Mat4 {
+        Mat4::from_quaternion(self)
+    }
+}
+
+// Internal quaternion helpers
+impl Quat {
+    pub(crate) fn components(&self) -> [f64; 4] {
+        [self.x, self.y, self.z, self.w]
+    }
+}
+
+impl From<[f64; 4]> for Quat {
+    fn from([x, y, z, w]: [f64; 4]) -> Quat {
+        Quat::new(x, y, z, w)
+    }
+}
+
+impl Mul for Quat {
+    type Output = Quat;
+
+    fn mul(self, other: Quat) -> Quat {
+        self.multiply(&other)
+    }
+}
+
+// Affine or projective transform as a column-major 4x4 matrix
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[wasm_bindgen]
+pub struct Mat4 {
+    elements: [f64; 16],
+}
+
+#[wasm_bindgen]
+impl Mat4 {
+    // Matrix from 16 column-major elements, as in Three.js Matrix4.elements;
+    // None for any other length
+    pub fn from_elements(elements: &[f64]) -> Option<Mat4> {
+        Some(Mat4 { elements: elements.try_into().ok()? })
+    }
+
+    pub fn identity() -> Mat4 {
+        Mat4::from_columns(
+            Vec3::new(1.0, 0.0, 0.0),
+            Vec3::new(0.0, 1.0, 0.0),
+            Vec3::new(0.0, 0.0, 1.0),
+            Vec3::new(0.0, 0.0, 0.0),
+        )
+    }
+
+    pub fn from_translation(translation: &Vec3) -> Mat4 {
+        Mat4::compose(translation, &Quat::identity(), &Vec3::new(1.0, 1.0, 1.0))
+    }
+
+    pub fn from_quaternion(rotation: &Quat) -> Mat4 {
+        Mat4::compose(&Vec3::new(0.0, 0.0, 0.0), rotation, &Vec3::new(1.0, 1.0, 1.0))
+    }
+
+    // Rotation about an axis by an angle (radians)
+    pub fn from_axis_angle(axis: &Vec3, angle: f64) -> Mat4 {
+        Mat4::from_quaternion(&Quat::from_axis_angle(axis, angle))
+    }
+
+    // Transform that scales, then rotates, then translates
+    pub fn compose(position: &Vec3, rotation: &Quat, scale: &Vec3) -> Mat4 {
+        Mat4::from_columns(
+            rotation.rotate(&Vec3::new(scale.x, 0.0, 0.0)),
+            rotation.rotate(&Vec3::new(0.0, scale.y, 0.0)),
+            rotation.rotate(&Vec3::new(0.0, 0.0, scale.z)),
+            *position,
+        )
+    }
+
+    // World transform of an object at eye facing target (see Quat::look_at);
+    // its inverse is the view matrix
+    pub fn look_at(eye: &Vec3, target: &Vec3, up: &Vec3) -> Mat4 {
+        Mat4::compose(eye, &Quat::look_at(eye, target, up), &Vec3::new(1.0, 1.0, 1.0))
+    }
+
+    // Composition: the transform other followed by self
+    pub fn multiply(&self, other: &Mat4) -> Mat4 {
+        let (a, b) = (&self.elements, &other.elements);
+        Mat4 {
+            elements: std::array::from_fn(|i| {
+                let (column, row) = (i / 4, i % 4);
+                (0..4).fold(0.0, |sum, k| sum + a[k * 4 + row] * b[column * 4 + k])
+            }),
+        }
+    }
+
+    pub fn transpose(&self) -> Mat4 {
+        Mat4 { elements: std::array::from_fn(|i| self.elements[(i % 4) * 4 + i / 4]) }
+    }
+
+    // Transform a point, dividing through by w for projective matrices
+    pub fn transform_point(&self, point: &Vec3) -> Vec3 {
+        let m = &self.elements;
+        let w = m[3] * point.x + m[7] * point.y + m[11] * point.z + m[15];
+        self.transform_direction(point).add(&Vec3::new(m[12], m[13], m[14])).scale(1.0 / w)
+    }
+
+    // Transform a direction, ignoring translation
+    pub fn transform_direction(&self, direction: &Vec3) -> Vec3 {
+        let m = &self.elements;
+        Vec3::new(
+            m[0] * direction.x + m[4] * direction.y + m[8] * direction.z,
+            m[1] * direction.x + m[5] * direction.y + m[9] * direction.z,
+            m[2] * direction.x + m[6] * direction.y + m[10] * direction.z,
+        )
+    }
+
+    // Column-major elements, ready to copy into a Three.js Matrix4
+    #[wasm_bindgen(getter)]
+    pub fn elements(&self) -> Float64Array {
+        Float64Array::from(&self.elements[..])
+    }
+}
+
+// Internal matrix helpers
+impl Mat4 {
+    pub(crate) fn as_array(&self) -> &[f64; 16] {
+        &self.elements
+    }
+
+    // Affine matrix with the given basis columns and translation
+    fn from_columns(x: Vec3, y: Vec3, z: Vec3, translation: Vec3) -> Mat4 {
+        Mat4 {
+            elements: [
+                x.x, x.y, x.z, 0.0,
+                y.x, y.y, y.z, 0.0,
+                z.x, z.y, z.z, 0.0,
+                translation.x, translation.y, translation.z, 1.0,
+            ],
+        }
+    }
+}
+
+impl Mul for Mat4 {
+    type Output = Mat4;
+
+    fn mul(self, other: Mat4) -> Mat4 {
+        self.multiply(&other)
+    }
+}
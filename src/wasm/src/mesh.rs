@@ -0,0 +1,160 @@
+// Procedural meshes shared by every body in the scene
+
+use wasm_bindgen::prelude::*;
+use js_sys::{Float32Array, Uint32Array};
+use std::collections::HashMap;
+
+use crate::Vec3;
+
+// 20 * 4^7 = 327,680 triangles, well beyond what any body needs on screen
+const MAX_SUBDIVISIONS: u32 = 7;
+
+// Unit-radius indexed triangle mesh
+#[derive(Debug, Clone, Default)]
+#[wasm_bindgen]
+pub struct SphereMesh {
+    positions: Vec<f32>,
+    normals: Vec<f32>,
+    uvs: Vec<f32>,
+    indices: Vec<u32>,
+}
+
+#[wasm_bindgen]
+impl SphereMesh {
+    // Packed [x, y, z, ...] vertex positions on the unit sphere
+    #[wasm_bindgen(getter)]
+    pub fn positions(&self) -> Float32Array {
+        Float32Array::from(self.positions.as_slice())
+    }
+
+    // Packed [x, y, z, ...] outward unit normals
+    #[wasm_bindgen(getter)]
+    pub fn normals(&self) -> Float32Array {
+        Float32Array::from(self.normals.as_slice())
+    }
+
+    // Packed [u, v, ...] equirectangular coordinates laid out like Three.js
+    // SphereGeometry, so existing planet textures map unchanged
+    #[wasm_bindgen(getter)]
+    pub fn uvs(&self) -> Float32Array {
+        Float32Array::from(self.uvs.as_slice())
+    }
+
+    // Triangle list, counter-clockwise seen from outside
+    #[wasm_bindgen(getter)]
+    pub fn indices(&self) -> Uint32Array {
+        Uint32Array::from(self.indices.as_slice())
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn vertex_count(&self) -> u32 {
+        (self.positions.len() / 3) as u32
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn triangle_count(&self) -> u32 {
+        (self.indices.len() / 3) as u32
+    }
+}
+
+// Icosahedron vertices (before normalization) and faces
+fn icosahedron() -> (Vec<Vec3>, Vec<[u32; 3]>) {
+    let t = (1.0 + 5f64.sqrt()) / 2.0;
+    let vertices = [
+        (-1.0, t, 0.0),
+        (1.0, t, 0.0),
+        (-1.0, -t, 0.0),
+        (1.0, -t, 0.0),
+        (0.0, -1.0, t),
+        (0.0, 1.0, t),
+        (0.0, -1.0, -t),
+        (0.0, 1.0, -t),
+        (t, 0.0, -1.0),
+        (t, 0.0, 1.0),
+        (-t, 0.0, -1.0),
+        (-t, 0.0, 1.0),
+    ]
+    .iter()
+    .map(|&(x, y, z)| Vec3::new(x, y, z).normalize())
+    .collect();
+    let faces = vec![
+        [0, 11, 5], [0, 5, 1], [0, 1, 7], [0, 7, 10], [0, 10, 11],
+        [1, 5, 9], [5, 11, 4], [11, 10, 2], [10, 7, 6], [7, 1, 8],
+        [3, 9, 4], [3, 4, 2], [3, 2, 6], [3, 6, 8], [3, 8, 9],
+        [4, 9, 5], [2, 4, 11], [6, 2, 10], [8, 6, 7], [9, 8, 1],
+    ];
+    (vertices, faces)
+}
+
+// Split every triangle into four, sharing midpoints between neighbours
+fn subdivide(vertices: &mut Vec<Vec3>, faces: &[[u32; 3]]) -> Vec<[u32; 3]> {
+    let mut midpoints: HashMap<(u32, u32), u32> = HashMap::new();
+    let mut midpoint = |a: u32, b: u32, vertices: &mut Vec<Vec3>| {
+        *midpoints.entry((a.min(b), a.max(b))).or_insert_with(|| {
+            vertices.push(vertices[a as usize].add(&vertices[b as usize]).normalize());
+            vertices.len() as u32 - 1
+        })
+    };
+    let mut result = Vec::with_capacity(faces.len() * 4);
+    for &[a, b, c] in faces {
+        let ab = midpoint(a, b, vertices);
+        let bc = midpoint(b, c, vertices);
+        let ca = midpoint(c, a, vertices);
+        result.extend_from_slice(&[[a, ab, ca], [b, bc, ab], [c, ca, bc], [ab, bc, ca]]);
+    }
+    result
+}
+
+// Equirectangular coordinates of a unit vector, matching Three.js SphereGeometry
+// (u = 0 along -x, increasing toward +z; v = 1 at the +y pole)
+fn sphere_uv(p: &Vec3) -> (f64, f64) {
+    let u = (p.z.atan2(-p.x) / (2.0 * std::f64::consts::PI)).rem_euclid(1.0);
+    let v = 1.0 - p.y.clamp(-1.0, 1.0).acos() / std::f64::consts::PI;
+    (u, v)
+}
+
+// Generate an icosphere of unit radius. Each subdivision level quadruples the
+// triangle count (0 gives the bare icosahedron; capped at 7). Vertices along
+// the texture seam and at the poles are duplicated so UVs never wrap across a
+// triangle.
+#[wasm_bindgen]
+pub fn generate_sphere_mesh(subdivisions: u32) -> SphereMesh {
+    let (mut vertices, mut faces) = icosahedron();
+    for _ in 0..subdivisions.min(MAX_SUBDIVISIONS) {
+        faces = subdivide(&mut vertices, &faces);
+    }
+
+    let mut mesh = SphereMesh::default();
+    // Output vertex for each (source vertex, u) pair
+    let mut emitted: HashMap<(u32, u64), u32> = HashMap::new();
+    for face in &faces {
+        let mut uvs = face.map(|index| sphere_uv(&vertices[index as usize]));
+        // Unwrap triangles straddling the seam onto the u > 1 side
+        let max_u = uvs.iter().map(|uv| uv.0).fold(0.0, f64::max);
+        for uv in uvs.iter_mut() {
+            if max_u - uv.0 > 0.5 {
+                uv.0 += 1.0;
+            }
+        }
+        // A pole has no longitude; give it the mean u of the other two corners
+        for k in 0..3 {
+            let p = &vertices[face[k] as usize];
+            if p.x.abs() < 1e-9 && p.z.abs() < 1e-9 {
+                uvs[k].0 = (uvs[(k + 1) % 3].0 + uvs[(k + 2) % 3].0) / 2.0;
+            }
+        }
+
+        for (k, &index) in face.iter().enumerate() {
+            let (u, v) = uvs[k];
+            let output = *emitted.entry((index, u.to_bits())).or_insert_with(|| {
+                let p = &vertices[index as usize];
+                mesh.positions.extend_from_slice(&[p.x as f32, p.y as f32, p.z as f32]);
+                mesh.normals.extend_from_slice(&[p.x as f32, p.y as f32, p.z as f32]);
+                mesh.uvs.extend_from_slice(&[u as f32, v as f32]);
+                (mesh.positions.len() / 3 - 1) as u32
+            });
+            mesh.indices.push(output);
+        }
+    }
+    mesh
+}
@@ -0,0 +1,205 @@
+// Historical spacecraft trajectories reconstructed from their encounter
+// sequences: Lambert arcs between the bodies visited, a final cruise towards a
+// known later position, and orbit phases tied to repeated flybys
+
+use wasm_bindgen::prelude::*;
+use std::f64::consts::PI;
+
+use crate::errors::check_julian_date;
+use crate::lagrange::body_state_and_mass;
+use crate::lambert::{propagate_universal, solve_lambert};
+use crate::state::StateVector;
+use crate::{to_scene, Vec3, GM_SUN};
+
+struct MissionPreset {
+    name: &'static str,
+    // Bodies visited and encounter dates (JD), starting with launch from Earth
+    encounters: &'static [(&'static str, f64)],
+    // Heliocentric ecliptic longitude and latitude (degrees) and distance (AU)
+    // on a date (JD) after the last encounter, for missions leaving the planets
+    cruise: Option<(f64, f64, f64, f64)>,
+    // Orbits shaped by repeated flybys of one body after the last encounter:
+    // flyby date (JD), date of the following perihelion (JD), perihelion
+    // distance (AU) and period (days)
+    orbits: &'static [(f64, f64, f64, f64)],
+    // Date (JD) the spacecraft settles into orbit around its final body, or ends
+    orbit_insertion: Option<f64>,
+    end: Option<f64>,
+}
+
+static MISSIONS: &[MissionPreset] = &[
+    MissionPreset {
+        name: "Voyager 1",
+        encounters: &[
+            ("Earth", 2443392.0),   // 1977-09-05 launch
+            ("Jupiter", 2443937.5), // 1979-03-05
+            ("Saturn", 2444555.5),  // 1980-11-12
+        ],
+        // Crossing the heliopause, 2012-08-25
+        cruise: Some((2456164.5, 255.7, 34.8, 121.6)),
+        orbits: &[],
+        orbit_insertion: None,
+        end: None,
+    },
+    MissionPreset {
+        name: "Voyager 2",
+        encounters: &[
+            ("Earth", 2443375.5),   // 1977-08-20 launch
+            ("Jupiter", 2444063.5), // 1979-07-09
+            ("Saturn", 2444842.5),  // 1981-08-26
+            ("Uranus", 2446454.5),  // 1986-01-24
+            ("Neptune", 2447763.5), // 1989-08-25
+        ],
+        // Crossing the heliopause, 2018-11-05
+        cruise: Some((2458427.5, 289.3, -35.4, 119.0)),
+        orbits: &[],
+        orbit_insertion: None,
+        end: None,
+    },
+    MissionPreset {
+        name: "Cassini",
+        encounters: &[
+            ("Earth", 2450736.9),   // 1997-10-15 launch
+            ("Venus", 2450929.5),   // 1998-04-26
+            ("Venus", 2451353.5),   // 1999-06-24
+            ("Earth", 2451408.5),   // 1999-08-18
+            ("Jupiter", 2451908.5), // 2000-12-30
+            ("Saturn", 2453187.5),  // 2004-07-01 orbit insertion
+        ],
+        cruise: None,
+        orbits: &[],
+        orbit_insertion: Some(2453187.5),
+        // Grand Finale plunge into Saturn, 2017-09-15
+        end: Some(2458011.9),
+    },
+    MissionPreset {
+        name: "New Horizons",
+        encounters: &[
+            ("Earth", 2453755.3),   // 2006-01-19 launch
+            ("Jupiter", 2454159.5), // 2007-02-28
+            ("Pluto", 2457218.0),   // 2015-07-14
+        ],
+        // Flyby of Arrokoth, 2019-01-01
+        cruise: Some((2458484.7, 288.2, 1.6, 43.4)),
+        orbits: &[],
+        orbit_insertion: None,
+        end: None,
+    },
+    MissionPreset {
+        name: "Parker Solar Probe",
+        encounters: &[
+            ("Earth", 2458342.8), // 2018-08-12 launch
+            ("Venus", 2458394.5), // 2018-10-03
+        ],
+        cruise: None,
+        // Seven Venus flybys step the perihelion down to 9.86 solar radii
+        orbits: &[
+            (2458394.5, 2458428.6, 0.1663, 150.0), // 2018-10-03
+            (2458843.5, 2458877.5, 0.1297, 130.0), // 2019-12-26
+            (2459041.5, 2459119.5, 0.0936, 112.5), // 2020-07-11
+            (2459265.5, 2459333.5, 0.0741, 102.0), // 2021-02-20
+            (2459503.5, 2459539.5, 0.0617, 96.0),  // 2021-10-16
+            (2460177.5, 2460214.5, 0.0531, 92.0),  // 2023-08-21
+            (2460620.5, 2460668.5, 0.0459, 88.0),  // 2024-11-06
+        ],
+        orbit_insertion: None,
+        end: None,
+    },
+];
+
+// How the spacecraft moves from a date until the next segment begins
+enum Arc {
+    // Heliocentric conic from a state at the segment start
+    Coast(StateVector),
+    // Riding along with a body it orbits
+    Orbiting(&'static str),
+}
+
+fn heliocentric_state(body: &str, julian_date: f64) -> Option<StateVector> {
+    body_state_and_mass(body, julian_date).map(|(state, _)| state)
+}
+
+// State just after a flyby placing the spacecraft on an orbit with the given
+// perihelion distance, period and perihelion date, lying in the flyby body's
+// orbital plane and passing through the body's position
+fn flyby_orbit(body: &str, flyby: f64, perihelion: f64, q: f64, period: f64) -> Option<StateVector> {
+    let body = heliocentric_state(body, flyby)?;
+    let a = (GM_SUN * (period / (2.0 * PI)).powi(2)).cbrt();
+    let e = 1.0 - q / a;
+
+    // Propagate from perihelion in the orbit's own frame, then turn that frame
+    // so the spacecraft sits at the body's heliocentric longitude
+    let at_perihelion = StateVector::new(Vec3::new(q, 0.0, 0.0), Vec3::new(0.0, (GM_SUN * (1.0 + e) / q).sqrt(), 0.0));
+    let local = propagate_universal(&at_perihelion, flyby - perihelion, GM_SUN);
+    let radial_direction = local.position.normalize();
+    let transverse_direction = Vec3::new(-radial_direction.y, radial_direction.x, 0.0);
+
+    let radial = body.position.normalize();
+    let normal = body.position.cross(&body.velocity).normalize();
+    let transverse = normal.cross(&radial);
+    let velocity = radial
+        .scale(local.velocity.dot(&radial_direction))
+        .add(&transverse.scale(local.velocity.dot(&transverse_direction)));
+    Some(StateVector::new(radial.scale(local.position.length()), velocity))
+}
+
+// Trajectory segments of a mission as (start date, arc), in date order
+fn segments(mission: &MissionPreset) -> Option<Vec<(f64, Arc)>> {
+    let mut segments = Vec::new();
+    for pair in mission.encounters.windows(2) {
+        let ((from, departure), (to, arrival)) = (pair[0], pair[1]);
+        let start = heliocentric_state(from, departure)?.position;
+        let end = heliocentric_state(to, arrival)?.position;
+        let (velocity, _) = solve_lambert(&start, &end, arrival - departure, GM_SUN)?;
+        segments.push((departure, Arc::Coast(StateVector::new(start, velocity))));
+    }
+
+    let &(last_body, last_date) = mission.encounters.last()?;
+    if let Some((date, longitude, latitude, distance)) = mission.cruise {
+        let (lon, lat) = (longitude.to_radians(), latitude.to_radians());
+        let waypoint = Vec3::new(lat.cos() * lon.cos(), lat.cos() * lon.sin(), lat.sin()).scale(distance);
+        let start = heliocentric_state(last_body, last_date)?.position;
+        let (velocity, _) = solve_lambert(&start, &waypoint, date - last_date, GM_SUN)?;
+        segments.push((last_date, Arc::Coast(StateVector::new(start, velocity))));
+    }
+    for &(flyby, perihelion, q, period) in mission.orbits {
+        segments.push((flyby, Arc::Coast(flyby_orbit(last_body, flyby, perihelion, q, period)?)));
+    }
+    if let Some(date) = mission.orbit_insertion {
+        segments.push((date, Arc::Orbiting(last_body)));
+    }
+    Some(segments)
+}
+
+// Names of the missions with reconstructed trajectories
+#[wasm_bindgen]
+pub fn mission_names() -> Vec<String> {
+    MISSIONS.iter().map(|mission| mission.name.to_string()).collect()
+}
+
+// Scene position of a historical spacecraft at a Julian date, reconstructed
+// from its flybys, or None for unknown missions and dates before launch or
+// after the mission ended. Positions are approximate — deep-space maneuvers
+// are not modelled — but follow the real route through each encounter, so
+// "where is Voyager now" lands in the right part of the sky.
+#[wasm_bindgen]
+pub fn mission_position(name: &str, julian_date: f64) -> Result<Option<Vec3>, JsError> {
+    check_julian_date(julian_date)?;
+    Ok(mission_position_at(name, julian_date))
+}
+
+// Position along a mission's route at a date already known to be valid
+fn mission_position_at(name: &str, julian_date: f64) -> Option<Vec3> {
+    let mission = MISSIONS.iter().find(|mission| mission.name == name)?;
+    let launch = mission.encounters.first()?.1;
+    if julian_date < launch || mission.end.is_some_and(|end| julian_date > end) {
+        return None;
+    }
+    let segments = segments(mission)?;
+    let (start, arc) = segments.iter().rev().find(|(start, _)| *start <= julian_date)?;
+    let position = match arc {
+        Arc::Coast(state) => propagate_universal(state, julian_date - start, GM_SUN).position,
+        Arc::Orbiting(body) => heliocentric_state(body, julian_date)?.position,
+    };
+    Some(to_scene(position))
+}
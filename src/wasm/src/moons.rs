@@ -0,0 +1,368 @@
+use wasm_bindgen::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::culling::{CameraView, VisibleSet};
+use crate::errors::{check_julian_date, SimulationError};
+use crate::precession::ecliptic_of_date_to_j2000;
+use crate::rotation::equator_axes;
+use crate::scene::scene_axes;
+use crate::units::EARTH_RADIUS_KM;
+use crate::{deg_to_rad, orbital_to_reference, solve_kepler, Vec3, DWARF_PLANET_DATA, PLANET_DATA};
+
+// Moon data structure
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[wasm_bindgen]
+pub struct MoonData {
+    name: String,
+    parent: String,
+    position: Vec3,
+    radius: f64,
+    orbit_radius: f64,
+    orbital_period: f64,
+}
+
+#[wasm_bindgen]
+impl MoonData {
+    #[wasm_bindgen(getter)]
+    pub fn name(&self) -> String {
+        self.name.clone()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn parent(&self) -> String {
+        self.parent.clone()
+    }
+
+    // Position relative to the parent planet, in parent equatorial radii
+    #[wasm_bindgen(getter)]
+    pub fn position(&self) -> Vec3 {
+        self.position
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn radius(&self) -> f64 {
+        self.radius
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn orbit_radius(&self) -> f64 {
+        self.orbit_radius
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn orbital_period(&self) -> f64 {
+        self.orbital_period
+    }
+}
+
+// Satellite mean orbital elements, referred to the parent's equator
+#[derive(Debug, Clone, Copy)]
+struct SatelliteElements {
+    a: f64,      // Semi-major axis (km)
+    e: f64,      // Eccentricity
+    i: f64,      // Inclination (degrees)
+    omega: f64,  // Longitude of ascending node (degrees)
+    w: f64,      // Argument of periapsis (degrees)
+    m0: f64,     // Mean anomaly at epoch (degrees)
+    n: f64,      // Mean motion (degrees/day)
+}
+
+// Satellite elements at J2000.0 epoch, with mean radius (km)
+static SATELLITE_ELEMENTS: &[(&str, &str, f64, SatelliteElements)] = &[
+    ("Io", "Jupiter", 1821.6, SatelliteElements {
+        a: 421800.0, e: 0.0041, i: 0.036, omega: 43.977, w: 84.129, m0: 342.021, n: 203.4889553,
+    }),
+    ("Europa", "Jupiter", 1560.8, SatelliteElements {
+        a: 671100.0, e: 0.0094, i: 0.466, omega: 219.106, w: 88.970, m0: 171.016, n: 101.3747235,
+    }),
+    ("Ganymede", "Jupiter", 2631.2, SatelliteElements {
+        a: 1070400.0, e: 0.0013, i: 0.177, omega: 63.552, w: 192.417, m0: 317.540, n: 50.3176081,
+    }),
+    ("Callisto", "Jupiter", 2410.3, SatelliteElements {
+        a: 1882700.0, e: 0.0074, i: 0.192, omega: 298.848, w: 52.643, m0: 181.408, n: 21.5710715,
+    }),
+    ("Mimas", "Saturn", 198.2, SatelliteElements {
+        a: 185539.0, e: 0.0196, i: 1.574, omega: 173.027, w: 332.499, m0: 14.848, n: 381.994516,
+    }),
+    ("Enceladus", "Saturn", 252.1, SatelliteElements {
+        a: 238042.0, e: 0.0047, i: 0.003, omega: 342.507, w: 0.076, m0: 199.686, n: 262.7318996,
+    }),
+    ("Tethys", "Saturn", 531.1, SatelliteElements {
+        a: 294672.0, e: 0.0001, i: 1.091, omega: 259.842, w: 45.202, m0: 243.367, n: 190.6979085,
+    }),
+    ("Dione", "Saturn", 561.4, SatelliteElements {
+        a: 377415.0, e: 0.0022, i: 0.028, omega: 290.415, w: 284.315, m0: 322.232, n: 131.5349316,
+    }),
+    ("Rhea", "Saturn", 763.8, SatelliteElements {
+        a: 527068.0, e: 0.0002, i: 0.333, omega: 351.042, w: 241.619, m0: 179.781, n: 79.6900478,
+    }),
+    ("Titan", "Saturn", 2574.7, SatelliteElements {
+        a: 1221865.0, e: 0.0288, i: 0.306, omega: 28.060, w: 180.532, m0: 163.310, n: 22.5769768,
+    }),
+    ("Iapetus", "Saturn", 734.5, SatelliteElements {
+        a: 3560854.0, e: 0.0293, i: 8.298, omega: 81.105, w: 271.606, m0: 201.789, n: 4.5379572,
+    }),
+];
+
+// Equatorial radii (km) of planets with modelled satellite systems
+pub(crate) static PARENT_RADII: &[(&str, f64)] = &[
+    ("Earth", EARTH_RADIUS_KM),
+    ("Jupiter", 71492.0),
+    ("Saturn", 60268.0),
+];
+
+// Periodic terms for the Moon's longitude (1e-6 deg) and distance (1e-3 km),
+// as multiples of D, M, M', F (truncated ELP2000-82, Meeus table 47.A)
+static LUNAR_LONGITUDE_DISTANCE_TERMS: &[(i8, i8, i8, i8, f64, f64)] = &[
+    (0, 0, 1, 0, 6288774.0, -20905355.0),
+    (2, 0, -1, 0, 1274027.0, -3699111.0),
+    (2, 0, 0, 0, 658314.0, -2955968.0),
+    (0, 0, 2, 0, 213618.0, -569925.0),
+    (0, 1, 0, 0, -185116.0, 48888.0),
+    (0, 0, 0, 2, -114332.0, -3149.0),
+    (2, 0, -2, 0, 58793.0, 246158.0),
+    (2, -1, -1, 0, 57066.0, -152138.0),
+    (2, 0, 1, 0, 53322.0, -170733.0),
+    (2, -1, 0, 0, 45758.0, -204586.0),
+    (0, 1, -1, 0, -40923.0, -129620.0),
+    (1, 0, 0, 0, -34720.0, 108743.0),
+    (0, 1, 1, 0, -30383.0, 104755.0),
+    (2, 0, 0, -2, 15327.0, 10321.0),
+    (0, 0, 1, 2, -12528.0, 0.0),
+    (0, 0, 1, -2, 10980.0, 79661.0),
+    (4, 0, -1, 0, 10675.0, -34782.0),
+    (0, 0, 3, 0, 10034.0, -23210.0),
+    (4, 0, -2, 0, 8548.0, -21636.0),
+    (2, 1, -1, 0, -7888.0, 24208.0),
+    (2, 1, 0, 0, -6766.0, 30824.0),
+    (1, 0, -1, 0, -5163.0, -8379.0),
+    (1, 1, 0, 0, 4987.0, -16675.0),
+    (2, -1, 1, 0, 4036.0, -12831.0),
+    (2, 0, 2, 0, 3994.0, -10445.0),
+    (4, 0, 0, 0, 3861.0, -11650.0),
+    (2, 0, -3, 0, 3665.0, 14403.0),
+    (0, 1, -2, 0, -2689.0, -7003.0),
+    (2, 0, -1, 2, -2602.0, 0.0),
+    (2, -1, -2, 0, 2390.0, 10056.0),
+    (1, 0, 1, 0, -2348.0, 6322.0),
+    (2, -2, 0, 0, 2236.0, -9884.0),
+];
+
+// Periodic terms for the Moon's latitude (1e-6 deg), as multiples of D, M, M', F
+// (truncated ELP2000-82, Meeus table 47.B)
+static LUNAR_LATITUDE_TERMS: &[(i8, i8, i8, i8, f64)] = &[
+    (0, 0, 0, 1, 5128122.0),
+    (0, 0, 1, 1, 280602.0),
+    (0, 0, 1, -1, 277693.0),
+    (2, 0, 0, -1, 173237.0),
+    (2, 0, -1, 1, 55413.0),
+    (2, 0, -1, -1, 46271.0),
+    (2, 0, 0, 1, 32573.0),
+    (0, 0, 2, 1, 17198.0),
+    (2, 0, 1, -1, 9266.0),
+    (0, 0, 2, -1, 8822.0),
+    (2, -1, 0, -1, 8216.0),
+    (2, 0, -2, -1, 4324.0),
+    (2, 0, 1, 1, 4200.0),
+    (2, 1, 0, -1, -3359.0),
+    (2, -1, -1, 1, 2463.0),
+    (2, -1, 0, 1, 2211.0),
+    (2, -1, -1, -1, 2065.0),
+    (0, 1, -1, -1, -1870.0),
+    (4, 0, -1, -1, 1828.0),
+    (0, 1, 0, 1, -1794.0),
+    (0, 0, 0, 3, -1749.0),
+    (0, 1, -1, 1, -1565.0),
+    (1, 0, 0, 1, -1491.0),
+    (0, 1, 1, 1, -1475.0),
+    (0, 1, 1, -1, -1410.0),
+    (0, 1, 0, -1, -1344.0),
+    (1, 0, 0, -1, -1335.0),
+    (0, 0, 3, 1, 1107.0),
+    (4, 0, 0, -1, 1021.0),
+    (4, 0, -1, 1, 833.0),
+];
+
+// Calculate the Moon's geocentric ecliptic longitude, latitude (degrees)
+// and distance (km) from the truncated ELP2000-82 series
+pub(crate) fn lunar_coordinates(julian_date: f64) -> (f64, f64, f64) {
+    let t = (julian_date - 2451545.0) / 36525.0; // Julian centuries since J2000.0
+
+    // Mean longitude, mean elongation, solar and lunar mean anomalies
+    let l = 218.3164477 + 481267.88123421 * t - 0.0015786 * t * t + t * t * t / 538841.0;
+    let d = 297.8501921 + 445267.1114034 * t - 0.0018819 * t * t + t * t * t / 545868.0;
+    let m = 357.5291092 + 35999.0502909 * t - 0.0001536 * t * t;
+    let mp = 134.9633964 + 477198.8675055 * t + 0.0087414 * t * t + t * t * t / 69699.0;
+
+    // Argument of latitude; its rate relative to the mean longitude is the
+    // regression of the node (one revolution in ~18.6 years)
+    let f = 93.2720950 + 483202.0175233 * t - 0.0036539 * t * t - t * t * t / 3526000.0;
+
+    // Planetary perturbation arguments and the decreasing eccentricity of Earth's orbit
+    let a1 = 119.75 + 131.849 * t;
+    let a2 = 53.09 + 479264.290 * t;
+    let a3 = 313.45 + 481266.484 * t;
+    let ecc = 1.0 - 0.002516 * t - 0.0000074 * t * t;
+
+    let argument = |cd: i8, cm: i8, cmp: i8, cf: i8| {
+        deg_to_rad(cd as f64 * d + cm as f64 * m + cmp as f64 * mp + cf as f64 * f)
+    };
+    let eccentricity_factor = |cm: i8| ecc.powi(cm.abs() as i32);
+
+    let mut sum_l = 0.0;
+    let mut sum_r = 0.0;
+    for &(cd, cm, cmp, cf, sl, sr) in LUNAR_LONGITUDE_DISTANCE_TERMS {
+        let arg = argument(cd, cm, cmp, cf);
+        sum_l += sl * eccentricity_factor(cm) * arg.sin();
+        sum_r += sr * eccentricity_factor(cm) * arg.cos();
+    }
+
+    let mut sum_b = 0.0;
+    for &(cd, cm, cmp, cf, sb) in LUNAR_LATITUDE_TERMS {
+        sum_b += sb * eccentricity_factor(cm) * argument(cd, cm, cmp, cf).sin();
+    }
+
+    sum_l += 3958.0 * deg_to_rad(a1).sin()
+        + 1962.0 * deg_to_rad(l - f).sin()
+        + 318.0 * deg_to_rad(a2).sin();
+    sum_b += -2235.0 * deg_to_rad(l).sin()
+        + 382.0 * deg_to_rad(a3).sin()
+        + 175.0 * deg_to_rad(a1 - f).sin()
+        + 175.0 * deg_to_rad(a1 + f).sin()
+        + 127.0 * deg_to_rad(l - mp).sin()
+        - 115.0 * deg_to_rad(l + mp).sin();
+
+    let longitude = (l + sum_l / 1e6).rem_euclid(360.0);
+    let latitude = sum_b / 1e6;
+    let distance = 385000.56 + sum_r / 1000.0;
+
+    (longitude, latitude, distance)
+}
+
+// The Moon's geocentric position (km) on the J2000.0 ecliptic, the frame of
+// the planets. The lunar series gives it on the ecliptic of date, which
+// precession carries about 50" a year away.
+pub(crate) fn lunar_geocentric_ecliptic(julian_date: f64) -> Vec3 {
+    let (longitude, latitude, distance) = lunar_coordinates(julian_date);
+    let (lon, lat) = (deg_to_rad(longitude), deg_to_rad(latitude));
+    let of_date = Vec3::new(lat.cos() * lon.cos(), lat.cos() * lon.sin(), lat.sin()).scale(distance);
+    ecliptic_of_date_to_j2000(&of_date, julian_date)
+}
+
+// Calculate the Moon's geocentric position, in Earth equatorial radii
+fn calculate_lunar_position(julian_date: f64) -> Vec3 {
+    let position = lunar_geocentric_ecliptic(julian_date).scale(1.0 / EARTH_RADIUS_KM);
+    Vec3::new(position.x, position.z, position.y)
+}
+
+// Calculate the geocentric position of Earth's Moon
+#[wasm_bindgen]
+pub fn moon_position(julian_date: f64) -> Result<MoonData, JsError> {
+    check_julian_date(julian_date)?;
+    Ok(MoonData {
+        name: "Moon".to_string(),
+        parent: "Earth".to_string(),
+        position: calculate_lunar_position(julian_date),
+        radius: 1737.4,
+        orbit_radius: 384399.0,
+        orbital_period: 27.321661,
+    })
+}
+
+// Calculate a satellite's planetocentric position, in parent equatorial radii
+fn calculate_moon_position(
+    elements: &SatelliteElements,
+    parent_radius: f64,
+    equator: &[Vec3; 3],
+    julian_date: f64,
+) -> Vec3 {
+    let days_since_epoch = julian_date - 2451545.0; // J2000.0 epoch
+
+    let mean_anomaly = deg_to_rad(elements.m0 + elements.n * days_since_epoch);
+    let eccentric_anomaly = solve_kepler(mean_anomaly, elements.e);
+    let true_anomaly = 2.0 * ((1.0 + elements.e).sqrt() * (eccentric_anomaly / 2.0).tan())
+        .atan2((1.0 - elements.e).sqrt());
+    let r = elements.a * (1.0 - elements.e * eccentric_anomaly.cos()) / parent_radius;
+
+    // Position in the parent's equatorial frame
+    let equatorial = orbital_to_reference(
+        r * true_anomaly.cos(),
+        r * true_anomaly.sin(),
+        deg_to_rad(elements.omega),
+        deg_to_rad(elements.w),
+        deg_to_rad(elements.i),
+    );
+
+    // Carry it onto the ecliptic along the parent's IAU equator axes
+    let [node, quadrature, pole] = equator;
+    let ecliptic = node.scale(equatorial.x).add(&quadrature.scale(equatorial.y)).add(&pole.scale(equatorial.z));
+    scene_axes(&ecliptic)
+}
+
+// Calculate positions of the major moons of a planet
+#[wasm_bindgen]
+pub fn moon_positions(planet_name: &str, julian_date: f64) -> Result<Vec<MoonData>, JsError> {
+    check_julian_date(julian_date)?;
+    if planet_name == "Earth" {
+        return Ok(vec![moon_position(julian_date)?]);
+    }
+
+    let Some(&(_, parent_radius)) = PARENT_RADII.iter().find(|(name, _)| *name == planet_name) else {
+        // Known bodies without modelled moons have none to list
+        let known = PLANET_DATA.iter().chain(DWARF_PLANET_DATA.iter()).any(|data| data.0 == planet_name);
+        return if known { Ok(Vec::new()) } else { Err(SimulationError::unknown_body(planet_name).into()) };
+    };
+    let equator = equator_axes(planet_name, julian_date).ok_or_else(|| SimulationError::unknown_body(planet_name))?;
+
+    Ok(SATELLITE_ELEMENTS
+        .iter()
+        .filter(|(_, parent, _, _)| *parent == planet_name)
+        .map(|(name, parent, radius, elements)| MoonData {
+            name: name.to_string(),
+            parent: parent.to_string(),
+            position: calculate_moon_position(elements, parent_radius, &equator, julian_date),
+            radius: *radius,
+            orbit_radius: elements.a,
+            orbital_period: 360.0 / elements.n,
+        })
+        .collect())
+}
+
+// Lunar apogee distance (km), bounding the Moon's orbit
+const LUNAR_APOGEE: f64 = 406_700.0;
+
+// The moons of a planet a camera can see, given where the renderer draws the
+// planet and at what scale (scene units per parent equatorial radius), with
+// indices in moon_positions order. If the system's bounding sphere is
+// off-screen or below the pixel threshold no moon is computed.
+#[wasm_bindgen]
+pub fn visible_moons(
+    planet_name: &str,
+    julian_date: f64,
+    camera: &CameraView,
+    center: &Vec3,
+    scale: f64,
+) -> Result<VisibleSet, JsError> {
+    check_julian_date(julian_date)?;
+    let parent_radius = PARENT_RADII.iter().find(|(name, _)| *name == planet_name).map(|(_, radius)| *radius);
+    let extent = match planet_name {
+        "Earth" => LUNAR_APOGEE,
+        _ => SATELLITE_ELEMENTS
+            .iter()
+            .filter(|(_, parent, _, _)| *parent == planet_name)
+            .fold(0.0, |max: f64, (_, _, _, elements)| max.max(elements.a * (1.0 + elements.e))),
+    };
+    if let Some(parent_radius) = parent_radius {
+        if !camera.is_visible(center, extent / parent_radius * scale) {
+            return Ok(VisibleSet::default());
+        }
+    }
+
+    // Unknown bodies fail here, and known ones without moons come back empty
+    let moons = moon_positions(planet_name, julian_date)?;
+    let parent_radius = parent_radius.unwrap_or(1.0);
+    let spheres = moons
+        .iter()
+        .map(|moon| (center.add(&moon.position.scale(scale)), moon.radius / parent_radius * scale));
+    Ok(VisibleSet::cull(camera, spheres))
+}
@@ -0,0 +1,101 @@
+// Minor Planet Center one-line orbit formats (MPCORB.DAT and CometEls.txt)
+
+use crate::calendar::calendar_to_julian_date;
+use crate::comets::CometElements;
+use crate::OrbitalElements;
+
+// A parsed MPC orbit: asteroids use mean elements at an epoch, comets use
+// perihelion elements
+pub(crate) enum MpcOrbit {
+    Asteroid { elements: OrbitalElements, absolute_magnitude: Option<f64> },
+    Comet(CometElements),
+}
+
+// Extract a fixed-width field by 1-based inclusive column range
+fn field(line: &str, start: usize, end: usize) -> Option<&str> {
+    line.get(start - 1..end.min(line.len())).map(str::trim).filter(|s| !s.is_empty())
+}
+
+fn number(line: &str, start: usize, end: usize) -> Option<f64> {
+    field(line, start, end)?.parse().ok()
+}
+
+// Decode an MPC packed digit: 0-9, then A-Z for 10-35 and a-z for 36-61
+fn unpack_digit(c: char) -> Option<u32> {
+    match c {
+        '0'..='9' => c.to_digit(10),
+        'A'..='Z' => Some(c as u32 - 'A' as u32 + 10),
+        'a'..='z' => Some(c as u32 - 'a' as u32 + 36),
+        _ => None,
+    }
+}
+
+// Decode a packed epoch such as "K239D" (2023 Sep 13.0 TT) to a Julian date
+fn unpack_epoch(packed: &str) -> Option<f64> {
+    let chars: Vec<char> = packed.chars().collect();
+    if chars.len() != 5 {
+        return None;
+    }
+    let century = unpack_digit(chars[0])?;
+    let year = century as i32 * 100 + packed.get(1..3)?.parse::<i32>().ok()?;
+    let month = unpack_digit(chars[3])?;
+    let day = unpack_digit(chars[4])?;
+    if !(1..=12).contains(&month) || !(1..=31).contains(&day) {
+        return None;
+    }
+    Some(calendar_to_julian_date(year, month, day as f64))
+}
+
+// Parse an MPCORB asteroid line; the mean anomaly is referred back to J2000.0
+fn parse_asteroid(line: &str) -> Option<(String, MpcOrbit)> {
+    let epoch = unpack_epoch(field(line, 21, 25)?)?;
+    let mean_anomaly = number(line, 27, 35)?;
+    let w = number(line, 38, 46)?;
+    let omega = number(line, 49, 57)?;
+    let i = number(line, 60, 68)?;
+    let e = number(line, 71, 79)?;
+    let n = number(line, 81, 91)?;
+    let a = number(line, 93, 103)?;
+    if !(0.0..1.0).contains(&e) || a <= 0.0 || n <= 0.0 {
+        return None;
+    }
+
+    let m0 = (mean_anomaly - n * (epoch - 2451545.0)).rem_euclid(360.0);
+    let name = field(line, 167, 194).or_else(|| field(line, 1, 7))?;
+    let orbit = MpcOrbit::Asteroid {
+        elements: OrbitalElements::new(a, e, i, omega, w, m0, n),
+        absolute_magnitude: number(line, 9, 13),
+    };
+    Some((name.to_string(), orbit))
+}
+
+// Parse a CometEls line: perihelion date, q, e, ω, Ω, i
+fn parse_comet(line: &str) -> Option<(String, MpcOrbit)> {
+    let year = field(line, 15, 18)?.parse::<i32>().ok()?;
+    let month = field(line, 20, 21)?.parse::<u32>().ok()?;
+    let day = number(line, 23, 29)?;
+    let q = number(line, 31, 39)?;
+    let e = number(line, 42, 49)?;
+    let w = number(line, 52, 59)?;
+    let omega = number(line, 62, 69)?;
+    let i = number(line, 72, 79)?;
+    if !(1..=12).contains(&month) || q <= 0.0 || e < 0.0 {
+        return None;
+    }
+
+    let perihelion_time = calendar_to_julian_date(year, month, day);
+    let name = field(line, 103, 158).or_else(|| field(line, 1, 12))?;
+    Some((name.to_string(), MpcOrbit::Comet(CometElements::new(q, e, i, omega, w, perihelion_time))))
+}
+
+// Parse a line in either MPC format. Comet lines carry a calendar perihelion
+// date in columns 15-29 where asteroid lines carry H, G and a packed epoch.
+pub(crate) fn parse_mpc_line(line: &str) -> Option<(String, MpcOrbit)> {
+    let line = line.trim_end();
+    let is_comet = field(line, 15, 18).is_some_and(|year| year.parse::<i32>().is_ok());
+    if is_comet {
+        parse_comet(line)
+    } else {
+        parse_asteroid(line)
+    }
+}
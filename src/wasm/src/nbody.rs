@@ -0,0 +1,435 @@
+use wasm_bindgen::prelude::*;
+use js_sys::Float64Array;
+
+use crate::errors::{check_julian_date, SimulationError};
+use crate::parallel;
+use crate::propagator::{rkf45, PropagatorConfig};
+use crate::system_graph::SystemGraph;
+use crate::{
+    planet_elements, state, to_scene, Precision, Vec3, GM_SUN, PLANET_DATA, PLANET_ELEMENTS,
+    SUN_EARTH_MASS_RATIO,
+};
+
+// Most physics steps sample() takes in one call
+const MAX_CATCH_UP_STEPS: u32 = 1000;
+
+// Most timesteps advance() takes in one call
+const MAX_ADVANCE_STEPS: f64 = 1.0e6;
+
+// Bodies needed before force sums are spread across the thread pool
+const PARALLEL_MIN_BODIES: usize = 64;
+
+// Integration scheme: classical RK4, symplectic kick-drift-kick leapfrog
+// whose energy error stays bounded over very long runs, or adaptive RKF45
+// which refines steps during close approaches
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Integrator {
+    RungeKutta4 = 0,
+    Leapfrog = 1,
+    Rkf45 = 2,
+}
+
+// Gravitational N-body simulation of the Sun and planets, seeded from the
+// Keplerian ephemeris. Units are AU, days and AU³/day² for GM.
+#[wasm_bindgen]
+pub struct NBodySimulator {
+    names: Vec<String>,
+    gm: Vec<f64>,
+    positions: Vec<Vec3>,
+    velocities: Vec<Vec3>,
+    julian_date: f64,
+    timestep: f64,
+    integrator: Integrator,
+    propagator_config: PropagatorConfig,
+    adaptive_step: f64,
+    // Body that output positions are relative to; None for the raw barycentric frame
+    origin: Option<usize>,
+    // State before the most recent step, which sample() interpolates from
+    previous_date: f64,
+    previous_positions: Vec<Vec3>,
+    previous_velocities: Vec<Vec3>,
+}
+
+#[wasm_bindgen]
+impl NBodySimulator {
+    // Seed the Sun and planets from the ephemeris at a Julian date; timestep in days
+    #[wasm_bindgen(constructor)]
+    pub fn new(julian_date: f64, timestep: f64) -> Result<NBodySimulator, JsError> {
+        check_julian_date(julian_date)?;
+        check_timestep(timestep)?;
+        let mut names = vec!["Sun".to_string()];
+        let mut gm = vec![GM_SUN];
+        let mut positions = vec![Vec3::new(0.0, 0.0, 0.0)];
+        let mut velocities = vec![Vec3::new(0.0, 0.0, 0.0)];
+
+        for (i, (name, _)) in PLANET_ELEMENTS.iter().enumerate() {
+            let elements = planet_elements(i, julian_date, Precision::High);
+            let state = state::elements_to_state(&elements, julian_date);
+            names.push(name.to_string());
+            gm.push(GM_SUN * PLANET_DATA[i].9 / SUN_EARTH_MASS_RATIO);
+            positions.push(state.position);
+            velocities.push(state.velocity);
+        }
+
+        let mut simulator = NBodySimulator {
+            names,
+            gm,
+            positions,
+            velocities,
+            julian_date,
+            timestep,
+            integrator: Integrator::RungeKutta4,
+            propagator_config: PropagatorConfig::default(),
+            adaptive_step: timestep,
+            origin: Some(0),
+            previous_date: julian_date,
+            previous_positions: Vec::new(),
+            previous_velocities: Vec::new(),
+        };
+        simulator.remove_net_momentum();
+        simulator.mark_previous();
+        Ok(simulator)
+    }
+
+    // Seed every star and planet of a system graph at a Julian date. Output
+    // positions are relative to the system barycenter rather than one body.
+    pub fn from_system_graph(graph: &SystemGraph, julian_date: f64, timestep: f64) -> Result<NBodySimulator, JsError> {
+        check_julian_date(julian_date)?;
+        check_timestep(timestep)?;
+        let states = graph.states(julian_date);
+        let mut names = Vec::new();
+        let mut gm = Vec::new();
+        let mut positions = Vec::new();
+        let mut velocities = Vec::new();
+        for (index, name, mass) in graph.massive_nodes() {
+            names.push(name.to_string());
+            gm.push(GM_SUN * mass);
+            positions.push(states[index].position);
+            velocities.push(states[index].velocity);
+        }
+
+        let mut simulator = NBodySimulator {
+            names,
+            gm,
+            positions,
+            velocities,
+            julian_date,
+            timestep,
+            integrator: Integrator::RungeKutta4,
+            propagator_config: PropagatorConfig::default(),
+            adaptive_step: timestep,
+            origin: None,
+            previous_date: julian_date,
+            previous_positions: Vec::new(),
+            previous_velocities: Vec::new(),
+        };
+        simulator.remove_net_momentum();
+        simulator.remove_barycenter_offset();
+        simulator.mark_previous();
+        Ok(simulator)
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn julian_date(&self) -> f64 {
+        self.julian_date
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn timestep(&self) -> f64 {
+        self.timestep
+    }
+
+    #[wasm_bindgen(setter)]
+    pub fn set_timestep(&mut self, timestep: f64) -> Result<(), JsError> {
+        check_timestep(timestep)?;
+        self.timestep = timestep;
+        Ok(())
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn integrator(&self) -> Integrator {
+        self.integrator
+    }
+
+    #[wasm_bindgen(setter)]
+    pub fn set_integrator(&mut self, integrator: Integrator) {
+        self.integrator = integrator;
+    }
+
+    // Tolerances and step limits used by the RKF45 integrator
+    pub fn set_propagator_config(&mut self, config: &PropagatorConfig) {
+        self.propagator_config = *config;
+        self.adaptive_step = config.initial_step();
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn body_count(&self) -> usize {
+        self.names.len()
+    }
+
+    pub fn body_name(&self, index: usize) -> Option<String> {
+        self.names.get(index).cloned()
+    }
+
+    // Multiply a body's mass, e.g. ("Jupiter", 10.0); returns false for unknown names
+    pub fn scale_mass(&mut self, name: &str, factor: f64) -> bool {
+        match self.names.iter().position(|n| n == name) {
+            Some(index) => {
+                self.gm[index] *= factor;
+                self.remove_net_momentum();
+                true
+            }
+            None => false,
+        }
+    }
+
+    // Advance one timestep
+    pub fn step(&mut self) -> Result<(), JsError> {
+        self.integrate(self.timestep)?;
+        Ok(())
+    }
+
+    // Advance by a span of days in whole timesteps, finishing with a partial
+    // step; spans needing more than MAX_ADVANCE_STEPS timesteps are rejected
+    pub fn advance(&mut self, days: f64) -> Result<(), JsError> {
+        let steps = (days / self.timestep).floor().max(0.0);
+        if !days.is_finite() || steps > MAX_ADVANCE_STEPS {
+            return Err(SimulationError::invalid_argument(&format!(
+                "Cannot advance {} days in steps of {} days",
+                days, self.timestep
+            ))
+            .into());
+        }
+        let steps = steps as u64;
+        for _ in 0..steps {
+            self.integrate(self.timestep)?;
+        }
+        let remainder = days - steps as f64 * self.timestep;
+        if remainder > 0.0 {
+            self.integrate(remainder)?;
+        }
+        Ok(())
+    }
+
+    // Body positions relative to the Sun (or the barycenter for simulators
+    // seeded from a system graph), packed [x, y, z, ...] in scene coordinates
+    pub fn positions(&self) -> Float64Array {
+        self.scene_buffer(&self.positions)
+    }
+
+    // Fixed-timestep mode for rendering: step the physics in whole timesteps
+    // until it reaches or passes render_time (a Julian date), then return
+    // positions, packed like positions(), interpolated at render_time between
+    // the last two steps. The physics only ever takes full steps, so its
+    // states are the same whatever the frame rate. Times before the previous
+    // step hold it; at most MAX_CATCH_UP_STEPS are taken per call, so after a
+    // large jump the physics catches up over several frames.
+    pub fn sample(&mut self, render_time: f64) -> Result<Float64Array, JsError> {
+        check_julian_date(render_time)?;
+        let mut steps = 0;
+        while self.julian_date < render_time && steps < MAX_CATCH_UP_STEPS {
+            self.integrate(self.timestep)?;
+            steps += 1;
+        }
+
+        // Cubic Hermite interpolation, which follows the curvature of the
+        // orbits using the velocities at both ends
+        let span = self.julian_date - self.previous_date;
+        let t = if span > 0.0 { ((render_time - self.previous_date) / span).clamp(0.0, 1.0) } else { 1.0 };
+        let (t2, t3) = (t * t, t * t * t);
+        let (h00, h10, h01, h11) = (2.0 * t3 - 3.0 * t2 + 1.0, t3 - 2.0 * t2 + t, 3.0 * t2 - 2.0 * t3, t3 - t2);
+        let positions: Vec<Vec3> = (0..self.positions.len())
+            .map(|i| {
+                self.previous_positions[i].scale(h00)
+                    + self.previous_velocities[i].scale(h10 * span)
+                    + self.positions[i].scale(h01)
+                    + self.velocities[i].scale(h11 * span)
+            })
+            .collect();
+        Ok(self.scene_buffer(&positions))
+    }
+
+    // Total energy (kinetic plus potential), useful for monitoring integration drift
+    pub fn total_energy(&self) -> f64 {
+        let mut energy = 0.0;
+        for i in 0..self.positions.len() {
+            energy += 0.5 * self.gm[i] * self.velocities[i].dot(&self.velocities[i]);
+            for j in (i + 1)..self.positions.len() {
+                let distance = self.positions[j].sub(&self.positions[i]).length();
+                energy -= self.gm[i] * self.gm[j] / distance;
+            }
+        }
+        energy
+    }
+}
+
+impl NBodySimulator {
+    // Shift velocities into the barycentric frame so the system does not drift
+    fn remove_net_momentum(&mut self) {
+        let total_gm: f64 = self.gm.iter().sum();
+        let mut momentum = Vec3::new(0.0, 0.0, 0.0);
+        for (gm, velocity) in self.gm.iter().zip(&self.velocities) {
+            momentum = momentum.add(&velocity.scale(*gm));
+        }
+        let drift = momentum.scale(1.0 / total_gm);
+        for velocity in &mut self.velocities {
+            *velocity = velocity.sub(&drift);
+        }
+    }
+
+    // Translate positions so the center of mass sits at the origin
+    fn remove_barycenter_offset(&mut self) {
+        let total_gm: f64 = self.gm.iter().sum();
+        let mut moment = Vec3::new(0.0, 0.0, 0.0);
+        for (gm, position) in self.gm.iter().zip(&self.positions) {
+            moment = moment.add(&position.scale(*gm));
+        }
+        let offset = moment.scale(1.0 / total_gm);
+        for position in &mut self.positions {
+            *position = position.sub(&offset);
+        }
+    }
+
+    // Gravitational acceleration on every body from all others
+    fn accelerations(&self, positions: &[Vec3]) -> Vec<Vec3> {
+        // Large systems on the thread pool: each body sums its own pull, which
+        // visits every pair twice but needs no shared writes
+        if parallel::threads_enabled() && positions.len() >= PARALLEL_MIN_BODIES {
+            return parallel::map_indices(positions.len(), PARALLEL_MIN_BODIES, |i| {
+                positions.iter().zip(&self.gm).enumerate().filter(|(j, _)| *j != i).fold(
+                    Vec3::new(0.0, 0.0, 0.0),
+                    |acceleration, (_, (position, gm))| {
+                        let delta = position.sub(&positions[i]);
+                        let distance = delta.length();
+                        acceleration.add(&delta.scale(gm / (distance * distance * distance)))
+                    },
+                )
+            });
+        }
+        let mut accelerations = vec![Vec3::new(0.0, 0.0, 0.0); positions.len()];
+        for i in 0..positions.len() {
+            for j in (i + 1)..positions.len() {
+                let delta = positions[j].sub(&positions[i]);
+                let distance = delta.length();
+                let inv_cube = 1.0 / (distance * distance * distance);
+                accelerations[i] = accelerations[i].add(&delta.scale(self.gm[j] * inv_cube));
+                accelerations[j] = accelerations[j].sub(&delta.scale(self.gm[i] * inv_cube));
+            }
+        }
+        accelerations
+    }
+
+    // Pack positions relative to the output origin in scene coordinates
+    fn scene_buffer(&self, positions: &[Vec3]) -> Float64Array {
+        let origin = self.origin.map_or(Vec3::new(0.0, 0.0, 0.0), |index| positions[index]);
+        let mut buffer = Vec::with_capacity(positions.len() * 3);
+        for position in positions {
+            let scene = to_scene(position.sub(&origin));
+            buffer.extend_from_slice(&[scene.x, scene.y, scene.z]);
+        }
+        Float64Array::from(buffer.as_slice())
+    }
+
+    // Remember the current state as the start of the next step
+    fn mark_previous(&mut self) {
+        self.previous_date = self.julian_date;
+        self.previous_positions.clone_from(&self.positions);
+        self.previous_velocities.clone_from(&self.velocities);
+    }
+
+    // Advance the state and clock by dt days with the selected integrator
+    fn integrate(&mut self, dt: f64) -> Result<(), SimulationError> {
+        self.mark_previous();
+        match self.integrator {
+            Integrator::RungeKutta4 => self.step_rk4(dt),
+            Integrator::Leapfrog => self.step_leapfrog(dt),
+            Integrator::Rkf45 => self.step_rkf45(dt)?,
+        }
+        self.julian_date += dt;
+        Ok(())
+    }
+
+    // Adaptive RKF45 over dt, packing state as [positions..., velocities...]
+    fn step_rkf45(&mut self, dt: f64) -> Result<(), SimulationError> {
+        let count = self.positions.len();
+        let mut y = Vec::with_capacity(count * 6);
+        for v in self.positions.iter().chain(&self.velocities) {
+            y.extend_from_slice(&[v.x, v.y, v.z]);
+        }
+
+        let config = self.propagator_config;
+        self.adaptive_step = rkf45(&mut y, dt, &config, self.adaptive_step, |y, dy| {
+            let positions: Vec<Vec3> = y[..count * 3]
+                .chunks(3)
+                .map(|c| Vec3::new(c[0], c[1], c[2]))
+                .collect();
+            dy[..count * 3].copy_from_slice(&y[count * 3..]);
+            for (i, a) in self.accelerations(&positions).iter().enumerate() {
+                dy[count * 3 + i * 3..count * 3 + i * 3 + 3].copy_from_slice(&[a.x, a.y, a.z]);
+            }
+        })?;
+
+        for i in 0..count {
+            self.positions[i] = Vec3::new(y[i * 3], y[i * 3 + 1], y[i * 3 + 2]);
+            let j = (count + i) * 3;
+            self.velocities[i] = Vec3::new(y[j], y[j + 1], y[j + 2]);
+        }
+        Ok(())
+    }
+
+    // Kick-drift-kick leapfrog (velocity Verlet) step
+    fn step_leapfrog(&mut self, dt: f64) {
+        let accelerations = self.accelerations(&self.positions);
+        for (velocity, acceleration) in self.velocities.iter_mut().zip(&accelerations) {
+            *velocity = velocity.add(&acceleration.scale(dt / 2.0));
+        }
+        for (position, velocity) in self.positions.iter_mut().zip(&self.velocities) {
+            *position = position.add(&velocity.scale(dt));
+        }
+        let accelerations = self.accelerations(&self.positions);
+        for (velocity, acceleration) in self.velocities.iter_mut().zip(&accelerations) {
+            *velocity = velocity.add(&acceleration.scale(dt / 2.0));
+        }
+    }
+
+    // Classical fourth-order Runge-Kutta step
+    fn step_rk4(&mut self, dt: f64) {
+        let offset = |base: &[Vec3], delta: &[Vec3], factor: f64| -> Vec<Vec3> {
+            base.iter().zip(delta).map(|(b, d)| b.add(&d.scale(factor))).collect()
+        };
+
+        let k1_v = self.accelerations(&self.positions);
+        let k1_x = self.velocities.clone();
+
+        let k2_x = offset(&self.velocities, &k1_v, dt / 2.0);
+        let k2_v = self.accelerations(&offset(&self.positions, &k1_x, dt / 2.0));
+
+        let k3_x = offset(&self.velocities, &k2_v, dt / 2.0);
+        let k3_v = self.accelerations(&offset(&self.positions, &k2_x, dt / 2.0));
+
+        let k4_x = offset(&self.velocities, &k3_v, dt);
+        let k4_v = self.accelerations(&offset(&self.positions, &k3_x, dt));
+
+        for i in 0..self.positions.len() {
+            let dx = k1_x[i]
+                .add(&k2_x[i].scale(2.0))
+                .add(&k3_x[i].scale(2.0))
+                .add(&k4_x[i]);
+            let dv = k1_v[i]
+                .add(&k2_v[i].scale(2.0))
+                .add(&k3_v[i].scale(2.0))
+                .add(&k4_v[i]);
+            self.positions[i] = self.positions[i].add(&dx.scale(dt / 6.0));
+            self.velocities[i] = self.velocities[i].add(&dv.scale(dt / 6.0));
+        }
+    }
+}
+
+// Timesteps must be positive and finite, or stepping never makes progress
+fn check_timestep(timestep: f64) -> Result<(), SimulationError> {
+    if !(timestep.is_finite() && timestep > 0.0) {
+        return Err(SimulationError::invalid_argument(&format!("Timestep must be positive, got {}", timestep)));
+    }
+    Ok(())
+}
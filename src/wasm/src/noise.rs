@@ -0,0 +1,113 @@
+// Seeded 3D simplex noise and fractal sums for procedural textures
+
+use crate::random::Rng;
+
+// Gradient directions toward the edge midpoints of a cube
+const GRADIENTS: [[f64; 3]; 12] = [
+    [1.0, 1.0, 0.0],
+    [-1.0, 1.0, 0.0],
+    [1.0, -1.0, 0.0],
+    [-1.0, -1.0, 0.0],
+    [1.0, 0.0, 1.0],
+    [-1.0, 0.0, 1.0],
+    [1.0, 0.0, -1.0],
+    [-1.0, 0.0, -1.0],
+    [0.0, 1.0, 1.0],
+    [0.0, -1.0, 1.0],
+    [0.0, 1.0, -1.0],
+    [0.0, -1.0, -1.0],
+];
+
+// Skewing factors between the cubic grid and the simplex lattice
+const SKEW: f64 = 1.0 / 3.0;
+const UNSKEW: f64 = 1.0 / 6.0;
+
+// Simplex noise (Gustavson's formulation) over a seeded permutation table
+pub(crate) struct SimplexNoise {
+    permutation: [u8; 512],
+}
+
+impl SimplexNoise {
+    pub(crate) fn new(seed: u64) -> SimplexNoise {
+        let mut rng = Rng::new(seed);
+        let mut table: [u8; 256] = std::array::from_fn(|i| i as u8);
+        for i in (1..256).rev() {
+            let j = (rng.next_u64() % (i as u64 + 1)) as usize;
+            table.swap(i, j);
+        }
+        SimplexNoise { permutation: std::array::from_fn(|i| table[i & 255]) }
+    }
+
+    fn hash(&self, i: i64, j: i64, k: i64) -> usize {
+        let p = |n: usize| self.permutation[n] as usize;
+        p((i & 255) as usize + p((j & 255) as usize + p((k & 255) as usize))) % 12
+    }
+
+    // Noise value in roughly [-1, 1]
+    pub(crate) fn sample(&self, x: f64, y: f64, z: f64) -> f64 {
+        // Cell origin in skewed space and the offset from it in unskewed space
+        let s = (x + y + z) * SKEW;
+        let (i, j, k) = ((x + s).floor(), (y + s).floor(), (z + s).floor());
+        let t = (i + j + k) * UNSKEW;
+        let offset = [x - (i - t), y - (j - t), z - (k - t)];
+
+        // Which of the cube's six tetrahedra the point lies in
+        let [x0, y0, z0] = offset;
+        let (second, third) = if x0 >= y0 {
+            if y0 >= z0 {
+                ([1, 0, 0], [1, 1, 0])
+            } else if x0 >= z0 {
+                ([1, 0, 0], [1, 0, 1])
+            } else {
+                ([0, 0, 1], [1, 0, 1])
+            }
+        } else if y0 < z0 {
+            ([0, 0, 1], [0, 1, 1])
+        } else if x0 < z0 {
+            ([0, 1, 0], [0, 1, 1])
+        } else {
+            ([0, 1, 0], [1, 1, 0])
+        };
+
+        let (i, j, k) = (i as i64, j as i64, k as i64);
+        let corners = [[0, 0, 0], second, third, [1, 1, 1]];
+        let mut total = 0.0;
+        for (n, corner) in corners.iter().enumerate() {
+            let d: [f64; 3] = std::array::from_fn(|axis| offset[axis] - corner[axis] as f64 + n as f64 * UNSKEW);
+            let falloff = 0.6 - d[0] * d[0] - d[1] * d[1] - d[2] * d[2];
+            if falloff > 0.0 {
+                let g = GRADIENTS[self.hash(i + corner[0], j + corner[1], k + corner[2])];
+                total += falloff.powi(4) * (g[0] * d[0] + g[1] * d[1] + g[2] * d[2]);
+            }
+        }
+        32.0 * total
+    }
+
+    // Fractional Brownian motion: octaves doubling in frequency and halving in
+    // amplitude, normalized back to roughly [-1, 1]
+    pub(crate) fn fbm(&self, x: f64, y: f64, z: f64, octaves: u32) -> f64 {
+        let (mut frequency, mut amplitude) = (1.0, 1.0);
+        let (mut sum, mut norm) = (0.0, 0.0);
+        for _ in 0..octaves {
+            sum += amplitude * self.sample(x * frequency, y * frequency, z * frequency);
+            norm += amplitude;
+            frequency *= 2.0;
+            amplitude *= 0.5;
+        }
+        sum / norm
+    }
+
+    // Ridged multifractal in [0, 1], peaking along the noise's zero crossings
+    pub(crate) fn ridged(&self, x: f64, y: f64, z: f64, octaves: u32) -> f64 {
+        let (mut frequency, mut amplitude) = (1.0, 1.0);
+        let (mut sum, mut norm) = (0.0, 0.0);
+        for _ in 0..octaves {
+            let ridge = 1.0 - self.sample(x * frequency, y * frequency, z * frequency).abs();
+            sum += amplitude * ridge * ridge;
+            norm += amplitude;
+            frequency *= 2.0;
+            amplitude *= 0.5;
+        }
+        sum / norm
+    }
+}
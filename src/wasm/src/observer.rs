@@ -0,0 +1,348 @@
+use wasm_bindgen::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::errors::{check_julian_date, SimulationError};
+use crate::lagrange::body_state_and_mass;
+use crate::moons::lunar_geocentric_ecliptic;
+use crate::precession::{apparent_sidereal_time, ecliptic_to_true_equator};
+use crate::state::StateVector;
+use crate::units::EARTH_RADIUS_KM;
+use crate::{
+    build_planet_data, deg_to_rad, planet_elements, planet_state_with_precision, to_scene, PlanetData,
+    Precision, Vec3, AU_KM, PLANET_DATA, PLANET_ELEMENTS,
+};
+
+// Index of Earth in PLANET_ELEMENTS
+pub(crate) const EARTH_INDEX: usize = 2;
+
+// Geocentric equatorial coordinates of a body
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[wasm_bindgen]
+pub struct EquatorialCoordinates {
+    name: String,
+    right_ascension: f64,
+    declination: f64,
+    distance: f64,
+}
+
+#[wasm_bindgen]
+impl EquatorialCoordinates {
+    #[wasm_bindgen(getter)]
+    pub fn name(&self) -> String {
+        self.name.clone()
+    }
+
+    // Right ascension (hours)
+    #[wasm_bindgen(getter)]
+    pub fn right_ascension(&self) -> f64 {
+        self.right_ascension
+    }
+
+    // Declination (degrees)
+    #[wasm_bindgen(getter)]
+    pub fn declination(&self) -> f64 {
+        self.declination
+    }
+
+    // Distance from Earth's center (AU)
+    #[wasm_bindgen(getter)]
+    pub fn distance(&self) -> f64 {
+        self.distance
+    }
+}
+
+// Speed of light (AU/day)
+pub(crate) const SPEED_OF_LIGHT: f64 = 173.1446326846693;
+
+// Geographic location of a ground observer
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[wasm_bindgen]
+pub struct ObserverLocation {
+    lat: f64,
+    lon: f64,
+    elevation: f64,
+}
+
+#[wasm_bindgen]
+impl ObserverLocation {
+    // Latitude and longitude in degrees (east positive), elevation in meters
+    #[wasm_bindgen(constructor)]
+    pub fn new(lat: f64, lon: f64, elevation: f64) -> ObserverLocation {
+        ObserverLocation { lat, lon, elevation }
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn lat(&self) -> f64 {
+        self.lat
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn lon(&self) -> f64 {
+        self.lon
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn elevation(&self) -> f64 {
+        self.elevation
+    }
+}
+
+// Topocentric horizontal coordinates of a body
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[wasm_bindgen]
+pub struct HorizontalCoordinates {
+    altitude: f64,
+    azimuth: f64,
+    distance: f64,
+}
+
+#[wasm_bindgen]
+impl HorizontalCoordinates {
+    // Altitude above the geometric horizon (degrees), without refraction
+    #[wasm_bindgen(getter)]
+    pub fn altitude(&self) -> f64 {
+        self.altitude
+    }
+
+    // Azimuth measured from north through east (degrees)
+    #[wasm_bindgen(getter)]
+    pub fn azimuth(&self) -> f64 {
+        self.azimuth
+    }
+
+    // Distance from the observer (AU)
+    #[wasm_bindgen(getter)]
+    pub fn distance(&self) -> f64 {
+        self.distance
+    }
+}
+
+// Earth's flattening (WGS84); the ellipsoid's equatorial radius is EARTH_RADIUS_KM
+const EARTH_FLATTENING: f64 = 1.0 / 298.257223563;
+
+// Heliocentric ecliptic state of a planet by index
+pub(crate) fn planet_state(index: usize, julian_date: f64) -> StateVector {
+    planet_state_with_precision(index, julian_date, Precision::High)
+}
+
+// Heliocentric state of a planet at the moment its light left it, as seen from
+// an observer position (AU), and the light travel time (days)
+pub(crate) fn light_time_corrected(index: usize, julian_date: f64, observer: &Vec3) -> (StateVector, f64) {
+    let mut tau = 0.0;
+    let mut emitted = planet_state(index, julian_date);
+    for _ in 0..3 {
+        tau = emitted.position.sub(observer).length() / SPEED_OF_LIGHT;
+        emitted = planet_state(index, julian_date - tau);
+    }
+    (emitted, tau)
+}
+
+// Mean obliquity of the ecliptic (radians), IAU 2006
+pub(crate) fn mean_obliquity(julian_date: f64) -> f64 {
+    let t = (julian_date - 2451545.0) / 36525.0;
+    let arcseconds = 84381.406 - 46.836769 * t - 0.0001831 * t * t + 0.00200340 * t * t * t;
+    deg_to_rad(arcseconds / 3600.0)
+}
+
+// Rotate an ecliptic vector into the equatorial frame
+pub(crate) fn ecliptic_to_equatorial(ecliptic: &Vec3, obliquity: f64) -> Vec3 {
+    let (sin_e, cos_e) = obliquity.sin_cos();
+    Vec3::new(
+        ecliptic.x,
+        ecliptic.y * cos_e - ecliptic.z * sin_e,
+        ecliptic.y * sin_e + ecliptic.z * cos_e,
+    )
+}
+
+// Geocentric ecliptic position (AU) of the Sun, Moon or a planet, light-time
+// corrected for planets
+pub(crate) fn geocentric_ecliptic(name: &str, julian_date: f64) -> Option<Vec3> {
+    let earth = planet_state(EARTH_INDEX, julian_date).position;
+    match name {
+        "Sun" => Some(earth.scale(-1.0)),
+        "Moon" => Some(lunar_geocentric_ecliptic(julian_date).scale(1.0 / AU_KM)),
+        "Earth" => None,
+        _ => {
+            let index = PLANET_ELEMENTS.iter().position(|(n, _)| *n == name)?;
+            let (emitted, _) = light_time_corrected(index, julian_date, &earth);
+            Some(emitted.position.sub(&earth))
+        }
+    }
+}
+
+// Convert a geocentric ecliptic position into right ascension (hours),
+// declination (degrees) and distance on the true equator and equinox of date
+pub(crate) fn to_right_ascension_declination(ecliptic: &Vec3, julian_date: f64) -> (f64, f64, f64) {
+    let equatorial = ecliptic_to_true_equator(ecliptic, julian_date);
+    let distance = equatorial.length();
+    let right_ascension = equatorial.y.atan2(equatorial.x).to_degrees().rem_euclid(360.0) / 15.0;
+    let declination = (equatorial.z / distance).asin().to_degrees();
+    (right_ascension, declination, distance)
+}
+
+// Calculate geocentric right ascension, declination and distance of the Sun,
+// the Moon and the planets, for a "view from Earth" sky
+#[wasm_bindgen]
+pub fn equatorial_coordinates(julian_date: f64) -> Result<Vec<EquatorialCoordinates>, JsError> {
+    check_julian_date(julian_date)?;
+    let names = ["Sun", "Moon"]
+        .into_iter()
+        .chain(PLANET_ELEMENTS.iter().map(|(name, _)| *name));
+
+    Ok(names
+        .filter_map(|name| {
+            let ecliptic = geocentric_ecliptic(name, julian_date)?;
+            let (right_ascension, declination, distance) = to_right_ascension_declination(&ecliptic, julian_date);
+            Some(EquatorialCoordinates {
+                name: name.to_string(),
+                right_ascension,
+                declination,
+                distance,
+            })
+        })
+        .collect())
+}
+
+// Greenwich mean sidereal time (degrees), IAU 2006 expression: the Earth
+// rotation angle plus the accumulated precession in right ascension
+pub(crate) fn sidereal_time(julian_date: f64) -> f64 {
+    let t = (julian_date - 2451545.0) / 36525.0;
+    let arcseconds = 0.014506
+        + t * (4612.156534 + t * (1.3915817 + t * (-0.00000044 + t * (-0.000029956 + t * -0.0000000368))));
+    (rotation_angle(julian_date) + arcseconds / 3600.0).rem_euclid(360.0)
+}
+
+// Greenwich mean sidereal time (degrees) at a Julian date (UT)
+#[wasm_bindgen]
+pub fn greenwich_sidereal_time(julian_date: f64) -> Result<f64, JsError> {
+    check_julian_date(julian_date)?;
+    Ok(sidereal_time(julian_date))
+}
+
+// Earth rotation angle (degrees), IAU 2000: the angle of the prime meridian
+// from the celestial intermediate origin, for orienting Earth's texture
+#[wasm_bindgen]
+pub fn earth_rotation_angle(julian_date: f64) -> Result<f64, JsError> {
+    check_julian_date(julian_date)?;
+    Ok(rotation_angle(julian_date))
+}
+
+// Earth rotation angle (degrees) at a date already known to be valid
+pub(crate) fn rotation_angle(julian_date: f64) -> f64 {
+    let d = julian_date - 2451545.0;
+    (360.0 * (0.7790572732640 + 0.00273781191135448 * d + d.rem_euclid(1.0))).rem_euclid(360.0)
+}
+
+// Geocentric equatorial position (AU) of an observer on the rotating Earth
+fn observer_equatorial_position(observer: &ObserverLocation, julian_date: f64) -> Vec3 {
+    let lat = deg_to_rad(observer.lat);
+    let local_sidereal = deg_to_rad(apparent_sidereal_time(julian_date) + observer.lon);
+    let elevation_km = observer.elevation / 1000.0;
+
+    // Geodetic to geocentric on the reference ellipsoid
+    let e2 = EARTH_FLATTENING * (2.0 - EARTH_FLATTENING);
+    let n = EARTH_RADIUS_KM / (1.0 - e2 * lat.sin() * lat.sin()).sqrt();
+    let rho_cos = (n + elevation_km) * lat.cos() / AU_KM;
+    let rho_sin = (n * (1.0 - e2) + elevation_km) * lat.sin() / AU_KM;
+
+    Vec3::new(rho_cos * local_sidereal.cos(), rho_cos * local_sidereal.sin(), rho_sin)
+}
+
+// Topocentric altitude and azimuth of a body's equatorial position
+pub(crate) fn horizontal_from_ecliptic(
+    geocentric: &Vec3,
+    julian_date: f64,
+    observer: &ObserverLocation,
+) -> HorizontalCoordinates {
+    let equatorial =
+        ecliptic_to_true_equator(geocentric, julian_date).sub(&observer_equatorial_position(observer, julian_date));
+    let distance = equatorial.length();
+    let declination = (equatorial.z / distance).asin();
+    let right_ascension = equatorial.y.atan2(equatorial.x);
+
+    let lat = deg_to_rad(observer.lat);
+    let hour_angle = deg_to_rad(apparent_sidereal_time(julian_date) + observer.lon) - right_ascension;
+
+    let altitude = (lat.sin() * declination.sin() + lat.cos() * declination.cos() * hour_angle.cos()).asin();
+    let azimuth = (-hour_angle.sin() * declination.cos())
+        .atan2(declination.sin() * lat.cos() - declination.cos() * lat.sin() * hour_angle.cos());
+
+    HorizontalCoordinates {
+        altitude: altitude.to_degrees(),
+        azimuth: azimuth.to_degrees().rem_euclid(360.0),
+        distance,
+    }
+}
+
+// Calculate the altitude and azimuth of the Sun, Moon or a planet for a
+// ground observer, accounting for Earth rotation and parallax
+#[wasm_bindgen]
+pub fn altaz(body: &str, julian_date: f64, observer: &ObserverLocation) -> Result<HorizontalCoordinates, JsError> {
+    check_julian_date(julian_date)?;
+    let geocentric = geocentric_ecliptic(body, julian_date).ok_or_else(|| SimulationError::unknown_body(body))?;
+    Ok(horizontal_from_ecliptic(&geocentric, julian_date, observer))
+}
+
+// Angular separation (degrees) between two bodies as seen from a third — the
+// Sun, the Moon, a planet or a dwarf planet — using light-time corrected
+// positions. Fails for unknown names or when the observer coincides with
+// either body.
+#[wasm_bindgen]
+pub fn angular_separation(body_a: &str, body_b: &str, observer: &str, julian_date: f64) -> Result<f64, JsError> {
+    check_julian_date(julian_date)?;
+    let state = |body: &str, julian_date: f64| {
+        body_state_and_mass(body, julian_date).map(|(state, _)| state).ok_or_else(|| SimulationError::unknown_body(body))
+    };
+    let observer_state = state(observer, julian_date)?;
+    let direction = |body: &str| -> Result<Vec3, SimulationError> {
+        let mut offset = state(body, julian_date)?.position.sub(&observer_state.position);
+        for _ in 0..2 {
+            let tau = offset.length() / SPEED_OF_LIGHT;
+            offset = state(body, julian_date - tau)?.position.sub(&observer_state.position);
+        }
+        Ok(offset)
+    };
+    let (a, b) = (direction(body_a)?, direction(body_b)?);
+    if a.length() == 0.0 || b.length() == 0.0 {
+        return Err(SimulationError::invalid_argument("The observer must differ from both bodies").into());
+    }
+    Ok(a.angle_to(&b).to_degrees())
+}
+
+// Calculate where each planet appears to an observer on another planet,
+// applying light-time correction and annual aberration. Positions are in
+// scene coordinates: the observer's position plus the apparent direction
+// scaled to the light-time corrected distance.
+#[wasm_bindgen]
+pub fn apparent_planet_positions(julian_date: f64, observer: &str) -> Result<Vec<PlanetData>, JsError> {
+    check_julian_date(julian_date)?;
+    let observer_index = PLANET_ELEMENTS
+        .iter()
+        .position(|(name, _)| *name == observer)
+        .ok_or_else(|| SimulationError::unknown_body(observer))?;
+    let observer_state = planet_state(observer_index, julian_date);
+    let mut planets = Vec::new();
+
+    for (i, (name, _)) in PLANET_ELEMENTS.iter().enumerate() {
+        if i == observer_index {
+            continue;
+        }
+
+        let (emitted, tau) = light_time_corrected(i, julian_date, &observer_state.position);
+
+        // Annual aberration: shift the direction toward the observer's motion
+        let offset = emitted.position.sub(&observer_state.position);
+        let distance = offset.length();
+        let direction = offset.scale(1.0 / distance);
+        let aberrated = direction.add(&observer_state.velocity.scale(1.0 / SPEED_OF_LIGHT));
+        let apparent = aberrated.scale(distance / aberrated.length());
+
+        let elements = planet_elements(i, julian_date - tau, Precision::High);
+        let mut planet = build_planet_data(name, &elements, julian_date - tau, &PLANET_DATA[i]);
+        planet.true_position = observer_state.position.add(&apparent);
+        planet.position = to_scene(planet.true_position);
+        planets.push(planet);
+    }
+
+    Ok(planets)
+}
@@ -0,0 +1,133 @@
+// Orbit polylines sampled by true anomaly and refined where the curve bends:
+// an eccentric orbit gets dense vertices around perihelion, where uniform
+// time or eccentric-anomaly steps leave visible corners, and few along its
+// slow, nearly straight outer arc
+
+use wasm_bindgen::prelude::*;
+use js_sys::Float64Array;
+use std::f64::consts::PI;
+
+use crate::comets::{CometElements, COMET_ELEMENTS};
+use crate::errors::{check_julian_date, SimulationError};
+use crate::{deg_to_rad, find_orbital_elements, orbital_to_reference, to_scene, Precision, Vec3};
+
+// Arcs the orbit is first cut into before refinement
+const INITIAL_ARCS: usize = 16;
+
+// Deepest halving of an arc, bounding the vertex count to INITIAL_ARCS << 12
+const MAX_DEPTH: u32 = 12;
+
+// Shape and orientation of a conic: semi-latus rectum (AU), eccentricity and
+// angles (radians)
+struct Conic {
+    p: f64,
+    e: f64,
+    omega: f64,
+    w: f64,
+    i: f64,
+}
+
+impl Conic {
+    fn point(&self, true_anomaly: f64) -> Vec3 {
+        let r = self.p / (1.0 + self.e * true_anomaly.cos());
+        to_scene(orbital_to_reference(
+            r * true_anomaly.cos(),
+            r * true_anomaly.sin(),
+            self.omega,
+            self.w,
+            self.i,
+        ))
+    }
+
+    // Scene vertices from true anomaly start to end, each arc halved until
+    // its midpoint lies within tolerance of the chord
+    fn sample(&self, start: f64, end: f64, tolerance: f64) -> Vec<f64> {
+        let first = self.point(start);
+        let mut buffer = vec![first.x, first.y, first.z];
+        let step = (end - start) / INITIAL_ARCS as f64;
+        for arc in 0..INITIAL_ARCS {
+            let (from, to) = (start + step * arc as f64, start + step * (arc + 1) as f64);
+            self.refine((from, self.point(from)), (to, self.point(to)), tolerance, 0, &mut buffer);
+        }
+        buffer
+    }
+
+    // Append the vertices after `start` up to and including `end`, each given
+    // as (true anomaly, vertex)
+    fn refine(&self, start: (f64, Vec3), end: (f64, Vec3), tolerance: f64, depth: u32, buffer: &mut Vec<f64>) {
+        let anomaly = 0.5 * (start.0 + end.0);
+        let middle = (anomaly, self.point(anomaly));
+        if depth < MAX_DEPTH && distance_to_segment(&middle.1, &start.1, &end.1) > tolerance {
+            self.refine(start, middle, tolerance, depth + 1, buffer);
+            self.refine(middle, end, tolerance, depth + 1, buffer);
+        } else {
+            buffer.extend_from_slice(&[end.1.x, end.1.y, end.1.z]);
+        }
+    }
+}
+
+fn distance_to_segment(point: &Vec3, a: &Vec3, b: &Vec3) -> f64 {
+    let chord = b.sub(a);
+    let length_squared = chord.dot(&chord);
+    let t = if length_squared > 0.0 { (point.sub(a).dot(&chord) / length_squared).clamp(0.0, 1.0) } else { 0.0 };
+    point.distance(&a.add(&chord.scale(t)))
+}
+
+fn check_tolerance(tolerance: f64) -> Result<(), SimulationError> {
+    if tolerance > 0.0 && tolerance.is_finite() {
+        Ok(())
+    } else {
+        Err(SimulationError::invalid_argument(&format!("Tolerance must be positive, got {}", tolerance)))
+    }
+}
+
+// A body's orbit at julian_date as a closed scene-space polyline packed
+// [x, y, z, ...], whose segments stray at most `tolerance` scene units from
+// the true ellipse. Starts and ends at aphelion. Precision is as for orbit_path.
+#[wasm_bindgen]
+pub fn orbit_path_adaptive(
+    planet_name: &str,
+    julian_date: f64,
+    tolerance: f64,
+    precision: Precision,
+) -> Result<Float64Array, JsError> {
+    check_julian_date(julian_date)?;
+    check_tolerance(tolerance)?;
+    let elements =
+        find_orbital_elements(planet_name, julian_date, precision).ok_or_else(|| SimulationError::unknown_body(planet_name))?;
+    let conic = Conic {
+        p: elements.a * (1.0 - elements.e * elements.e),
+        e: elements.e,
+        omega: deg_to_rad(elements.omega),
+        w: deg_to_rad(elements.w),
+        i: deg_to_rad(elements.i),
+    };
+    Ok(Float64Array::from(conic.sample(-PI, PI, tolerance).as_slice()))
+}
+
+// A comet's path through perihelion, within `tolerance` scene units, out to
+// max_distance AU on each leg (the whole ellipse if its aphelion is nearer).
+// Parabolic and hyperbolic paths are open.
+fn comet_path(elements: &CometElements, tolerance: f64, max_distance: f64) -> Vec<f64> {
+    let conic = Conic {
+        p: elements.q() * (1.0 + elements.e()),
+        e: elements.e(),
+        omega: deg_to_rad(elements.omega()),
+        w: deg_to_rad(elements.w()),
+        i: deg_to_rad(elements.i()),
+    };
+    // True anomaly where r reaches max_distance, or aphelion
+    let cos_limit = (conic.p / max_distance.max(elements.q()) - 1.0) / conic.e;
+    let limit = if cos_limit <= -1.0 { PI } else { cos_limit.min(1.0).acos() };
+    conic.sample(-limit, limit, tolerance)
+}
+
+// Path of a built-in comet by name (see comet_positions), sampled as for
+// orbit_path_adaptive and cut off max_distance AU from the Sun
+#[wasm_bindgen]
+pub fn comet_orbit_path(name: &str, tolerance: f64, max_distance: f64) -> Result<Float64Array, JsError> {
+    check_tolerance(tolerance)?;
+    let (_, elements) =
+        COMET_ELEMENTS.iter().find(|(comet, _)| *comet == name).ok_or_else(|| SimulationError::unknown_body(name))?;
+    Ok(Float64Array::from(comet_path(elements, tolerance, max_distance).as_slice()))
+}
@@ -0,0 +1,53 @@
+// Optional parallelism on a rayon pool of Web Workers (the "threads" feature).
+// The pool shares WASM memory through SharedArrayBuffer, which browsers only
+// provide to cross-origin isolated pages, so JS starts it only when
+// `crossOriginIsolated` is true:
+//
+//     if (crossOriginIsolated) {
+//         await initThreadPool(navigator.hardwareConcurrency);
+//         set_threads_enabled(true);
+//     }
+//
+// Until then, and in builds without the feature, everything runs on the
+// calling thread.
+
+use wasm_bindgen::prelude::*;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+#[cfg(feature = "threads")]
+pub use wasm_bindgen_rayon::init_thread_pool;
+
+// Shared by every worker, unlike the thread-local configuration elsewhere
+static THREADS_ENABLED: AtomicBool = AtomicBool::new(false);
+
+// Switch work onto the thread pool once initThreadPool has resolved, or back
+// to the calling thread. Returns whether threads are now in use, which is
+// always false in builds without the feature.
+#[wasm_bindgen]
+pub fn set_threads_enabled(enabled: bool) -> bool {
+    THREADS_ENABLED.store(enabled && cfg!(feature = "threads"), Ordering::Relaxed);
+    threads_enabled()
+}
+
+#[wasm_bindgen]
+pub fn threads_enabled() -> bool {
+    THREADS_ENABLED.load(Ordering::Relaxed)
+}
+
+// Evaluate f for each index in 0..count, in order, across the pool when it is
+// running and count reaches min_parallel (below that, dispatch costs more than
+// it saves)
+pub(crate) fn map_indices<T, F>(count: usize, min_parallel: usize, f: F) -> Vec<T>
+where
+    T: Send,
+    F: Fn(usize) -> T + Send + Sync,
+{
+    #[cfg(feature = "threads")]
+    if count >= min_parallel && threads_enabled() {
+        use rayon::prelude::*;
+        return (0..count).into_par_iter().map(f).collect();
+    }
+    #[cfg(not(feature = "threads"))]
+    let _ = min_parallel;
+    (0..count).map(f).collect()
+}
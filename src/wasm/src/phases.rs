@@ -0,0 +1,225 @@
+use wasm_bindgen::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::errors::check_julian_date;
+use crate::observer::{ecliptic_to_equatorial, geocentric_ecliptic, mean_obliquity};
+use crate::search::newton_root;
+use crate::{deg_to_rad, Vec3, AU_KM};
+
+// Mean synodic month (days)
+const SYNODIC_MONTH: f64 = 29.530588853;
+
+// Lunar phase and illumination
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[wasm_bindgen]
+pub struct MoonPhase {
+    phase_angle: f64,
+    illuminated_fraction: f64,
+    elongation: f64,
+    phase_name: String,
+    next_new_moon: f64,
+    next_full_moon: f64,
+}
+
+#[wasm_bindgen]
+impl MoonPhase {
+    // Sun-Moon-Earth angle (degrees): 0 at full moon, 180 at new moon
+    #[wasm_bindgen(getter)]
+    pub fn phase_angle(&self) -> f64 {
+        self.phase_angle
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn illuminated_fraction(&self) -> f64 {
+        self.illuminated_fraction
+    }
+
+    // Ecliptic longitude of the Moon east of the Sun (degrees, 0-360)
+    #[wasm_bindgen(getter)]
+    pub fn elongation(&self) -> f64 {
+        self.elongation
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn phase_name(&self) -> String {
+        self.phase_name.clone()
+    }
+
+    // Julian date of the next new moon
+    #[wasm_bindgen(getter)]
+    pub fn next_new_moon(&self) -> f64 {
+        self.next_new_moon
+    }
+
+    // Julian date of the next full moon
+    #[wasm_bindgen(getter)]
+    pub fn next_full_moon(&self) -> f64 {
+        self.next_full_moon
+    }
+}
+
+// Phase names for each eighth of the lunation, centered on the principal phases
+static PHASE_NAMES: [&str; 8] = [
+    "New Moon",
+    "Waxing Crescent",
+    "First Quarter",
+    "Waxing Gibbous",
+    "Full Moon",
+    "Waning Gibbous",
+    "Last Quarter",
+    "Waning Crescent",
+];
+
+// Phase angle (degrees) at a body given its geocentric vector and the Sun's
+pub(crate) fn phase_angle(body: &Vec3, sun: &Vec3) -> f64 {
+    let body_to_earth = body.scale(-1.0);
+    let body_to_sun = sun.sub(body);
+    let cos = body_to_earth.dot(&body_to_sun) / (body_to_earth.length() * body_to_sun.length());
+    cos.clamp(-1.0, 1.0).acos().to_degrees()
+}
+
+// Longitude of the Moon east of the Sun (degrees, 0-360)
+fn lunar_elongation(julian_date: f64) -> f64 {
+    let longitude = |body: &str| geocentric_ecliptic(body, julian_date).map_or(0.0, |v| v.y.atan2(v.x).to_degrees());
+    (longitude("Moon") - longitude("Sun")).rem_euclid(360.0)
+}
+
+// First Julian date after julian_date at which the Moon reaches the given elongation
+fn next_lunar_elongation(julian_date: f64, target: f64) -> f64 {
+    // Offset from the target wrapped to (-180, 180]; increases through zero
+    let offset = |jd: f64| {
+        let difference = (lunar_elongation(jd) - target).rem_euclid(360.0);
+        if difference > 180.0 { difference - 360.0 } else { difference }
+    };
+    let mut t0 = julian_date;
+    let mut f0 = offset(t0);
+    while t0 < julian_date + SYNODIC_MONTH + 2.0 {
+        let t1 = t0 + 1.0;
+        let f1 = offset(t1);
+        if f0 < 0.0 && f1 >= 0.0 && f1 - f0 < 90.0 {
+            return newton_root(offset, t0, t1, 1e-6);
+        }
+        t0 = t1;
+        f0 = f1;
+    }
+    julian_date
+}
+
+// Calculate the Moon's phase, illumination and upcoming new and full moons
+#[wasm_bindgen]
+pub fn moon_phase(julian_date: f64) -> Result<MoonPhase, JsError> {
+    check_julian_date(julian_date)?;
+    let sun = geocentric_ecliptic("Sun", julian_date).unwrap_or(Vec3::new(1.0, 0.0, 0.0));
+    let moon = geocentric_ecliptic("Moon", julian_date).unwrap_or(Vec3::new(0.0, 0.0, 0.0));
+    let phase_angle = phase_angle(&moon, &sun);
+    let elongation = lunar_elongation(julian_date);
+    let sector = ((elongation + 22.5) / 45.0) as usize % PHASE_NAMES.len();
+
+    Ok(MoonPhase {
+        phase_angle,
+        illuminated_fraction: (1.0 + phase_angle.to_radians().cos()) / 2.0,
+        elongation,
+        phase_name: PHASE_NAMES[sector].to_string(),
+        next_new_moon: next_lunar_elongation(julian_date, 0.0),
+        next_full_moon: next_lunar_elongation(julian_date, 180.0),
+    })
+}
+
+// Apparent appearance of a planet as seen from Earth
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[wasm_bindgen]
+pub struct ApparentData {
+    name: String,
+    phase_angle: f64,
+    illuminated_fraction: f64,
+    angular_diameter: f64,
+    magnitude: f64,
+}
+
+#[wasm_bindgen]
+impl ApparentData {
+    #[wasm_bindgen(getter)]
+    pub fn name(&self) -> String {
+        self.name.clone()
+    }
+
+    // Sun-planet-Earth angle (degrees)
+    #[wasm_bindgen(getter)]
+    pub fn phase_angle(&self) -> f64 {
+        self.phase_angle
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn illuminated_fraction(&self) -> f64 {
+        self.illuminated_fraction
+    }
+
+    // Equatorial angular diameter (arcseconds)
+    #[wasm_bindgen(getter)]
+    pub fn angular_diameter(&self) -> f64 {
+        self.angular_diameter
+    }
+
+    // Apparent visual magnitude
+    #[wasm_bindgen(getter)]
+    pub fn magnitude(&self) -> f64 {
+        self.magnitude
+    }
+}
+
+// Equatorial radius (km) and visual magnitude coefficients V(1,0), then
+// linear, quadratic and cubic phase angle terms (Astronomical Almanac)
+static PLANET_PHOTOMETRY: &[(&str, f64, f64, f64, f64, f64)] = &[
+    ("Mercury", 2439.7, -0.42, 0.0380, -0.000273, 0.000002),
+    ("Venus", 6051.8, -4.40, 0.0009, 0.000239, -0.00000065),
+    ("Mars", 3396.2, -1.52, 0.016, 0.0, 0.0),
+    ("Jupiter", 71492.0, -9.40, 0.005, 0.0, 0.0),
+    ("Saturn", 60268.0, -8.88, 0.044, 0.0, 0.0),
+    ("Uranus", 25559.0, -7.19, 0.0, 0.0, 0.0),
+    ("Neptune", 24764.0, -6.87, 0.0, 0.0, 0.0),
+];
+
+// Saturn's north pole (J2000.0 right ascension and declination, degrees)
+const SATURN_POLE: (f64, f64) = (40.589, 83.537);
+
+// Saturnicentric latitude of Earth (radians), the tilt of the rings toward us
+fn saturn_ring_tilt(geocentric: &Vec3) -> f64 {
+    let (ra, dec) = (deg_to_rad(SATURN_POLE.0), deg_to_rad(SATURN_POLE.1));
+    let pole = Vec3::new(dec.cos() * ra.cos(), dec.cos() * ra.sin(), dec.sin());
+    // The pole is referred to J2000.0, so use the J2000.0 equator too
+    let direction = ecliptic_to_equatorial(geocentric, mean_obliquity(2451545.0));
+    (-pole.dot(&direction) / direction.length()).asin()
+}
+
+// Calculate phase, illumination, angular size and visual magnitude of each
+// planet as seen from Earth
+#[wasm_bindgen]
+pub fn apparent_data(julian_date: f64) -> Result<Vec<ApparentData>, JsError> {
+    check_julian_date(julian_date)?;
+    let sun = geocentric_ecliptic("Sun", julian_date).unwrap_or(Vec3::new(1.0, 0.0, 0.0));
+
+    Ok(PLANET_PHOTOMETRY
+        .iter()
+        .filter_map(|&(name, radius, v0, c1, c2, c3)| {
+            let geocentric = geocentric_ecliptic(name, julian_date)?;
+            let delta = geocentric.length();
+            let r = sun.sub(&geocentric).length();
+            let i = phase_angle(&geocentric, &sun);
+
+            let mut magnitude = v0 + 5.0 * (r * delta).log10() + c1 * i + c2 * i * i + c3 * i * i * i;
+            if name == "Saturn" {
+                // Brightening from the rings as they open toward Earth
+                let tilt = saturn_ring_tilt(&geocentric).sin().abs();
+                magnitude += -2.60 * tilt + 1.25 * tilt * tilt;
+            }
+
+            Some(ApparentData {
+                name: name.to_string(),
+                phase_angle: i,
+                illuminated_fraction: (1.0 + i.to_radians().cos()) / 2.0,
+                angular_diameter: 2.0 * (radius / (delta * AU_KM)).atan().to_degrees() * 3600.0,
+                magnitude,
+            })
+        })
+        .collect())
+}
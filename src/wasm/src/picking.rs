@@ -0,0 +1,51 @@
+// Mouse picking with a screen-space tolerance: each sphere is treated as at
+// least pixel_tolerance pixels in radius, so a tiny distant planet can be
+// clicked as easily as a nearby one
+
+use crate::culling::CameraView;
+use crate::spatial::RayHit;
+use crate::Vec3;
+
+// Best pick along a ray among (center, radius) spheres in scene units. A ray
+// that truly hits spheres picks the nearest; otherwise the sphere whose edge
+// passes closest on screen, within the tolerance, wins.
+pub(crate) fn pick_spheres(
+    spheres: impl Iterator<Item = (Vec3, f64)>,
+    ray_origin: &Vec3,
+    ray_direction: &Vec3,
+    pixel_tolerance: f64,
+    camera: &CameraView,
+) -> Option<RayHit> {
+    let direction = ray_direction.normalize();
+    if direction.length() == 0.0 {
+        return None;
+    }
+    // (pixels missed, distance along the ray, index)
+    let mut best: Option<(f64, f64, usize)> = None;
+    for (index, (center, radius)) in spheres.enumerate() {
+        let offset = center.sub(ray_origin);
+        let along = offset.dot(&direction);
+        if along < 0.0 && offset.length() > radius {
+            continue;
+        }
+        let miss = (offset.dot(&offset) - along * along).max(0.0).sqrt();
+        let pixels = camera.pixel_radius(&center, (miss - radius).max(0.0));
+        if pixels > pixel_tolerance {
+            continue;
+        }
+        // Distance to the sphere's surface for true hits, else to the point
+        // of closest approach
+        let distance = if miss <= radius {
+            (along - (radius * radius - miss * miss).sqrt()).max(0.0)
+        } else {
+            along
+        };
+        let better = best.is_none_or(|(best_pixels, best_distance, _)| {
+            pixels < best_pixels || (pixels == best_pixels && distance < best_distance)
+        });
+        if better {
+            best = Some((pixels, distance, index));
+        }
+    }
+    best.map(|(_, distance, index)| RayHit::new(index as u32, distance, ray_origin.add(&direction.scale(distance))))
+}
@@ -0,0 +1,145 @@
+// Combined gravitational potential of the Sun and planets sampled over the
+// ecliptic plane, for rubber-sheet "gravity well" meshes
+
+use wasm_bindgen::prelude::*;
+use js_sys::{Float32Array, Uint32Array};
+
+use crate::errors::{check_julian_date, SimulationError};
+use crate::observer::planet_state;
+use crate::scene::to_scene;
+use crate::{Vec3, PLANET_DATA, PLANET_ELEMENTS, SUN_EARTH_MASS_RATIO};
+
+// Largest number of samples along each side of the grid
+const MAX_RESOLUTION: u32 = 2048;
+
+// Potential on a square grid centred on the Sun, in units of the Sun's
+// potential at 1 AU (so about -1 at Earth's orbit)
+#[wasm_bindgen]
+pub struct PotentialGrid {
+    resolution: u32,
+    extent: f64,
+    potentials: Vec<f64>,
+}
+
+#[wasm_bindgen]
+impl PotentialGrid {
+    // Samples along each side
+    #[wasm_bindgen(getter)]
+    pub fn resolution(&self) -> u32 {
+        self.resolution
+    }
+
+    // Half-width of the grid (AU)
+    #[wasm_bindgen(getter)]
+    pub fn extent(&self) -> f64 {
+        self.extent
+    }
+
+    // Potential at each sample, row by row: rows run along ecliptic y and
+    // columns along ecliptic x, both from -extent to +extent
+    #[wasm_bindgen(getter)]
+    pub fn potentials(&self) -> Float32Array {
+        let potentials: Vec<f32> = self.potentials.iter().map(|&p| p as f32).collect();
+        Float32Array::from(potentials.as_slice())
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn min_potential(&self) -> f64 {
+        self.potentials.iter().copied().fold(f64::INFINITY, f64::min)
+    }
+
+    // Sheet heights in scene units: potential × depth_scale, with the wells
+    // cut off at max_depth below the plane
+    pub fn heights(&self, depth_scale: f64, max_depth: f64) -> Float32Array {
+        let heights: Vec<f32> = self.potentials.iter().map(|&p| sheet_height(p, depth_scale, max_depth) as f32).collect();
+        Float32Array::from(heights.as_slice())
+    }
+
+    // Packed [x, y, z, ...] scene positions of the sheet, laid over the
+    // ecliptic through the scene mapping (so it follows log compression) and
+    // displaced down by the heights
+    pub fn vertices(&self, depth_scale: f64, max_depth: f64) -> Float32Array {
+        let mut vertices = Vec::with_capacity(self.potentials.len() * 3);
+        for (index, &potential) in self.potentials.iter().enumerate() {
+            let (x, y) = self.sample_point(index);
+            let flat = to_scene(Vec3::new(x, y, 0.0));
+            let height = sheet_height(potential, depth_scale, max_depth);
+            vertices.extend_from_slice(&[flat.x as f32, height as f32, flat.z as f32]);
+        }
+        Float32Array::from(vertices.as_slice())
+    }
+
+    // Triangle list over the grid, counter-clockwise seen from above
+    pub fn indices(&self) -> Uint32Array {
+        let n = self.resolution;
+        let mut indices = Vec::with_capacity(((n - 1) * (n - 1) * 6) as usize);
+        for row in 0..n - 1 {
+            for column in 0..n - 1 {
+                let a = row * n + column;
+                let (b, c, d) = (a + 1, a + n, a + n + 1);
+                indices.extend_from_slice(&[a, c, b, b, c, d]);
+            }
+        }
+        Uint32Array::from(indices.as_slice())
+    }
+}
+
+impl PotentialGrid {
+    fn spacing(&self) -> f64 {
+        2.0 * self.extent / (self.resolution - 1) as f64
+    }
+
+    // Ecliptic x and y (AU) of a sample
+    fn sample_point(&self, index: usize) -> (f64, f64) {
+        let n = self.resolution as usize;
+        let (row, column) = (index / n, index % n);
+        (-self.extent + column as f64 * self.spacing(), -self.extent + row as f64 * self.spacing())
+    }
+}
+
+fn sheet_height(potential: f64, depth_scale: f64, max_depth: f64) -> f64 {
+    (potential * depth_scale).max(-max_depth.abs())
+}
+
+// Sample the potential of the Sun and planets at a Julian date over a grid of
+// resolution × resolution points spanning ±extent AU in the ecliptic plane.
+// Each mass is softened over half a grid cell so its well stays finite.
+#[wasm_bindgen]
+pub fn potential_grid(extent: f64, resolution: u32, julian_date: f64) -> Result<PotentialGrid, JsError> {
+    check_julian_date(julian_date)?;
+    if !(extent.is_finite() && extent > 0.0 && (2..=MAX_RESOLUTION).contains(&resolution)) {
+        return Err(SimulationError::invalid_argument(&format!(
+            "Expected a positive extent and a resolution from 2 to {}, got {} and {}",
+            MAX_RESOLUTION, extent, resolution
+        ))
+        .into());
+    }
+
+    // (position, GM as a fraction of the Sun's)
+    let masses: Vec<(Vec3, f64)> = std::iter::once((Vec3::new(0.0, 0.0, 0.0), 1.0))
+        .chain(
+            (0..PLANET_ELEMENTS.len())
+                .map(|index| (planet_state(index, julian_date).position, PLANET_DATA[index].9 / SUN_EARTH_MASS_RATIO)),
+        )
+        .collect();
+
+    let mut grid = PotentialGrid { resolution, extent, potentials: Vec::new() };
+    let softening_squared = (0.5 * grid.spacing()).powi(2);
+    let count = (resolution * resolution) as usize;
+    grid.potentials = (0..count)
+        .map(|index| {
+            let (x, y) = grid.sample_point(index);
+            let point = Vec3::new(x, y, 0.0);
+            // In units of GM_SUN / 1 AU, each mass contributes its fraction
+            // of the Sun's over its distance in AU
+            -masses
+                .iter()
+                .map(|(position, fraction)| {
+                    let offset = point.sub(position);
+                    fraction / (offset.dot(&offset) + softening_squared).sqrt()
+                })
+                .sum::<f64>()
+        })
+        .collect();
+    Ok(grid)
+}
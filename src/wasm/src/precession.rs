@@ -0,0 +1,236 @@
+// Precession (Vondrák et al. 2011) and nutation of Earth's axis, linking the fixed J2000.0
+// frames used for ephemerides to the true equator and equinox of date
+
+use wasm_bindgen::prelude::*;
+use js_sys::Float64Array;
+
+use crate::errors::check_finite_date;
+use crate::observer::{mean_obliquity, sidereal_time};
+use crate::{deg_to_rad, Vec3};
+
+type Matrix = [[f64; 3]; 3];
+
+// Frame rotations about the x and z axes, in the SOFA sense of rotating the
+// coordinate axes by an angle in radians
+fn rotation_x(angle: f64) -> Matrix {
+    let (s, c) = angle.sin_cos();
+    [[1.0, 0.0, 0.0], [0.0, c, s], [0.0, -s, c]]
+}
+
+fn rotation_z(angle: f64) -> Matrix {
+    let (s, c) = angle.sin_cos();
+    [[c, s, 0.0], [-s, c, 0.0], [0.0, 0.0, 1.0]]
+}
+
+fn multiply(a: &Matrix, b: &Matrix) -> Matrix {
+    std::array::from_fn(|i| std::array::from_fn(|j| (0..3).map(|k| a[i][k] * b[k][j]).sum()))
+}
+
+fn apply(m: &Matrix, v: &Vec3) -> Vec3 {
+    Vec3::new(
+        m[0][0] * v.x + m[0][1] * v.y + m[0][2] * v.z,
+        m[1][0] * v.x + m[1][1] * v.y + m[1][2] * v.z,
+        m[2][0] * v.x + m[2][1] * v.y + m[2][2] * v.z,
+    )
+}
+
+fn apply_transpose(m: &Matrix, v: &Vec3) -> Vec3 {
+    Vec3::new(
+        m[0][0] * v.x + m[1][0] * v.y + m[2][0] * v.z,
+        m[0][1] * v.x + m[1][1] * v.y + m[2][1] * v.z,
+        m[0][2] * v.x + m[1][2] * v.y + m[2][2] * v.z,
+    )
+}
+
+fn arcseconds(value: f64) -> f64 {
+    deg_to_rad(value / 3600.0)
+}
+
+// Vondrák, Capitaine & Wallace (2011) long-term precession, valid for
+// ±200,000 years and within a few milliarcseconds of IAU 2006 near J2000.0.
+// Each series is a cubic in Julian centuries plus periodic terms of
+// [period (centuries), cos p or x, cos q or y, sin p or x, sin q or y], in
+// arcseconds.
+const ECLIPTIC_POLE_POLYNOMIAL: [[f64; 4]; 2] = [
+    [5851.607687, -0.1189000, -0.00028913, 0.000000101],
+    [-1600.886300, 1.1689818, -0.00000020, -0.000000437],
+];
+const ECLIPTIC_POLE_PERIODIC: [[f64; 5]; 8] = [
+    [708.15, -5486.751211, -684.661560, 667.666730, -5523.863691],
+    [2309.00, -17.127623, 2446.283880, -2354.886252, -549.747450],
+    [1620.00, -617.517403, 399.671049, -428.152441, -310.998056],
+    [492.20, 413.442940, -356.652376, 376.202861, 421.535876],
+    [1183.00, 78.614193, -186.387003, 184.778874, -36.776172],
+    [622.00, -180.732815, -316.800070, 335.321713, -145.278396],
+    [882.00, -87.676083, 198.296701, -185.138669, -34.744450],
+    [547.00, 46.140315, 101.135679, -120.972830, 22.885731],
+];
+const EQUATOR_POLE_POLYNOMIAL: [[f64; 4]; 2] = [
+    [5453.282155, 0.4252841, -0.00037173, -0.000000152],
+    [-73750.930350, -0.7675452, -0.00018725, 0.000000231],
+];
+const EQUATOR_POLE_PERIODIC: [[f64; 5]; 14] = [
+    [256.75, -819.940624, 75004.344875, 81491.287984, 1558.515853],
+    [708.15, -8444.676815, 624.033993, 787.163481, 7774.939698],
+    [274.20, 2600.009459, 1251.136893, 1251.296102, -2219.534038],
+    [241.45, 2755.175630, -1102.212834, -1257.950837, -2523.969396],
+    [2309.00, -167.659835, -2660.664980, -2966.799730, 247.850422],
+    [492.20, 871.855056, 699.291817, 639.744522, -846.485643],
+    [396.10, 44.769698, 153.167220, 131.600209, -1393.124055],
+    [288.90, -512.313065, -950.865637, -445.040117, 368.526116],
+    [231.10, -819.415595, 499.754645, 584.522874, 749.045012],
+    [1610.00, -538.071099, -145.188210, -89.756563, 444.704518],
+    [620.00, -189.793622, 558.116553, 524.429630, 235.934465],
+    [157.87, -402.922932, -23.923029, -13.549067, 374.049623],
+    [220.30, 179.516345, -165.405086, -210.157124, -171.330180],
+    [1200.00, -9.814756, 9.344131, -44.919798, -22.899655],
+];
+
+// Obliquity of the J2000.0 ecliptic the ecliptic pole series is referred to
+const J2000_OBLIQUITY: f64 = 84381.406;
+
+// Sum a polynomial and periodic series pair at t Julian centuries from J2000.0,
+// returning both components in radians
+fn long_term_series(polynomial: &[[f64; 4]; 2], periodic: &[[f64; 5]], t: f64) -> (f64, f64) {
+    let (mut a, mut b) = (0.0, 0.0);
+    for term in periodic {
+        let (s, c) = (2.0 * std::f64::consts::PI * t / term[0]).sin_cos();
+        a += c * term[1] + s * term[3];
+        b += c * term[2] + s * term[4];
+    }
+    let mut power = 1.0;
+    for (coefficient_a, coefficient_b) in polynomial[0].iter().zip(&polynomial[1]) {
+        a += coefficient_a * power;
+        b += coefficient_b * power;
+        power *= t;
+    }
+    (arcseconds(a), arcseconds(b))
+}
+
+// Unit vectors of the long-term ecliptic and equator poles of date on the
+// J2000.0 mean equator
+fn precession_poles(julian_date: f64) -> (Vec3, Vec3) {
+    let t = (julian_date - 2451545.0) / 36525.0;
+
+    let (p, q) = long_term_series(&ECLIPTIC_POLE_POLYNOMIAL, &ECLIPTIC_POLE_PERIODIC, t);
+    let z = (1.0 - p * p - q * q).max(0.0).sqrt();
+    let (s, c) = arcseconds(J2000_OBLIQUITY).sin_cos();
+    let ecliptic_pole = Vec3::new(p, -q * c - z * s, -q * s + z * c);
+
+    let (x, y) = long_term_series(&EQUATOR_POLE_POLYNOMIAL, &EQUATOR_POLE_PERIODIC, t);
+    let equator_pole = Vec3::new(x, y, (1.0 - x * x - y * y).max(0.0).sqrt());
+    (ecliptic_pole, equator_pole)
+}
+
+// Precession matrix from the J2000.0 mean equator to the mean equator and
+// equinox of date, built from the long-term ecliptic and equator poles
+fn precession_matrix(julian_date: f64) -> Matrix {
+    let (ecliptic_pole, equator_pole) = precession_poles(julian_date);
+    // The equinox lies along the intersection of the equator and ecliptic
+    let equinox = equator_pole.cross(&ecliptic_pole).normalize();
+    let y_axis = equator_pole.cross(&equinox);
+    [
+        [equinox.x, equinox.y, equinox.z],
+        [y_axis.x, y_axis.y, y_axis.z],
+        [equator_pole.x, equator_pole.y, equator_pole.z],
+    ]
+}
+
+// Nutation in longitude and obliquity (radians) from the largest terms of the
+// IAU 1980 series, good to about half an arcsecond
+pub(crate) fn nutation(julian_date: f64) -> (f64, f64) {
+    let t = (julian_date - 2451545.0) / 36525.0;
+    let node = deg_to_rad(125.04452 - 1934.136261 * t);
+    let sun = deg_to_rad(2.0 * (280.4665 + 36000.7698 * t));
+    let moon = deg_to_rad(2.0 * (218.3165 + 481267.8813 * t));
+    let longitude = -17.20 * node.sin() - 1.32 * sun.sin() - 0.23 * moon.sin() + 0.21 * (2.0 * node).sin();
+    let obliquity = 9.20 * node.cos() + 0.57 * sun.cos() + 0.10 * moon.cos() - 0.09 * (2.0 * node).cos();
+    (arcseconds(longitude), arcseconds(obliquity))
+}
+
+// Combined rotation from the J2000.0 mean equator to the true equator of date
+fn precession_nutation_matrix(julian_date: f64) -> Matrix {
+    let epsilon = mean_obliquity(julian_date);
+    let (delta_psi, delta_epsilon) = nutation(julian_date);
+    let nutation = multiply(
+        &rotation_x(-(epsilon + delta_epsilon)),
+        &multiply(&rotation_z(-delta_psi), &rotation_x(epsilon)),
+    );
+    multiply(&nutation, &precession_matrix(julian_date))
+}
+
+// Rotate a J2000.0 ecliptic vector onto the true equator and equinox of date
+pub(crate) fn ecliptic_to_true_equator(ecliptic: &Vec3, julian_date: f64) -> Vec3 {
+    let equatorial = apply(&rotation_x(-mean_obliquity(2451545.0)), ecliptic);
+    apply(&precession_nutation_matrix(julian_date), &equatorial)
+}
+
+// Rotate a vector on the mean ecliptic and equinox of date, the frame of the
+// lunar series, onto the J2000.0 ecliptic
+pub(crate) fn ecliptic_of_date_to_j2000(ecliptic: &Vec3, julian_date: f64) -> Vec3 {
+    let (ecliptic_pole, equator_pole) = precession_poles(julian_date);
+    let equinox = equator_pole.cross(&ecliptic_pole).normalize();
+    let y_axis = ecliptic_pole.cross(&equinox);
+    let equatorial = equinox.scale(ecliptic.x).add(&y_axis.scale(ecliptic.y)).add(&ecliptic_pole.scale(ecliptic.z));
+    apply_transpose(&rotation_x(-mean_obliquity(2451545.0)), &equatorial)
+}
+
+// Rotate a true-equator-of-date vector back to the J2000.0 ecliptic
+pub(crate) fn true_equator_to_ecliptic(equatorial: &Vec3, julian_date: f64) -> Vec3 {
+    let j2000 = apply_transpose(&precession_nutation_matrix(julian_date), equatorial);
+    apply_transpose(&rotation_x(-mean_obliquity(2451545.0)), &j2000)
+}
+
+// Equation of the equinoxes (degrees): apparent minus mean sidereal time
+fn equation_of_equinoxes(julian_date: f64) -> f64 {
+    let (delta_psi, delta_epsilon) = nutation(julian_date);
+    (delta_psi * (mean_obliquity(julian_date) + delta_epsilon).cos()).to_degrees()
+}
+
+// Greenwich apparent sidereal time (degrees), the hour angle of the true equinox
+pub(crate) fn apparent_sidereal_time(julian_date: f64) -> f64 {
+    (sidereal_time(julian_date) + equation_of_equinoxes(julian_date)).rem_euclid(360.0)
+}
+
+// Right ascension, declination (degrees, ICRF) and prime meridian angle
+// (degrees) of Earth's true pole and Greenwich meridian, for pole_and_meridian
+pub(crate) fn earth_pole_and_meridian(julian_date: f64) -> (f64, f64, f64) {
+    let matrix = precession_nutation_matrix(julian_date);
+    let pole = apply_transpose(&matrix, &Vec3::new(0.0, 0.0, 1.0));
+    let gast = deg_to_rad(apparent_sidereal_time(julian_date));
+    let meridian = apply_transpose(&matrix, &Vec3::new(gast.cos(), gast.sin(), 0.0));
+
+    // Measure the meridian from the ascending node of the equator of date on
+    // the ICRF equator, as the IAU rotation elements do
+    let ra = pole.y.atan2(pole.x);
+    let node = Vec3::new(-ra.sin(), ra.cos(), 0.0);
+    let prime_meridian = meridian.dot(&pole.cross(&node)).atan2(meridian.dot(&node));
+    (
+        ra.to_degrees().rem_euclid(360.0),
+        pole.z.clamp(-1.0, 1.0).asin().to_degrees(),
+        prime_meridian.to_degrees().rem_euclid(360.0),
+    )
+}
+
+// Greenwich apparent sidereal time (degrees) at a Julian date (UT), including
+// the nutation of the equinox
+#[wasm_bindgen]
+pub fn greenwich_apparent_sidereal_time(julian_date: f64) -> Result<f64, JsError> {
+    check_finite_date(julian_date)?;
+    Ok(apparent_sidereal_time(julian_date))
+}
+
+// Precess J2000.0 catalog coordinates, right ascension (hours) and declination
+// (degrees), to the true equator and equinox of a date, returned as
+// [right ascension, declination]. Use this to show the sky of other epochs,
+// such as the pole star drifting away from Polaris.
+#[wasm_bindgen]
+pub fn precess_equatorial(right_ascension: f64, declination: f64, julian_date: f64) -> Result<Float64Array, JsError> {
+    check_finite_date(julian_date)?;
+    let (ra, dec) = (deg_to_rad(right_ascension * 15.0), deg_to_rad(declination));
+    let j2000 = Vec3::new(dec.cos() * ra.cos(), dec.cos() * ra.sin(), dec.sin());
+    let of_date = apply(&precession_nutation_matrix(julian_date), &j2000);
+    let right_ascension = of_date.y.atan2(of_date.x).to_degrees().rem_euclid(360.0) / 15.0;
+    let declination = of_date.z.clamp(-1.0, 1.0).asin().to_degrees();
+    Ok(Float64Array::from(&[right_ascension, declination][..]))
+}
@@ -0,0 +1,125 @@
+// Catalog of known exoplanet systems
+
+use wasm_bindgen::prelude::*;
+
+use crate::system::{install_system, reset_system, BodyDefinition, ElementsDefinition, StarDefinition, SystemDefinition};
+use crate::PLANET_ELEMENTS;
+
+// Star row: name, mass (solar masses), radius (solar radii), luminosity (solar), color
+type PresetStar = (&'static str, f64, f64, f64, &'static str);
+
+// Planet row: name, semi-major axis (AU), eccentricity, inclination to the
+// system's mean plane (degrees), mean anomaly (degrees) at epoch (JD),
+// radius (Earth radii), mass (Earth masses), color
+type PresetPlanet = (&'static str, f64, f64, f64, f64, f64, f64, f64, &'static str);
+
+struct PresetSystem {
+    name: &'static str,
+    star: PresetStar,
+    planets: &'static [PresetPlanet],
+}
+
+// TRAPPIST-1 (Agol et al. 2021). Mean anomalies of 90° at the transit
+// midtimes line every planet up along the line of sight at its own transit,
+// which fixes the true relative phases.
+static TRAPPIST_1: &[PresetPlanet] = &[
+    ("TRAPPIST-1 b", 0.01154, 0.006, 0.27, 90.0, 2457257.55044, 1.116, 1.374, "#c8846a"),
+    ("TRAPPIST-1 c", 0.01580, 0.007, 0.22, 90.0, 2457258.58728, 1.097, 1.308, "#b98e74"),
+    ("TRAPPIST-1 d", 0.02227, 0.008, 0.11, 90.0, 2457257.06768, 0.788, 0.388, "#a99c8a"),
+    ("TRAPPIST-1 e", 0.02925, 0.005, 0.26, 90.0, 2457257.82771, 0.920, 0.692, "#7d9bb5"),
+    ("TRAPPIST-1 f", 0.03849, 0.010, 0.28, 90.0, 2457257.07426, 1.045, 1.039, "#8fb0c9"),
+    ("TRAPPIST-1 g", 0.04683, 0.002, 0.28, 90.0, 2457252.06604, 1.129, 1.321, "#9cb8cf"),
+    ("TRAPPIST-1 h", 0.06189, 0.006, 0.20, 90.0, 2457249.60677, 0.755, 0.326, "#c4cdd6"),
+];
+
+// Kepler-90. Masses of the small planets are mass-radius estimates; transit
+// phases are not modelled, so the mean anomalies are illustrative only.
+static KEPLER_90: &[PresetPlanet] = &[
+    ("Kepler-90 b", 0.074, 0.0, 0.0, 0.0, 2451545.0, 1.31, 2.0, "#b07c5a"),
+    ("Kepler-90 c", 0.089, 0.0, 0.0, 137.5, 2451545.0, 1.18, 1.6, "#a8876a"),
+    ("Kepler-90 i", 0.107, 0.0, 0.0, 275.0, 2451545.0, 1.32, 2.1, "#9f8d78"),
+    ("Kepler-90 d", 0.32, 0.0, 0.0, 52.5, 2451545.0, 2.88, 8.0, "#8c9aa8"),
+    ("Kepler-90 e", 0.42, 0.0, 0.0, 190.0, 2451545.0, 2.67, 7.0, "#90a3b4"),
+    ("Kepler-90 f", 0.48, 0.01, 0.0, 327.5, 2451545.0, 2.89, 8.0, "#97abbf"),
+    ("Kepler-90 g", 0.71, 0.049, 0.0, 105.0, 2451545.0, 8.13, 15.0, "#c9b48f"),
+    ("Kepler-90 h", 1.01, 0.011, 0.0, 242.5, 2451545.0, 11.32, 203.0, "#d8c39d"),
+];
+
+// Proxima Centauri, radial-velocity planets with radii from mass-radius
+// estimates; c remains a candidate. Phases are illustrative.
+static PROXIMA_CENTAURI: &[PresetPlanet] = &[
+    ("Proxima d", 0.02885, 0.04, 0.0, 0.0, 2451545.0, 0.81, 0.26, "#b39a86"),
+    ("Proxima b", 0.04856, 0.02, 0.0, 120.0, 2451545.0, 1.03, 1.07, "#a47d63"),
+    ("Proxima c", 1.489, 0.04, 0.0, 240.0, 2451545.0, 1.9, 7.0, "#c6d3dc"),
+];
+
+static PRESETS: &[PresetSystem] = &[
+    PresetSystem {
+        name: "TRAPPIST-1",
+        star: ("TRAPPIST-1", 0.0898, 0.1192, 0.000553, "#ff6b3d"),
+        planets: TRAPPIST_1,
+    },
+    PresetSystem {
+        name: "Kepler-90",
+        star: ("Kepler-90", 1.2, 1.2, 1.77, "#fff6e8"),
+        planets: KEPLER_90,
+    },
+    PresetSystem {
+        name: "Proxima Centauri",
+        star: ("Proxima Centauri", 0.1221, 0.1542, 0.00155, "#ff5533"),
+        planets: PROXIMA_CENTAURI,
+    },
+];
+
+// Name of the built-in system, which load_preset_system restores
+const SOLAR_SYSTEM: &str = "Solar System";
+
+fn definition(preset: &PresetSystem) -> SystemDefinition {
+    let (name, mass, radius, luminosity, color) = preset.star;
+    SystemDefinition {
+        replace: true,
+        star: Some(StarDefinition {
+            name: name.to_string(),
+            mass,
+            radius,
+            luminosity: Some(luminosity),
+            color: Some(color.to_string()),
+        }),
+        bodies: preset
+            .planets
+            .iter()
+            .map(|&(name, a, e, i, m0, epoch, radius, mass, color)| BodyDefinition {
+                name: name.to_string(),
+                elements: ElementsDefinition { a, e, i, omega: 0.0, w: 0.0, m0, epoch: Some(epoch) },
+                radius,
+                color: Some(color.to_string()),
+                mass,
+                axial_tilt: 0.0,
+                day_length: 0.0,
+                temperature: None,
+                moons: 0,
+            })
+            .collect(),
+    }
+}
+
+// Names of the selectable systems, starting with the Solar System
+#[wasm_bindgen]
+pub fn preset_system_names() -> Vec<String> {
+    std::iter::once(SOLAR_SYSTEM)
+        .chain(PRESETS.iter().map(|preset| preset.name))
+        .map(str::to_string)
+        .collect()
+}
+
+// Switch to a cataloged system, returning its planet count, or None if the
+// name is unknown
+#[wasm_bindgen]
+pub fn load_preset_system(name: &str) -> Option<u32> {
+    if name == SOLAR_SYSTEM {
+        reset_system();
+        return Some(PLANET_ELEMENTS.len() as u32);
+    }
+    let preset = PRESETS.iter().find(|preset| preset.name == name)?;
+    install_system(definition(preset)).ok()
+}
@@ -0,0 +1,243 @@
+use wasm_bindgen::prelude::*;
+
+use crate::errors::SimulationError;
+use crate::state::StateVector;
+use crate::Vec3;
+
+// Step size and error tolerance settings for the adaptive propagator
+#[derive(Debug, Clone, Copy)]
+#[wasm_bindgen]
+pub struct PropagatorConfig {
+    abs_tolerance: f64,
+    rel_tolerance: f64,
+    min_step: f64,
+    max_step: f64,
+    initial_step: f64,
+}
+
+#[wasm_bindgen]
+impl PropagatorConfig {
+    // Tolerances are per state component and must be positive; steps are in
+    // days, with 0 < min_step <= max_step
+    #[wasm_bindgen(constructor)]
+    pub fn new(abs_tolerance: f64, rel_tolerance: f64) -> Result<PropagatorConfig, JsError> {
+        check_positive("abs_tolerance", abs_tolerance)?;
+        check_positive("rel_tolerance", rel_tolerance)?;
+        Ok(PropagatorConfig {
+            abs_tolerance,
+            rel_tolerance,
+            ..PropagatorConfig::default()
+        })
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn abs_tolerance(&self) -> f64 {
+        self.abs_tolerance
+    }
+
+    #[wasm_bindgen(setter)]
+    pub fn set_abs_tolerance(&mut self, value: f64) -> Result<(), JsError> {
+        check_positive("abs_tolerance", value)?;
+        self.abs_tolerance = value;
+        Ok(())
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn rel_tolerance(&self) -> f64 {
+        self.rel_tolerance
+    }
+
+    #[wasm_bindgen(setter)]
+    pub fn set_rel_tolerance(&mut self, value: f64) -> Result<(), JsError> {
+        check_positive("rel_tolerance", value)?;
+        self.rel_tolerance = value;
+        Ok(())
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn min_step(&self) -> f64 {
+        self.min_step
+    }
+
+    #[wasm_bindgen(setter)]
+    pub fn set_min_step(&mut self, value: f64) -> Result<(), JsError> {
+        check_positive("min_step", value)?;
+        if value > self.max_step {
+            return Err(SimulationError::invalid_argument(&format!(
+                "min_step {} exceeds max_step {}",
+                value, self.max_step
+            ))
+            .into());
+        }
+        self.min_step = value;
+        Ok(())
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn max_step(&self) -> f64 {
+        self.max_step
+    }
+
+    #[wasm_bindgen(setter)]
+    pub fn set_max_step(&mut self, value: f64) -> Result<(), JsError> {
+        check_positive("max_step", value)?;
+        if value < self.min_step {
+            return Err(SimulationError::invalid_argument(&format!(
+                "max_step {} is below min_step {}",
+                value, self.min_step
+            ))
+            .into());
+        }
+        self.max_step = value;
+        Ok(())
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn initial_step(&self) -> f64 {
+        self.initial_step
+    }
+
+    #[wasm_bindgen(setter)]
+    pub fn set_initial_step(&mut self, value: f64) -> Result<(), JsError> {
+        check_positive("initial_step", value)?;
+        self.initial_step = value;
+        Ok(())
+    }
+}
+
+impl Default for PropagatorConfig {
+    fn default() -> Self {
+        PropagatorConfig {
+            abs_tolerance: 1e-12,
+            rel_tolerance: 1e-10,
+            min_step: 1e-6,
+            max_step: 30.0,
+            initial_step: 1.0,
+        }
+    }
+}
+
+// Tolerances and step sizes must be positive and finite
+fn check_positive(name: &str, value: f64) -> Result<(), SimulationError> {
+    if !(value.is_finite() && value > 0.0) {
+        return Err(SimulationError::invalid_argument(&format!("{} must be positive, got {}", name, value)));
+    }
+    Ok(())
+}
+
+// Most steps, accepted or rejected, rkf45() tries before giving up
+const MAX_RKF_STEPS: u32 = 1_000_000;
+
+// Runge-Kutta-Fehlberg 4(5) tableau
+const RKF_C: [[f64; 5]; 5] = [
+    [1.0 / 4.0, 0.0, 0.0, 0.0, 0.0],
+    [3.0 / 32.0, 9.0 / 32.0, 0.0, 0.0, 0.0],
+    [1932.0 / 2197.0, -7200.0 / 2197.0, 7296.0 / 2197.0, 0.0, 0.0],
+    [439.0 / 216.0, -8.0, 3680.0 / 513.0, -845.0 / 4104.0, 0.0],
+    [-8.0 / 27.0, 2.0, -3544.0 / 2565.0, 1859.0 / 4104.0, -11.0 / 40.0],
+];
+const RKF_B4: [f64; 6] = [25.0 / 216.0, 0.0, 1408.0 / 2565.0, 2197.0 / 4104.0, -1.0 / 5.0, 0.0];
+const RKF_B5: [f64; 6] = [16.0 / 135.0, 0.0, 6656.0 / 12825.0, 28561.0 / 56430.0, -9.0 / 50.0, 2.0 / 55.0];
+
+// Integrate an autonomous system dy/dt = f(y) over `span` days with adaptive
+// RKF45 steps. Returns the step size to carry into the next call, or an
+// error when the span needs more than MAX_RKF_STEPS steps.
+pub(crate) fn rkf45<F>(
+    y: &mut [f64],
+    span: f64,
+    config: &PropagatorConfig,
+    step_hint: f64,
+    derivative: F,
+) -> Result<f64, SimulationError>
+where
+    F: Fn(&[f64], &mut [f64]),
+{
+    let n = y.len();
+    let mut k = vec![vec![0.0; n]; 6];
+    let mut stage = vec![0.0; n];
+    let mut y4 = vec![0.0; n];
+    let mut y5 = vec![0.0; n];
+
+    let direction = span.signum();
+    let mut remaining = span.abs();
+    let mut h = step_hint.abs().clamp(config.min_step, config.max_step);
+    if h.is_nan() {
+        h = config.initial_step.clamp(config.min_step, config.max_step);
+    }
+
+    let mut steps = 0;
+    while remaining > 0.0 {
+        steps += 1;
+        if steps > MAX_RKF_STEPS {
+            return Err(SimulationError::invalid_argument(&format!(
+                "Propagation of {} days needs more than {} steps",
+                span.abs(),
+                MAX_RKF_STEPS
+            )));
+        }
+        let step = h.min(remaining);
+        let dt = step * direction;
+
+        derivative(y, &mut k[0]);
+        for s in 1..6 {
+            for j in 0..n {
+                let mut sum = 0.0;
+                for (m, coefficient) in RKF_C[s - 1].iter().enumerate().take(s) {
+                    sum += coefficient * k[m][j];
+                }
+                stage[j] = y[j] + dt * sum;
+            }
+            derivative(&stage, &mut k[s]);
+        }
+
+        let mut error: f64 = 0.0;
+        for j in 0..n {
+            let mut sum4 = 0.0;
+            let mut sum5 = 0.0;
+            for s in 0..6 {
+                sum4 += RKF_B4[s] * k[s][j];
+                sum5 += RKF_B5[s] * k[s][j];
+            }
+            y4[j] = y[j] + dt * sum4;
+            y5[j] = y[j] + dt * sum5;
+            let scale = config.abs_tolerance + config.rel_tolerance * y[j].abs().max(y5[j].abs());
+            error = error.max((y5[j] - y4[j]).abs() / scale);
+        }
+
+        // Accept the step when within tolerance, or when it cannot shrink further
+        if error <= 1.0 || step <= config.min_step {
+            y.copy_from_slice(&y5);
+            remaining -= step;
+        }
+
+        let factor = if error > 0.0 { 0.9 * error.powf(-0.2) } else { 5.0 };
+        h = (step * factor.clamp(0.2, 5.0)).clamp(config.min_step, config.max_step);
+    }
+
+    Ok(h)
+}
+
+// Propagate a heliocentric state under two-body gravity for dt days with
+// adaptive steps; mu is the central gravitational parameter (AU³/day²)
+#[wasm_bindgen]
+pub fn propagate_state(state: &StateVector, mu: f64, dt: f64, config: &PropagatorConfig) -> Result<StateVector, JsError> {
+    if !dt.is_finite() {
+        return Err(SimulationError::invalid_argument(&format!("Invalid propagation span {}", dt)).into());
+    }
+    let p = state.position;
+    let v = state.velocity;
+    let mut y = [p.x, p.y, p.z, v.x, v.y, v.z];
+
+    rkf45(&mut y, dt, config, config.initial_step, |y, dy| {
+        let r = (y[0] * y[0] + y[1] * y[1] + y[2] * y[2]).sqrt();
+        let factor = -mu / (r * r * r);
+        dy[0] = y[3];
+        dy[1] = y[4];
+        dy[2] = y[5];
+        dy[3] = factor * y[0];
+        dy[4] = factor * y[1];
+        dy[5] = factor * y[2];
+    })?;
+
+    Ok(StateVector::new(Vec3::new(y[0], y[1], y[2]), Vec3::new(y[3], y[4], y[5])))
+}
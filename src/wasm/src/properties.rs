@@ -0,0 +1,111 @@
+// Physical and orbital properties derived from the mass, radius and elements
+// tables, for info panels
+
+use wasm_bindgen::prelude::*;
+use std::f64::consts::PI;
+
+use crate::influence::hill_radius;
+use crate::tides::fluid_roche_limit;
+use crate::units::{EARTH_RADIUS_KM, GM_EARTH};
+use crate::{
+    PhysicalData, AU_KM, DWARF_PLANET_DATA, DWARF_PLANET_ELEMENTS, GM_SUN, PLANET_DATA, PLANET_ELEMENTS,
+    SUN_EARTH_MASS_RATIO,
+};
+
+// Density (g/cm³) of the satellite assumed for the Roche limit: water ice,
+// as for ring particles
+const ROCHE_SATELLITE_DENSITY: f64 = 0.92;
+
+// Earth's sidereal orbital period (days), for synodic periods
+const EARTH_YEAR: f64 = 365.256363;
+
+// Quantities computed from a body's tabulated mass, radius and orbit
+#[wasm_bindgen]
+#[derive(Debug, Clone)]
+pub struct DerivedProperties {
+    name: String,
+    surface_gravity: f64,
+    escape_velocity: f64,
+    orbital_period: f64,
+    synodic_period: Option<f64>,
+    hill_radius: f64,
+    roche_limit: f64,
+}
+
+#[wasm_bindgen]
+impl DerivedProperties {
+    #[wasm_bindgen(getter)]
+    pub fn name(&self) -> String {
+        self.name.clone()
+    }
+
+    // Gravitational acceleration at the equator (m/s²), ignoring rotation
+    #[wasm_bindgen(getter)]
+    pub fn surface_gravity(&self) -> f64 {
+        self.surface_gravity
+    }
+
+    // Escape velocity from the surface (km/s)
+    #[wasm_bindgen(getter)]
+    pub fn escape_velocity(&self) -> f64 {
+        self.escape_velocity
+    }
+
+    // Sidereal orbital period from the semi-major axis (days)
+    #[wasm_bindgen(getter)]
+    pub fn orbital_period(&self) -> f64 {
+        self.orbital_period
+    }
+
+    // Interval between successive oppositions or conjunctions as seen from
+    // Earth (days); None for Earth itself
+    #[wasm_bindgen(getter)]
+    pub fn synodic_period(&self) -> Option<f64> {
+        self.synodic_period
+    }
+
+    // Radius of the Hill sphere at perihelion (km), inside which the body's
+    // gravity can hold satellites against the Sun's tides
+    #[wasm_bindgen(getter)]
+    pub fn hill_radius(&self) -> f64 {
+        self.hill_radius
+    }
+
+    // Fluid Roche limit for an icy satellite (km), inside which tides tear it apart
+    #[wasm_bindgen(getter)]
+    pub fn roche_limit(&self) -> f64 {
+        self.roche_limit
+    }
+}
+
+// Derived properties from a body's physical data row and heliocentric a and e
+fn derive(data: &PhysicalData, a: f64, e: f64) -> DerivedProperties {
+    let &(name, radius, _, _, _, _, _, _, _, mass, density) = data;
+    let gm = GM_EARTH * mass;
+    let radius = radius * EARTH_RADIUS_KM;
+    let mass_ratio = mass / SUN_EARTH_MASS_RATIO;
+    let orbital_period = 2.0 * PI * (a * a * a / (GM_SUN * (1.0 + mass_ratio))).sqrt();
+    let synodic_period = (name != "Earth").then(|| 1.0 / (1.0 / orbital_period - 1.0 / EARTH_YEAR).abs());
+    DerivedProperties {
+        name: name.to_string(),
+        surface_gravity: gm / (radius * radius) * 1000.0,
+        escape_velocity: (2.0 * gm / radius).sqrt(),
+        orbital_period,
+        synodic_period,
+        hill_radius: hill_radius(a * (1.0 - e), mass_ratio) * AU_KM,
+        roche_limit: fluid_roche_limit(radius, density, ROCHE_SATELLITE_DENSITY),
+    }
+}
+
+// Derived properties of a planet or dwarf planet: surface gravity, escape
+// velocity, orbital and synodic periods, Hill sphere and Roche limit. Returns
+// None for unknown names.
+#[wasm_bindgen]
+pub fn derived_properties(planet: &str) -> Option<DerivedProperties> {
+    let (elements, data) = PLANET_ELEMENTS
+        .iter()
+        .zip(PLANET_DATA.iter())
+        .chain(DWARF_PLANET_ELEMENTS.iter().zip(DWARF_PLANET_DATA.iter()))
+        .find(|((name, _), _)| *name == planet)?;
+    Some(derive(data, elements.1.a, elements.1.e))
+}
@@ -0,0 +1,35 @@
+// Deterministic pseudo-random numbers for seeded procedural generation
+
+// SplitMix64 generator: small, fast and reproducible across platforms
+pub(crate) struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub(crate) fn new(seed: u64) -> Rng {
+        Rng { state: seed }
+    }
+
+    pub(crate) fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E3779B97F4A7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58476D1CE4E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D049BB133111EB);
+        z ^ (z >> 31)
+    }
+
+    // Uniform in [0, 1)
+    pub(crate) fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    // Uniform in [min, max)
+    pub(crate) fn range(&mut self, min: f64, max: f64) -> f64 {
+        min + (max - min) * self.next_f64()
+    }
+
+    // Rayleigh deviate with the given scale, as for orbital e and i distributions
+    pub(crate) fn rayleigh(&mut self, sigma: f64) -> f64 {
+        sigma * (-2.0 * (1.0 - self.next_f64()).ln()).sqrt()
+    }
+}
@@ -0,0 +1,129 @@
+// Bodies registered at runtime from user-supplied orbit data
+
+use wasm_bindgen::prelude::*;
+use std::cell::RefCell;
+
+use crate::comets::{comet_state_at, CometElements};
+use crate::errors::check_julian_date;
+use crate::mpc::{parse_mpc_line, MpcOrbit};
+use crate::state::elements_to_state;
+use crate::units::{EARTH_MASS_KG, EARTH_RADIUS_KM};
+use crate::{planet_data_from_state, OrbitalElements, PhysicalData, PlanetData, GAUSSIAN_MEAN_MOTION};
+
+// Geometric albedo assumed when sizing a body from its absolute magnitude
+const DEFAULT_ALBEDO: f64 = 0.14;
+
+// Bulk density assumed for registered small bodies (g/cm³)
+const DEFAULT_DENSITY: f64 = 2.0;
+
+// Orbit of a registered body
+#[derive(Clone, Copy)]
+pub(crate) enum Orbit {
+    Keplerian(OrbitalElements),
+    Conic(CometElements),
+}
+
+pub(crate) struct RegisteredBody {
+    pub(crate) name: String,
+    pub(crate) orbit: Orbit,
+    pub(crate) radius_km: f64,
+}
+
+thread_local! {
+    static REGISTRY: RefCell<Vec<RegisteredBody>> = const { RefCell::new(Vec::new()) };
+}
+
+// Add a body, replacing any existing registration with the same name
+pub(crate) fn register(body: RegisteredBody) {
+    REGISTRY.with(|registry| {
+        let mut registry = registry.borrow_mut();
+        registry.retain(|existing| existing.name != body.name);
+        registry.push(body);
+    });
+}
+
+// Look up the elliptical elements of a registered body by name
+pub(crate) fn registered_elements(name: &str) -> Option<OrbitalElements> {
+    REGISTRY.with(|registry| {
+        registry.borrow().iter().find(|body| body.name == name).and_then(|body| match body.orbit {
+            Orbit::Keplerian(elements) => Some(elements),
+            Orbit::Conic(_) => None,
+        })
+    })
+}
+
+// Diameter (km) of a body with absolute magnitude H and the given albedo
+fn diameter_from_magnitude(absolute_magnitude: f64, albedo: f64) -> f64 {
+    1329.0 / albedo.sqrt() * 10f64.powf(-absolute_magnitude / 5.0)
+}
+
+// Estimated physical data row for a registered body
+fn physical_data(body: &RegisteredBody, orbit_radius: f64, year_length: f64) -> PhysicalData {
+    let radius_m = body.radius_km * 1000.0;
+    let mass_kg = 4.0 / 3.0 * std::f64::consts::PI * radius_m.powi(3) * DEFAULT_DENSITY * 1000.0;
+    (
+        "",
+        body.radius_km / EARTH_RADIUS_KM,
+        "#9a9a9a",
+        orbit_radius,
+        0.0,
+        0.0,
+        year_length,
+        278.6 / orbit_radius.sqrt(), // Equilibrium temperature of a fast rotator
+        0,
+        mass_kg / EARTH_MASS_KG,
+        DEFAULT_DENSITY,
+    )
+}
+
+// Register an asteroid or comet from a Minor Planet Center one-line orbit
+// (MPCORB or CometEls format), returning its name, or None if the line is invalid
+#[wasm_bindgen]
+pub fn register_body_from_mpc(line: &str) -> Option<String> {
+    let (name, orbit) = parse_mpc_line(line)?;
+    let (orbit, radius_km) = match orbit {
+        MpcOrbit::Asteroid { elements, absolute_magnitude } => (
+            Orbit::Keplerian(elements),
+            absolute_magnitude.map_or(1.0, |h| diameter_from_magnitude(h, DEFAULT_ALBEDO) / 2.0),
+        ),
+        // Cometary nuclei are rarely more than a few kilometres across
+        MpcOrbit::Comet(elements) => (Orbit::Conic(elements), 5.0),
+    };
+    register(RegisteredBody { name: name.clone(), orbit, radius_km });
+    Some(name)
+}
+
+// Calculate positions of all registered bodies
+#[wasm_bindgen]
+pub fn registered_body_positions(julian_date: f64) -> Result<Vec<PlanetData>, JsError> {
+    check_julian_date(julian_date)?;
+    Ok(REGISTRY.with(|registry| {
+        registry
+            .borrow()
+            .iter()
+            .map(|body| match body.orbit {
+                Orbit::Keplerian(elements) => {
+                    let data = physical_data(body, elements.a, 360.0 / elements.n);
+                    planet_data_from_state(&body.name, &elements_to_state(&elements, julian_date), &data)
+                }
+                Orbit::Conic(elements) => {
+                    let state = comet_state_at(&elements, julian_date);
+                    // Unbound orbits have no period
+                    let year_length = if elements.e() < 1.0 {
+                        360.0 / (GAUSSIAN_MEAN_MOTION * ((1.0 - elements.e()) / elements.q()).powf(1.5))
+                    } else {
+                        f64::INFINITY
+                    };
+                    let data = physical_data(body, elements.q(), year_length);
+                    planet_data_from_state(&body.name, &state, &data)
+                }
+            })
+            .collect()
+    }))
+}
+
+// Remove all registered bodies
+#[wasm_bindgen]
+pub fn clear_registered_bodies() {
+    REGISTRY.with(|registry| registry.borrow_mut().clear());
+}
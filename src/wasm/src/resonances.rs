@@ -0,0 +1,145 @@
+// Near mean-motion resonances between orbiting bodies, from their mean
+// motions, for annotations such as "Neptune:Pluto 3:2"
+
+use wasm_bindgen::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::errors::SimulationError;
+use crate::{find_orbital_elements, Precision, DWARF_PLANET_ELEMENTS, PLANET_ELEMENTS};
+
+// Largest fractional difference between the period ratio and p/q still
+// counted as near resonance
+const RESONANCE_TOLERANCE: f64 = 0.01;
+
+// Largest p considered; near-equal periods fit some high-integer ratio to
+// within any tolerance, which says nothing about the dynamics
+const MAX_INTEGER: u32 = 12;
+
+// A p:q commensurability: the inner body completes p orbits while the outer
+// completes q
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[wasm_bindgen]
+pub struct Resonance {
+    inner: String,
+    outer: String,
+    p: u32,
+    q: u32,
+    period_ratio: f64,
+}
+
+#[wasm_bindgen]
+impl Resonance {
+    // The faster, inner body
+    #[wasm_bindgen(getter)]
+    pub fn inner(&self) -> String {
+        self.inner.clone()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn outer(&self) -> String {
+        self.outer.clone()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn p(&self) -> u32 {
+        self.p
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn q(&self) -> u32 {
+        self.q
+    }
+
+    // p - q; lower orders are stronger
+    #[wasm_bindgen(getter)]
+    pub fn order(&self) -> u32 {
+        self.p - self.q
+    }
+
+    // Outer period over inner period
+    #[wasm_bindgen(getter)]
+    pub fn period_ratio(&self) -> f64 {
+        self.period_ratio
+    }
+
+    // Fractional distance of the period ratio from exact p/q
+    #[wasm_bindgen(getter)]
+    pub fn mismatch(&self) -> f64 {
+        (self.period_ratio * self.q as f64 / self.p as f64 - 1.0).abs()
+    }
+
+    // e.g. "Neptune:Pluto 3:2"
+    #[wasm_bindgen(getter)]
+    pub fn label(&self) -> String {
+        format!("{}:{} {}:{}", self.inner, self.outer, self.p, self.q)
+    }
+}
+
+fn gcd(a: u32, b: u32) -> u32 {
+    if b == 0 { a } else { gcd(b, a % b) }
+}
+
+// Mean motion (degrees/day) of any body with orbital elements
+pub(crate) fn mean_motion(name: &str) -> Result<f64, SimulationError> {
+    find_orbital_elements(name, 2451545.0, Precision::High)
+        .map(|elements| elements.n)
+        .ok_or_else(|| SimulationError::unknown_body(name))
+}
+
+// Near resonances of two bodies up to an order, closest first
+fn resonances_between(a: (&str, f64), b: (&str, f64), max_order: u32) -> Vec<Resonance> {
+    let ((inner, n_inner), (outer, n_outer)) = if a.1 >= b.1 { (a, b) } else { (b, a) };
+    let ratio = n_inner / n_outer;
+    let mut found = Vec::new();
+    // No order beyond MAX_INTEGER can have p within it, so higher requests
+    // would only spin through empty orders
+    for order in 1..=max_order.min(MAX_INTEGER) {
+        // p/q = ratio with p = q + order puts q near order / (ratio - 1)
+        let q_estimate = order as f64 / (ratio - 1.0);
+        if !q_estimate.is_finite() {
+            continue;
+        }
+        for q in [q_estimate.floor(), q_estimate.ceil()] {
+            if q < 1.0 || q > u32::MAX as f64 {
+                continue;
+            }
+            let q = q as u32;
+            let Some(p) = q.checked_add(order) else {
+                continue;
+            };
+            let candidate = Resonance { inner: inner.to_string(), outer: outer.to_string(), p, q, period_ratio: ratio };
+            let duplicate = found.iter().any(|r: &Resonance| r.p == p && r.q == q);
+            if p <= MAX_INTEGER && gcd(p, q) == 1 && !duplicate && candidate.mismatch() <= RESONANCE_TOLERANCE {
+                found.push(candidate);
+            }
+        }
+    }
+    found.sort_by(|a, b| a.mismatch().total_cmp(&b.mismatch()));
+    found
+}
+
+// Near mean-motion resonances of two bodies (planets, dwarf planets, minor
+// planets or registered bodies) of order p - q up to max_order, closest first
+#[wasm_bindgen]
+pub fn find_resonances(body_a: &str, body_b: &str, max_order: u32) -> Result<Vec<Resonance>, JsError> {
+    let (n_a, n_b) = (mean_motion(body_a)?, mean_motion(body_b)?);
+    Ok(resonances_between((body_a, n_a), (body_b, n_b), max_order))
+}
+
+// Near resonances between every pair of planets and dwarf planets, closest
+// first
+#[wasm_bindgen]
+pub fn find_all_resonances(max_order: u32) -> Vec<Resonance> {
+    let bodies: Vec<(&str, f64)> = PLANET_ELEMENTS
+        .iter()
+        .chain(DWARF_PLANET_ELEMENTS.iter())
+        .map(|(name, elements)| (*name, elements.n))
+        .collect();
+    let mut found: Vec<Resonance> = bodies
+        .iter()
+        .enumerate()
+        .flat_map(|(i, a)| bodies[i + 1..].iter().flat_map(move |b| resonances_between(*a, *b, max_order)))
+        .collect();
+    found.sort_by(|a, b| a.mismatch().total_cmp(&b.mismatch()));
+    found
+}
@@ -0,0 +1,322 @@
+// Planetary ring systems: radial structure and annulus meshes in each
+// planet's equatorial plane
+
+use wasm_bindgen::prelude::*;
+use serde::{Deserialize, Serialize};
+use js_sys::{Float32Array, Float64Array, Uint32Array};
+
+use crate::errors::check_julian_date;
+use crate::random::Rng;
+use crate::rotation::{orientation_quaternion, pole_and_meridian};
+
+// Angular subdivisions of every annulus
+const RING_SEGMENTS: u32 = 128;
+
+// Ring row: name, inner and outer radius (km), typical opacity (0-1)
+type Ring = (&'static str, f64, f64, f64);
+
+// Ring particle sizes (metres) follow a power law n(s) ~ s^-3 over this range
+const PARTICLE_SIZE_MIN: f64 = 0.01;
+const PARTICLE_SIZE_MAX: f64 = 10.0;
+
+// Gap row: name, inner and outer radius (km)
+type Gap = (&'static str, f64, f64);
+
+struct RingSystem {
+    planet: &'static str,
+    // Equatorial radius (km) that ring radii are normalized by
+    equatorial_radius: f64,
+    // Gravitational parameter (km^3/s^2) setting the particles' orbital speeds
+    gm: f64,
+    rings: &'static [Ring],
+    gaps: &'static [Gap],
+}
+
+static SATURN_RINGS: &[Ring] = &[
+    ("D", 66900.0, 74510.0, 0.05),
+    ("C", 74658.0, 92000.0, 0.25),
+    ("B", 92000.0, 117580.0, 0.9),
+    ("A", 122170.0, 136775.0, 0.6),
+    ("F", 140130.0, 140230.0, 0.4),
+];
+
+// The Cassini Division separates B and A; the others are cut out of C and A
+static SATURN_GAPS: &[Gap] = &[
+    ("Colombo", 77870.0, 77970.0),
+    ("Maxwell", 87491.0, 87761.0),
+    ("Cassini Division", 117580.0, 122170.0),
+    ("Encke", 133423.0, 133745.0),
+    ("Keeler", 136485.0, 136527.0),
+];
+
+static URANUS_RINGS: &[Ring] = &[
+    ("Zeta", 37850.0, 41350.0, 0.02),
+    ("6", 41836.0, 41838.0, 0.3),
+    ("5", 42233.0, 42235.0, 0.5),
+    ("4", 42570.0, 42572.0, 0.3),
+    ("Alpha", 44714.0, 44722.0, 0.4),
+    ("Beta", 45657.0, 45665.0, 0.3),
+    ("Eta", 47175.0, 47177.0, 0.4),
+    ("Gamma", 47625.0, 47629.0, 1.0),
+    ("Delta", 48297.0, 48303.0, 0.5),
+    ("Epsilon", 51120.0, 51178.0, 0.7),
+    ("Nu", 66100.0, 69900.0, 0.01),
+    ("Mu", 86000.0, 103000.0, 0.01),
+];
+
+static JUPITER_RINGS: &[Ring] = &[
+    ("Halo", 92000.0, 122500.0, 0.02),
+    ("Main", 122500.0, 129000.0, 0.1),
+    ("Amalthea Gossamer", 129000.0, 182000.0, 0.01),
+    ("Thebe Gossamer", 182000.0, 226000.0, 0.01),
+];
+
+static NEPTUNE_RINGS: &[Ring] = &[
+    ("Galle", 40900.0, 42900.0, 0.05),
+    ("Le Verrier", 53143.0, 53256.0, 0.3),
+    ("Lassell", 53256.0, 57200.0, 0.02),
+    ("Adams", 62915.0, 62950.0, 0.3),
+];
+
+static RING_SYSTEMS: &[RingSystem] = &[
+    RingSystem { planet: "Jupiter", equatorial_radius: 71492.0, gm: 126686534.0, rings: JUPITER_RINGS, gaps: &[] },
+    RingSystem { planet: "Saturn", equatorial_radius: 60268.0, gm: 37931187.0, rings: SATURN_RINGS, gaps: SATURN_GAPS },
+    RingSystem { planet: "Uranus", equatorial_radius: 25559.0, gm: 5793939.0, rings: URANUS_RINGS, gaps: &[] },
+    RingSystem { planet: "Neptune", equatorial_radius: 24764.0, gm: 6836529.0, rings: NEPTUNE_RINGS, gaps: &[] },
+];
+
+// A named ring or gap, with radii in planet equatorial radii
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[wasm_bindgen]
+pub struct RingFeature {
+    name: String,
+    inner_radius: f64,
+    outer_radius: f64,
+    opacity: f64,
+}
+
+#[wasm_bindgen]
+impl RingFeature {
+    #[wasm_bindgen(getter)]
+    pub fn name(&self) -> String {
+        self.name.clone()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn inner_radius(&self) -> f64 {
+        self.inner_radius
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn outer_radius(&self) -> f64 {
+        self.outer_radius
+    }
+
+    // Typical opacity (0-1); zero for gaps
+    #[wasm_bindgen(getter)]
+    pub fn opacity(&self) -> f64 {
+        self.opacity
+    }
+}
+
+// Triangulated ring system in the planet's local frame, where the ring plane
+// is y = 0 and lengths are in planet equatorial radii
+#[derive(Debug, Clone)]
+#[wasm_bindgen]
+pub struct RingGeometry {
+    inner_radius: f64,
+    outer_radius: f64,
+    quaternion: [f64; 4],
+    positions: Vec<f32>,
+    uvs: Vec<f32>,
+    opacities: Vec<f32>,
+    indices: Vec<u32>,
+    rings: Vec<RingFeature>,
+    gaps: Vec<RingFeature>,
+}
+
+#[wasm_bindgen]
+impl RingGeometry {
+    // Innermost ring edge (planet radii)
+    #[wasm_bindgen(getter)]
+    pub fn inner_radius(&self) -> f64 {
+        self.inner_radius
+    }
+
+    // Outermost ring edge (planet radii)
+    #[wasm_bindgen(getter)]
+    pub fn outer_radius(&self) -> f64 {
+        self.outer_radius
+    }
+
+    // Scene-space rotation [x, y, z, w] of the ring plane from the IAU pole
+    #[wasm_bindgen(getter)]
+    pub fn quaternion(&self) -> Float64Array {
+        Float64Array::from(&self.quaternion[..])
+    }
+
+    // Packed [x, y, z, ...] vertex positions
+    #[wasm_bindgen(getter)]
+    pub fn positions(&self) -> Float32Array {
+        Float32Array::from(self.positions.as_slice())
+    }
+
+    // Packed [u, v, ...] with u running 0-1 from the inner to the outer edge of
+    // the whole system and v running 0-1 around it
+    #[wasm_bindgen(getter)]
+    pub fn uvs(&self) -> Float32Array {
+        Float32Array::from(self.uvs.as_slice())
+    }
+
+    // Opacity of the ring each vertex belongs to
+    #[wasm_bindgen(getter)]
+    pub fn opacities(&self) -> Float32Array {
+        Float32Array::from(self.opacities.as_slice())
+    }
+
+    // Triangle list, counter-clockwise seen from the north (+y) side
+    #[wasm_bindgen(getter)]
+    pub fn indices(&self) -> Uint32Array {
+        Uint32Array::from(self.indices.as_slice())
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn rings(&self) -> Vec<RingFeature> {
+        self.rings.clone()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn gaps(&self) -> Vec<RingFeature> {
+        self.gaps.clone()
+    }
+}
+
+// Radial intervals (km) of a ring left after cutting out any gaps inside it
+fn visible_intervals(ring: &Ring, gaps: &[Gap]) -> Vec<(f64, f64)> {
+    let mut intervals = vec![(ring.1, ring.2)];
+    for &(_, gap_inner, gap_outer) in gaps {
+        intervals = intervals
+            .into_iter()
+            .flat_map(|(inner, outer)| {
+                if gap_outer <= inner || gap_inner >= outer {
+                    return vec![(inner, outer)];
+                }
+                [(inner, gap_inner), (gap_outer, outer)].into_iter().filter(|(a, b)| b > a).collect()
+            })
+            .collect();
+    }
+    intervals
+}
+
+impl RingGeometry {
+    // Append one annulus of RING_SEGMENTS quads
+    fn push_annulus(&mut self, inner: f64, outer: f64, opacity: f64) {
+        let base = (self.positions.len() / 3) as u32;
+        let span = self.outer_radius - self.inner_radius;
+        for s in 0..=RING_SEGMENTS {
+            let v = s as f64 / RING_SEGMENTS as f64;
+            let angle = 2.0 * std::f64::consts::PI * v;
+            for radius in [inner, outer] {
+                self.positions.extend_from_slice(&[
+                    (radius * angle.cos()) as f32,
+                    0.0,
+                    (-radius * angle.sin()) as f32,
+                ]);
+                self.uvs.extend_from_slice(&[((radius - self.inner_radius) / span) as f32, v as f32]);
+                self.opacities.push(opacity as f32);
+            }
+        }
+        for s in 0..RING_SEGMENTS {
+            let (i0, o0, i1, o1) = (base + 2 * s, base + 2 * s + 1, base + 2 * s + 2, base + 2 * s + 3);
+            self.indices.extend_from_slice(&[i0, o0, i1, i1, o0, o1]);
+        }
+    }
+}
+
+// Build the ring system of "Jupiter", "Saturn", "Uranus" or "Neptune" at a
+// Julian date: named rings and gaps plus an annulus mesh for each visible band,
+// oriented in the planet's equatorial plane. Returns None for ringless bodies.
+#[wasm_bindgen]
+pub fn ring_geometry(planet: &str, julian_date: f64) -> Result<Option<RingGeometry>, JsError> {
+    check_julian_date(julian_date)?;
+    Ok(build_ring_geometry(planet, julian_date))
+}
+
+// Ring system at a date already known to be valid; None for ringless bodies
+fn build_ring_geometry(planet: &str, julian_date: f64) -> Option<RingGeometry> {
+    let system = RING_SYSTEMS.iter().find(|system| system.planet == planet)?;
+    let (pole_ra, pole_dec, _) = pole_and_meridian(planet, julian_date)?;
+    let scale = 1.0 / system.equatorial_radius;
+    let feature = |name: &str, inner: f64, outer: f64, opacity: f64| RingFeature {
+        name: name.to_string(),
+        inner_radius: inner * scale,
+        outer_radius: outer * scale,
+        opacity,
+    };
+
+    let mut geometry = RingGeometry {
+        inner_radius: system.rings.iter().map(|ring| ring.1).fold(f64::INFINITY, f64::min) * scale,
+        outer_radius: system.rings.iter().map(|ring| ring.2).fold(0.0, f64::max) * scale,
+        // Rings are axisymmetric, so only the pole matters
+        quaternion: orientation_quaternion(pole_ra, pole_dec, 0.0),
+        positions: Vec::new(),
+        uvs: Vec::new(),
+        opacities: Vec::new(),
+        indices: Vec::new(),
+        rings: system.rings.iter().map(|&(name, inner, outer, opacity)| feature(name, inner, outer, opacity)).collect(),
+        gaps: system.gaps.iter().map(|&(name, inner, outer)| feature(name, inner, outer, 0.0)).collect(),
+    };
+    for ring in system.rings {
+        for (inner, outer) in visible_intervals(ring, system.gaps) {
+            geometry.push_annulus(inner * scale, outer * scale, ring.3);
+        }
+    }
+    Some(geometry)
+}
+
+// Generate ring particles for a planet's ring system, returned as packed
+// [x, y, z, size, ...] f32 values in the same local frame as ring_geometry
+// (planet radii, ring plane y = 0) with sizes in metres. Particles are spread in
+// proportion to each band's area and opacity, leaving the gaps empty, and each
+// follows a circular Keplerian orbit, so the same seed can be evaluated every
+// frame. Returns None for ringless bodies.
+#[wasm_bindgen]
+pub fn ring_particles(planet: &str, count: u32, seed: u32, julian_date: f64) -> Result<Option<Float32Array>, JsError> {
+    check_julian_date(julian_date)?;
+    Ok(build_ring_particles(planet, count, seed, julian_date))
+}
+
+// Ring particles at a date already known to be valid; None for ringless bodies
+fn build_ring_particles(planet: &str, count: u32, seed: u32, julian_date: f64) -> Option<Float32Array> {
+    let system = RING_SYSTEMS.iter().find(|system| system.planet == planet)?;
+    let seconds = (julian_date - 2451545.0) * 86400.0;
+
+    // Visible bands (km) with their cumulative particle weights
+    let mut bands = Vec::new();
+    let mut total_weight = 0.0;
+    for ring in system.rings {
+        for (inner, outer) in visible_intervals(ring, system.gaps) {
+            total_weight += ring.3 * (outer * outer - inner * inner);
+            bands.push((inner, outer, total_weight));
+        }
+    }
+
+    let mut rng = Rng::new(seed as u64);
+    let exponent = -2.0;
+    let (size_min, size_max) = (PARTICLE_SIZE_MIN.powf(exponent), PARTICLE_SIZE_MAX.powf(exponent));
+    let mut buffer = Vec::with_capacity(count as usize * 4);
+    for _ in 0..count {
+        let pick = rng.range(0.0, total_weight);
+        let &(inner, outer, _) = bands.iter().find(|band| pick < band.2).unwrap_or(&bands[bands.len() - 1]);
+        // Uniform over the annulus area
+        let radius = (inner * inner + rng.next_f64() * (outer * outer - inner * inner)).sqrt();
+        let mean_motion = (system.gm / radius.powi(3)).sqrt();
+        let phase = rng.range(0.0, 2.0 * std::f64::consts::PI);
+        let angle = phase + (mean_motion * seconds).rem_euclid(2.0 * std::f64::consts::PI);
+        let size = (size_min + rng.next_f64() * (size_max - size_min)).powf(1.0 / exponent);
+
+        let r = radius / system.equatorial_radius;
+        buffer.extend_from_slice(&[(r * angle.cos()) as f32, 0.0, (-r * angle.sin()) as f32, size as f32]);
+    }
+    Some(Float32Array::from(buffer.as_slice()))
+}
@@ -0,0 +1,117 @@
+use wasm_bindgen::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::errors::{check_julian_date, SimulationError};
+use crate::observer::{geocentric_ecliptic, horizontal_from_ecliptic, ObserverLocation};
+use crate::search::{find_minima, find_roots};
+
+// Search resolution (days) and time tolerance (days, about 1 second)
+const SEARCH_STEP: f64 = 10.0 / 1440.0;
+const TIME_TOLERANCE: f64 = 1.0 / 86400.0;
+
+// Rise, transit and set events of a body over one day
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[wasm_bindgen]
+pub struct RiseSetTimes {
+    rise: Option<f64>,
+    rise_azimuth: Option<f64>,
+    transit: Option<f64>,
+    transit_altitude: Option<f64>,
+    set: Option<f64>,
+    set_azimuth: Option<f64>,
+}
+
+#[wasm_bindgen]
+impl RiseSetTimes {
+    // Julian date of rising, absent for circumpolar or never-rising bodies
+    #[wasm_bindgen(getter)]
+    pub fn rise(&self) -> Option<f64> {
+        self.rise
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn rise_azimuth(&self) -> Option<f64> {
+        self.rise_azimuth
+    }
+
+    // Julian date of upper culmination
+    #[wasm_bindgen(getter)]
+    pub fn transit(&self) -> Option<f64> {
+        self.transit
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn transit_altitude(&self) -> Option<f64> {
+        self.transit_altitude
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn set(&self) -> Option<f64> {
+        self.set
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn set_azimuth(&self) -> Option<f64> {
+        self.set_azimuth
+    }
+}
+
+// Altitude (degrees) of the body's center at the instant of rising or setting,
+// allowing for refraction and, for the Sun and Moon, their apparent size
+fn standard_altitude(body: &str) -> f64 {
+    match body {
+        "Sun" => -0.8333,
+        "Moon" => 0.125,
+        _ => -0.5667,
+    }
+}
+
+// Find rise, transit and set of the Sun, Moon or a planet during the day
+// starting at the given Julian date; fails for an unknown body
+#[wasm_bindgen]
+pub fn rise_set_times(body: &str, date: f64, observer: &ObserverLocation) -> Result<RiseSetTimes, JsError> {
+    check_julian_date(date)?;
+    find_rise_set(body, date, observer).ok_or_else(|| SimulationError::unknown_body(body).into())
+}
+
+// Rise, transit and set during a day already known to be valid, or None for
+// an unknown body
+fn find_rise_set(body: &str, date: f64, observer: &ObserverLocation) -> Option<RiseSetTimes> {
+    let horizontal = |jd: f64| {
+        geocentric_ecliptic(body, jd).map(|geocentric| horizontal_from_ecliptic(&geocentric, jd, observer))
+    };
+    horizontal(date)?;
+
+    let h0 = standard_altitude(body);
+    let altitude = |jd: f64| horizontal(jd).map_or(0.0, |h| h.altitude()) - h0;
+    let end = date + 1.0;
+
+    let mut times = RiseSetTimes {
+        rise: None,
+        rise_azimuth: None,
+        transit: None,
+        transit_altitude: None,
+        set: None,
+        set_azimuth: None,
+    };
+
+    for root in find_roots(altitude, date, end, SEARCH_STEP, TIME_TOLERANCE) {
+        let rising = altitude(root + TIME_TOLERANCE) > altitude(root - TIME_TOLERANCE);
+        let azimuth = horizontal(root).map(|h| h.azimuth());
+        if rising && times.rise.is_none() {
+            times.rise = Some(root);
+            times.rise_azimuth = azimuth;
+        } else if !rising && times.set.is_none() {
+            times.set = Some(root);
+            times.set_azimuth = azimuth;
+        }
+    }
+
+    // Upper culmination is the highest point, i.e. the minimum of -altitude
+    if let Some(&transit) = find_minima(|jd| -altitude(jd), date, end, SEARCH_STEP, TIME_TOLERANCE).first() {
+        times.transit = Some(transit);
+        times.transit_altitude = horizontal(transit).map(|h| h.altitude());
+    }
+
+    Some(times)
+}
@@ -0,0 +1,164 @@
+use wasm_bindgen::prelude::*;
+use serde::{Deserialize, Serialize};
+use js_sys::Float64Array;
+
+use crate::errors::{check_julian_date, SimulationError};
+use crate::math::Quat;
+use crate::observer::{ecliptic_to_equatorial, mean_obliquity};
+use crate::precession::earth_pole_and_meridian;
+use crate::scene::scene_axes;
+use crate::{deg_to_rad, Vec3, PLANET_ELEMENTS};
+
+// Orientation of a planet's body-fixed frame at an epoch
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[wasm_bindgen]
+pub struct RotationState {
+    pole_ra: f64,
+    pole_dec: f64,
+    prime_meridian: f64,
+    quaternion: [f64; 4],
+}
+
+#[wasm_bindgen]
+impl RotationState {
+    // Right ascension of the north pole (degrees, ICRF)
+    #[wasm_bindgen(getter)]
+    pub fn pole_ra(&self) -> f64 {
+        self.pole_ra
+    }
+
+    // Declination of the north pole (degrees, ICRF)
+    #[wasm_bindgen(getter)]
+    pub fn pole_dec(&self) -> f64 {
+        self.pole_dec
+    }
+
+    // Prime meridian angle W (degrees)
+    #[wasm_bindgen(getter)]
+    pub fn prime_meridian(&self) -> f64 {
+        self.prime_meridian
+    }
+
+    // Scene-space rotation [x, y, z, w] of a model whose north pole is +Y and
+    // whose prime meridian faces +X
+    #[wasm_bindgen(getter)]
+    pub fn quaternion(&self) -> Float64Array {
+        Float64Array::from(&self.quaternion[..])
+    }
+
+    // The same rotation as a Quat
+    #[wasm_bindgen(getter)]
+    pub fn orientation(&self) -> Quat {
+        Quat::from(self.quaternion)
+    }
+}
+
+// IAU/WGCCRE cartographic rotation elements: pole right ascension and
+// declination (degrees, degrees/century) and prime meridian W (degrees, degrees/day)
+static IAU_ROTATION: &[(&str, f64, f64, f64, f64, f64, f64)] = &[
+    ("Mercury", 281.0103, -0.0328, 61.4155, -0.0049, 329.5988, 6.1385108),
+    ("Venus", 272.76, 0.0, 67.16, 0.0, 160.20, -1.4813688),
+    ("Mars", 317.269202, -0.10927547, 54.432516, -0.05827105, 176.049863, 350.891982443297),
+    ("Jupiter", 268.056595, -0.006499, 64.495303, 0.002413, 284.95, 870.5360000),
+    ("Saturn", 40.589, -0.036, 83.537, -0.004, 38.90, 810.7939024),
+    ("Uranus", 257.311, 0.0, -15.175, 0.0, 203.81, -501.1600928),
+    ("Neptune", 299.36, 0.0, 43.46, 0.0, 249.978, 541.1397757),
+];
+
+// Pole right ascension, declination and prime meridian angle (degrees) of a
+// body. Earth's follow precession, nutation and sidereal time rather than the
+// linear IAU rates, which drift over long spans.
+pub(crate) fn pole_and_meridian(name: &str, julian_date: f64) -> Option<(f64, f64, f64)> {
+    if name == "Earth" {
+        return Some(earth_pole_and_meridian(julian_date));
+    }
+    let &(_, ra0, ra_rate, dec0, dec_rate, w0, w_rate) = IAU_ROTATION.iter().find(|row| row.0 == name)?;
+    let d = julian_date - 2451545.0;
+    let t = d / 36525.0;
+    Some((ra0 + ra_rate * t, dec0 + dec_rate * t, (w0 + w_rate * d).rem_euclid(360.0)))
+}
+
+// Axes of a body's equatorial frame on the J2000.0 ecliptic: the ascending
+// node of its equator on the ICRF equator, the equator 90° on from it, and the
+// IAU north pole. Satellite elements are referred to this frame.
+pub(crate) fn equator_axes(name: &str, julian_date: f64) -> Option<[Vec3; 3]> {
+    let (pole_ra, pole_dec, _) = pole_and_meridian(name, julian_date)?;
+    let (ra, dec) = (deg_to_rad(pole_ra), deg_to_rad(pole_dec));
+    let pole = Vec3::new(dec.cos() * ra.cos(), dec.cos() * ra.sin(), dec.sin());
+    let node = Vec3::new(-ra.sin(), ra.cos(), 0.0);
+    let to_ecliptic = |v: &Vec3| ecliptic_to_equatorial(v, -mean_obliquity(2451545.0));
+    Some([to_ecliptic(&node), to_ecliptic(&pole.cross(&node)), to_ecliptic(&pole)])
+}
+
+// Convert an ICRF direction into scene axes, keeping unit vectors unit length
+pub(crate) fn icrf_to_scene(v: &Vec3) -> Vec3 {
+    scene_axes(&ecliptic_to_equatorial(v, -mean_obliquity(2451545.0)))
+}
+
+// Quaternion [x, y, z, w] of the rotation whose matrix has the given columns
+pub(crate) fn quaternion_from_axes(x: &Vec3, y: &Vec3, z: &Vec3) -> [f64; 4] {
+    let trace = x.x + y.y + z.z;
+    if trace > 0.0 {
+        let s = 0.5 / (trace + 1.0).sqrt();
+        [(y.z - z.y) * s, (z.x - x.z) * s, (x.y - y.x) * s, 0.25 / s]
+    } else if x.x > y.y && x.x > z.z {
+        let s = 2.0 * (1.0 + x.x - y.y - z.z).sqrt();
+        [0.25 * s, (y.x + x.y) / s, (z.x + x.z) / s, (y.z - z.y) / s]
+    } else if y.y > z.z {
+        let s = 2.0 * (1.0 + y.y - x.x - z.z).sqrt();
+        [(y.x + x.y) / s, 0.25 * s, (z.y + y.z) / s, (z.x - x.z) / s]
+    } else {
+        let s = 2.0 * (1.0 + z.z - x.x - y.y).sqrt();
+        [(z.x + x.z) / s, (z.y + y.z) / s, 0.25 * s, (x.y - y.x) / s]
+    }
+}
+
+// Scene-space orientation quaternion from pole and prime meridian angles (degrees)
+pub(crate) fn orientation_quaternion(pole_ra: f64, pole_dec: f64, prime_meridian: f64) -> [f64; 4] {
+    let (ra, dec, w) = (deg_to_rad(pole_ra), deg_to_rad(pole_dec), deg_to_rad(prime_meridian));
+
+    // Pole, and the ascending node of the body equator on the ICRF equator
+    let pole = Vec3::new(dec.cos() * ra.cos(), dec.cos() * ra.sin(), dec.sin());
+    let node = Vec3::new(-ra.sin(), ra.cos(), 0.0);
+    let node_normal = pole.cross(&node);
+
+    // Prime meridian lies W along the body equator from the node
+    let meridian = node.scale(w.cos()).add(&node_normal.scale(w.sin()));
+
+    let model_x = icrf_to_scene(&meridian);
+    let model_y = icrf_to_scene(&pole);
+    let model_z = model_x.cross(&model_y);
+    quaternion_from_axes(&model_x, &model_y, &model_z)
+}
+
+// Orientation quaternion of a body, or the identity for one without a rotation model
+pub(crate) fn body_orientation(name: &str, julian_date: f64) -> [f64; 4] {
+    pole_and_meridian(name, julian_date)
+        .map_or([0.0, 0.0, 0.0, 1.0], |(ra, dec, w)| orientation_quaternion(ra, dec, w))
+}
+
+// Calculate a planet's IAU pole, prime meridian and orientation quaternion
+#[wasm_bindgen]
+pub fn planet_rotation(name: &str, julian_date: f64) -> Result<RotationState, JsError> {
+    check_julian_date(julian_date)?;
+    let (pole_ra, pole_dec, prime_meridian) =
+        pole_and_meridian(name, julian_date).ok_or_else(|| SimulationError::unknown_body(name))?;
+    Ok(RotationState {
+        pole_ra,
+        pole_dec,
+        prime_meridian,
+        quaternion: orientation_quaternion(pole_ra, pole_dec, prime_meridian),
+    })
+}
+
+// Calculate every planet's orientation as packed [x, y, z, w, ...] quaternions
+// in PLANET_ELEMENTS order, ready to copy into Three.js object quaternions
+#[wasm_bindgen]
+pub fn planet_orientations(julian_date: f64) -> Result<Float64Array, JsError> {
+    check_julian_date(julian_date)?;
+    let mut buffer = Vec::with_capacity(PLANET_ELEMENTS.len() * 4);
+    for (name, _) in PLANET_ELEMENTS.iter() {
+        buffer.extend_from_slice(&body_orientation(name, julian_date));
+    }
+    Ok(Float64Array::from(buffer.as_slice()))
+}
@@ -0,0 +1,516 @@
+// Earth satellites from NORAD two-line element sets, propagated with SGP4
+//
+// This follows the SGP4 model of Spacetrack Report #3 as revised by Vallado et
+// al. (2006). Element sets with periods of 225 minutes or more (GPS, Molniya,
+// geostationary) add the deep-space SDP4 terms from deep_space.
+
+use wasm_bindgen::prelude::*;
+use std::f64::consts::PI;
+
+use crate::calendar::calendar_to_julian_date;
+use crate::deep_space::{DeepSpace, MeanElements};
+use crate::errors::{check_julian_date, SimulationError};
+use crate::precession::true_equator_to_ecliptic;
+use crate::state::StateVector;
+use crate::units;
+use crate::Vec3;
+
+// WGS-72 constants used by the element sets
+const EARTH_RADIUS_KM: f64 = 6378.135;
+const EARTH_MU: f64 = 398600.8; // km³/s²
+const J2: f64 = 0.001082616;
+const J3: f64 = -0.00000253881;
+const J4: f64 = -0.00000165597;
+
+const TWO_PI: f64 = 2.0 * PI;
+const MINUTES_PER_DAY: f64 = 1440.0;
+
+// Shortest period (minutes) that needs the deep-space model
+const DEEP_SPACE_PERIOD: f64 = 225.0;
+
+// Square root of mu in Earth radii³/min²
+fn xke() -> f64 {
+    60.0 / (EARTH_RADIUS_KM.powi(3) / EARTH_MU).sqrt()
+}
+
+// Inclination-dependent coefficients of the long- and short-period periodics:
+// aycof, xlcof, con41, x1mth2 and x7thm1
+fn inclination_coefficients(inclination: f64) -> [f64; 5] {
+    let j3oj2 = J3 / J2;
+    let (sinio, cosio) = inclination.sin_cos();
+    let cosio2 = cosio * cosio;
+    // Avoid the singularity for retrograde equatorial orbits
+    let xlcof_denominator = if (cosio + 1.0).abs() > 1.5e-12 { 1.0 + cosio } else { 1.5e-12 };
+    [
+        -0.5 * j3oj2 * sinio,
+        -0.25 * j3oj2 * sinio * (3.0 + 5.0 * cosio) / xlcof_denominator,
+        3.0 * cosio2 - 1.0,
+        1.0 - cosio2,
+        7.0 * cosio2 - 1.0,
+    ]
+}
+
+// Extract a fixed-width TLE field by 1-based inclusive column range
+fn field(line: &str, start: usize, end: usize) -> Option<&str> {
+    line.get(start - 1..end).map(str::trim)
+}
+
+fn number(line: &str, start: usize, end: usize) -> Option<f64> {
+    field(line, start, end)?.parse().ok()
+}
+
+// Decode an assumed-decimal exponential field such as "-11606-4" (-0.11606e-4)
+fn exponential(line: &str, start: usize, end: usize) -> Option<f64> {
+    let text = field(line, start, end)?.replace(' ', "");
+    if text.len() < 3 {
+        return None;
+    }
+    let (mantissa, exponent) = text.split_at(text.len() - 2);
+    let (sign, digits) = match mantissa.strip_prefix('-') {
+        Some(digits) => (-1.0, digits),
+        None => (1.0, mantissa.trim_start_matches('+')),
+    };
+    let mantissa: f64 = format!("0.{digits}").parse().ok()?;
+    let exponent: i32 = exponent.parse().ok()?;
+    Some(sign * mantissa * 10f64.powi(exponent))
+}
+
+// An Earth satellite with SGP4 initialisation precomputed from its TLE
+#[derive(Debug, Clone)]
+#[wasm_bindgen]
+pub struct Satellite {
+    name: String,
+    catalog_number: u32,
+    epoch: f64,
+    // Mean elements at epoch (radians, radians/minute)
+    inclination: f64,
+    node: f64,
+    eccentricity: f64,
+    perigee: f64,
+    mean_anomaly: f64,
+    mean_motion: f64,
+    bstar: f64,
+    // Secular rates and drag coefficients
+    simple: bool,
+    aycof: f64,
+    con41: f64,
+    cc1: f64,
+    cc4: f64,
+    cc5: f64,
+    d2: f64,
+    d3: f64,
+    d4: f64,
+    delmo: f64,
+    eta: f64,
+    argpdot: f64,
+    omgcof: f64,
+    sinmao: f64,
+    t2cof: f64,
+    t3cof: f64,
+    t4cof: f64,
+    t5cof: f64,
+    x1mth2: f64,
+    x7thm1: f64,
+    mdot: f64,
+    nodedot: f64,
+    xlcof: f64,
+    xmcof: f64,
+    nodecf: f64,
+    // Lunar-solar and resonance terms for deep-space orbits
+    deep_space: Option<DeepSpace>,
+}
+
+#[wasm_bindgen]
+impl Satellite {
+    // Parse a two-line element set. Fails if either line is malformed.
+    pub fn from_tle(line1: &str, line2: &str) -> Result<Satellite, JsError> {
+        Satellite::from_named_tle("", line1, line2)
+    }
+
+    // Parse a three-line element set (name line plus the two element lines)
+    pub fn from_named_tle(name: &str, line1: &str, line2: &str) -> Result<Satellite, JsError> {
+        Ok(Satellite::parse(name, line1, line2)
+            .ok_or_else(|| SimulationError::invalid_argument("Malformed two-line element set"))?)
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn name(&self) -> String {
+        self.name.clone()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn catalog_number(&self) -> u32 {
+        self.catalog_number
+    }
+
+    // Element set epoch (Julian date, UTC)
+    #[wasm_bindgen(getter)]
+    pub fn epoch(&self) -> f64 {
+        self.epoch
+    }
+
+    // Orbital period (minutes)
+    #[wasm_bindgen(getter)]
+    pub fn period(&self) -> f64 {
+        TWO_PI / self.mean_motion
+    }
+
+    // TEME position (km) and velocity (km/s) at a Julian date, or None once
+    // the orbit has decayed
+    pub fn eci_state(&self, julian_date: f64) -> Result<Option<StateVector>, JsError> {
+        check_julian_date(julian_date)?;
+        Ok(self.propagate((julian_date - self.epoch) * MINUTES_PER_DAY))
+    }
+
+    // Geocentric position in Earth equatorial radii using the same scene axes
+    // as the Moon
+    pub fn scene_position(&self, julian_date: f64) -> Result<Option<Vec3>, JsError> {
+        let Some(state) = self.eci_state(julian_date)? else { return Ok(None) };
+        // TEME shares the true equator of date; undo nutation and precession
+        let ecliptic = true_equator_to_ecliptic(&state.position, julian_date);
+        // Scene units are the planet table's radius, not the WGS-72 one above
+        let scale = 1.0 / units::EARTH_RADIUS_KM;
+        Ok(Some(Vec3::new(ecliptic.x * scale, ecliptic.z * scale, ecliptic.y * scale)))
+    }
+}
+
+impl Satellite {
+    // Elements of a three-line element set, or None if a line is malformed
+    fn parse(name: &str, line1: &str, line2: &str) -> Option<Satellite> {
+        if !line1.starts_with('1') || !line2.starts_with('2') {
+            return None;
+        }
+        let catalog_number: u32 = field(line1, 3, 7)?.parse().ok()?;
+        let year: i32 = field(line1, 19, 20)?.parse().ok()?;
+        let day_of_year = number(line1, 21, 32)?;
+        let bstar = exponential(line1, 54, 61)?;
+
+        let inclination = number(line2, 9, 16)?.to_radians();
+        let node = number(line2, 18, 25)?.to_radians();
+        let eccentricity: f64 = format!("0.{}", field(line2, 27, 33)?).parse().ok()?;
+        let perigee = number(line2, 35, 42)?.to_radians();
+        let mean_anomaly = number(line2, 44, 51)?.to_radians();
+        let revolutions_per_day = number(line2, 53, 63)?;
+        if revolutions_per_day <= 0.0 {
+            return None;
+        }
+
+        // Two-digit years 57-99 are 1957-1999
+        let year = if year < 57 { 2000 + year } else { 1900 + year };
+        let epoch = calendar_to_julian_date(year, 1, day_of_year);
+        let name = if name.trim().is_empty() { catalog_number.to_string() } else { name.trim().to_string() };
+
+        Some(Satellite::initialize(
+            name,
+            catalog_number,
+            epoch,
+            [inclination, node, eccentricity, perigee, mean_anomaly, revolutions_per_day * TWO_PI / MINUTES_PER_DAY],
+            bstar,
+        ))
+    }
+
+    // SGP4 initialisation: recover the Brouwer mean motion and precompute the
+    // secular and drag coefficients
+    fn initialize(name: String, catalog_number: u32, epoch: f64, elements: [f64; 6], bstar: f64) -> Satellite {
+        let [inclination, node, eccentricity, perigee, mean_anomaly, kozai_motion] = elements;
+        let xke = xke();
+        let j3oj2 = J3 / J2;
+        let x2o3 = 2.0 / 3.0;
+
+        let eccsq = eccentricity * eccentricity;
+        let omeosq = 1.0 - eccsq;
+        let rteosq = omeosq.sqrt();
+        let cosio = inclination.cos();
+        let cosio2 = cosio * cosio;
+        let sinio = inclination.sin();
+
+        // Un-Kozai the mean motion
+        let ak = (xke / kozai_motion).powf(x2o3);
+        let d1 = 0.75 * J2 * (3.0 * cosio2 - 1.0) / (rteosq * omeosq);
+        let del = d1 / (ak * ak);
+        let adel = ak * (1.0 - del * del - del * (1.0 / 3.0 + 134.0 * del * del / 81.0));
+        let del = d1 / (adel * adel);
+        let mean_motion = kozai_motion / (1.0 + del);
+
+        let ao = (xke / mean_motion).powf(x2o3);
+        let po = ao * omeosq;
+        let con42 = 1.0 - 5.0 * cosio2;
+        let con41 = -con42 - cosio2 - cosio2;
+        let posq = po * po;
+        let rp = ao * (1.0 - eccentricity);
+
+        // Deep-space orbits and perigees below 220 km use the truncated drag
+        // model
+        let deep = TWO_PI / mean_motion >= DEEP_SPACE_PERIOD;
+        let simple = deep || rp < 220.0 / EARTH_RADIUS_KM + 1.0;
+
+        // Atmospheric density parameters, adjusted for low perigees
+        let mut sfour = 78.0 / EARTH_RADIUS_KM + 1.0;
+        let mut qzms24 = ((120.0 - 78.0) / EARTH_RADIUS_KM).powi(4);
+        let perigee_height = (rp - 1.0) * EARTH_RADIUS_KM;
+        if perigee_height < 156.0 {
+            let s = if perigee_height < 98.0 { 20.0 } else { perigee_height - 78.0 };
+            qzms24 = ((120.0 - s) / EARTH_RADIUS_KM).powi(4);
+            sfour = s / EARTH_RADIUS_KM + 1.0;
+        }
+
+        let pinvsq = 1.0 / posq;
+        let tsi = 1.0 / (ao - sfour);
+        let eta = ao * eccentricity * tsi;
+        let etasq = eta * eta;
+        let eeta = eccentricity * eta;
+        let psisq = (1.0 - etasq).abs();
+        let coef = qzms24 * tsi.powi(4);
+        let coef1 = coef / psisq.powf(3.5);
+        let cc2 = coef1
+            * mean_motion
+            * (ao * (1.0 + 1.5 * etasq + eeta * (4.0 + etasq))
+                + 0.375 * J2 * tsi / psisq * con41 * (8.0 + 3.0 * etasq * (8.0 + etasq)));
+        let cc1 = bstar * cc2;
+        let cc3 = if eccentricity > 1e-4 {
+            -2.0 * coef * tsi * j3oj2 * mean_motion * sinio / eccentricity
+        } else {
+            0.0
+        };
+        let x1mth2 = 1.0 - cosio2;
+        let cc4 = 2.0
+            * mean_motion
+            * coef1
+            * ao
+            * omeosq
+            * (eta * (2.0 + 0.5 * etasq) + eccentricity * (0.5 + 2.0 * etasq)
+                - J2 * tsi / (ao * psisq)
+                    * (-3.0 * con41 * (1.0 - 2.0 * eeta + etasq * (1.5 - 0.5 * eeta))
+                        + 0.75 * x1mth2 * (2.0 * etasq - eeta * (1.0 + etasq)) * (2.0 * perigee).cos()));
+        let cc5 = 2.0 * coef1 * ao * omeosq * (1.0 + 2.75 * (etasq + eeta) + eeta * etasq);
+
+        // Secular rates from J2 and J4
+        let cosio4 = cosio2 * cosio2;
+        let temp1 = 1.5 * J2 * pinvsq * mean_motion;
+        let temp2 = 0.5 * temp1 * J2 * pinvsq;
+        let temp3 = -0.46875 * J4 * pinvsq * pinvsq * mean_motion;
+        let mdot = mean_motion
+            + 0.5 * temp1 * rteosq * con41
+            + 0.0625 * temp2 * rteosq * (13.0 - 78.0 * cosio2 + 137.0 * cosio4);
+        let argpdot = -0.5 * temp1 * con42
+            + 0.0625 * temp2 * (7.0 - 114.0 * cosio2 + 395.0 * cosio4)
+            + temp3 * (3.0 - 36.0 * cosio2 + 49.0 * cosio4);
+        let xhdot1 = -temp1 * cosio;
+        let nodedot = xhdot1 + (0.5 * temp2 * (4.0 - 19.0 * cosio2) + 2.0 * temp3 * (3.0 - 7.0 * cosio2)) * cosio;
+
+        let omgcof = bstar * cc3 * perigee.cos();
+        let xmcof = if eccentricity > 1e-4 { -x2o3 * coef * bstar / eeta } else { 0.0 };
+        let nodecf = 3.5 * omeosq * xhdot1 * cc1;
+        let t2cof = 1.5 * cc1;
+        let [aycof, xlcof, _, _, x7thm1] = inclination_coefficients(inclination);
+        let delmo = (1.0 + eta * mean_anomaly.cos()).powi(3);
+        let sinmao = mean_anomaly.sin();
+
+        // Higher-order drag terms for the full model
+        let (d2, d3, d4, t3cof, t4cof, t5cof) = if simple {
+            (0.0, 0.0, 0.0, 0.0, 0.0, 0.0)
+        } else {
+            let cc1sq = cc1 * cc1;
+            let d2 = 4.0 * ao * tsi * cc1sq;
+            let temp = d2 * tsi * cc1 / 3.0;
+            let d3 = (17.0 * ao + sfour) * temp;
+            let d4 = 0.5 * temp * ao * tsi * (221.0 * ao + 31.0 * sfour) * cc1;
+            let t3cof = d2 + 2.0 * cc1sq;
+            let t4cof = 0.25 * (3.0 * d3 + cc1 * (12.0 * d2 + 10.0 * cc1sq));
+            let t5cof = 0.2 * (3.0 * d4 + 12.0 * cc1 * d3 + 6.0 * d2 * d2 + 15.0 * cc1sq * (2.0 * d2 + cc1sq));
+            (d2, d3, d4, t3cof, t4cof, t5cof)
+        };
+
+        let deep_space = deep.then(|| {
+            let elements = MeanElements {
+                eccentricity,
+                inclination,
+                node,
+                perigee,
+                mean_anomaly,
+                mean_motion,
+            };
+            DeepSpace::new(epoch, &elements, [mdot, argpdot, nodedot], xke)
+        });
+
+        Satellite {
+            name,
+            catalog_number,
+            epoch,
+            inclination,
+            node,
+            eccentricity,
+            perigee,
+            mean_anomaly,
+            mean_motion,
+            bstar,
+            simple,
+            aycof,
+            con41,
+            cc1,
+            cc4,
+            cc5,
+            d2,
+            d3,
+            d4,
+            delmo,
+            eta,
+            argpdot,
+            omgcof,
+            sinmao,
+            t2cof,
+            t3cof,
+            t4cof,
+            t5cof,
+            x1mth2,
+            x7thm1,
+            mdot,
+            nodedot,
+            xlcof,
+            xmcof,
+            nodecf,
+            deep_space,
+        }
+    }
+
+    // Propagate to a time in minutes since epoch, returning the TEME state in
+    // km and km/s, or None if the satellite has decayed
+    fn propagate(&self, minutes: f64) -> Option<StateVector> {
+        let xke = xke();
+        let t = minutes;
+
+        // Secular gravity and atmospheric drag
+        let xmdf = self.mean_anomaly + self.mdot * t;
+        let argpdf = self.perigee + self.argpdot * t;
+        let nodedf = self.node + self.nodedot * t;
+        let t2 = t * t;
+        let mut argpm = argpdf;
+        let mut mm = xmdf;
+        let nodem = nodedf + self.nodecf * t2;
+        let mut tempa = 1.0 - self.cc1 * t;
+        let mut tempe = self.bstar * self.cc4 * t;
+        let mut templ = self.t2cof * t2;
+
+        if !self.simple {
+            let delomg = self.omgcof * t;
+            let delm = self.xmcof * ((1.0 + self.eta * xmdf.cos()).powi(3) - self.delmo);
+            let temp = delomg + delm;
+            mm = xmdf + temp;
+            argpm = argpdf - temp;
+            let t3 = t2 * t;
+            let t4 = t3 * t;
+            tempa -= self.d2 * t2 + self.d3 * t3 + self.d4 * t4;
+            tempe += self.bstar * self.cc5 * (mm.sin() - self.sinmao);
+            templ += self.t3cof * t3 + t4 * (self.t4cof + t * self.t5cof);
+        }
+
+        // Lunar-solar secular rates and resonances
+        let mut mean = MeanElements {
+            eccentricity: self.eccentricity,
+            inclination: self.inclination,
+            node: nodem,
+            perigee: argpm,
+            mean_anomaly: mm,
+            mean_motion: self.mean_motion,
+        };
+        if let Some(deep_space) = &self.deep_space {
+            deep_space.secular(t, &mut mean);
+        }
+        if mean.mean_motion <= 0.0 {
+            return None;
+        }
+
+        let am = (xke / mean.mean_motion).powf(2.0 / 3.0) * tempa * tempa;
+        let nm = xke / am.powf(1.5);
+        let em = mean.eccentricity - tempe;
+        if !(-0.001..1.0).contains(&em) || am < 0.95 {
+            return None;
+        }
+        mean.eccentricity = em.max(1e-6);
+
+        let xlm = mean.mean_anomaly + self.mean_motion * templ + mean.perigee + mean.node;
+        mean.node = mean.node.rem_euclid(TWO_PI);
+        mean.perigee = mean.perigee.rem_euclid(TWO_PI);
+        mean.mean_anomaly = (xlm.rem_euclid(TWO_PI) - mean.perigee - mean.node).rem_euclid(TWO_PI);
+
+        // Lunar-solar periodics, which move the inclination the J2 and J3
+        // coefficients depend on
+        let (osculating, [aycof, xlcof, con41, x1mth2, x7thm1]) = match &self.deep_space {
+            Some(deep_space) => {
+                let perturbed = deep_space.periodics(t, &mean)?;
+                (perturbed, inclination_coefficients(perturbed.inclination))
+            }
+            None => (mean, [self.aycof, self.xlcof, self.con41, self.x1mth2, self.x7thm1]),
+        };
+        let MeanElements { eccentricity: ep, inclination: xincp, node: nodep, perigee: argpp, mean_anomaly: mp, .. } =
+            osculating;
+
+        // Long-period periodics
+        let axnl = ep * argpp.cos();
+        let temp = 1.0 / (am * (1.0 - ep * ep));
+        let aynl = ep * argpp.sin() + temp * aycof;
+        let xl = mp + argpp + nodep + temp * xlcof * axnl;
+
+        // Solve Kepler's equation in the equinoctial form
+        let u = (xl - nodep).rem_euclid(TWO_PI);
+        let mut eo1 = u;
+        let (mut sineo1, mut coseo1) = eo1.sin_cos();
+        for _ in 0..10 {
+            let tem5 = (u - aynl * coseo1 + axnl * sineo1 - eo1) / (1.0 - coseo1 * axnl - sineo1 * aynl);
+            eo1 += tem5.clamp(-0.95, 0.95);
+            (sineo1, coseo1) = eo1.sin_cos();
+            if tem5.abs() < 1e-12 {
+                break;
+            }
+        }
+
+        // Short-period preliminary quantities
+        let ecose = axnl * coseo1 + aynl * sineo1;
+        let esine = axnl * sineo1 - aynl * coseo1;
+        let el2 = axnl * axnl + aynl * aynl;
+        let pl = am * (1.0 - el2);
+        if pl < 0.0 {
+            return None;
+        }
+        let rl = am * (1.0 - ecose);
+        let rdotl = am.sqrt() * esine / rl;
+        let rvdotl = pl.sqrt() / rl;
+        let betal = (1.0 - el2).sqrt();
+        let temp = esine / (1.0 + betal);
+        let sinu = am / rl * (sineo1 - aynl - axnl * temp);
+        let cosu = am / rl * (coseo1 - axnl + aynl * temp);
+        let su = sinu.atan2(cosu);
+        let sin2u = (cosu + cosu) * sinu;
+        let cos2u = 1.0 - 2.0 * sinu * sinu;
+        let temp = 1.0 / pl;
+        let temp1 = 0.5 * J2 * temp;
+        let temp2 = temp1 * temp;
+
+        // Short-period periodics
+        let (sinip, cosip) = xincp.sin_cos();
+        let mrt = rl * (1.0 - 1.5 * temp2 * betal * con41) + 0.5 * temp1 * x1mth2 * cos2u;
+        let su = su - 0.25 * temp2 * x7thm1 * sin2u;
+        let xnode = nodep + 1.5 * temp2 * cosip * sin2u;
+        let xinc = xincp + 1.5 * temp2 * cosip * sinip * cos2u;
+        let mvt = rdotl - nm * temp1 * x1mth2 * sin2u / xke;
+        let rvdot = rvdotl + nm * temp1 * (x1mth2 * cos2u + 1.5 * con41) / xke;
+        if mrt < 1.0 {
+            return None;
+        }
+
+        // Orientation unit vectors
+        let (sinsu, cossu) = su.sin_cos();
+        let (snod, cnod) = xnode.sin_cos();
+        let (sini, cosi) = xinc.sin_cos();
+        let xmx = -snod * cosi;
+        let xmy = cnod * cosi;
+        let u_vec = Vec3::new(xmx * sinsu + cnod * cossu, xmy * sinsu + snod * cossu, sini * sinsu);
+        let v_vec = Vec3::new(xmx * cossu - cnod * sinsu, xmy * cossu - snod * sinsu, sini * cossu);
+
+        let velocity_scale = EARTH_RADIUS_KM * xke / 60.0;
+        Some(StateVector {
+            position: u_vec.scale(mrt * EARTH_RADIUS_KM),
+            velocity: u_vec.scale(mvt).add(&v_vec.scale(rvdot)).scale(velocity_scale),
+        })
+    }
+}
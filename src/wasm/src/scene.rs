@@ -0,0 +1,166 @@
+// Mapping from heliocentric ecliptic coordinates (AU) to the rendered scene:
+// distance unit and scale, optional logarithmic compression so the inner and
+// outer planets fit on one screen, and the exaggeration applied to body radii
+
+use wasm_bindgen::prelude::*;
+use std::cell::Cell;
+
+use crate::units::DistanceUnit;
+use crate::Vec3;
+
+// How positions and sizes are scaled into scene units
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SceneConfig {
+    distance_scale: f64,
+    radius_scale: f64,
+    log_compression: bool,
+    units: DistanceUnit,
+}
+
+impl Default for SceneConfig {
+    // Two scene units per AU, true distances and unscaled radii
+    fn default() -> SceneConfig {
+        SceneConfig { distance_scale: 2.0, radius_scale: 1.0, log_compression: false, units: DistanceUnit::Au }
+    }
+}
+
+#[wasm_bindgen]
+impl SceneConfig {
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> SceneConfig {
+        SceneConfig::default()
+    }
+
+    // Scene units per distance unit
+    #[wasm_bindgen(getter)]
+    pub fn distance_scale(&self) -> f64 {
+        self.distance_scale
+    }
+
+    #[wasm_bindgen(setter)]
+    pub fn set_distance_scale(&mut self, distance_scale: f64) {
+        self.distance_scale = distance_scale;
+    }
+
+    // Factor applied to body radii
+    #[wasm_bindgen(getter)]
+    pub fn radius_scale(&self) -> f64 {
+        self.radius_scale
+    }
+
+    #[wasm_bindgen(setter)]
+    pub fn set_radius_scale(&mut self, radius_scale: f64) {
+        self.radius_scale = radius_scale;
+    }
+
+    // Replace each heliocentric distance r (AU) with ln(1 + r) AU, keeping the
+    // direction, so Mercury sits at 0.33 and Neptune at 3.4 instead of 30
+    #[wasm_bindgen(getter)]
+    pub fn log_compression(&self) -> bool {
+        self.log_compression
+    }
+
+    #[wasm_bindgen(setter)]
+    pub fn set_log_compression(&mut self, log_compression: bool) {
+        self.log_compression = log_compression;
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn units(&self) -> DistanceUnit {
+        self.units
+    }
+
+    #[wasm_bindgen(setter)]
+    pub fn set_units(&mut self, units: DistanceUnit) {
+        self.units = units;
+    }
+}
+
+// Internal scene helpers
+impl SceneConfig {
+    // Scene units per AU, ignoring compression
+    fn linear_scale(&self) -> f64 {
+        self.distance_scale * self.units.per_au()
+    }
+
+    // Scene distance for a heliocentric distance (AU), and its derivative
+    fn compress(&self, r: f64) -> (f64, f64) {
+        let scale = self.linear_scale();
+        if self.log_compression {
+            (scale * r.ln_1p(), scale / (1.0 + r))
+        } else {
+            (scale * r, scale)
+        }
+    }
+}
+
+thread_local! {
+    static SCENE_CONFIG: Cell<SceneConfig> = Cell::new(SceneConfig::default());
+}
+
+pub(crate) fn active_config() -> SceneConfig {
+    SCENE_CONFIG.with(Cell::get)
+}
+
+// Reorder ecliptic axes into the scene's Y-up axes without scaling, for directions
+pub(crate) fn scene_axes(ecliptic: &Vec3) -> Vec3 {
+    Vec3::new(ecliptic.x, ecliptic.z, ecliptic.y)
+}
+
+// Convert an ecliptic position (AU) to scene coordinates (Y up)
+pub(crate) fn to_scene(ecliptic: Vec3) -> Vec3 {
+    let config = active_config();
+    let r = ecliptic.length();
+    if r == 0.0 {
+        return ecliptic;
+    }
+    let (distance, _) = config.compress(r);
+    scene_axes(&ecliptic.scale(distance / r))
+}
+
+// Convert an ecliptic velocity (AU/day) at a position (AU) to scene units per
+// day, following the position mapping so moving along it stays consistent
+pub(crate) fn scene_velocity(position: &Vec3, velocity: &Vec3) -> Vec3 {
+    let config = active_config();
+    let r = position.length();
+    if !config.log_compression || r == 0.0 {
+        return scene_axes(&velocity.scale(config.linear_scale()));
+    }
+    // Split into radial and transverse parts: the radial rate follows the
+    // compression's slope, the transverse part its ratio
+    let (distance, slope) = config.compress(r);
+    let direction = position.scale(1.0 / r);
+    let radial = direction.scale(velocity.dot(&direction));
+    let transverse = velocity.sub(&radial);
+    scene_axes(&radial.scale(slope).add(&transverse.scale(distance / r)))
+}
+
+// Scene length of a local distance (AU), such as a comet tail or a moon orbit,
+// at the uncompressed scale
+pub(crate) fn scene_length(au: f64) -> f64 {
+    au * active_config().linear_scale()
+}
+
+// Display radius for a true body radius
+pub(crate) fn scene_radius(radius: f64) -> f64 {
+    radius * active_config().radius_scale
+}
+
+// Make a scene configuration the active one for every scene-coordinate output
+#[wasm_bindgen]
+pub fn set_scene_config(config: &SceneConfig) {
+    SCENE_CONFIG.with(|active| active.set(*config));
+}
+
+// The active scene configuration
+#[wasm_bindgen]
+pub fn scene_config() -> SceneConfig {
+    active_config()
+}
+
+// Restore the default scene configuration: 2 units per AU, no compression
+#[wasm_bindgen]
+pub fn reset_scene_config() {
+    set_scene_config(&SceneConfig::default());
+}
@@ -0,0 +1,108 @@
+// Numerical search helpers shared by the event finders
+
+// Golden ratio conjugate used by the golden-section search
+const INV_PHI: f64 = 0.6180339887498949;
+
+// Refine a sign change of f in [a, b] by bisection to the given tolerance
+pub(crate) fn bisect<F: Fn(f64) -> f64>(f: F, mut a: f64, mut b: f64, tolerance: f64) -> f64 {
+    let mut fa = f(a);
+    while b - a > tolerance {
+        let mid = 0.5 * (a + b);
+        let fm = f(mid);
+        if (fa < 0.0) == (fm < 0.0) {
+            a = mid;
+            fa = fm;
+        } else {
+            b = mid;
+        }
+    }
+    0.5 * (a + b)
+}
+
+// Locate the minimum of a unimodal f in [a, b] by golden-section search
+pub(crate) fn golden_section_min<F: Fn(f64) -> f64>(f: F, mut a: f64, mut b: f64, tolerance: f64) -> f64 {
+    let mut c = b - INV_PHI * (b - a);
+    let mut d = a + INV_PHI * (b - a);
+    let mut fc = f(c);
+    let mut fd = f(d);
+    while b - a > tolerance {
+        if fc < fd {
+            b = d;
+            d = c;
+            fd = fc;
+            c = b - INV_PHI * (b - a);
+            fc = f(c);
+        } else {
+            a = c;
+            c = d;
+            fc = fd;
+            d = a + INV_PHI * (b - a);
+            fd = f(d);
+        }
+    }
+    0.5 * (a + b)
+}
+
+// Sample f on a regular grid over [start, end] and return refined roots
+pub(crate) fn find_roots<F: Fn(f64) -> f64>(f: F, start: f64, end: f64, step: f64, tolerance: f64) -> Vec<f64> {
+    let mut roots = Vec::new();
+    let mut t0 = start;
+    let mut f0 = f(t0);
+    while t0 < end {
+        let t1 = (t0 + step).min(end);
+        let f1 = f(t1);
+        if (f0 < 0.0) != (f1 < 0.0) {
+            roots.push(bisect(&f, t0, t1, tolerance));
+        }
+        t0 = t1;
+        f0 = f1;
+    }
+    roots
+}
+
+// Sample f on a regular grid over [start, end] and return refined local minima
+pub(crate) fn find_minima<F: Fn(f64) -> f64>(f: F, start: f64, end: f64, step: f64, tolerance: f64) -> Vec<f64> {
+    let mut minima = Vec::new();
+    let mut t = start;
+    let mut previous = f(t - step);
+    let mut current = f(t);
+    while t < end {
+        let next = f(t + step);
+        if current <= previous && current < next {
+            minima.push(golden_section_min(&f, t - step, t + step, tolerance));
+        }
+        previous = current;
+        current = next;
+        t += step;
+    }
+    minima
+}
+
+// Refine a bracketed root of f in [a, b] by Newton iteration with a numerical
+// derivative, falling back to bisection if an iterate leaves the bracket
+pub(crate) fn newton_root<F: Fn(f64) -> f64>(f: F, a: f64, b: f64, tolerance: f64) -> f64 {
+    let h = (b - a) * 1e-4;
+    let mut t = 0.5 * (a + b);
+    for _ in 0..30 {
+        let value = f(t);
+        let slope = (f(t + h) - f(t - h)) / (2.0 * h);
+        if slope == 0.0 {
+            break;
+        }
+        let next = t - value / slope;
+        if next < a || next > b {
+            break;
+        }
+        if (next - t).abs() < tolerance {
+            return next;
+        }
+        t = next;
+    }
+    bisect(f, a, b, tolerance)
+}
+
+// Refine an extremum of f bracketed in [a, b] by Newton iteration on its derivative
+pub(crate) fn newton_extremum<F: Fn(f64) -> f64>(f: F, a: f64, b: f64, tolerance: f64) -> f64 {
+    let h = (b - a) * 1e-3;
+    newton_root(|t| (f(t + h) - f(t - h)) / (2.0 * h), a, b, tolerance)
+}
@@ -0,0 +1,90 @@
+use wasm_bindgen::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::calendar::calendar_to_julian_date;
+use crate::errors::check_julian_date;
+use crate::observer::geocentric_ecliptic;
+use crate::search::newton_root;
+
+// Equinox and solstice instants of a year
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[wasm_bindgen]
+pub struct Seasons {
+    march_equinox: f64,
+    june_solstice: f64,
+    september_equinox: f64,
+    december_solstice: f64,
+}
+
+#[wasm_bindgen]
+impl Seasons {
+    #[wasm_bindgen(getter)]
+    pub fn march_equinox(&self) -> f64 {
+        self.march_equinox
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn june_solstice(&self) -> f64 {
+        self.june_solstice
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn september_equinox(&self) -> f64 {
+        self.september_equinox
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn december_solstice(&self) -> f64 {
+        self.december_solstice
+    }
+}
+
+// Apparent ecliptic longitude of the Sun (degrees) referred to the equinox of
+// date: precession in longitude from J2000.0 plus annual aberration
+pub(crate) fn apparent_solar_longitude(julian_date: f64) -> f64 {
+    let sun = match geocentric_ecliptic("Sun", julian_date) {
+        Some(sun) => sun,
+        None => return 0.0,
+    };
+    let t = (julian_date - 2451545.0) / 36525.0;
+    let geometric = sun.y.atan2(sun.x).to_degrees();
+    let precession = 1.396971 * t + 0.0003086 * t * t;
+    let aberration = -0.005691 / sun.length();
+    (geometric + precession + aberration).rem_euclid(360.0)
+}
+
+// First instant in the year starting at jd_start when the Sun reaches a longitude
+fn solar_longitude_crossing(jd_start: f64, target: f64) -> f64 {
+    let offset = |jd: f64| {
+        let difference = (apparent_solar_longitude(jd) - target).rem_euclid(360.0);
+        if difference > 180.0 { difference - 360.0 } else { difference }
+    };
+    let mut t0 = jd_start;
+    let mut f0 = offset(t0);
+    for _ in 0..370 {
+        let t1 = t0 + 1.0;
+        let f1 = offset(t1);
+        if f0 < 0.0 && f1 >= 0.0 {
+            return newton_root(offset, t0, t1, 1e-6);
+        }
+        t0 = t1;
+        f0 = f1;
+    }
+    jd_start
+}
+
+// Calculate the equinoxes and solstices of a Gregorian year as Julian dates;
+// fails for years outside the planet elements' range
+#[wasm_bindgen]
+pub fn seasons(year: i32) -> Result<Seasons, JsError> {
+    let jd_start = calendar_to_julian_date(year, 1, 1.0);
+    check_julian_date(jd_start)?;
+    check_julian_date(calendar_to_julian_date(year + 1, 1, 1.0))?;
+
+    Ok(Seasons {
+        march_equinox: solar_longitude_crossing(jd_start, 0.0),
+        june_solstice: solar_longitude_crossing(jd_start, 90.0),
+        september_equinox: solar_longitude_crossing(jd_start, 180.0),
+        december_solstice: solar_longitude_crossing(jd_start, 270.0),
+    })
+}
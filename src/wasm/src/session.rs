@@ -0,0 +1,402 @@
+// Recording and deterministic replay of simulation sessions: every clock
+// change and camera-target switch is logged to a compact binary stream that
+// reproduces the session frame for frame, small enough to share as a "tour"
+
+use wasm_bindgen::prelude::*;
+
+use crate::clock::SimulationClock;
+use crate::errors::check_julian_date;
+
+// File signature, including the format version
+const MAGIC: &[u8; 4] = b"SSR2";
+
+const TICK: u8 = 0;
+const RATE: u8 = 1;
+const PAUSE: u8 = 2;
+const RESUME: u8 = 3;
+const STEP: u8 = 4;
+const SEEK: u8 = 5;
+const FRAME_STEP: u8 = 6;
+const TARGET: u8 = 7;
+
+// Longest camera target name stored, in bytes
+const MAX_TARGET_LENGTH: usize = 255;
+
+#[derive(Debug, Clone, PartialEq)]
+enum Event {
+    // A run of frames with the same real frame time (milliseconds)
+    Ticks { real_dt_ms: f32, count: u16 },
+    Rate(f64),
+    Pause,
+    Resume,
+    Step(i32),
+    Seek(f64),
+    FrameStep(f64),
+    Target(String),
+}
+
+// Initial clock state written after the signature. The epoch is kept as the
+// clock's whole days and fraction rather than their sum, so replay starts
+// from bit-identical state.
+#[derive(Debug, Clone, Copy)]
+struct Header {
+    day: f64,
+    fraction: f64,
+    rate: f64,
+    frame_step: f64,
+    paused: bool,
+}
+
+impl Header {
+    fn of(clock: &SimulationClock) -> Header {
+        let (day, fraction) = clock.parts();
+        Header {
+            day,
+            fraction,
+            rate: clock.rate(),
+            frame_step: clock.frame_step(),
+            paused: clock.paused(),
+        }
+    }
+
+    fn clock(&self) -> SimulationClock {
+        let mut clock = SimulationClock::from_parts(self.day, self.fraction);
+        clock.set_rate(self.rate);
+        clock.set_frame_step(self.frame_step);
+        clock.set_paused(self.paused);
+        clock
+    }
+}
+
+fn encode(header: &Header, events: &[Event]) -> Vec<u8> {
+    let mut bytes = MAGIC.to_vec();
+    bytes.extend_from_slice(&header.day.to_le_bytes());
+    bytes.extend_from_slice(&header.fraction.to_le_bytes());
+    bytes.extend_from_slice(&header.rate.to_le_bytes());
+    bytes.extend_from_slice(&header.frame_step.to_le_bytes());
+    bytes.push(header.paused as u8);
+    for event in events {
+        match event {
+            Event::Ticks { real_dt_ms, count } => {
+                bytes.push(TICK);
+                bytes.extend_from_slice(&real_dt_ms.to_le_bytes());
+                bytes.extend_from_slice(&count.to_le_bytes());
+            }
+            Event::Rate(rate) => {
+                bytes.push(RATE);
+                bytes.extend_from_slice(&rate.to_le_bytes());
+            }
+            Event::Pause => bytes.push(PAUSE),
+            Event::Resume => bytes.push(RESUME),
+            Event::Step(frames) => {
+                bytes.push(STEP);
+                bytes.extend_from_slice(&frames.to_le_bytes());
+            }
+            Event::Seek(julian_date) => {
+                bytes.push(SEEK);
+                bytes.extend_from_slice(&julian_date.to_le_bytes());
+            }
+            Event::FrameStep(days) => {
+                bytes.push(FRAME_STEP);
+                bytes.extend_from_slice(&days.to_le_bytes());
+            }
+            Event::Target(name) => {
+                bytes.push(TARGET);
+                bytes.push(name.len() as u8);
+                bytes.extend_from_slice(name.as_bytes());
+            }
+        }
+    }
+    bytes
+}
+
+// Sequential reader over a little-endian binary stream
+pub(crate) struct Reader<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    pub(crate) fn new(bytes: &'a [u8]) -> Reader<'a> {
+        Reader { bytes }
+    }
+
+    // Bytes not yet read
+    pub(crate) fn remaining(&self) -> usize {
+        self.bytes.len()
+    }
+
+    pub(crate) fn take<const N: usize>(&mut self) -> Option<[u8; N]> {
+        let (head, rest) = self.bytes.split_first_chunk::<N>()?;
+        self.bytes = rest;
+        Some(*head)
+    }
+
+    pub(crate) fn f64(&mut self) -> Option<f64> {
+        self.take().map(f64::from_le_bytes)
+    }
+}
+
+fn decode(bytes: &[u8]) -> Option<(Header, Vec<Event>)> {
+    let mut reader = Reader::new(bytes);
+    if &reader.take::<4>()? != MAGIC {
+        return None;
+    }
+    let header = Header {
+        day: reader.f64()?,
+        fraction: reader.f64()?,
+        rate: reader.f64()?,
+        frame_step: reader.f64()?,
+        paused: reader.take::<1>()?[0] != 0,
+    };
+    let whole_day = header.day.fract() == 0.0 && (0.0..1.0).contains(&header.fraction);
+    if !whole_day || check_julian_date(header.day + header.fraction).is_err() {
+        return None;
+    }
+    // The recorder only logs finite clock settings and non-empty tick runs
+    if !header.rate.is_finite() || !header.frame_step.is_finite() {
+        return None;
+    }
+    let mut events = Vec::new();
+    while let Some([tag]) = reader.take::<1>() {
+        let event = match tag {
+            TICK => Event::Ticks {
+                real_dt_ms: f32::from_le_bytes(reader.take()?),
+                count: Some(u16::from_le_bytes(reader.take()?)).filter(|&count| count > 0)?,
+            },
+            RATE => Event::Rate(reader.f64().filter(|rate| rate.is_finite())?),
+            PAUSE => Event::Pause,
+            RESUME => Event::Resume,
+            STEP => Event::Step(i32::from_le_bytes(reader.take()?)),
+            SEEK => Event::Seek(reader.f64().filter(|&julian_date| check_julian_date(julian_date).is_ok())?),
+            FRAME_STEP => Event::FrameStep(reader.f64().filter(|days| days.is_finite())?),
+            TARGET => {
+                let [length] = reader.take::<1>()?;
+                let (name, rest) = reader.bytes.split_at_checked(length as usize)?;
+                reader.bytes = rest;
+                Event::Target(String::from_utf8(name.to_vec()).ok()?)
+            }
+            _ => return None,
+        };
+        events.push(event);
+    }
+    Some((header, events))
+}
+
+// Records a session while driving its own clock. Use it in place of a
+// SimulationClock: each call is applied and logged, and to_bytes() produces
+// the shareable log.
+#[wasm_bindgen]
+pub struct SessionRecorder {
+    header: Header,
+    clock: SimulationClock,
+    events: Vec<Event>,
+    camera_target: Option<String>,
+    frame_count: u32,
+}
+
+#[wasm_bindgen]
+impl SessionRecorder {
+    // Start recording from a clock's current state
+    #[wasm_bindgen(constructor)]
+    pub fn new(clock: &SimulationClock) -> SessionRecorder {
+        SessionRecorder {
+            header: Header::of(clock),
+            clock: clock.clone(),
+            events: Vec::new(),
+            camera_target: None,
+            frame_count: 0,
+        }
+    }
+
+    // Advance one frame as SimulationClock::tick and return the new Julian date.
+    // The frame time is stored at single precision, and the clock advances by
+    // that stored value so replay matches exactly.
+    pub fn tick(&mut self, real_dt_ms: f64) -> f64 {
+        let real_dt_ms = real_dt_ms as f32;
+        match self.events.last_mut() {
+            Some(Event::Ticks { real_dt_ms: last, count }) if *last == real_dt_ms && *count < u16::MAX => *count += 1,
+            _ => self.events.push(Event::Ticks { real_dt_ms, count: 1 }),
+        }
+        self.frame_count += 1;
+        self.clock.tick(real_dt_ms as f64)
+    }
+
+    pub fn set_rate(&mut self, rate: f64) {
+        self.clock.set_rate(rate);
+        self.events.push(Event::Rate(self.clock.rate()));
+    }
+
+    pub fn pause(&mut self) {
+        self.clock.pause();
+        self.events.push(Event::Pause);
+    }
+
+    pub fn resume(&mut self) {
+        self.clock.resume();
+        self.events.push(Event::Resume);
+    }
+
+    pub fn step(&mut self, frames: i32) -> f64 {
+        self.events.push(Event::Step(frames));
+        self.clock.step(frames)
+    }
+
+    pub fn seek(&mut self, julian_date: f64) -> Result<(), JsError> {
+        self.clock.set_julian_date(julian_date)?;
+        self.events.push(Event::Seek(julian_date));
+        Ok(())
+    }
+
+    pub fn set_frame_step(&mut self, days: f64) {
+        self.clock.set_frame_step(days);
+        self.events.push(Event::FrameStep(self.clock.frame_step()));
+    }
+
+    // Switch the body the camera follows; names are truncated to 255 bytes
+    pub fn set_camera_target(&mut self, name: &str) {
+        let mut end = name.len().min(MAX_TARGET_LENGTH);
+        while !name.is_char_boundary(end) {
+            end -= 1;
+        }
+        let name = name[..end].to_string();
+        self.camera_target = Some(name.clone());
+        self.events.push(Event::Target(name));
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn julian_date(&self) -> f64 {
+        self.clock.julian_date()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn camera_target(&self) -> Option<String> {
+        self.camera_target.clone()
+    }
+
+    // Frames recorded so far
+    #[wasm_bindgen(getter)]
+    pub fn frame_count(&self) -> u32 {
+        self.frame_count
+    }
+
+    // A copy of the recorded clock's current state
+    #[wasm_bindgen(getter)]
+    pub fn clock(&self) -> SimulationClock {
+        self.clock.clone()
+    }
+
+    // The session log, to save or share
+    pub fn to_bytes(&self) -> Vec<u8> {
+        encode(&self.header, &self.events)
+    }
+}
+
+// Replays a recorded session one frame at a time
+#[wasm_bindgen]
+pub struct SessionPlayer {
+    header: Header,
+    events: Vec<Event>,
+    clock: SimulationClock,
+    camera_target: Option<String>,
+    // Next event and frames already played from it, if it is a run of ticks
+    cursor: usize,
+    ticks_played: u16,
+    frame: u32,
+    frame_count: u32,
+}
+
+#[wasm_bindgen]
+impl SessionPlayer {
+    // Load a session log; None if the bytes aren't a valid log
+    pub fn from_bytes(bytes: &[u8]) -> Option<SessionPlayer> {
+        let (header, events) = decode(bytes)?;
+        let frame_count = events
+            .iter()
+            .map(|event| match event {
+                Event::Ticks { count, .. } => *count as u32,
+                _ => 0,
+            })
+            .sum();
+        Some(SessionPlayer {
+            clock: header.clock(),
+            header,
+            events,
+            camera_target: None,
+            cursor: 0,
+            ticks_played: 0,
+            frame: 0,
+            frame_count,
+        })
+    }
+
+    // Apply the events of the next recorded frame and return its Julian date,
+    // or None once the session has ended
+    pub fn next_frame(&mut self) -> Option<f64> {
+        while let Some(event) = self.events.get(self.cursor) {
+            match event {
+                Event::Ticks { real_dt_ms, count } => {
+                    let real_dt_ms = *real_dt_ms as f64;
+                    self.ticks_played += 1;
+                    if self.ticks_played == *count {
+                        self.cursor += 1;
+                        self.ticks_played = 0;
+                    }
+                    self.frame += 1;
+                    return Some(self.clock.tick(real_dt_ms));
+                }
+                Event::Rate(rate) => self.clock.set_rate(*rate),
+                Event::Pause => self.clock.pause(),
+                Event::Resume => self.clock.resume(),
+                Event::Step(frames) => {
+                    self.clock.step(*frames);
+                }
+                Event::Seek(julian_date) => self.clock.jump_to(*julian_date),
+                Event::FrameStep(days) => self.clock.set_frame_step(*days),
+                Event::Target(name) => self.camera_target = Some(name.clone()),
+            }
+            self.cursor += 1;
+        }
+        None
+    }
+
+    // Restart from the beginning of the session
+    pub fn rewind(&mut self) {
+        self.clock = self.header.clock();
+        self.camera_target = None;
+        self.cursor = 0;
+        self.ticks_played = 0;
+        self.frame = 0;
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn julian_date(&self) -> f64 {
+        self.clock.julian_date()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn camera_target(&self) -> Option<String> {
+        self.camera_target.clone()
+    }
+
+    // Frames played so far
+    #[wasm_bindgen(getter)]
+    pub fn frame(&self) -> u32 {
+        self.frame
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn frame_count(&self) -> u32 {
+        self.frame_count
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn finished(&self) -> bool {
+        self.cursor >= self.events.len()
+    }
+
+    // A copy of the replayed clock's current state
+    #[wasm_bindgen(getter)]
+    pub fn clock(&self) -> SimulationClock {
+        self.clock.clone()
+    }
+}
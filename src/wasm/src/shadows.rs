@@ -0,0 +1,98 @@
+// Umbra and penumbra cones cast by each planet, for drawing eclipse shadows
+// on moons passing behind it. Lengths are in the planet's equatorial radii,
+// the unit of moon_positions, so they scale with the planet's mesh.
+
+use wasm_bindgen::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::errors::check_julian_date;
+use crate::observer::planet_state;
+use crate::scene::scene_axes;
+use crate::units::{EARTH_RADIUS_KM, SOLAR_RADIUS_KM};
+use crate::{Vec3, AU_KM, PLANET_DATA, PLANET_ELEMENTS};
+
+// The shadow behind a planet
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[wasm_bindgen]
+pub struct ShadowCone {
+    name: String,
+    direction: Vec3,
+    umbra_length: f64,
+    umbra_angle: f64,
+    penumbra_angle: f64,
+    sun_distance: f64,
+}
+
+#[wasm_bindgen]
+impl ShadowCone {
+    #[wasm_bindgen(getter)]
+    pub fn name(&self) -> String {
+        self.name.clone()
+    }
+
+    // Unit axis pointing away from the Sun (scene axes)
+    #[wasm_bindgen(getter)]
+    pub fn direction(&self) -> Vec3 {
+        self.direction
+    }
+
+    // Distance from the planet's center to the umbra's tip (planet radii)
+    #[wasm_bindgen(getter)]
+    pub fn umbra_length(&self) -> f64 {
+        self.umbra_length
+    }
+
+    // Half-angle of the converging umbra (degrees)
+    #[wasm_bindgen(getter)]
+    pub fn umbra_angle(&self) -> f64 {
+        self.umbra_angle
+    }
+
+    // Half-angle of the diverging penumbra (degrees)
+    #[wasm_bindgen(getter)]
+    pub fn penumbra_angle(&self) -> f64 {
+        self.penumbra_angle
+    }
+
+    // Distance from the Sun (planet radii)
+    #[wasm_bindgen(getter)]
+    pub fn sun_distance(&self) -> f64 {
+        self.sun_distance
+    }
+
+    // Radius of the umbra at a distance behind the planet's center (planet
+    // radii), 0 beyond its tip
+    pub fn umbra_radius(&self, distance: f64) -> f64 {
+        (1.0 - distance / self.umbra_length).max(0.0)
+    }
+
+    // Radius of the penumbra at a distance behind the planet's center
+    pub fn penumbra_radius(&self, distance: f64) -> f64 {
+        // The penumbra widens by (sun radius + 1) / sun_distance per unit
+        // length, where the umbra narrows by (sun radius - 1) / sun_distance
+        1.0 + distance * (1.0 / self.umbra_length + 2.0 / self.sun_distance)
+    }
+}
+
+// Shadow cone of every planet at a Julian date, in PLANET_ELEMENTS order
+#[wasm_bindgen]
+pub fn shadow_cones(julian_date: f64) -> Result<Vec<ShadowCone>, JsError> {
+    check_julian_date(julian_date)?;
+    Ok(PLANET_ELEMENTS
+        .iter()
+        .enumerate()
+        .map(|(index, (name, _))| {
+            let position = planet_state(index, julian_date).position;
+            let radius = PLANET_DATA[index].1 * EARTH_RADIUS_KM;
+            let distance = position.length() * AU_KM;
+            ShadowCone {
+                name: name.to_string(),
+                direction: scene_axes(&position.normalize()),
+                umbra_length: distance / (SOLAR_RADIUS_KM - radius),
+                umbra_angle: ((SOLAR_RADIUS_KM - radius) / distance).asin().to_degrees(),
+                penumbra_angle: ((SOLAR_RADIUS_KM + radius) / distance).asin().to_degrees(),
+                sun_distance: distance / radius,
+            }
+        })
+        .collect())
+}
@@ -0,0 +1,75 @@
+// Compact binary frames of a SolarSystem, so the simulation can run in a Web
+// Worker and post each frame to the main thread as a transferable buffer
+// instead of structured-cloning objects.
+//
+// Layout, little-endian, 24-byte header keeping the states 8-byte aligned so
+// JS can also read them in place with new Float64Array(buffer, 24):
+//   0  signature "SSF1", including the format version
+//   4  precision, reference frame (u8 each), two reserved bytes
+//   8  body count (u32)
+//  12  revision (u32)
+//  16  Julian date (f64)
+//  24  per body: position x, y, z (AU) then velocity x, y, z (AU/day), f64
+
+use crate::session::Reader;
+use crate::state::StateVector;
+use crate::{Precision, ReferenceFrame, Vec3};
+
+const MAGIC: &[u8; 4] = b"SSF1";
+
+// One SolarSystem frame: frame-relative ecliptic states in buffer order
+#[derive(Debug, Clone)]
+pub(crate) struct Frame {
+    pub(crate) julian_date: f64,
+    pub(crate) precision: Precision,
+    pub(crate) frame: ReferenceFrame,
+    pub(crate) revision: u32,
+    pub(crate) states: Vec<StateVector>,
+}
+
+pub(crate) fn encode(frame: &Frame) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(24 + frame.states.len() * 48);
+    bytes.extend_from_slice(MAGIC);
+    bytes.extend_from_slice(&[frame.precision as u8, frame.frame as u8, 0, 0]);
+    bytes.extend_from_slice(&(frame.states.len() as u32).to_le_bytes());
+    bytes.extend_from_slice(&frame.revision.to_le_bytes());
+    bytes.extend_from_slice(&frame.julian_date.to_le_bytes());
+    for state in &frame.states {
+        for value in [state.position, state.velocity].iter().flat_map(|v| [v.x, v.y, v.z]) {
+            bytes.extend_from_slice(&value.to_le_bytes());
+        }
+    }
+    bytes
+}
+
+pub(crate) fn decode(bytes: &[u8]) -> Option<Frame> {
+    let mut reader = Reader::new(bytes);
+    if &reader.take::<4>()? != MAGIC {
+        return None;
+    }
+    let [precision, frame, _, _] = reader.take::<4>()?;
+    let precision = match precision {
+        0 => Precision::Standard,
+        1 => Precision::High,
+        _ => return None,
+    };
+    let frame = match frame {
+        0 => ReferenceFrame::Heliocentric,
+        1 => ReferenceFrame::Barycentric,
+        _ => return None,
+    };
+    let count = u32::from_le_bytes(reader.take()?) as usize;
+    let revision = u32::from_le_bytes(reader.take()?);
+    let julian_date = reader.f64()?;
+    // A count whose states can't even be sized is as corrupt as a short buffer
+    if count.checked_mul(48) != Some(reader.remaining()) {
+        return None;
+    }
+    let mut states = Vec::with_capacity(count);
+    for _ in 0..count {
+        let mut vector = || Some(Vec3::new(reader.f64()?, reader.f64()?, reader.f64()?));
+        let position = vector()?;
+        states.push(StateVector::new(position, vector()?));
+    }
+    Some(Frame { julian_date, precision, frame, revision, states })
+}
@@ -0,0 +1,456 @@
+// Stateful simulation handle: owns persistent per-body buffers that update in
+// place each frame, so the renderer reads views instead of freeing a fresh
+// PlanetData handle per body per frame
+
+use wasm_bindgen::prelude::*;
+use js_sys::Float64Array;
+
+use crate::chebyshev::StateCache;
+use crate::culling::{CameraView, VisibleSet};
+use crate::errors::{check_julian_date, SimulationError};
+use crate::labels::LabelLayout;
+use crate::math::Mat4;
+use crate::picking::pick_spheres;
+use crate::scene::{active_config, scene_radius, scene_velocity, to_scene, SceneConfig};
+use crate::snapshot;
+use crate::spatial::RayHit;
+use crate::state::{elements_to_state, StateVector};
+use crate::thermal;
+use crate::{
+    frame_origin, planet_data_from_state, planet_state_with_precision, PhysicalData, PlanetData, Precision,
+    ReferenceFrame, Vec3, DWARF_PLANET_DATA, DWARF_PLANET_ELEMENTS, PLANET_DATA, PLANET_ELEMENTS,
+};
+
+// Interpolation windows: a fraction of each body's orbital period, and a fixed
+// span for the barycenter, whose fastest wobble follows Mercury
+const WINDOWS_PER_ORBIT: f64 = 32.0;
+const BARYCENTER_WINDOW: f64 = 16.0;
+
+// Epochs closer than this (days, about a millisecond) count as unchanged
+const DEFAULT_EPOCH_TOLERANCE: f64 = 1e-8;
+
+// Starting epoch, ephemeris precision and reference frame of a simulation
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy)]
+pub struct SimulationConfig {
+    julian_date: f64,
+    precision: Precision,
+    frame: ReferenceFrame,
+    interpolation: bool,
+}
+
+impl Default for SimulationConfig {
+    // J2000.0, standard precision, heliocentric, interpolated
+    fn default() -> SimulationConfig {
+        SimulationConfig {
+            julian_date: 2451545.0,
+            precision: Precision::Standard,
+            frame: ReferenceFrame::Heliocentric,
+            interpolation: true,
+        }
+    }
+}
+
+#[wasm_bindgen]
+impl SimulationConfig {
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> SimulationConfig {
+        SimulationConfig::default()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn julian_date(&self) -> f64 {
+        self.julian_date
+    }
+
+    #[wasm_bindgen(setter)]
+    pub fn set_julian_date(&mut self, julian_date: f64) -> Result<(), JsError> {
+        check_julian_date(julian_date)?;
+        self.julian_date = julian_date;
+        Ok(())
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn precision(&self) -> Precision {
+        self.precision
+    }
+
+    #[wasm_bindgen(setter)]
+    pub fn set_precision(&mut self, precision: Precision) {
+        self.precision = precision;
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn frame(&self) -> ReferenceFrame {
+        self.frame
+    }
+
+    #[wasm_bindgen(setter)]
+    pub fn set_frame(&mut self, frame: ReferenceFrame) {
+        self.frame = frame;
+    }
+
+    // Evaluate states from cached Chebyshev fits rather than solving Kepler's
+    // equation for every body every frame; agrees with the direct solution to
+    // well under a kilometre
+    #[wasm_bindgen(getter)]
+    pub fn interpolation(&self) -> bool {
+        self.interpolation
+    }
+
+    #[wasm_bindgen(setter)]
+    pub fn set_interpolation(&mut self, interpolation: bool) {
+        self.interpolation = interpolation;
+    }
+}
+
+// A running simulation of the planets followed by the dwarf planets, in the
+// order of planet_positions and dwarf_planet_positions, caching each epoch's
+// states between frames
+#[wasm_bindgen]
+pub struct SolarSystem {
+    julian_date: f64,
+    precision: Precision,
+    frame: ReferenceFrame,
+    interpolation: bool,
+    epoch_tolerance: f64,
+    // Epoch and scene configuration the buffers were last computed for, and a
+    // counter bumped on every recomputation
+    computed: Option<(f64, SceneConfig)>,
+    revision: u32,
+    // Interpolation slots: one per body, then the barycenter
+    cache: StateCache,
+    // Frame-relative ecliptic states (AU, AU/day) at julian_date
+    states: Vec<StateVector>,
+    positions: Vec<f64>,
+    velocities: Vec<f64>,
+}
+
+#[wasm_bindgen]
+impl SolarSystem {
+    #[wasm_bindgen(constructor)]
+    pub fn new(config: &SimulationConfig) -> Result<SolarSystem, JsError> {
+        check_julian_date(config.julian_date)?;
+        let count = PLANET_ELEMENTS.len() + DWARF_PLANET_ELEMENTS.len();
+        let origin = StateVector::new(Vec3::new(0.0, 0.0, 0.0), Vec3::new(0.0, 0.0, 0.0));
+        let windows = (0..count)
+            .map(|index| physical_data(index).6 / WINDOWS_PER_ORBIT)
+            .chain([BARYCENTER_WINDOW])
+            .collect();
+        let mut system = SolarSystem {
+            julian_date: config.julian_date,
+            precision: config.precision,
+            frame: config.frame,
+            interpolation: config.interpolation,
+            epoch_tolerance: DEFAULT_EPOCH_TOLERANCE,
+            computed: None,
+            revision: 0,
+            cache: StateCache::new(windows),
+            states: vec![origin; count],
+            positions: vec![0.0; count * 3],
+            velocities: vec![0.0; count * 3],
+        };
+        system.recompute();
+        Ok(system)
+    }
+
+    // Move to a Julian date, recomputing every body into the buffers in place.
+    // Returns false when nothing changed — the epoch is within the tolerance of
+    // the last computed one and the scene configuration is the same — so the
+    // renderer can skip its buffer uploads while paused. The epoch is stored
+    // either way, so small steps accumulate rather than being dropped.
+    pub fn set_time(&mut self, julian_date: f64) -> Result<bool, JsError> {
+        check_julian_date(julian_date)?;
+        self.julian_date = julian_date;
+        let unchanged = self.computed.is_some_and(|(epoch, scene)| {
+            (julian_date - epoch).abs() <= self.epoch_tolerance && scene == active_config()
+        });
+        if unchanged {
+            return Ok(false);
+        }
+        self.recompute();
+        Ok(true)
+    }
+
+    // Same as set_time, for callers driving the buffers frame by frame
+    pub fn update(&mut self, julian_date: f64) -> Result<bool, JsError> {
+        self.set_time(julian_date)
+    }
+
+    // Step the epoch forward (or back, for negative dt) by dt days
+    pub fn advance(&mut self, dt_days: f64) -> Result<bool, JsError> {
+        self.set_time(self.julian_date + dt_days)
+    }
+
+    // A body at the current epoch, from the cached states
+    pub fn get_body(&self, name: &str) -> Result<PlanetData, JsError> {
+        let index = body_names()
+            .position(|body| body == name)
+            .ok_or_else(|| SimulationError::unknown_body(name))?;
+        Ok(self.body_data(index))
+    }
+
+    // Solar irradiance (W/m²) at a body at the current epoch, from its
+    // heliocentric distance whatever the frame
+    pub fn solar_flux_at(&self, name: &str) -> Result<f64, JsError> {
+        let index = body_names()
+            .position(|body| body == name)
+            .ok_or_else(|| SimulationError::unknown_body(name))?;
+        let distance = body_state(index, self.julian_date, self.precision).position.length();
+        Ok(thermal::solar_flux(distance))
+    }
+
+    // Every body at the current epoch as plain JavaScript objects, with no
+    // handles to free
+    pub fn snapshot(&self) -> Result<JsValue, JsError> {
+        let bodies: Vec<PlanetData> = (0..self.body_count()).map(|index| self.body_data(index)).collect();
+        Ok(serde_wasm_bindgen::to_value(&bodies)?)
+    }
+
+    // The current frame as a compact binary snapshot (see snapshot.rs for the
+    // layout). A worker posts its buffer to the main thread as a transferable.
+    pub fn encode_state(&self) -> Vec<u8> {
+        snapshot::encode(&snapshot::Frame {
+            julian_date: self.julian_date,
+            precision: self.precision,
+            frame: self.frame,
+            revision: self.revision,
+            states: self.states.clone(),
+        })
+    }
+
+    // Adopt a frame produced by encode_state in another instance, typically a
+    // worker's, without computing anything: the buffers, get_body and snapshot
+    // then reflect it, mapped through this thread's scene configuration
+    pub fn decode_state(&mut self, bytes: &[u8]) -> Result<(), JsError> {
+        let frame = snapshot::decode(bytes)
+            .ok_or_else(|| SimulationError::invalid_argument("Malformed state snapshot"))?;
+        if frame.states.len() != self.body_count() {
+            return Err(SimulationError::invalid_argument(&format!(
+                "Snapshot holds {} bodies, expected {}",
+                frame.states.len(),
+                self.body_count()
+            ))
+            .into());
+        }
+        if frame.precision != self.precision {
+            self.cache.clear();
+        }
+        self.julian_date = frame.julian_date;
+        self.precision = frame.precision;
+        self.frame = frame.frame;
+        for (index, state) in frame.states.into_iter().enumerate() {
+            self.write_state(index, state);
+        }
+        self.computed = Some((self.julian_date, active_config()));
+        self.revision = frame.revision;
+        Ok(())
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn julian_date(&self) -> f64 {
+        self.julian_date
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn precision(&self) -> Precision {
+        self.precision
+    }
+
+    #[wasm_bindgen(setter)]
+    pub fn set_precision(&mut self, precision: Precision) {
+        self.precision = precision;
+        self.cache.clear();
+        self.recompute();
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn frame(&self) -> ReferenceFrame {
+        self.frame
+    }
+
+    #[wasm_bindgen(setter)]
+    pub fn set_frame(&mut self, frame: ReferenceFrame) {
+        self.frame = frame;
+        self.recompute();
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn interpolation(&self) -> bool {
+        self.interpolation
+    }
+
+    #[wasm_bindgen(setter)]
+    pub fn set_interpolation(&mut self, interpolation: bool) {
+        self.interpolation = interpolation;
+        self.recompute();
+    }
+
+    // Largest epoch change (days) treated as unchanged by set_time
+    #[wasm_bindgen(getter)]
+    pub fn epoch_tolerance(&self) -> f64 {
+        self.epoch_tolerance
+    }
+
+    #[wasm_bindgen(setter)]
+    pub fn set_epoch_tolerance(&mut self, epoch_tolerance: f64) {
+        self.epoch_tolerance = epoch_tolerance.max(0.0);
+    }
+
+    // Incremented whenever the buffers are rewritten, for renderers that poll
+    #[wasm_bindgen(getter)]
+    pub fn revision(&self) -> u32 {
+        self.revision
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn body_count(&self) -> usize {
+        self.positions.len() / 3
+    }
+
+    // Body names in buffer order, fetched once at startup
+    pub fn body_names(&self) -> Vec<String> {
+        body_names().map(str::to_string).collect()
+    }
+
+    // Scene positions packed [x, y, z, ...] as a view into WASM memory. The
+    // view follows later updates but is invalidated if WASM memory grows, so
+    // take a fresh one each frame rather than keeping it.
+    pub fn positions(&self) -> Float64Array {
+        // Safety: the view is handed straight to JS, and no Rust allocation
+        // can happen before the caller reads it
+        unsafe { Float64Array::view(&self.positions) }
+    }
+
+    // Bodies the camera can see at their display radii, with their indices
+    // in buffer order
+    pub fn visible_bodies(&self, camera: &CameraView) -> VisibleSet {
+        let spheres = self.positions.chunks_exact(3).enumerate().map(|(index, p)| {
+            (Vec3::new(p[0], p[1], p[2]), scene_radius(physical_data(index).1))
+        });
+        VisibleSet::cull(camera, spheres)
+    }
+
+    // The body under the cursor: ray_origin and ray_direction in scene
+    // coordinates (from a Three.js Raycaster), with every body treated as at
+    // least pixel_tolerance pixels in radius. True hits on the display
+    // spheres win, nearest first; otherwise the body passing closest on
+    // screen. The hit index is in buffer order.
+    pub fn pick(
+        &self,
+        ray_origin: &Vec3,
+        ray_direction: &Vec3,
+        pixel_tolerance: f64,
+        camera: &CameraView,
+    ) -> Option<RayHit> {
+        let spheres = self.positions.chunks_exact(3).enumerate().map(|(index, p)| {
+            (Vec3::new(p[0], p[1], p[2]), scene_radius(physical_data(index).1))
+        });
+        pick_spheres(spheres, ray_origin, ray_direction, pixel_tolerance, camera)
+    }
+
+    // Screen positions, depths and occlusion of every body's label, projected
+    // with the renderer's view-projection matrix (camera.projectionMatrix ×
+    // camera.matrixWorldInverse) from the camera position. sun_radius is the
+    // Sun's display radius in scene units, which the renderer chooses.
+    pub fn project_labels(
+        &self,
+        view_projection: &Mat4,
+        camera_position: &Vec3,
+        viewport_width: f64,
+        viewport_height: f64,
+        sun_radius: f64,
+    ) -> LabelLayout {
+        let bodies: Vec<(Vec3, f64)> = self
+            .positions
+            .chunks_exact(3)
+            .enumerate()
+            .map(|(index, p)| (Vec3::new(p[0], p[1], p[2]), scene_radius(physical_data(index).1)))
+            .collect();
+        let sun = to_scene(frame_origin(self.julian_date, self.precision, self.frame).position.scale(-1.0));
+        LabelLayout::project(
+            &bodies,
+            &[(sun, sun_radius)],
+            view_projection,
+            camera_position,
+            viewport_width,
+            viewport_height,
+        )
+    }
+
+    // Scene velocities (scene units per day), packed like positions
+    pub fn velocities(&self) -> Float64Array {
+        // Safety: as for positions
+        unsafe { Float64Array::view(&self.velocities) }
+    }
+}
+
+pub(crate) fn body_names() -> impl Iterator<Item = &'static str> {
+    PLANET_ELEMENTS.iter().chain(DWARF_PLANET_ELEMENTS.iter()).map(|(name, _)| *name)
+}
+
+// Heliocentric state of a body by buffer index
+fn body_state(index: usize, julian_date: f64, precision: Precision) -> StateVector {
+    match index.checked_sub(PLANET_ELEMENTS.len()) {
+        None => planet_state_with_precision(index, julian_date, precision),
+        Some(dwarf) => elements_to_state(&DWARF_PLANET_ELEMENTS[dwarf].1, julian_date),
+    }
+}
+
+pub(crate) fn physical_data(index: usize) -> &'static PhysicalData {
+    match index.checked_sub(PLANET_ELEMENTS.len()) {
+        None => &PLANET_DATA[index],
+        Some(dwarf) => &DWARF_PLANET_DATA[dwarf],
+    }
+}
+
+// Internal update helpers
+impl SolarSystem {
+    // Packed scene positions, as exposed by positions()
+    pub(crate) fn scene_positions(&self) -> &[f64] {
+        &self.positions
+    }
+
+    fn body_data(&self, index: usize) -> PlanetData {
+        let data = physical_data(index);
+        planet_data_from_state(data.0, &self.states[index], data)
+    }
+
+    // Exact or interpolated state of a cache slot
+    fn slot_state(&mut self, slot: usize, exact: impl Fn(f64) -> StateVector) -> StateVector {
+        if self.interpolation {
+            self.cache.state(slot, self.julian_date, exact)
+        } else {
+            exact(self.julian_date)
+        }
+    }
+
+    fn recompute(&mut self) {
+        let (precision, frame, count) = (self.precision, self.frame, self.body_count());
+        let origin = match frame {
+            ReferenceFrame::Heliocentric => frame_origin(self.julian_date, precision, frame),
+            ReferenceFrame::Barycentric => {
+                self.slot_state(count, |julian_date| frame_origin(julian_date, precision, frame))
+            }
+        };
+        for index in 0..count {
+            let heliocentric = self.slot_state(index, |julian_date| body_state(index, julian_date, precision));
+            let state = StateVector::new(
+                heliocentric.position.sub(&origin.position),
+                heliocentric.velocity.sub(&origin.velocity),
+            );
+            self.write_state(index, state);
+        }
+        self.computed = Some((self.julian_date, active_config()));
+        self.revision = self.revision.wrapping_add(1);
+    }
+
+    // Store a body's state and its scene position and velocity
+    fn write_state(&mut self, index: usize, state: StateVector) {
+        self.states[index] = state;
+        let position = to_scene(state.position);
+        let velocity = scene_velocity(&state.position, &state.velocity);
+        self.positions[index * 3..index * 3 + 3].copy_from_slice(&[position.x, position.y, position.z]);
+        self.velocities[index * 3..index * 3 + 3].copy_from_slice(&[velocity.x, velocity.y, velocity.z]);
+    }
+}
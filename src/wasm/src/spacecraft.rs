@@ -0,0 +1,770 @@
+// Patched-conic spacecraft: two-body motion about one central body at a time,
+// handing over between the Sun and a planet at the planet's sphere of influence
+
+use wasm_bindgen::prelude::*;
+use js_sys::Float64Array;
+use std::f64::consts::PI;
+
+use crate::errors::{check_julian_date, SimulationError};
+use crate::influence::planet_sphere_of_influence;
+use crate::lambert::propagate_universal;
+use crate::observer::planet_state;
+use crate::state::StateVector;
+use crate::transfer::{plan_lambert, plan_lambert_revolutions, planet_index, LambertTransfer, KM_S_PER_AU_DAY};
+use crate::units::EARTH_RADIUS_KM;
+use crate::{to_scene, Vec3, AU_KM, GM_SUN, PLANET_DATA, PLANET_ELEMENTS, SUN_EARTH_MASS_RATIO};
+
+// Propagation substep limits (days). Steps shrink towards a sphere of
+// influence so crossings are caught to within the minimum step.
+const MIN_STEP: f64 = 1e-3;
+const MAX_STEP: f64 = 30.0;
+
+// Span (days) drawn for an escape trajectory about the Sun
+const ESCAPE_PREDICTION_DAYS: f64 = 3652.5;
+
+// Bisection iterations for flyby periapsis radii and escape times
+const BISECTION_ITERATIONS: usize = 100;
+
+// Upper bound on any planet's heliocentric speed (AU/day), Mercury at perihelion
+const MAX_PLANET_SPEED: f64 = 0.035;
+
+// Most whole revolutions tried for a leg returning to the planet it left
+const MAX_RETURN_REVOLUTIONS: u32 = 4;
+
+// Slowest departure (km/s) on such a leg; anything less is the planet's own
+// orbit, which trivially meets it again
+const MIN_RETURN_V_INFINITY: f64 = 0.5;
+
+// Gravitational parameter of a planet (AU³/day²)
+pub(crate) fn planet_gm(index: usize) -> f64 {
+    GM_SUN * PLANET_DATA[index].9 / SUN_EARTH_MASS_RATIO
+}
+
+// A handover between central bodies
+#[wasm_bindgen]
+#[derive(Debug, Clone)]
+pub struct SoiTransition {
+    julian_date: f64,
+    from: String,
+    to: String,
+}
+
+#[wasm_bindgen]
+impl SoiTransition {
+    #[wasm_bindgen(getter)]
+    pub fn julian_date(&self) -> f64 {
+        self.julian_date
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn from(&self) -> String {
+        self.from.clone()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn to(&self) -> String {
+        self.to.clone()
+    }
+}
+
+// A planned impulsive burn at a date, in the spacecraft's orbital frame
+// relative to its central body at that moment (km/s): prograde along the
+// velocity, normal along the orbit's angular momentum, radial completing the
+// right-handed set (outwards for a circular orbit)
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy)]
+pub struct ManeuverNode {
+    julian_date: f64,
+    prograde: f64,
+    normal: f64,
+    radial: f64,
+}
+
+#[wasm_bindgen]
+impl ManeuverNode {
+    #[wasm_bindgen(constructor)]
+    pub fn new(julian_date: f64, prograde: f64, normal: f64, radial: f64) -> Result<ManeuverNode, JsError> {
+        check_julian_date(julian_date)?;
+        Ok(ManeuverNode { julian_date, prograde, normal, radial })
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn julian_date(&self) -> f64 {
+        self.julian_date
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn prograde(&self) -> f64 {
+        self.prograde
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn normal(&self) -> f64 {
+        self.normal
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn radial(&self) -> f64 {
+        self.radial
+    }
+
+    // Burn magnitude (km/s)
+    #[wasm_bindgen(getter)]
+    pub fn delta_v(&self) -> f64 {
+        (self.prograde * self.prograde + self.normal * self.normal + self.radial * self.radial).sqrt()
+    }
+}
+
+fn body_name(central: Option<usize>) -> String {
+    central.map_or("Sun", |index| PLANET_ELEMENTS[index].0).to_string()
+}
+
+// A spacecraft coasting on patched conics. Inside a planet's sphere of
+// influence it follows a conic about the planet; outside, a conic about the Sun.
+#[wasm_bindgen]
+#[derive(Debug, Clone)]
+pub struct Spacecraft {
+    // None for the Sun, otherwise a planet index
+    central: Option<usize>,
+    // State relative to the central body (AU, AU/day, ecliptic J2000)
+    state: StateVector,
+    julian_date: f64,
+    transitions: Vec<SoiTransition>,
+    // Planned burns in date order, executed as the spacecraft coasts past them
+    maneuvers: Vec<ManeuverNode>,
+    executed_delta_v: f64,
+}
+
+#[wasm_bindgen]
+impl Spacecraft {
+    // A spacecraft at a heliocentric ecliptic position (AU) and velocity
+    // (AU/day). It starts in a planet's frame if already inside its sphere of
+    // influence.
+    #[wasm_bindgen(constructor)]
+    pub fn new(julian_date: f64, position: &Vec3, velocity: &Vec3) -> Result<Spacecraft, JsError> {
+        check_julian_date(julian_date)?;
+        let mut craft = Spacecraft {
+            central: None,
+            state: StateVector::new(*position, *velocity),
+            julian_date,
+            transitions: Vec::new(),
+            maneuvers: Vec::new(),
+            executed_delta_v: 0.0,
+        };
+        if let Some(index) = craft.entered_planet() {
+            craft.state = craft.relative_to(Some(index), julian_date);
+            craft.central = Some(index);
+        }
+        Ok(craft)
+    }
+
+    // A spacecraft leaving a planet on an escape hyperbola, placed at periapsis
+    // at the given altitude (km) so that it departs with the hyperbolic excess
+    // velocity v_infinity (km/s, ecliptic) — for example a LambertTransfer's
+    // departure velocity minus the planet's. The patched arcs drift from the
+    // single Lambert arc by around a percent of the distance flown, so reaching
+    // a target's sphere of influence may need a correction burn on the way.
+    // Fails for an unknown planet; returns None for a zero v_infinity.
+    pub fn depart(
+        planet: &str,
+        julian_date: f64,
+        v_infinity: &Vec3,
+        periapsis_altitude: f64,
+    ) -> Result<Option<Spacecraft>, JsError> {
+        check_julian_date(julian_date)?;
+        let index = planet_index(planet).ok_or_else(|| SimulationError::unknown_body(planet))?;
+        let mu = planet_gm(index);
+        let rp = (PLANET_DATA[index].1 * EARTH_RADIUS_KM + periapsis_altitude.max(0.0)) / AU_KM;
+        let v_inf = v_infinity.scale(1.0 / KM_S_PER_AU_DAY);
+        let speed = v_inf.length();
+        if speed == 0.0 {
+            return Ok(None);
+        }
+        let direction = v_inf.normalize();
+
+        // Orbit plane contains the asymptote, tilted as close to prograde as possible
+        let north = Vec3::new(0.0, 0.0, 1.0);
+        let normal = north.sub(&direction.scale(north.dot(&direction)));
+        let normal = if normal.length() > 1e-9 { normal.normalize() } else { Vec3::new(1.0, 0.0, 0.0) };
+        let across = normal.cross(&direction);
+
+        // Periapsis sits the asymptote's true anomaly behind the outgoing direction
+        let e = 1.0 + rp * speed * speed / mu;
+        let (sin, cos) = (-1.0 / e).acos().sin_cos();
+        let periapsis = direction.scale(cos).sub(&across.scale(sin));
+        let velocity = normal.cross(&periapsis).scale((speed * speed + 2.0 * mu / rp).sqrt());
+        Ok(Some(Spacecraft {
+            central: Some(index),
+            state: StateVector::new(periapsis.scale(rp), velocity),
+            julian_date,
+            transitions: Vec::new(),
+            maneuvers: Vec::new(),
+            executed_delta_v: 0.0,
+        }))
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn julian_date(&self) -> f64 {
+        self.julian_date
+    }
+
+    // "Sun" or the planet whose sphere of influence holds the spacecraft
+    #[wasm_bindgen(getter)]
+    pub fn central_body(&self) -> String {
+        body_name(self.central)
+    }
+
+    // State relative to the central body (AU, AU/day)
+    #[wasm_bindgen(getter)]
+    pub fn relative_state(&self) -> StateVector {
+        self.state
+    }
+
+    // Heliocentric ecliptic state (AU, AU/day)
+    #[wasm_bindgen(getter)]
+    pub fn heliocentric_state(&self) -> StateVector {
+        self.relative_to(None, self.julian_date)
+    }
+
+    // Heliocentric scene position
+    #[wasm_bindgen(getter)]
+    pub fn position(&self) -> Vec3 {
+        to_scene(self.heliocentric_state().position)
+    }
+
+    // Central-body changes so far, oldest first
+    #[wasm_bindgen(getter)]
+    pub fn transitions(&self) -> Vec<SoiTransition> {
+        self.transitions.clone()
+    }
+
+    // Apply an impulsive velocity change (km/s, ecliptic)
+    pub fn apply_delta_v(&mut self, delta_v: &Vec3) {
+        self.state.velocity = self.state.velocity.add(&delta_v.scale(1.0 / KM_S_PER_AU_DAY));
+        self.executed_delta_v += delta_v.length();
+    }
+
+    // Plan a burn, executed when the spacecraft coasts forwards past its date.
+    // Burns dated before the spacecraft's current epoch are rejected, as they
+    // could never execute and would hold back every later one.
+    pub fn add_maneuver(&mut self, node: &ManeuverNode) -> Result<(), JsError> {
+        if node.julian_date < self.julian_date {
+            return Err(SimulationError::invalid_argument(&format!(
+                "Maneuver at {} is before the spacecraft's epoch {}",
+                node.julian_date, self.julian_date
+            ))
+            .into());
+        }
+        let index = self.maneuvers.partition_point(|planned| planned.julian_date <= node.julian_date);
+        self.maneuvers.insert(index, *node);
+        Ok(())
+    }
+
+    pub fn clear_maneuvers(&mut self) {
+        self.maneuvers.clear();
+    }
+
+    // Planned burns not yet executed, in date order
+    #[wasm_bindgen(getter)]
+    pub fn maneuvers(&self) -> Vec<ManeuverNode> {
+        self.maneuvers.clone()
+    }
+
+    // Total of the planned burns (km/s)
+    #[wasm_bindgen(getter)]
+    pub fn planned_delta_v(&self) -> f64 {
+        self.maneuvers.iter().fold(0.0, |total, node| total + node.delta_v())
+    }
+
+    // Total of the burns performed so far (km/s)
+    #[wasm_bindgen(getter)]
+    pub fn executed_delta_v(&self) -> f64 {
+        self.executed_delta_v
+    }
+
+    // Predicted heliocentric path to a Julian date with every planned burn
+    // executed, as segments + 1 scene-space vertices packed [x, y, z, ...]
+    pub fn predict(&self, julian_date: f64, segments: u32) -> Result<Float64Array, JsError> {
+        check_julian_date(julian_date)?;
+        let segments = segments.max(1);
+        let mut craft = self.clone();
+        let step = (julian_date - self.julian_date) / segments as f64;
+        let mut buffer = Vec::with_capacity((segments as usize + 1) * 3);
+        for i in 0..=segments {
+            craft.fly_to(self.julian_date + step * i as f64);
+            let vertex = craft.position();
+            buffer.extend_from_slice(&[vertex.x, vertex.y, vertex.z]);
+        }
+        Ok(Float64Array::from(buffer.as_slice()))
+    }
+
+    // The orbit that would result from a burn, drawn around the central body as
+    // it stands at the burn: one full revolution if bound, otherwise the escape
+    // arc out to the sphere of influence (or ten years from the Sun). Planned
+    // burns before the node are executed first. Packed scene-space [x, y, z, ...].
+    pub fn orbit_after(&self, node: &ManeuverNode, segments: u32) -> Float64Array {
+        let segments = segments.max(3);
+        let mut craft = self.clone();
+        craft.maneuvers.retain(|planned| planned.julian_date < node.julian_date);
+        craft.fly_to(node.julian_date);
+        craft.burn(node);
+
+        let mu = craft.mu();
+        let state = craft.state;
+        let energy = state.velocity.dot(&state.velocity) / 2.0 - mu / state.position.length();
+        let span = if energy < 0.0 {
+            2.0 * PI * (-mu / (2.0 * energy)).powf(1.5) / mu.sqrt()
+        } else {
+            match craft.central {
+                Some(index) => escape_time(&state, mu, planet_sphere_of_influence(index)),
+                None => ESCAPE_PREDICTION_DAYS,
+            }
+        };
+        let center = craft.relative_to(None, node.julian_date).position.sub(&state.position);
+        let mut buffer = Vec::with_capacity((segments as usize + 1) * 3);
+        for i in 0..=segments {
+            let position = propagate_universal(&state, span * i as f64 / segments as f64, mu).position;
+            let vertex = to_scene(center.add(&position));
+            buffer.extend_from_slice(&[vertex.x, vertex.y, vertex.z]);
+        }
+        Float64Array::from(buffer.as_slice())
+    }
+
+    // Coast to a Julian date (forwards or backwards), switching central bodies
+    // at sphere-of-influence crossings. Going forwards, planned maneuvers are
+    // executed as their dates are reached.
+    pub fn advance_to(&mut self, julian_date: f64) -> Result<(), JsError> {
+        check_julian_date(julian_date)?;
+        self.fly_to(julian_date);
+        Ok(())
+    }
+
+    // Coast for a number of days
+    pub fn advance(&mut self, days: f64) -> Result<(), JsError> {
+        self.advance_to(self.julian_date + days)
+    }
+}
+
+impl Spacecraft {
+    // advance_to for a date already known to be valid
+    fn fly_to(&mut self, julian_date: f64) {
+        while let Some(node) = self
+            .maneuvers
+            .first()
+            .copied()
+            .filter(|node| node.julian_date >= self.julian_date && node.julian_date <= julian_date)
+        {
+            self.coast_to(node.julian_date);
+            self.burn(&node);
+            self.maneuvers.remove(0);
+        }
+        self.coast_to(julian_date);
+    }
+
+    // Execute a planned burn in the current orbital frame
+    fn burn(&mut self, node: &ManeuverNode) {
+        let prograde = self.state.velocity.normalize();
+        let normal = self.state.position.cross(&self.state.velocity).normalize();
+        let radial = prograde.cross(&normal);
+        let delta_v = prograde
+            .scale(node.prograde)
+            .add(&normal.scale(node.normal))
+            .add(&radial.scale(node.radial));
+        self.apply_delta_v(&delta_v);
+    }
+
+    // Coast on patched conics to a Julian date
+    fn coast_to(&mut self, julian_date: f64) {
+        let mut limit = MAX_STEP;
+        while (julian_date - self.julian_date).abs() > 1e-9 {
+            let remaining = julian_date - self.julian_date;
+            let dt = self.safe_step().min(limit).min(remaining.abs()).copysign(remaining);
+            let next = propagate_universal(&self.state, dt, self.mu());
+            let previous = std::mem::replace(&mut self.state, next);
+            self.julian_date += dt;
+
+            let crossed = match self.central {
+                Some(index) => self.state.position.length() > planet_sphere_of_influence(index),
+                None => self.entered_planet().is_some(),
+            };
+            // Retry shorter steps until the crossing is pinned down to about a
+            // minimum step, then hand over
+            if crossed && dt.abs() > MIN_STEP * 1.5 {
+                self.state = previous;
+                self.julian_date -= dt;
+                limit = dt.abs() / 4.0;
+                continue;
+            }
+            limit = MAX_STEP;
+            if crossed {
+                self.hand_over();
+            }
+        }
+    }
+
+    fn mu(&self) -> f64 {
+        self.central.map_or(GM_SUN, planet_gm)
+    }
+
+    // State relative to a body (None for the Sun) at a Julian date
+    fn relative_to(&self, body: Option<usize>, julian_date: f64) -> StateVector {
+        let heliocentric = match self.central {
+            Some(index) => {
+                let planet = planet_state(index, julian_date);
+                StateVector::new(planet.position.add(&self.state.position), planet.velocity.add(&self.state.velocity))
+            }
+            None => self.state,
+        };
+        match body {
+            Some(index) => {
+                let planet = planet_state(index, julian_date);
+                StateVector::new(
+                    heliocentric.position.sub(&planet.position),
+                    heliocentric.velocity.sub(&planet.velocity),
+                )
+            }
+            None => heliocentric,
+        }
+    }
+
+    // Planet whose sphere of influence a heliocentric spacecraft lies inside
+    fn entered_planet(&self) -> Option<usize> {
+        (0..PLANET_ELEMENTS.len()).find(|&index| {
+            let planet = planet_state(index, self.julian_date).position;
+            planet.sub(&self.state.position).length() < planet_sphere_of_influence(index)
+        })
+    }
+
+    // Longest step (days) that cannot carry the spacecraft across a sphere of
+    // influence boundary unnoticed
+    fn safe_step(&self) -> f64 {
+        let speed = self.state.velocity.length();
+        let (gap, closing_speed) = match self.central {
+            Some(index) => (planet_sphere_of_influence(index) - self.state.position.length(), speed),
+            None => {
+                let gap = (0..PLANET_ELEMENTS.len())
+                    .map(|index| {
+                        let planet = planet_state(index, self.julian_date).position;
+                        planet.sub(&self.state.position).length() - planet_sphere_of_influence(index)
+                    })
+                    .fold(f64::INFINITY, f64::min);
+                (gap, speed + MAX_PLANET_SPEED)
+            }
+        };
+        (0.5 * gap / closing_speed).clamp(MIN_STEP, MAX_STEP)
+    }
+
+    fn hand_over(&mut self) {
+        let from = self.central;
+        let to = match from {
+            Some(_) => None,
+            None => self.entered_planet(),
+        };
+        self.state = self.relative_to(to, self.julian_date);
+        self.central = to;
+        self.transitions.push(SoiTransition {
+            julian_date: self.julian_date,
+            from: body_name(from),
+            to: body_name(to),
+        });
+    }
+}
+
+// Time (days) for an unbound state to climb to a distance, or zero if it is
+// already beyond it
+fn escape_time(state: &StateVector, mu: f64, distance: f64) -> f64 {
+    let (mut low, mut high) = (0.0, 1.0);
+    while propagate_universal(state, high, mu).position.length() < distance && high < ESCAPE_PREDICTION_DAYS {
+        low = high;
+        high *= 2.0;
+    }
+    for _ in 0..BISECTION_ITERATIONS {
+        let mid = 0.5 * (low + high);
+        if propagate_universal(state, mid, mu).position.length() < distance {
+            low = mid;
+        } else {
+            high = mid;
+        }
+    }
+    high.min(ESCAPE_PREDICTION_DAYS)
+}
+
+// Planet radius (AU)
+fn planet_radius(index: usize) -> f64 {
+    PLANET_DATA[index].1 * EARTH_RADIUS_KM / AU_KM
+}
+
+// Angle (radians) through which a hyperbolic flyby bends the excess velocity,
+// 2·asin(1/e) with e = 1 + rp·v∞²/μ
+pub(crate) fn turn_angle(mu: f64, periapsis_radius: f64, v_infinity: f64) -> f64 {
+    2.0 * (1.0 / (1.0 + periapsis_radius * v_infinity * v_infinity / mu)).asin()
+}
+
+// Rotate a vector about a unit axis (Rodrigues' formula)
+fn rotate_about(v: &Vec3, axis: &Vec3, angle: f64) -> Vec3 {
+    let (sin, cos) = angle.sin_cos();
+    v.scale(cos).add(&axis.cross(v).scale(sin)).add(&axis.scale(axis.dot(v) * (1.0 - cos)))
+}
+
+// A gravity-assist encounter with a planet
+#[wasm_bindgen]
+#[derive(Debug, Clone)]
+pub struct Flyby {
+    planet: String,
+    julian_date: f64,
+    incoming: Vec3,
+    outgoing: Vec3,
+    turn_angle: f64,
+    periapsis_altitude: f64,
+    powered_delta_v: f64,
+}
+
+#[wasm_bindgen]
+impl Flyby {
+    #[wasm_bindgen(getter)]
+    pub fn planet(&self) -> String {
+        self.planet.clone()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn julian_date(&self) -> f64 {
+        self.julian_date
+    }
+
+    // Excess velocity (km/s, ecliptic) relative to the planet on approach
+    #[wasm_bindgen(getter)]
+    pub fn incoming_v_infinity(&self) -> Vec3 {
+        self.incoming
+    }
+
+    // Excess velocity (km/s, ecliptic) relative to the planet on departure
+    #[wasm_bindgen(getter)]
+    pub fn outgoing_v_infinity(&self) -> Vec3 {
+        self.outgoing
+    }
+
+    // Bend of the excess velocity (degrees)
+    #[wasm_bindgen(getter)]
+    pub fn turn_angle(&self) -> f64 {
+        self.turn_angle
+    }
+
+    // Closest approach above the planet's surface (km); negative altitudes
+    // mean the bend needs a trajectory through the planet
+    #[wasm_bindgen(getter)]
+    pub fn periapsis_altitude(&self) -> f64 {
+        self.periapsis_altitude
+    }
+
+    // Burn at periapsis (km/s) making up any difference between incoming and
+    // outgoing excess speeds; zero for a pure gravity assist
+    #[wasm_bindgen(getter)]
+    pub fn powered_delta_v(&self) -> f64 {
+        self.powered_delta_v
+    }
+
+    // Change in heliocentric speed (km/s) gained from the encounter
+    #[wasm_bindgen(getter)]
+    pub fn heliocentric_delta_v(&self) -> f64 {
+        self.outgoing.sub(&self.incoming).length()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn feasible(&self) -> bool {
+        self.periapsis_altitude >= 0.0
+    }
+}
+
+// Unpowered flyby of a planet: bend an incoming excess velocity (km/s,
+// ecliptic) passing at a periapsis altitude (km). The b-plane angle (degrees)
+// picks the side of the planet passed: 0 bends in the prograde orbital sense,
+// 180 the opposite way, 90 and 270 out of the ecliptic. Fails for an unknown
+// planet; returns None for a zero excess velocity.
+#[wasm_bindgen]
+pub fn flyby(
+    planet: &str,
+    julian_date: f64,
+    v_infinity: &Vec3,
+    periapsis_altitude: f64,
+    b_plane_angle: f64,
+) -> Result<Option<Flyby>, JsError> {
+    check_julian_date(julian_date)?;
+    let index = planet_index(planet).ok_or_else(|| SimulationError::unknown_body(planet))?;
+    let speed = v_infinity.length();
+    if speed == 0.0 {
+        return Ok(None);
+    }
+    let direction = v_infinity.normalize();
+    let north = Vec3::new(0.0, 0.0, 1.0);
+    let prograde = north.sub(&direction.scale(north.dot(&direction)));
+    let prograde = if prograde.length() > 1e-9 { prograde.normalize() } else { Vec3::new(1.0, 0.0, 0.0) };
+    let angle = b_plane_angle.to_radians();
+    let axis = prograde.scale(angle.cos()).add(&direction.cross(&prograde).scale(angle.sin()));
+
+    let rp = planet_radius(index) + periapsis_altitude.max(0.0) / AU_KM;
+    let delta = turn_angle(planet_gm(index), rp, speed / KM_S_PER_AU_DAY);
+    Ok(Some(Flyby {
+        planet: planet.to_string(),
+        julian_date,
+        incoming: *v_infinity,
+        outgoing: rotate_about(v_infinity, &axis, delta),
+        turn_angle: delta.to_degrees(),
+        periapsis_altitude: periapsis_altitude.max(0.0),
+        powered_delta_v: 0.0,
+    }))
+}
+
+// Leg from a planet back to the same planet. Every arc of up to
+// MAX_RETURN_REVOLUTIONS is a candidate; after a flyby the one whose departure
+// excess speed best matches the arrival excess speed carried in is kept, as an
+// unpowered flyby only turns it, and a route that opens with the leg takes the
+// slowest departure that leaves the planet's orbit.
+fn plan_return_leg(planet: &str, departure_jd: f64, arrival_jd: f64, incoming: Option<&LambertTransfer>) -> Option<LambertTransfer> {
+    let arrival_speed = incoming.map(|leg| leg.arrival_excess().length());
+    let cost = |leg: &LambertTransfer| {
+        let speed = leg.departure_excess().length();
+        arrival_speed.map_or(speed, |arrival_speed| (speed - arrival_speed).abs())
+    };
+    (0..=MAX_RETURN_REVOLUTIONS)
+        .flat_map(|revolutions| [(revolutions, false), (revolutions, true)])
+        .filter(|&(revolutions, upper)| revolutions > 0 || !upper)
+        .filter_map(|(revolutions, upper)| plan_lambert_revolutions(planet, planet, departure_jd, arrival_jd, revolutions, upper))
+        .filter(|leg| leg.departure_v_infinity() >= MIN_RETURN_V_INFINITY)
+        .min_by(|a, b| cost(a).total_cmp(&cost(b)))
+}
+
+// Flyby joining two excess velocities (AU/day): the periapsis radius whose
+// combined half-bends of the incoming and outgoing hyperbolas give the turn
+// between them, and the periapsis burn matching their speeds
+fn patched_flyby(index: usize, julian_date: f64, incoming: &Vec3, outgoing: &Vec3) -> Flyby {
+    let mu = planet_gm(index);
+    let (v_in, v_out) = (incoming.length(), outgoing.length());
+    let cos_turn = incoming.dot(outgoing) / (v_in * v_out);
+    let delta = cos_turn.clamp(-1.0, 1.0).acos();
+
+    // The combined bend shrinks monotonically from π as the periapsis widens
+    let bend = |rp: f64| 0.5 * (turn_angle(mu, rp, v_in) + turn_angle(mu, rp, v_out));
+    let (mut low, mut high) = (0.0, planet_sphere_of_influence(index));
+    for _ in 0..BISECTION_ITERATIONS {
+        let rp = 0.5 * (low + high);
+        if bend(rp) > delta {
+            low = rp;
+        } else {
+            high = rp;
+        }
+    }
+    let rp = 0.5 * (low + high);
+    let periapsis_speed = |v: f64| (v * v + 2.0 * mu / rp).sqrt();
+    Flyby {
+        planet: PLANET_ELEMENTS[index].0.to_string(),
+        julian_date,
+        incoming: incoming.scale(KM_S_PER_AU_DAY),
+        outgoing: outgoing.scale(KM_S_PER_AU_DAY),
+        turn_angle: delta.to_degrees(),
+        periapsis_altitude: (rp - planet_radius(index)) * AU_KM,
+        powered_delta_v: (periapsis_speed(v_out) - periapsis_speed(v_in)).abs() * KM_S_PER_AU_DAY,
+    }
+}
+
+// A multi-planet route of Lambert legs joined by flybys
+#[wasm_bindgen]
+#[derive(Debug, Clone)]
+pub struct GravityAssistRoute {
+    legs: Vec<LambertTransfer>,
+    flybys: Vec<Flyby>,
+}
+
+#[wasm_bindgen]
+impl GravityAssistRoute {
+    #[wasm_bindgen(getter)]
+    pub fn legs(&self) -> Vec<LambertTransfer> {
+        self.legs.clone()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn flybys(&self) -> Vec<Flyby> {
+        self.flybys.clone()
+    }
+
+    // Launch energy C3 (km²/s²) at the first planet
+    #[wasm_bindgen(getter)]
+    pub fn c3(&self) -> f64 {
+        self.legs[0].c3()
+    }
+
+    // Excess speed (km/s) arriving at the final planet
+    #[wasm_bindgen(getter)]
+    pub fn arrival_v_infinity(&self) -> f64 {
+        self.legs[self.legs.len() - 1].arrival_v_infinity()
+    }
+
+    // Launch excess speed plus every powered flyby burn (km/s)
+    #[wasm_bindgen(getter)]
+    pub fn total_delta_v(&self) -> f64 {
+        self.legs[0].departure_v_infinity() + self.flybys.iter().map(|flyby| flyby.powered_delta_v).sum::<f64>()
+    }
+
+    // Whether every flyby clears its planet's surface
+    #[wasm_bindgen(getter)]
+    pub fn feasible(&self) -> bool {
+        self.flybys.iter().all(Flyby::feasible)
+    }
+
+    // All legs as one scene-space polyline packed [x, y, z, ...]
+    #[wasm_bindgen(getter)]
+    pub fn trajectory(&self) -> Float64Array {
+        let buffer: Vec<f64> = self.legs.iter().flat_map(|leg| leg.trajectory().to_vec()).collect();
+        Float64Array::from(buffer.as_slice())
+    }
+
+    // Scene position of the spacecraft at a Julian date along the route
+    pub fn position_at(&self, julian_date: f64) -> Result<Vec3, JsError> {
+        check_julian_date(julian_date)?;
+        let leg = self
+            .legs
+            .iter()
+            .find(|leg| julian_date <= leg.arrival_date())
+            .unwrap_or(&self.legs[self.legs.len() - 1]);
+        Ok(leg.position_within(julian_date))
+    }
+}
+
+// Reconstruct a gravity-assist tour through a sequence of planets encountered
+// on the given Julian dates (one per planet), as for Voyager 2's Earth,
+// Jupiter, Saturn, Uranus, Neptune grand tour. Each leg is a Lambert arc; each
+// intermediate planet gets the flyby that joins its legs. A leg back to the
+// planet it left (Cassini's Venus, Venus) is a resonant orbit of one or more
+// revolutions. Returns None unless there are at least two planets, dates match
+// and every leg solves.
+#[wasm_bindgen]
+pub fn gravity_assist_route(planets: Vec<String>, dates: &[f64]) -> Result<Option<GravityAssistRoute>, JsError> {
+    for &julian_date in dates {
+        check_julian_date(julian_date)?;
+    }
+    Ok(plan_route(planets, dates))
+}
+
+// Route through planets on dates already known to be valid
+fn plan_route(planets: Vec<String>, dates: &[f64]) -> Option<GravityAssistRoute> {
+    if planets.len() < 2 || planets.len() != dates.len() {
+        return None;
+    }
+    let mut legs: Vec<LambertTransfer> = Vec::with_capacity(planets.len() - 1);
+    for i in 1..planets.len() {
+        let leg = if planet_index(&planets[i - 1])? == planet_index(&planets[i])? {
+            plan_return_leg(&planets[i], dates[i - 1], dates[i], legs.last())?
+        } else {
+            plan_lambert(&planets[i - 1], &planets[i], dates[i - 1], dates[i])?
+        };
+        legs.push(leg);
+    }
+    let flybys = legs
+        .windows(2)
+        .zip(&planets[1..])
+        .map(|(pair, planet)| {
+            let index = planet_index(planet)?;
+            Some(patched_flyby(index, pair[0].arrival_date(), &pair[0].arrival_excess(), &pair[1].departure_excess()))
+        })
+        .collect::<Option<Vec<_>>>()?;
+    Some(GravityAssistRoute { legs, flybys })
+}
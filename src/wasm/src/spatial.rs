@@ -0,0 +1,283 @@
+// Bounding-volume k-d tree over scene-space spheres (bodies or particles) for
+// ray picking and proximity queries. Building over 10k particles takes about a
+// millisecond, so it is simply rebuilt whenever positions change.
+
+use wasm_bindgen::prelude::*;
+use js_sys::Uint32Array;
+
+use crate::belts::BeltParticles;
+use crate::culling::CameraView;
+use crate::errors::{check_julian_date, SimulationError};
+use crate::picking::pick_spheres;
+use crate::scene::scene_radius;
+use crate::solar_system::{physical_data, SolarSystem};
+use crate::Vec3;
+
+// Most spheres kept in one leaf
+const LEAF_SIZE: usize = 8;
+
+// Axis-aligned box enclosing every sphere below a node
+#[derive(Debug, Clone, Copy)]
+struct Bounds {
+    min: [f64; 3],
+    max: [f64; 3],
+}
+
+impl Bounds {
+    fn empty() -> Bounds {
+        Bounds { min: [f64::INFINITY; 3], max: [f64::NEG_INFINITY; 3] }
+    }
+
+    fn include(&mut self, center: &[f64; 3], radius: f64) {
+        for (axis, &value) in center.iter().enumerate() {
+            self.min[axis] = self.min[axis].min(value - radius);
+            self.max[axis] = self.max[axis].max(value + radius);
+        }
+    }
+
+    // Ray parameter where the ray enters the box, if it hits it at all
+    fn ray_entry(&self, origin: &[f64; 3], inverse_direction: &[f64; 3]) -> Option<f64> {
+        let (mut near, mut far) = (0.0_f64, f64::INFINITY);
+        for axis in 0..3 {
+            let t1 = (self.min[axis] - origin[axis]) * inverse_direction[axis];
+            let t2 = (self.max[axis] - origin[axis]) * inverse_direction[axis];
+            near = near.max(t1.min(t2));
+            far = far.min(t1.max(t2));
+        }
+        (near <= far).then_some(near)
+    }
+
+    fn distance_squared(&self, point: &[f64; 3]) -> f64 {
+        (0..3)
+            .map(|axis| {
+                let excess = (self.min[axis] - point[axis]).max(point[axis] - self.max[axis]).max(0.0);
+                excess * excess
+            })
+            .sum()
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Node {
+    bounds: Bounds,
+    // Range of `order` below this node, and the children (none for a leaf)
+    start: usize,
+    end: usize,
+    children: Option<(usize, usize)>,
+}
+
+// Where a ray first meets a sphere, or passes closest to a picked one
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy)]
+pub struct RayHit {
+    index: u32,
+    distance: f64,
+    point: Vec3,
+}
+
+#[wasm_bindgen]
+impl RayHit {
+    // Index of the sphere in the order the index was built from
+    #[wasm_bindgen(getter)]
+    pub fn index(&self) -> u32 {
+        self.index
+    }
+
+    // Distance along the ray (scene units, for a unit direction)
+    #[wasm_bindgen(getter)]
+    pub fn distance(&self) -> f64 {
+        self.distance
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn point(&self) -> Vec3 {
+        self.point
+    }
+}
+
+#[wasm_bindgen]
+pub struct SpatialIndex {
+    centers: Vec<[f64; 3]>,
+    radii: Vec<f64>,
+    // Sphere indices grouped by node
+    order: Vec<usize>,
+    nodes: Vec<Node>,
+}
+
+#[wasm_bindgen]
+impl SpatialIndex {
+    // Index scene positions packed [x, y, z, ...] with one radius each, or a
+    // single radius shared by all
+    #[wasm_bindgen(constructor)]
+    pub fn new(positions: &[f64], radii: &[f64]) -> Result<SpatialIndex, JsError> {
+        let count = positions.len() / 3;
+        if !positions.len().is_multiple_of(3) || (radii.len() != 1 && radii.len() != count) {
+            return Err(SimulationError::invalid_argument(&format!(
+                "Expected packed positions and 1 or {} radii, got {} values and {} radii",
+                count,
+                positions.len(),
+                radii.len()
+            ))
+            .into());
+        }
+        let centers = positions.chunks_exact(3).map(|p| [p[0], p[1], p[2]]).collect();
+        let radii = (0..count).map(|index| radii[index.min(radii.len() - 1)]).collect();
+        Ok(SpatialIndex::build(centers, radii))
+    }
+
+    // Every body of a simulation at its display radius, indexed in buffer order
+    pub fn from_solar_system(system: &SolarSystem) -> SpatialIndex {
+        let centers = system.scene_positions().chunks_exact(3).map(|p| [p[0], p[1], p[2]]).collect();
+        let radii = (0..system.body_count()).map(|index| scene_radius(physical_data(index).1)).collect();
+        SpatialIndex::build(centers, radii)
+    }
+
+    // Belt particles at a Julian date, each a sphere of particle_radius
+    pub fn from_belt(belt: &BeltParticles, julian_date: f64, particle_radius: f64) -> Result<SpatialIndex, JsError> {
+        check_julian_date(julian_date)?;
+        let centers: Vec<[f64; 3]> = belt.scene_positions(julian_date).iter().map(|p| [p.x, p.y, p.z]).collect();
+        let radii = vec![particle_radius; centers.len()];
+        Ok(SpatialIndex::build(centers, radii))
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn count(&self) -> usize {
+        self.centers.len()
+    }
+
+    // First sphere hit by a ray, or None for a miss. Rays starting inside a
+    // sphere hit it at distance 0.
+    pub fn nearest_body(&self, ray_origin: &Vec3, ray_direction: &Vec3) -> Option<RayHit> {
+        let direction = ray_direction.normalize();
+        if direction.length() == 0.0 {
+            return None;
+        }
+        let origin = [ray_origin.x, ray_origin.y, ray_origin.z];
+        let inverse = [1.0 / direction.x, 1.0 / direction.y, 1.0 / direction.z];
+        let mut best: Option<(usize, f64)> = None;
+        let mut stack = vec![0];
+        while let Some(node) = stack.pop() {
+            let node = &self.nodes[node];
+            let limit = best.map_or(f64::INFINITY, |(_, distance)| distance);
+            if !node.bounds.ray_entry(&origin, &inverse).is_some_and(|entry| entry <= limit) {
+                continue;
+            }
+            match node.children {
+                Some((left, right)) => stack.extend([left, right]),
+                None => {
+                    for &index in &self.order[node.start..node.end] {
+                        let hit = ray_sphere(&origin, &direction, &self.centers[index], self.radii[index]);
+                        match (hit, best) {
+                            (Some(distance), Some((_, nearest))) if distance >= nearest => {}
+                            (Some(distance), _) => best = Some((index, distance)),
+                            (None, _) => {}
+                        }
+                    }
+                }
+            }
+        }
+        best.map(|(index, distance)| RayHit::new(index as u32, distance, ray_origin.add(&direction.scale(distance))))
+    }
+
+    // Pick with a screen-space tolerance (see picking.rs): true hits first,
+    // nearest along the ray, else the sphere passing closest on screen within
+    // pixel_tolerance pixels
+    pub fn pick(
+        &self,
+        ray_origin: &Vec3,
+        ray_direction: &Vec3,
+        pixel_tolerance: f64,
+        camera: &CameraView,
+    ) -> Option<RayHit> {
+        let spheres = self.centers.iter().zip(&self.radii).map(|(c, r)| (Vec3::new(c[0], c[1], c[2]), *r));
+        pick_spheres(spheres, ray_origin, ray_direction, pixel_tolerance, camera)
+    }
+
+    // Indices, ascending, of the spheres whose centers lie within radius of
+    // a point
+    pub fn bodies_within(&self, center: &Vec3, radius: f64) -> Uint32Array {
+        let point = [center.x, center.y, center.z];
+        let mut found = Vec::new();
+        let mut stack = vec![0];
+        while let Some(node) = stack.pop() {
+            let node = &self.nodes[node];
+            if node.bounds.distance_squared(&point) > radius * radius {
+                continue;
+            }
+            match node.children {
+                Some((left, right)) => stack.extend([left, right]),
+                None => {
+                    for &index in &self.order[node.start..node.end] {
+                        let c = &self.centers[index];
+                        let (dx, dy, dz) = (c[0] - point[0], c[1] - point[1], c[2] - point[2]);
+                        if dx * dx + dy * dy + dz * dz <= radius * radius {
+                            found.push(index as u32);
+                        }
+                    }
+                }
+            }
+        }
+        found.sort_unstable();
+        Uint32Array::from(found.as_slice())
+    }
+}
+
+impl RayHit {
+    pub(crate) fn new(index: u32, distance: f64, point: Vec3) -> RayHit {
+        RayHit { index, distance, point }
+    }
+}
+
+impl SpatialIndex {
+    fn build(centers: Vec<[f64; 3]>, radii: Vec<f64>) -> SpatialIndex {
+        let mut index = SpatialIndex {
+            order: (0..centers.len()).collect(),
+            centers,
+            radii,
+            nodes: Vec::new(),
+        };
+        index.build_node(0, index.order.len());
+        index
+    }
+
+    // Add the node over order[start..end], splitting at the median of its
+    // widest axis, and return its position in nodes
+    fn build_node(&mut self, start: usize, end: usize) -> usize {
+        let mut bounds = Bounds::empty();
+        for &index in &self.order[start..end] {
+            bounds.include(&self.centers[index], self.radii[index]);
+        }
+        let node = self.nodes.len();
+        self.nodes.push(Node { bounds, start, end, children: None });
+        if end - start <= LEAF_SIZE {
+            return node;
+        }
+
+        let extent = |axis: usize| bounds.max[axis] - bounds.min[axis];
+        let axis = (0..3).max_by(|&a, &b| extent(a).total_cmp(&extent(b))).unwrap_or(0);
+        let middle = (end - start) / 2;
+        let centers = &self.centers;
+        self.order[start..end]
+            .select_nth_unstable_by(middle, |&a, &b| centers[a][axis].total_cmp(&centers[b][axis]));
+        let left = self.build_node(start, start + middle);
+        let right = self.build_node(start + middle, end);
+        self.nodes[node].children = Some((left, right));
+        node
+    }
+}
+
+// Distance along a unit-direction ray to a sphere, 0 from inside it
+fn ray_sphere(origin: &[f64; 3], direction: &Vec3, center: &[f64; 3], radius: f64) -> Option<f64> {
+    let offset = Vec3::new(center[0] - origin[0], center[1] - origin[1], center[2] - origin[2]);
+    let along = offset.dot(direction);
+    let miss_squared = offset.dot(&offset) - along * along;
+    let radius_squared = radius * radius;
+    if miss_squared > radius_squared {
+        return None;
+    }
+    if offset.dot(&offset) <= radius_squared {
+        return Some(0.0);
+    }
+    let distance = along - (radius_squared - miss_squared).sqrt();
+    (distance >= 0.0).then_some(distance)
+}
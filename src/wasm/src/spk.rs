@@ -0,0 +1,250 @@
+// JPL SPK ephemeris kernels (DAF container, Chebyshev segment types 2 and 3)
+//
+// A kernel such as a DE440s excerpt is supplied from JavaScript as a byte
+// buffer. While one is loaded, high-precision planet states are interpolated
+// from it; otherwise callers fall back to the analytic model.
+
+use wasm_bindgen::prelude::*;
+use std::cell::RefCell;
+
+use crate::observer::ecliptic_to_equatorial;
+use crate::state::StateVector;
+use crate::{Vec3, AU_KM};
+
+const RECORD_BYTES: usize = 1024;
+const SECONDS_PER_DAY: f64 = 86400.0;
+
+// Obliquity of the ecliptic at J2000.0 relating ICRF to the ecliptic frame (radians)
+const J2000_OBLIQUITY: f64 = 84381.406 / 3600.0 * std::f64::consts::PI / 180.0;
+
+// NAIF identifiers
+const SOLAR_SYSTEM_BARYCENTER: i32 = 0;
+const SUN: i32 = 10;
+
+// A Chebyshev segment giving a target's state relative to a center
+struct Segment {
+    target: i32,
+    center: i32,
+    start: f64,
+    end: f64,
+    // Type 3 records carry separate velocity coefficients
+    has_velocity: bool,
+    init: f64,
+    interval: f64,
+    record_size: usize,
+    records: Vec<f64>,
+}
+
+impl Segment {
+    // State (km, km/s) at seconds past J2000 TDB
+    fn state(&self, et: f64) -> StateVector {
+        let count = self.records.len() / self.record_size;
+        let index = (((et - self.init) / self.interval).floor().max(0.0) as usize).min(count - 1);
+        let record = &self.records[index * self.record_size..(index + 1) * self.record_size];
+        let (mid, radius) = (record[0], record[1]);
+        let components = if self.has_velocity { 6 } else { 3 };
+        let degree = (self.record_size - 2) / components;
+        let tau = (et - mid) / radius;
+
+        let coefficients = |axis: usize| &record[2 + axis * degree..2 + (axis + 1) * degree];
+        let (x, dx) = chebyshev(coefficients(0), tau);
+        let (y, dy) = chebyshev(coefficients(1), tau);
+        let (z, dz) = chebyshev(coefficients(2), tau);
+
+        let velocity = if self.has_velocity {
+            Vec3::new(chebyshev(coefficients(3), tau).0, chebyshev(coefficients(4), tau).0, chebyshev(coefficients(5), tau).0)
+        } else {
+            Vec3::new(dx, dy, dz).scale(1.0 / radius)
+        };
+        StateVector { position: Vec3::new(x, y, z), velocity }
+    }
+}
+
+// Evaluate a Chebyshev series and its derivative at tau in [-1, 1]
+fn chebyshev(coefficients: &[f64], tau: f64) -> (f64, f64) {
+    let (mut t_prev, mut t) = (1.0, tau);
+    let (mut d_prev, mut d) = (0.0, 1.0);
+    let mut value = coefficients[0];
+    let mut derivative = 0.0;
+    for (k, c) in coefficients.iter().enumerate().skip(1) {
+        value += c * t;
+        derivative += c * d;
+        if k + 1 < coefficients.len() {
+            let t_next = 2.0 * tau * t - t_prev;
+            let d_next = 2.0 * t + 2.0 * tau * d - d_prev;
+            (t_prev, t) = (t, t_next);
+            (d_prev, d) = (d, d_next);
+        }
+    }
+    (value, derivative)
+}
+
+// Byte reader honouring the kernel's declared endianness
+struct DafReader<'a> {
+    bytes: &'a [u8],
+    little_endian: bool,
+}
+
+impl DafReader<'_> {
+    fn i32_at(&self, offset: usize) -> Option<i32> {
+        let raw: [u8; 4] = self.bytes.get(offset..offset + 4)?.try_into().ok()?;
+        Some(if self.little_endian { i32::from_le_bytes(raw) } else { i32::from_be_bytes(raw) })
+    }
+
+    fn f64_at(&self, offset: usize) -> Option<f64> {
+        let raw: [u8; 8] = self.bytes.get(offset..offset + 8)?.try_into().ok()?;
+        Some(if self.little_endian { f64::from_le_bytes(raw) } else { f64::from_be_bytes(raw) })
+    }
+
+    // DAF addresses count 8-byte words from 1
+    fn word(&self, address: usize) -> Option<f64> {
+        self.f64_at(address.checked_sub(1)?.checked_mul(8)?)
+    }
+}
+
+// Parse every type 2 and 3 segment in an SPK file
+fn parse_spk(bytes: &[u8]) -> Option<Vec<Segment>> {
+    if bytes.get(0..8)? != b"DAF/SPK " {
+        return None;
+    }
+    let little_endian = match bytes.get(88..96)? {
+        b"LTL-IEEE" => true,
+        b"BIG-IEEE" => false,
+        _ => return None,
+    };
+    let reader = DafReader { bytes, little_endian };
+    let nd = reader.i32_at(8)? as usize;
+    let ni = reader.i32_at(12)? as usize;
+    if nd != 2 || ni != 6 {
+        return None;
+    }
+    let summary_words = nd + ni.div_ceil(2);
+    // Summaries that fit in a record after its three control words
+    let max_summaries = (RECORD_BYTES - 24) / (summary_words * 8);
+
+    let mut segments = Vec::new();
+    // Summary records already read, so a corrupt chain that loops back ends
+    let mut visited = Vec::new();
+    let mut record = usize::try_from(reader.i32_at(76)?).ok()?;
+    while record != 0 {
+        if visited.contains(&record) {
+            return None;
+        }
+        visited.push(record);
+        let base = record.checked_sub(1)?.checked_mul(RECORD_BYTES)?;
+        let next = reader.f64_at(base)? as usize;
+        let count = reader.f64_at(base + 16)? as usize;
+        if count > max_summaries {
+            return None;
+        }
+        for s in 0..count {
+            let summary = base + 24 + s * summary_words * 8;
+            let start = reader.f64_at(summary)?;
+            let end = reader.f64_at(summary + 8)?;
+            let ints: Vec<i32> = (0..ni).map(|k| reader.i32_at(summary + 16 + k * 4)).collect::<Option<_>>()?;
+            let (target, center, data_type) = (ints[0], ints[1], ints[3]);
+            let (first, last) = (usize::try_from(ints[4]).ok()?, usize::try_from(ints[5]).ok()?);
+            if data_type != 2 && data_type != 3 {
+                continue;
+            }
+
+            // Segment directory: INIT, INTLEN, RSIZE, N in the final four words
+            let init = reader.word(last.checked_sub(3)?)?;
+            let interval = reader.word(last - 2)?;
+            let record_size = reader.word(last - 1)? as usize;
+            let n = reader.word(last)? as usize;
+            // Each record holds its midpoint, radius and at least one
+            // coefficient per component
+            let components = if data_type == 3 { 6 } else { 3 };
+            let end_address = record_size.checked_mul(n).and_then(|words| words.checked_add(first))?;
+            if record_size < 2 + components || n == 0 || end_address > last + 1 {
+                return None;
+            }
+            let records = (first..end_address).map(|address| reader.word(address)).collect::<Option<_>>()?;
+            segments.push(Segment {
+                target,
+                center,
+                start,
+                end,
+                has_velocity: data_type == 3,
+                init,
+                interval,
+                record_size,
+                records,
+            });
+        }
+        record = next;
+    }
+    Some(segments)
+}
+
+thread_local! {
+    static KERNEL: RefCell<Vec<Segment>> = const { RefCell::new(Vec::new()) };
+}
+
+// State of a body relative to the solar system barycenter (km, km/s), chaining
+// through segment centers, or None if the chain breaks or loops back on itself
+fn barycentric_state(segments: &[Segment], target: i32, et: f64) -> Option<StateVector> {
+    let mut body = target;
+    let mut position = Vec3::new(0.0, 0.0, 0.0);
+    let mut velocity = Vec3::new(0.0, 0.0, 0.0);
+    let mut visited = Vec::new();
+    while body != SOLAR_SYSTEM_BARYCENTER {
+        if visited.contains(&body) {
+            return None;
+        }
+        visited.push(body);
+        let segment = segments.iter().find(|s| s.target == body && s.start <= et && et <= s.end)?;
+        let state = segment.state(et);
+        position = position.add(&state.position);
+        velocity = velocity.add(&state.velocity);
+        body = segment.center;
+    }
+    Some(StateVector { position, velocity })
+}
+
+// Heliocentric ecliptic state (AU, AU/day) of a planet by index from the loaded
+// kernel, or None if no kernel covers it
+pub(crate) fn planet_state(index: usize, julian_date: f64) -> Option<StateVector> {
+    let et = (julian_date - 2451545.0) * SECONDS_PER_DAY;
+    let planet = (index as i32 + 1) * 100 + 99;
+    let barycenter = index as i32 + 1;
+    KERNEL.with(|kernel| {
+        let segments = kernel.borrow();
+        if segments.is_empty() {
+            return None;
+        }
+        // Outer planet centers are often absent from small kernels; their system
+        // barycenters are within a few hundred kilometres
+        let body = barycentric_state(&segments, planet, et).or_else(|| barycentric_state(&segments, barycenter, et))?;
+        let sun = barycentric_state(&segments, SUN, et)?;
+
+        let to_ecliptic = |v: Vec3| ecliptic_to_equatorial(&v, -J2000_OBLIQUITY);
+        Some(StateVector {
+            position: to_ecliptic(body.position.sub(&sun.position)).scale(1.0 / AU_KM),
+            velocity: to_ecliptic(body.velocity.sub(&sun.velocity)).scale(SECONDS_PER_DAY / AU_KM),
+        })
+    })
+}
+
+// Load a binary SPK kernel, replacing any previous one; returns the number of
+// usable Chebyshev segments, or None if the buffer is not a valid kernel
+#[wasm_bindgen]
+pub fn load_spk_kernel(bytes: &[u8]) -> Option<u32> {
+    let segments = parse_spk(bytes)?;
+    let count = segments.len() as u32;
+    KERNEL.with(|kernel| *kernel.borrow_mut() = segments);
+    Some(count)
+}
+
+// Discard the loaded kernel and return to the analytic model
+#[wasm_bindgen]
+pub fn unload_spk_kernel() {
+    KERNEL.with(|kernel| kernel.borrow_mut().clear());
+}
+
+// Whether a kernel is currently loaded
+#[wasm_bindgen]
+pub fn spk_kernel_loaded() -> bool {
+    KERNEL.with(|kernel| !kernel.borrow().is_empty())
+}
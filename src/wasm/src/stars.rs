@@ -0,0 +1,597 @@
+// Background stars on the celestial sphere: a procedural field and an
+// embedded bright-star catalog with constellation figures
+
+use wasm_bindgen::prelude::*;
+use serde::{Deserialize, Serialize};
+use js_sys::{Float32Array, Uint32Array};
+
+use crate::random::Rng;
+use crate::rotation::icrf_to_scene;
+use crate::{deg_to_rad, Vec3};
+
+// Magnitude range of generated stars, from the brightest to the naked-eye limit
+const BRIGHTEST_MAGNITUDE: f64 = -1.5;
+const FAINTEST_MAGNITUDE: f64 = 6.5;
+
+// Star counts grow by about this many dex per magnitude down to the naked-eye limit
+const COUNT_SLOPE: f64 = 0.45;
+
+// Faintest magnitude to which the bright-star catalog holds every star
+const COMPLETE_MAGNITUDE: f64 = 1.5;
+
+// Galactic-to-ICRS rotation matrix (rows), the transpose of the IAU
+// equatorial-to-galactic matrix
+const GALACTIC_TO_ICRS: [[f64; 3]; 3] = [
+    [-0.0548755604, 0.4941094279, -0.8676661490],
+    [-0.8734370902, -0.4448296300, -0.1980763734],
+    [-0.4838350155, 0.7469822445, 0.4559837762],
+];
+
+// Spectral classes of magnitude-limited samples: share of stars and
+// effective temperature range (K)
+static SPECTRAL_CLASSES: &[(f64, f64, f64)] = &[
+    (0.01, 30000.0, 40000.0), // O
+    (0.20, 10000.0, 30000.0), // B
+    (0.22, 7500.0, 10000.0),  // A
+    (0.14, 6000.0, 7500.0),   // F
+    (0.14, 5200.0, 6000.0),   // G
+    (0.24, 3700.0, 5200.0),   // K
+    (0.05, 2400.0, 3700.0),   // M
+];
+
+// Approximate sRGB color (0-1) of a blackbody at a temperature (K)
+pub(crate) fn temperature_to_rgb(temperature: f64) -> [f64; 3] {
+    let t = temperature / 100.0;
+    let red = if t <= 66.0 { 255.0 } else { 329.698727446 * (t - 60.0).powf(-0.1332047592) };
+    let green = if t <= 66.0 {
+        99.4708025861 * t.ln() - 161.1195681661
+    } else {
+        288.1221695283 * (t - 60.0).powf(-0.0755148492)
+    };
+    let blue = if t >= 66.0 {
+        255.0
+    } else if t <= 19.0 {
+        0.0
+    } else {
+        138.5177312231 * (t - 10.0).ln() - 305.0447927307
+    };
+    [red, green, blue].map(|c| (c / 255.0).clamp(0.0, 1.0))
+}
+
+// Procedurally generated sky
+#[derive(Debug, Clone, Default)]
+#[wasm_bindgen]
+pub struct Starfield {
+    positions: Vec<f32>,
+    magnitudes: Vec<f32>,
+    temperatures: Vec<f32>,
+    colors: Vec<f32>,
+}
+
+#[wasm_bindgen]
+impl Starfield {
+    // Packed [x, y, z, ...] unit directions in scene axes
+    #[wasm_bindgen(getter)]
+    pub fn positions(&self) -> Float32Array {
+        Float32Array::from(self.positions.as_slice())
+    }
+
+    // Apparent visual magnitude of each star
+    #[wasm_bindgen(getter)]
+    pub fn magnitudes(&self) -> Float32Array {
+        Float32Array::from(self.magnitudes.as_slice())
+    }
+
+    // Effective temperature of each star (K)
+    #[wasm_bindgen(getter)]
+    pub fn temperatures(&self) -> Float32Array {
+        Float32Array::from(self.temperatures.as_slice())
+    }
+
+    // Packed [r, g, b, ...] blackbody colors (0-1)
+    #[wasm_bindgen(getter)]
+    pub fn colors(&self) -> Float32Array {
+        Float32Array::from(self.colors.as_slice())
+    }
+}
+
+// Draw a magnitude from the cumulative count law N(< m) ~ 10^(COUNT_SLOPE m)
+fn sample_magnitude(rng: &mut Rng) -> f64 {
+    let (low, high) = (10f64.powf(COUNT_SLOPE * BRIGHTEST_MAGNITUDE), 10f64.powf(COUNT_SLOPE * FAINTEST_MAGNITUDE));
+    (low + rng.next_f64() * (high - low)).log10() / COUNT_SLOPE
+}
+
+// Draw a galactic direction, crowding fainter stars toward the Milky Way's plane
+fn sample_galactic_direction(rng: &mut Rng, magnitude: f64) -> Vec3 {
+    let faintness = (magnitude - BRIGHTEST_MAGNITUDE) / (FAINTEST_MAGNITUDE - BRIGHTEST_MAGNITUDE);
+    let concentration = 3.0 * faintness;
+    loop {
+        let sin_b = rng.range(-1.0, 1.0);
+        let l = rng.range(0.0, 2.0 * std::f64::consts::PI);
+        let acceptance = (1.0 + concentration * (-sin_b.abs() / 0.2).exp()) / (1.0 + concentration);
+        if rng.next_f64() < acceptance {
+            let cos_b = (1.0 - sin_b * sin_b).sqrt();
+            return Vec3::new(cos_b * l.cos(), cos_b * l.sin(), sin_b);
+        }
+    }
+}
+
+fn sample_temperature(rng: &mut Rng) -> f64 {
+    let mut pick = rng.next_f64() * SPECTRAL_CLASSES.iter().map(|class| class.0).sum::<f64>();
+    for &(share, low, high) in SPECTRAL_CLASSES {
+        if pick < share {
+            // Log-uniform, since hot classes span several times their lower bound
+            return (low.ln() + rng.next_f64() * (high.ln() - low.ln())).exp();
+        }
+        pick -= share;
+    }
+    5800.0
+}
+
+// Generate a deterministic naked-eye sky of `count` stars. Magnitudes follow the
+// observed growth of star counts toward fainter limits, faint stars cluster
+// along the galactic plane, and temperatures follow the spectral-class mix of
+// bright-star catalogs. Directions are in scene axes, consistent with planet
+// positions.
+#[wasm_bindgen]
+pub fn generate_starfield(count: u32, seed: u32) -> Starfield {
+    let mut rng = Rng::new(seed as u64);
+    let mut field = Starfield::default();
+    for _ in 0..count {
+        let magnitude = sample_magnitude(&mut rng);
+        let g = sample_galactic_direction(&mut rng, magnitude);
+        let m = &GALACTIC_TO_ICRS;
+        let icrs = Vec3::new(
+            m[0][0] * g.x + m[0][1] * g.y + m[0][2] * g.z,
+            m[1][0] * g.x + m[1][1] * g.y + m[1][2] * g.z,
+            m[2][0] * g.x + m[2][1] * g.y + m[2][2] * g.z,
+        );
+        let direction = icrf_to_scene(&icrs);
+        let temperature = sample_temperature(&mut rng);
+
+        field.positions.extend_from_slice(&[direction.x as f32, direction.y as f32, direction.z as f32]);
+        field.magnitudes.push(magnitude as f32);
+        field.temperatures.push(temperature as f32);
+        field.colors.extend(temperature_to_rgb(temperature).map(|c| c as f32));
+    }
+    field
+}
+
+// Catalog row: Hipparcos number, name, right ascension and declination (degrees,
+// J2000.0), visual magnitude and B-V color index. The stars forming the figures
+// below plus the remaining first-magnitude stars: 139 in all, complete only to
+// COMPLETE_MAGNITUDE.
+type CatalogStar = (u32, &'static str, f64, f64, f64, f64);
+
+static BRIGHT_STAR_CATALOG: &[CatalogStar] = &[
+    // Orion
+    (27989, "Betelgeuse", 88.7929, 7.4071, 0.42, 1.85),
+    (24436, "Rigel", 78.6345, -8.2016, 0.13, -0.03),
+    (25336, "Bellatrix", 81.2828, 6.3497, 1.64, -0.22),
+    (25930, "Mintaka", 83.0017, -0.2991, 2.23, -0.22),
+    (26311, "Alnilam", 84.0534, -1.2019, 1.69, -0.18),
+    (26727, "Alnitak", 85.1897, -1.9426, 1.77, -0.21),
+    (27366, "Saiph", 86.9391, -9.6696, 2.09, -0.17),
+    (26207, "Meissa", 83.7845, 9.9342, 3.33, -0.18),
+    // Ursa Major
+    (54061, "Dubhe", 165.9320, 61.7510, 1.79, 1.07),
+    (53910, "Merak", 165.4603, 56.3824, 2.37, -0.02),
+    (58001, "Phecda", 178.4577, 53.6948, 2.44, 0.04),
+    (59774, "Megrez", 183.8565, 57.0326, 3.31, 0.08),
+    (62956, "Alioth", 193.5073, 55.9598, 1.77, -0.02),
+    (65378, "Mizar", 200.9814, 54.9254, 2.27, 0.06),
+    (67301, "Alkaid", 206.8852, 49.3133, 1.86, -0.10),
+    // Ursa Minor
+    (11767, "Polaris", 37.9546, 89.2641, 1.97, 0.64),
+    (72607, "Kochab", 222.6764, 74.1555, 2.07, 1.47),
+    (75097, "Pherkad", 230.1821, 71.8340, 3.00, 0.05),
+    (85822, "Yildun", 263.0540, 86.5865, 4.35, 0.02),
+    (82080, "Epsilon Ursae Minoris", 251.4926, 82.0373, 4.21, 0.89),
+    (77055, "Zeta Ursae Minoris", 236.0146, 77.7945, 4.29, 0.04),
+    (79822, "Eta Ursae Minoris", 244.3760, 75.7553, 4.95, 0.37),
+    // Cassiopeia
+    (3179, "Schedar", 10.1268, 56.5373, 2.24, 1.17),
+    (746, "Caph", 2.2945, 59.1498, 2.28, 0.38),
+    (4427, "Gamma Cassiopeiae", 14.1772, 60.7167, 2.47, -0.15),
+    (6686, "Ruchbah", 21.4540, 60.2353, 2.68, 0.13),
+    (8886, "Segin", 28.5989, 63.6701, 3.37, -0.15),
+    // Cygnus
+    (102098, "Deneb", 310.3580, 45.2803, 1.25, 0.09),
+    (100453, "Sadr", 305.5571, 40.2567, 2.23, 0.67),
+    (102488, "Gienah", 311.5528, 33.9703, 2.48, 1.03),
+    (97165, "Delta Cygni", 296.2437, 45.1308, 2.87, -0.03),
+    (95947, "Albireo", 292.6803, 27.9597, 3.05, 1.13),
+    // Lyra
+    (91262, "Vega", 279.2347, 38.7837, 0.03, 0.00),
+    (92420, "Sheliak", 282.5200, 33.3627, 3.52, 0.00),
+    (93194, "Sulafat", 284.7359, 32.6896, 3.25, -0.05),
+    (91971, "Zeta Lyrae", 281.1932, 37.6050, 4.34, 0.19),
+    (92791, "Delta Lyrae", 283.6262, 36.8986, 4.22, 1.68),
+    (91919, "Epsilon Lyrae", 281.0846, 39.6700, 4.67, 0.17),
+    // Aquila
+    (97649, "Altair", 297.6958, 8.8683, 0.76, 0.22),
+    (97278, "Tarazed", 296.5650, 10.6133, 2.72, 1.52),
+    (98036, "Alshain", 298.8283, 6.4068, 3.71, 0.86),
+    (95501, "Delta Aquilae", 291.3746, 3.1148, 3.36, 0.32),
+    (93747, "Okab", 286.3525, 13.8635, 2.99, 0.01),
+    (99473, "Theta Aquilae", 302.8262, -0.8215, 3.24, -0.07),
+    (93805, "Lambda Aquilae", 286.5623, -4.8826, 3.43, -0.09),
+    // Scorpius
+    (80763, "Antares", 247.3519, -26.4320, 1.06, 1.83),
+    (78820, "Graffias", 241.3593, -19.8055, 2.56, -0.07),
+    (78401, "Dschubba", 240.0833, -22.6217, 2.29, -0.12),
+    (78265, "Pi Scorpii", 239.7130, -26.1141, 2.89, -0.19),
+    (80112, "Alniyat", 245.2971, -25.5928, 2.90, 0.13),
+    (81266, "Tau Scorpii", 248.9706, -28.2160, 2.82, -0.25),
+    (82396, "Larawag", 252.5409, -34.2932, 2.29, 1.15),
+    (82514, "Mu Scorpii", 252.9676, -38.0474, 3.00, -0.20),
+    (82729, "Zeta Scorpii", 253.6458, -42.3613, 3.62, 1.37),
+    (84143, "Eta Scorpii", 258.0383, -43.2392, 3.32, 0.41),
+    (86228, "Sargas", 264.3297, -42.9978, 1.86, 0.40),
+    (87073, "Iota Scorpii", 266.8962, -40.1270, 2.99, 0.51),
+    (86670, "Girtab", 265.6220, -39.0300, 2.39, -0.22),
+    (85927, "Shaula", 263.4022, -37.1038, 1.62, -0.22),
+    (85696, "Lesath", 262.6910, -37.2958, 2.70, -0.22),
+    // Leo
+    (49669, "Regulus", 152.0930, 11.9672, 1.36, -0.09),
+    (57632, "Denebola", 177.2649, 14.5721, 2.14, 0.09),
+    (50583, "Algieba", 154.9931, 19.8415, 2.01, 1.13),
+    (54872, "Zosma", 168.5271, 20.5237, 2.56, 0.12),
+    (54879, "Chertan", 168.5600, 15.4296, 3.33, -0.01),
+    (49583, "Eta Leonis", 151.8331, 16.7627, 3.48, -0.03),
+    (50335, "Adhafera", 154.1726, 23.4173, 3.43, 0.31),
+    (48455, "Rasalas", 148.1909, 26.0070, 3.88, 1.22),
+    (47908, "Algenubi", 146.4626, 23.7743, 2.98, 0.81),
+    // Gemini
+    (36850, "Castor", 113.6498, 31.8883, 1.58, 0.03),
+    (37826, "Pollux", 116.3290, 28.0262, 1.14, 1.00),
+    (31681, "Alhena", 99.4280, 16.3993, 1.93, 0.00),
+    (32246, "Mebsuta", 100.9830, 25.1311, 2.98, 1.40),
+    (30343, "Tejat", 95.7400, 22.5136, 2.87, 1.64),
+    (35550, "Wasat", 110.0307, 21.9823, 3.53, 0.37),
+    (32362, "Alzirr", 101.3224, 12.8956, 3.35, 0.43),
+    (29655, "Propus", 93.7194, 22.5068, 3.31, 1.60),
+    (37740, "Kappa Geminorum", 116.1119, 24.3980, 3.57, 0.93),
+    (35350, "Lambda Geminorum", 109.5232, 16.5403, 3.58, 0.11),
+    (34088, "Mekbuda", 106.0272, 20.5703, 3.79, 0.79),
+    // Taurus
+    (21421, "Aldebaran", 68.9802, 16.5093, 0.87, 1.54),
+    (25428, "Elnath", 81.5730, 28.6075, 1.65, -0.13),
+    (26451, "Tianguan", 84.4112, 21.1426, 3.00, -0.19),
+    (17702, "Alcyone", 56.8712, 24.1050, 2.87, -0.09),
+    (20205, "Prima Hyadum", 64.9483, 15.6275, 3.65, 0.99),
+    (20455, "Secunda Hyadum", 65.7337, 17.5425, 3.76, 0.98),
+    (20889, "Ain", 67.1542, 19.1804, 3.53, 1.01),
+    (20894, "Chamukuy", 67.1656, 15.8709, 3.40, 0.18),
+    // Auriga
+    (24608, "Capella", 79.1723, 45.9980, 0.08, 0.80),
+    (28360, "Menkalinan", 89.8822, 44.9474, 1.90, 0.08),
+    (28380, "Mahasim", 89.9303, 37.2125, 2.65, -0.08),
+    (23015, "Hassaleh", 74.2484, 33.1661, 2.69, 1.53),
+    (23416, "Almaaz", 75.4922, 43.8233, 2.99, 0.54),
+    // Crux and Centaurus
+    (60718, "Acrux", 186.6496, -63.0991, 0.77, -0.24),
+    (62434, "Mimosa", 191.9303, -59.6888, 1.25, -0.24),
+    (61084, "Gacrux", 187.7915, -57.1132, 1.59, 1.60),
+    (59747, "Imai", 183.7863, -58.7489, 2.79, -0.23),
+    (60260, "Ginan", 185.3400, -60.4012, 3.59, 1.42),
+    (71683, "Rigil Kentaurus", 219.9021, -60.8340, -0.01, 0.71),
+    (68702, "Hadar", 210.9559, -60.3730, 0.61, -0.23),
+    // Canis Major and Canis Minor
+    (32349, "Sirius", 101.2872, -16.7161, -1.46, 0.00),
+    (30324, "Mirzam", 95.6750, -17.9559, 1.98, -0.24),
+    (33579, "Adhara", 104.6565, -28.9721, 1.50, -0.21),
+    (34444, "Wezen", 107.0979, -26.3932, 1.84, 0.68),
+    (35904, "Aludra", 111.0238, -29.3031, 2.45, -0.08),
+    (30122, "Furud", 95.0783, -30.0634, 3.02, -0.19),
+    (33977, "Omicron2 Canis Majoris", 105.7561, -23.8333, 3.02, -0.08),
+    (37279, "Procyon", 114.8255, 5.2250, 0.34, 0.42),
+    (36188, "Gomeisa", 111.7877, 8.2893, 2.89, -0.09),
+    // Bootes
+    (69673, "Arcturus", 213.9153, 19.1824, -0.05, 1.23),
+    (72105, "Izar", 221.2468, 27.0742, 2.35, 0.97),
+    (67927, "Muphrid", 208.6712, 18.3977, 2.68, 0.58),
+    (71075, "Seginus", 218.0195, 38.3083, 3.04, 0.19),
+    (73555, "Nekkar", 225.4865, 40.3906, 3.49, 0.97),
+    (74666, "Delta Bootis", 228.8757, 33.3148, 3.46, 0.95),
+    (71053, "Rho Bootis", 217.9574, 30.3714, 3.57, 1.30),
+    // Pegasus and Andromeda
+    (113963, "Markab", 346.1902, 15.2053, 2.49, -0.04),
+    (113881, "Scheat", 345.9436, 28.0828, 2.44, 1.67),
+    (1067, "Algenib", 3.3090, 15.1836, 2.83, -0.23),
+    (107315, "Enif", 326.0465, 9.8750, 2.38, 1.53),
+    (112029, "Homam", 340.3655, 10.8314, 3.40, -0.09),
+    (109427, "Biham", 332.5499, 6.1978, 3.52, 0.08),
+    (112158, "Matar", 340.7506, 30.2214, 2.93, 0.86),
+    (677, "Alpheratz", 2.0969, 29.0904, 2.07, -0.11),
+    (3092, "Delta Andromedae", 9.8320, 30.8610, 3.27, 1.28),
+    (5447, "Mirach", 17.4330, 35.6206, 2.07, 1.58),
+    (9640, "Almach", 30.9748, 42.3297, 2.10, 1.37),
+    // Sagittarius
+    (90185, "Kaus Australis", 276.0430, -34.3846, 1.85, -0.03),
+    (92855, "Nunki", 283.8164, -26.2967, 2.05, -0.13),
+    (93506, "Ascella", 285.6530, -29.8801, 2.60, 0.08),
+    (89931, "Kaus Media", 275.2485, -29.8280, 2.70, 1.38),
+    (90496, "Kaus Borealis", 276.9927, -25.4217, 2.81, 1.04),
+    (88635, "Alnasl", 271.4520, -30.4241, 2.99, 1.00),
+    (92041, "Phi Sagittarii", 281.4141, -26.9908, 3.17, -0.11),
+    (93864, "Tau Sagittarii", 286.7350, -27.6704, 3.32, 1.19),
+    // Other first-magnitude stars
+    (30438, "Canopus", 95.9880, -52.6957, -0.74, 0.15),
+    (7588, "Achernar", 24.4285, -57.2368, 0.46, -0.16),
+    (65474, "Spica", 201.2983, -11.1613, 0.97, -0.24),
+    (113368, "Fomalhaut", 344.4127, -29.6222, 1.16, 0.09),
+];
+
+// Stick figures as pairs of Hipparcos numbers
+static CONSTELLATION_FIGURES: &[(&str, &[(u32, u32)])] = &[
+    (
+        "Orion",
+        &[
+            (27989, 26207),
+            (26207, 25336),
+            (27989, 26727),
+            (25336, 25930),
+            (25930, 26311),
+            (26311, 26727),
+            (26727, 27366),
+            (25930, 24436),
+        ],
+    ),
+    (
+        "Ursa Major",
+        &[(67301, 65378), (65378, 62956), (62956, 59774), (59774, 54061), (54061, 53910), (53910, 58001), (58001, 59774)],
+    ),
+    (
+        "Ursa Minor",
+        &[(11767, 85822), (85822, 82080), (82080, 77055), (77055, 72607), (72607, 75097), (75097, 79822), (79822, 77055)],
+    ),
+    ("Cassiopeia", &[(746, 3179), (3179, 4427), (4427, 6686), (6686, 8886)]),
+    ("Cygnus", &[(102098, 100453), (100453, 95947), (102488, 100453), (100453, 97165)]),
+    ("Lyra", &[(91262, 91971), (91971, 92420), (92420, 93194), (93194, 92791), (92791, 91971), (91262, 91919)]),
+    ("Aquila", &[(97278, 97649), (97649, 98036), (97649, 95501), (95501, 93747), (95501, 93805), (98036, 99473)]),
+    (
+        "Scorpius",
+        &[
+            (78820, 78401),
+            (78401, 78265),
+            (78401, 80112),
+            (80112, 80763),
+            (80763, 81266),
+            (81266, 82396),
+            (82396, 82514),
+            (82514, 82729),
+            (82729, 84143),
+            (84143, 86228),
+            (86228, 87073),
+            (87073, 86670),
+            (86670, 85927),
+            (85927, 85696),
+        ],
+    ),
+    (
+        "Leo",
+        &[
+            (49669, 49583),
+            (49583, 50583),
+            (50583, 50335),
+            (50335, 48455),
+            (48455, 47908),
+            (50583, 54872),
+            (54872, 57632),
+            (57632, 54879),
+            (54879, 49669),
+        ],
+    ),
+    (
+        "Gemini",
+        &[
+            (36850, 32246),
+            (32246, 30343),
+            (30343, 29655),
+            (37826, 37740),
+            (37740, 35550),
+            (35550, 34088),
+            (34088, 31681),
+            (35550, 35350),
+            (35350, 32362),
+        ],
+    ),
+    (
+        "Taurus",
+        &[(26451, 21421), (21421, 20894), (20894, 20205), (20205, 20455), (20455, 20889), (20889, 25428)],
+    ),
+    ("Auriga", &[(24608, 28360), (28360, 28380), (28380, 25428), (25428, 23015), (23015, 23416), (23416, 24608)]),
+    ("Crux", &[(60718, 61084), (62434, 59747)]),
+    ("Centaurus", &[(71683, 68702)]),
+    ("Canis Major", &[(32349, 30324), (32349, 33977), (33977, 34444), (34444, 35904), (34444, 33579), (33579, 30122)]),
+    ("Canis Minor", &[(37279, 36188)]),
+    (
+        "Bootes",
+        &[(69673, 72105), (72105, 74666), (74666, 73555), (73555, 71075), (71075, 71053), (71053, 69673), (69673, 67927)],
+    ),
+    (
+        "Pegasus",
+        &[
+            (113963, 113881),
+            (113881, 677),
+            (677, 1067),
+            (1067, 113963),
+            (113963, 112029),
+            (112029, 109427),
+            (109427, 107315),
+            (113881, 112158),
+        ],
+    ),
+    ("Andromeda", &[(677, 3092), (3092, 5447), (5447, 9640)]),
+    (
+        "Sagittarius",
+        &[
+            (90185, 89931),
+            (89931, 90496),
+            (90496, 92041),
+            (92041, 92855),
+            (92855, 93864),
+            (93864, 93506),
+            (93506, 92041),
+            (90185, 93506),
+            (89931, 92041),
+            (90185, 88635),
+            (88635, 89931),
+        ],
+    ),
+];
+
+// Effective temperature (K) from the B-V color index (Ballesteros 2012)
+fn color_index_temperature(color_index: f64) -> f64 {
+    4600.0 * (1.0 / (0.92 * color_index + 1.7) + 1.0 / (0.92 * color_index + 0.62))
+}
+
+// A cataloged naked-eye star
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[wasm_bindgen]
+pub struct BrightStar {
+    hip: u32,
+    name: String,
+    right_ascension: f64,
+    declination: f64,
+    magnitude: f64,
+    color_index: f64,
+    temperature: f64,
+    color: String,
+    position: Vec3,
+}
+
+#[wasm_bindgen]
+impl BrightStar {
+    // Hipparcos catalog number
+    #[wasm_bindgen(getter)]
+    pub fn hip(&self) -> u32 {
+        self.hip
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn name(&self) -> String {
+        self.name.clone()
+    }
+
+    // Right ascension (degrees, J2000.0)
+    #[wasm_bindgen(getter)]
+    pub fn right_ascension(&self) -> f64 {
+        self.right_ascension
+    }
+
+    // Declination (degrees, J2000.0)
+    #[wasm_bindgen(getter)]
+    pub fn declination(&self) -> f64 {
+        self.declination
+    }
+
+    // Visual magnitude
+    #[wasm_bindgen(getter)]
+    pub fn magnitude(&self) -> f64 {
+        self.magnitude
+    }
+
+    // B-V color index
+    #[wasm_bindgen(getter)]
+    pub fn color_index(&self) -> f64 {
+        self.color_index
+    }
+
+    // Effective temperature estimated from the color index (K)
+    #[wasm_bindgen(getter)]
+    pub fn temperature(&self) -> f64 {
+        self.temperature
+    }
+
+    // Display color as a #rrggbb hex string
+    #[wasm_bindgen(getter)]
+    pub fn color(&self) -> String {
+        self.color.clone()
+    }
+
+    // Unit direction in scene axes
+    #[wasm_bindgen(getter)]
+    pub fn position(&self) -> Vec3 {
+        self.position
+    }
+}
+
+// A constellation's stick figure
+#[derive(Debug, Clone)]
+#[wasm_bindgen]
+pub struct Constellation {
+    name: String,
+    lines: Vec<u32>,
+}
+
+#[wasm_bindgen]
+impl Constellation {
+    #[wasm_bindgen(getter)]
+    pub fn name(&self) -> String {
+        self.name.clone()
+    }
+
+    // Packed [from, to, ...] index pairs into the bright_stars() list
+    #[wasm_bindgen(getter)]
+    pub fn lines(&self) -> Uint32Array {
+        Uint32Array::from(self.lines.as_slice())
+    }
+}
+
+// Unit ICRF vector toward a right ascension and declination (degrees)
+fn equatorial_direction(right_ascension: f64, declination: f64) -> Vec3 {
+    let (ra, dec) = (deg_to_rad(right_ascension), deg_to_rad(declination));
+    Vec3::new(dec.cos() * ra.cos(), dec.cos() * ra.sin(), dec.sin())
+}
+
+// The embedded bright-star catalog with scene directions and display colors.
+// Directions share the scene's ecliptic axes, so the zodiac lines up with the
+// planets' paths. This is not a full naked-eye catalog: it has every star to
+// bright_star_complete_magnitude() and, fainter than that, only the stars of
+// the constellation figures, so fill the sky with generate_starfield.
+#[wasm_bindgen]
+pub fn bright_stars() -> Vec<BrightStar> {
+    BRIGHT_STAR_CATALOG
+        .iter()
+        .map(|&(hip, name, right_ascension, declination, magnitude, color_index)| {
+            let temperature = color_index_temperature(color_index);
+            let [r, g, b] = temperature_to_rgb(temperature).map(|c| (c * 255.0).round() as u8);
+            BrightStar {
+                hip,
+                name: name.to_string(),
+                right_ascension,
+                declination,
+                magnitude,
+                color_index,
+                temperature,
+                color: format!("#{r:02x}{g:02x}{b:02x}"),
+                position: icrf_to_scene(&equatorial_direction(right_ascension, declination)),
+            }
+        })
+        .collect()
+}
+
+// Faintest magnitude to which bright_stars() includes every star
+#[wasm_bindgen]
+pub fn bright_star_complete_magnitude() -> f64 {
+    COMPLETE_MAGNITUDE
+}
+
+// Constellation stick figures as index pairs into bright_stars()
+#[wasm_bindgen]
+pub fn constellation_lines() -> Vec<Constellation> {
+    let index = |hip: u32| BRIGHT_STAR_CATALOG.iter().position(|star| star.0 == hip).map(|i| i as u32);
+    CONSTELLATION_FIGURES
+        .iter()
+        .map(|&(name, figure)| Constellation {
+            name: name.to_string(),
+            lines: figure
+                .iter()
+                .filter_map(|&(from, to)| Some([index(from)?, index(to)?]))
+                .flatten()
+                .collect(),
+        })
+        .collect()
+}
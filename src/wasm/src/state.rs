@@ -0,0 +1,143 @@
+use wasm_bindgen::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::f64::consts::PI;
+
+use crate::errors::{check_julian_date, SimulationError};
+use crate::{deg_to_rad, orbital_to_reference, solve_kepler, OrbitalElements, Vec3};
+
+// Tolerance below which an orbit is treated as circular or equatorial
+const DEGENERATE_EPSILON: f64 = 1e-11;
+
+// Heliocentric ecliptic state: position (AU) and velocity (AU/day)
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[wasm_bindgen]
+pub struct StateVector {
+    pub(crate) position: Vec3,
+    pub(crate) velocity: Vec3,
+}
+
+#[wasm_bindgen]
+impl StateVector {
+    #[wasm_bindgen(constructor)]
+    pub fn new(position: Vec3, velocity: Vec3) -> StateVector {
+        StateVector { position, velocity }
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn position(&self) -> Vec3 {
+        self.position
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn velocity(&self) -> Vec3 {
+        self.velocity
+    }
+}
+
+// Calculate the ecliptic state vector of an elliptical orbit at a Julian date
+pub(crate) fn elements_to_state(elements: &OrbitalElements, julian_date: f64) -> StateVector {
+    let days_since_epoch = julian_date - 2451545.0; // J2000.0 epoch
+    let mean_anomaly = deg_to_rad(elements.m0 + elements.n * days_since_epoch);
+    let eccentric_anomaly = solve_kepler(mean_anomaly, elements.e);
+    let mean_motion = deg_to_rad(elements.n);
+
+    let (sin_e, cos_e) = eccentric_anomaly.sin_cos();
+    let semi_minor = elements.a * (1.0 - elements.e * elements.e).sqrt();
+    let e_dot = mean_motion / (1.0 - elements.e * cos_e);
+
+    let omega = deg_to_rad(elements.omega);
+    let w = deg_to_rad(elements.w);
+    let i = deg_to_rad(elements.i);
+
+    StateVector {
+        position: orbital_to_reference(elements.a * (cos_e - elements.e), semi_minor * sin_e, omega, w, i),
+        velocity: orbital_to_reference(-elements.a * sin_e * e_dot, semi_minor * cos_e * e_dot, omega, w, i),
+    }
+}
+
+// Calculate the state vector of orbital elements at a Julian date
+#[wasm_bindgen]
+pub fn state_from_elements(elements: &OrbitalElements, julian_date: f64) -> Result<StateVector, JsError> {
+    check_julian_date(julian_date)?;
+    Ok(elements_to_state(elements, julian_date))
+}
+
+// Recover osculating elliptical elements from a state vector observed at a
+// Julian date; mu is the central body's gravitational parameter (AU³/day²).
+// Fails for a non-positive mu and for states that are not on an ellipse:
+// parabolic, hyperbolic or radial motion.
+#[wasm_bindgen]
+pub fn elements_from_state(position: &Vec3, velocity: &Vec3, mu: f64, julian_date: f64) -> Result<OrbitalElements, JsError> {
+    check_julian_date(julian_date)?;
+    if !(mu > 0.0 && mu.is_finite()) {
+        return Err(SimulationError::invalid_argument(&format!(
+            "Gravitational parameter {} must be positive and finite",
+            mu
+        ))
+        .into());
+    }
+    osculating_elements(position, velocity, mu, julian_date)
+        .ok_or_else(|| SimulationError::invalid_argument("State does not describe an elliptical orbit").into())
+}
+
+// Osculating elements of a state at a date already known to be valid, or None
+// unless the state is on an ellipse about a positive finite mu
+pub(crate) fn osculating_elements(position: &Vec3, velocity: &Vec3, mu: f64, julian_date: f64) -> Option<OrbitalElements> {
+    let r = position.length();
+    let v2 = velocity.dot(velocity);
+    let h = position.cross(velocity);
+    let h_len = h.length();
+
+    // Radial, zero or non-finite states have no orbital plane
+    let finite = r.is_finite() && v2.is_finite() && mu.is_finite();
+    if !finite || mu <= 0.0 || h_len <= DEGENERATE_EPSILON * r * v2.sqrt() {
+        return None;
+    }
+
+    // Eccentricity vector points at periapsis
+    let e_vec = position
+        .scale(v2 - mu / r)
+        .sub(&velocity.scale(position.dot(velocity)))
+        .scale(1.0 / mu);
+    let e = e_vec.length();
+    if e >= 1.0 {
+        return None;
+    }
+    let a = 1.0 / (2.0 / r - v2 / mu);
+
+    let i = (h.z / h_len).clamp(-1.0, 1.0).acos();
+    let node = Vec3::new(-h.y, h.x, 0.0);
+    let node_len = node.length();
+
+    // Longitude of ascending node, undefined (zero) for equatorial orbits
+    let omega = if node_len > DEGENERATE_EPSILON { node.y.atan2(node.x) } else { 0.0 };
+
+    // Angle in the orbital plane measured from the node (or the x axis)
+    let reference = if node_len > DEGENERATE_EPSILON { node.scale(1.0 / node_len) } else { Vec3::new(1.0, 0.0, 0.0) };
+    let angle_from_reference = |v: &Vec3| {
+        let sin = h.dot(&reference.cross(v)) / h_len;
+        sin.atan2(reference.dot(v))
+    };
+
+    // Argument of periapsis, undefined (zero) for circular orbits
+    let w = if e > DEGENERATE_EPSILON { angle_from_reference(&e_vec) } else { 0.0 };
+    let true_anomaly = angle_from_reference(position) - w;
+
+    let eccentric_anomaly = 2.0 * ((1.0 - e).sqrt() * (true_anomaly / 2.0).sin())
+        .atan2((1.0 + e).sqrt() * (true_anomaly / 2.0).cos());
+    let mean_anomaly = eccentric_anomaly - e * eccentric_anomaly.sin();
+
+    // Mean motion in degrees/day; mean anomaly referred back to J2000.0
+    let n = (mu / (a * a * a)).sqrt() * 180.0 / PI;
+    let m0 = (mean_anomaly * 180.0 / PI - n * (julian_date - 2451545.0)).rem_euclid(360.0);
+
+    Some(OrbitalElements {
+        a,
+        e,
+        i: i * 180.0 / PI,
+        omega: (omega * 180.0 / PI).rem_euclid(360.0),
+        w: (w * 180.0 / PI).rem_euclid(360.0),
+        m0,
+        n,
+    })
+}
@@ -0,0 +1,374 @@
+// User-defined planetary systems loaded at runtime
+
+use wasm_bindgen::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::cell::RefCell;
+
+use crate::habitable::HabitableZone;
+use crate::influence::{hill_radius, sphere_of_influence};
+use crate::state::elements_to_state;
+use crate::units::{EARTH_RADIUS_KM, SOLAR_RADIUS_KM};
+use crate::{
+    planet_data_from_state, planet_positions, OrbitalElements, PlanetData, GAUSSIAN_MEAN_MOTION, SUN_EARTH_MASS_RATIO,
+};
+
+// Sun's radius in Earth radii
+const SOLAR_RADIUS: f64 = SOLAR_RADIUS_KM / EARTH_RADIUS_KM;
+
+// Sun's effective temperature (K)
+const SOLAR_TEMPERATURE: f64 = 5772.0;
+
+// Orbital elements of a user-defined body: semi-major axis (AU), eccentricity,
+// angles (degrees) and mean anomaly at an epoch (JD, default J2000.0)
+#[derive(Debug, Clone, Deserialize)]
+pub(crate) struct ElementsDefinition {
+    pub(crate) a: f64,
+    #[serde(default)]
+    pub(crate) e: f64,
+    #[serde(default)]
+    pub(crate) i: f64,
+    #[serde(default)]
+    pub(crate) omega: f64,
+    #[serde(default)]
+    pub(crate) w: f64,
+    #[serde(default)]
+    pub(crate) m0: f64,
+    #[serde(default)]
+    pub(crate) epoch: Option<f64>,
+}
+
+// A body orbiting the system's star. Radius is in Earth radii, mass in Earth
+// masses, day length in hours and temperature in kelvin.
+#[derive(Debug, Clone, Deserialize)]
+pub(crate) struct BodyDefinition {
+    pub(crate) name: String,
+    pub(crate) elements: ElementsDefinition,
+    pub(crate) radius: f64,
+    #[serde(default)]
+    pub(crate) color: Option<String>,
+    #[serde(default)]
+    pub(crate) mass: f64,
+    #[serde(default)]
+    pub(crate) axial_tilt: f64,
+    #[serde(default)]
+    pub(crate) day_length: f64,
+    #[serde(default)]
+    pub(crate) temperature: Option<f64>,
+    #[serde(default)]
+    pub(crate) moons: u32,
+}
+
+// The central star: mass and luminosity in solar units, radius in solar radii
+#[derive(Debug, Clone, Deserialize)]
+pub(crate) struct StarDefinition {
+    pub(crate) name: String,
+    pub(crate) mass: f64,
+    pub(crate) radius: f64,
+    #[serde(default)]
+    pub(crate) luminosity: Option<f64>,
+    #[serde(default)]
+    pub(crate) color: Option<String>,
+}
+
+// A complete system definition. With `replace` set the built-in planets are
+// dropped; otherwise the bodies are added alongside them around the Sun.
+#[derive(Debug, Clone, Deserialize)]
+pub(crate) struct SystemDefinition {
+    #[serde(default)]
+    pub(crate) replace: bool,
+    #[serde(default)]
+    pub(crate) star: Option<StarDefinition>,
+    pub(crate) bodies: Vec<BodyDefinition>,
+}
+
+const DEFAULT_BODY_COLOR: &str = "#9a9a9a";
+const DEFAULT_STAR_COLOR: &str = "#fff4e0";
+
+// A problem found while validating a system definition
+#[derive(Debug, Clone, Serialize)]
+pub struct ValidationError {
+    pub path: String,
+    pub message: String,
+}
+
+fn error(path: impl Into<String>, message: impl Into<String>) -> ValidationError {
+    ValidationError { path: path.into(), message: message.into() }
+}
+
+// Central star of the active system
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[wasm_bindgen]
+pub struct StarData {
+    name: String,
+    mass: f64,
+    radius: f64,
+    luminosity: f64,
+    color: String,
+}
+
+#[wasm_bindgen]
+impl StarData {
+    #[wasm_bindgen(getter)]
+    pub fn name(&self) -> String {
+        self.name.clone()
+    }
+
+    // Mass in solar masses
+    #[wasm_bindgen(getter)]
+    pub fn mass(&self) -> f64 {
+        self.mass
+    }
+
+    // Radius in Earth radii, matching planet radii
+    #[wasm_bindgen(getter)]
+    pub fn radius(&self) -> f64 {
+        self.radius
+    }
+
+    // Luminosity in solar luminosities
+    #[wasm_bindgen(getter)]
+    pub fn luminosity(&self) -> f64 {
+        self.luminosity
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn color(&self) -> String {
+        self.color.clone()
+    }
+
+    // Effective temperature (K) from luminosity and radius
+    #[wasm_bindgen(getter)]
+    pub fn effective_temperature(&self) -> f64 {
+        let radius = self.radius / SOLAR_RADIUS;
+        SOLAR_TEMPERATURE * (self.luminosity / (radius * radius)).powf(0.25)
+    }
+
+    // Habitable zone for the star's luminosity and temperature
+    pub fn habitable_zone(&self) -> HabitableZone {
+        HabitableZone::for_star(self.luminosity, self.effective_temperature())
+    }
+}
+
+impl StarData {
+    fn sun() -> StarData {
+        StarData {
+            name: "Sun".to_string(),
+            mass: 1.0,
+            radius: SOLAR_RADIUS,
+            luminosity: 1.0,
+            color: "#fdb813".to_string(),
+        }
+    }
+}
+
+struct CustomBody {
+    name: String,
+    elements: OrbitalElements,
+    radius: f64,
+    color: String,
+    mass: f64,
+    axial_tilt: f64,
+    day_length: f64,
+    temperature: f64,
+    moons: u32,
+}
+
+struct CustomSystem {
+    star: StarData,
+    replace: bool,
+    bodies: Vec<CustomBody>,
+}
+
+thread_local! {
+    static ACTIVE_SYSTEM: RefCell<Option<CustomSystem>> = const { RefCell::new(None) };
+}
+
+fn is_hex_color(color: &str) -> bool {
+    color.len() == 7 && color.starts_with('#') && color[1..].chars().all(|c| c.is_ascii_hexdigit())
+}
+
+// True for positive numbers, false for zero, negatives and NaN
+fn is_positive(value: f64) -> bool {
+    value > 0.0
+}
+
+// Check a definition, collecting every problem rather than stopping at the first
+fn validate(definition: &SystemDefinition) -> Vec<ValidationError> {
+    let mut errors = Vec::new();
+    if let Some(star) = &definition.star {
+        if star.name.trim().is_empty() {
+            errors.push(error("star.name", "must not be empty"));
+        }
+        if !is_positive(star.mass) {
+            errors.push(error("star.mass", "must be positive"));
+        }
+        if !is_positive(star.radius) {
+            errors.push(error("star.radius", "must be positive"));
+        }
+        if star.luminosity.is_some_and(|l| !is_positive(l)) {
+            errors.push(error("star.luminosity", "must be positive"));
+        }
+        if star.color.as_deref().is_some_and(|color| !is_hex_color(color)) {
+            errors.push(error("star.color", "must be a #rrggbb hex color"));
+        }
+    }
+    match (definition.replace, &definition.star) {
+        (true, None) => errors.push(error("star", "is required when replacing the built-in planets")),
+        (false, Some(_)) => errors.push(error("star", "can only be set when replacing the built-in planets")),
+        _ => {}
+    }
+
+    for (index, body) in definition.bodies.iter().enumerate() {
+        let path = |field: &str| format!("bodies[{index}].{field}");
+        if body.name.trim().is_empty() {
+            errors.push(error(path("name"), "must not be empty"));
+        } else if definition.bodies[..index].iter().any(|other| other.name == body.name) {
+            errors.push(error(path("name"), format!("duplicate body name \"{}\"", body.name)));
+        }
+        let elements = &body.elements;
+        if !is_positive(elements.a) {
+            errors.push(error(path("elements.a"), "semi-major axis must be positive"));
+        }
+        if !(0.0..1.0).contains(&elements.e) {
+            errors.push(error(path("elements.e"), "eccentricity must be in [0, 1)"));
+        }
+        if !(0.0..=180.0).contains(&elements.i) {
+            errors.push(error(path("elements.i"), "inclination must be in [0, 180] degrees"));
+        }
+        let angles = [
+            ("elements.omega", elements.omega),
+            ("elements.w", elements.w),
+            ("elements.m0", elements.m0),
+            ("elements.epoch", elements.epoch.unwrap_or(0.0)),
+        ];
+        for (field, value) in angles {
+            if !value.is_finite() {
+                errors.push(error(path(field), "must be a finite number"));
+            }
+        }
+        if !is_positive(body.radius) {
+            errors.push(error(path("radius"), "must be positive"));
+        }
+        if body.mass.is_nan() || body.mass < 0.0 {
+            errors.push(error(path("mass"), "must not be negative"));
+        }
+        if body.color.as_deref().is_some_and(|color| !is_hex_color(color)) {
+            errors.push(error(path("color"), "must be a #rrggbb hex color"));
+        }
+    }
+    errors
+}
+
+// Build the runtime system from a validated definition
+fn build_system(definition: SystemDefinition) -> CustomSystem {
+    let star = match definition.star {
+        Some(star) => StarData {
+            name: star.name,
+            mass: star.mass,
+            radius: star.radius * SOLAR_RADIUS,
+            // Main-sequence mass-luminosity relation when not given
+            luminosity: star.luminosity.unwrap_or_else(|| star.mass.powf(3.5)),
+            color: star.color.unwrap_or_else(|| DEFAULT_STAR_COLOR.to_string()),
+        },
+        None => StarData::sun(),
+    };
+
+    let bodies = definition
+        .bodies
+        .into_iter()
+        .map(|body| {
+            let e = &body.elements;
+            let n = GAUSSIAN_MEAN_MOTION * star.mass.sqrt() / e.a.powf(1.5);
+            // Refer the mean anomaly back to J2000.0 for the fixed-epoch propagation
+            let m0 = (e.m0 - n * (e.epoch.unwrap_or(2451545.0) - 2451545.0)).rem_euclid(360.0);
+            CustomBody {
+                elements: OrbitalElements::new(e.a, e.e, e.i, e.omega, e.w, m0, n),
+                // Equilibrium temperature of a fast rotator with zero albedo
+                temperature: body.temperature.unwrap_or(278.6 * star.luminosity.powf(0.25) / e.a.sqrt()),
+                name: body.name,
+                radius: body.radius,
+                color: body.color.unwrap_or_else(|| DEFAULT_BODY_COLOR.to_string()),
+                mass: body.mass,
+                axial_tilt: body.axial_tilt,
+                day_length: body.day_length,
+                moons: body.moons,
+            }
+        })
+        .collect();
+
+    CustomSystem { star, replace: definition.replace, bodies }
+}
+
+// Validate and activate a system definition, returning the number of bodies
+pub(crate) fn install_system(definition: SystemDefinition) -> Result<u32, Vec<ValidationError>> {
+    let errors = validate(&definition);
+    if !errors.is_empty() {
+        return Err(errors);
+    }
+    let count = definition.bodies.len() as u32;
+    let system = build_system(definition);
+    ACTIVE_SYSTEM.with(|active| *active.borrow_mut() = Some(system));
+    Ok(count)
+}
+
+// Load a planetary system from JSON, replacing or augmenting the built-in
+// planets. On failure the error is an array of { path, message } objects and
+// the previously active system is kept.
+#[wasm_bindgen]
+pub fn load_system_from_json(json: &str) -> Result<u32, JsValue> {
+    let to_js = |errors: Vec<ValidationError>| serde_wasm_bindgen::to_value(&errors).unwrap_or(JsValue::NULL);
+    let value = js_sys::JSON::parse(json).map_err(|_| to_js(vec![error("", "invalid JSON")]))?;
+    let definition: SystemDefinition =
+        serde_wasm_bindgen::from_value(value).map_err(|e| to_js(vec![error("", e.to_string())]))?;
+    install_system(definition).map_err(to_js)
+}
+
+// Return to the built-in Solar System
+#[wasm_bindgen]
+pub fn reset_system() {
+    ACTIVE_SYSTEM.with(|active| *active.borrow_mut() = None);
+}
+
+// Central star of the active system
+#[wasm_bindgen]
+pub fn system_star() -> StarData {
+    ACTIVE_SYSTEM.with(|active| active.borrow().as_ref().map_or_else(StarData::sun, |system| system.star.clone()))
+}
+
+// Calculate positions of every body in the active system: the built-in planets
+// unless replaced, followed by any user-defined bodies
+#[wasm_bindgen]
+pub fn system_positions(julian_date: f64) -> Result<Vec<PlanetData>, JsError> {
+    ACTIVE_SYSTEM.with(|active| {
+        let active = active.borrow();
+        let Some(system) = active.as_ref() else {
+            return planet_positions(julian_date);
+        };
+
+        let mut bodies = if system.replace { Vec::new() } else { planet_positions(julian_date)? };
+        for body in &system.bodies {
+            let state = elements_to_state(&body.elements, julian_date);
+            let data = (
+                "",
+                body.radius,
+                "",
+                body.elements.a,
+                body.axial_tilt,
+                body.day_length,
+                360.0 / body.elements.n,
+                body.temperature,
+                body.moons,
+                body.mass,
+                5.514 * body.mass / body.radius.powi(3), // Density relative to Earth's
+            );
+            let mut planet = planet_data_from_state(&body.name, &state, &data);
+            planet.color = body.color.clone();
+            // Influence radii scale with the mass of this system's star
+            let mass_ratio = body.mass / (system.star.mass * SUN_EARTH_MASS_RATIO);
+            planet.hill_radius = hill_radius(state.position.length(), mass_ratio);
+            planet.sphere_of_influence = sphere_of_influence(body.elements.a, mass_ratio);
+            bodies.push(planet);
+        }
+        Ok(bodies)
+    })
+}
@@ -0,0 +1,191 @@
+// Hierarchical systems: stars, barycenters and planets arranged in a tree
+// where every node orbits its parent
+
+use wasm_bindgen::prelude::*;
+use js_sys::Float64Array;
+
+use crate::errors::check_julian_date;
+use crate::state::{elements_to_state, StateVector};
+use crate::{to_scene, OrbitalElements, Vec3, GAUSSIAN_MEAN_MOTION};
+
+// How a node moves relative to its parent
+#[derive(Debug, Clone, Copy)]
+enum NodeOrbit {
+    // The tree's root, fixed at the origin
+    Root,
+    // A Keplerian orbit about the parent; the two-body mean motion is computed
+    // from the current masses when the graph is evaluated
+    Kepler(OrbitalElements),
+    // One component of a binary whose parent node is the pair's barycenter. Both
+    // components share the relative orbit of B about A; each is offset from the
+    // barycenter by a signed fraction of the separation.
+    BinaryComponent { relative: OrbitalElements, fraction: f64 },
+}
+
+#[derive(Debug, Clone)]
+struct GraphNode {
+    name: String,
+    // Solar masses; a barycenter node accumulates its binary components' masses
+    mass: f64,
+    barycenter: bool,
+    parent: Option<usize>,
+    orbit: NodeOrbit,
+}
+
+// Parent/child description of a planetary system, from a single star with
+// planets to circumbinary planets around a pair of stars or hierarchical triples.
+// Node indices are assigned in insertion order, with the root at 0.
+#[wasm_bindgen]
+#[derive(Debug, Clone)]
+pub struct SystemGraph {
+    nodes: Vec<GraphNode>,
+}
+
+#[wasm_bindgen]
+impl SystemGraph {
+    // Start a graph from its root: a star, or a massless barycenter that
+    // binary components will be added to
+    #[wasm_bindgen(constructor)]
+    pub fn new(root_name: &str, root_mass: f64) -> SystemGraph {
+        SystemGraph {
+            nodes: vec![GraphNode {
+                name: root_name.to_string(),
+                mass: root_mass,
+                barycenter: false,
+                parent: None,
+                orbit: NodeOrbit::Root,
+            }],
+        }
+    }
+
+    // Add a body (planet, star, or massless barycenter) orbiting a parent node,
+    // with mass in solar masses. The mean motion in the elements is ignored and
+    // recomputed from the masses. Returns the new node's index, or None if the
+    // parent does not exist.
+    pub fn add_body(&mut self, name: &str, parent: usize, mass: f64, elements: &OrbitalElements) -> Option<usize> {
+        self.nodes.get(parent)?;
+        self.nodes.push(GraphNode {
+            name: name.to_string(),
+            mass,
+            barycenter: false,
+            parent: Some(parent),
+            orbit: NodeOrbit::Kepler(*elements),
+        });
+        Some(self.nodes.len() - 1)
+    }
+
+    // Add a pair of stars orbiting an existing barycenter node. The elements
+    // describe B's orbit relative to A (a is the separation in AU). Returns the
+    // index of A; B follows it. The barycenter's mass grows by both masses.
+    pub fn add_binary(
+        &mut self,
+        barycenter: usize,
+        name_a: &str,
+        mass_a: f64,
+        name_b: &str,
+        mass_b: f64,
+        relative: &OrbitalElements,
+    ) -> Option<usize> {
+        let total = mass_a + mass_b;
+        if total <= 0.0 {
+            return None;
+        }
+        let node = self.nodes.get_mut(barycenter)?;
+        node.mass += total;
+        node.barycenter = true;
+        let relative = *relative;
+        for (name, mass, fraction) in [(name_a, mass_a, -mass_b / total), (name_b, mass_b, mass_a / total)] {
+            self.nodes.push(GraphNode {
+                name: name.to_string(),
+                mass,
+                barycenter: false,
+                parent: Some(barycenter),
+                orbit: NodeOrbit::BinaryComponent { relative, fraction },
+            });
+        }
+        Some(self.nodes.len() - 2)
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn node_count(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn node_name(&self, index: usize) -> Option<String> {
+        self.nodes.get(index).map(|node| node.name.clone())
+    }
+
+    // Mass in solar masses; for a barycenter, the total of its binary components
+    pub fn node_mass(&self, index: usize) -> Option<f64> {
+        self.nodes.get(index).map(|node| node.mass)
+    }
+
+    // Index of a node's parent, or None for the root
+    pub fn node_parent(&self, index: usize) -> Option<usize> {
+        self.nodes.get(index)?.parent
+    }
+
+    // Positions of every node at a Julian date, packed [x, y, z, ...] in scene
+    // coordinates with the root at the origin
+    pub fn positions(&self, julian_date: f64) -> Result<Float64Array, JsError> {
+        check_julian_date(julian_date)?;
+        let mut buffer = Vec::with_capacity(self.nodes.len() * 3);
+        for state in self.states(julian_date) {
+            let scene = to_scene(state.position);
+            buffer.extend_from_slice(&[scene.x, scene.y, scene.z]);
+        }
+        Ok(Float64Array::from(buffer.as_slice()))
+    }
+}
+
+impl SystemGraph {
+    // Two-body mean motion (degrees/day) for a gravitational parameter in solar masses
+    fn with_mean_motion(elements: &OrbitalElements, mass: f64) -> OrbitalElements {
+        let mut elements = *elements;
+        elements.n = GAUSSIAN_MEAN_MOTION * mass.sqrt() / elements.a.powf(1.5);
+        elements
+    }
+
+    // State of a node relative to its parent (AU, AU/day)
+    fn relative_state(&self, node: &GraphNode, julian_date: f64) -> StateVector {
+        match (node.orbit, node.parent) {
+            (NodeOrbit::Kepler(elements), Some(parent)) => {
+                let mass = self.nodes[parent].mass + node.mass;
+                elements_to_state(&Self::with_mean_motion(&elements, mass), julian_date)
+            }
+            (NodeOrbit::BinaryComponent { relative, fraction }, Some(parent)) => {
+                let state = elements_to_state(&Self::with_mean_motion(&relative, self.nodes[parent].mass), julian_date);
+                StateVector { position: state.position.scale(fraction), velocity: state.velocity.scale(fraction) }
+            }
+            _ => StateVector { position: Vec3::new(0.0, 0.0, 0.0), velocity: Vec3::new(0.0, 0.0, 0.0) },
+        }
+    }
+
+    // States of every node relative to the root (AU, AU/day). Parents always
+    // precede their children, so one pass accumulates the offsets.
+    pub(crate) fn states(&self, julian_date: f64) -> Vec<StateVector> {
+        let mut states: Vec<StateVector> = Vec::with_capacity(self.nodes.len());
+        for node in &self.nodes {
+            let relative = self.relative_state(node, julian_date);
+            let state = match node.parent {
+                Some(parent) => StateVector {
+                    position: states[parent].position.add(&relative.position),
+                    velocity: states[parent].velocity.add(&relative.velocity),
+                },
+                None => relative,
+            };
+            states.push(state);
+        }
+        states
+    }
+
+    // Index, name and mass (solar masses) of every physical body, skipping
+    // barycenters whose mass is carried by their components
+    pub(crate) fn massive_nodes(&self) -> impl Iterator<Item = (usize, &str, f64)> {
+        self.nodes
+            .iter()
+            .enumerate()
+            .filter(|(_, node)| node.mass > 0.0 && !node.barycenter)
+            .map(|(index, node)| (index, node.name.as_str(), node.mass))
+    }
+}
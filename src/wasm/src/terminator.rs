@@ -0,0 +1,102 @@
+// Subsolar point and day/night terminator of a planet in its body-fixed
+// frame, so night-side textures and shading follow the real illumination
+
+use wasm_bindgen::prelude::*;
+use serde::{Deserialize, Serialize};
+use js_sys::Float64Array;
+use std::f64::consts::PI;
+
+use crate::errors::{check_julian_date, SimulationError};
+use crate::observer::{ecliptic_to_equatorial, mean_obliquity, planet_state};
+use crate::rotation::pole_and_meridian;
+use crate::{deg_to_rad, Vec3, PLANET_ELEMENTS};
+
+// Where the Sun is overhead on a planet, and the circle where it sets
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[wasm_bindgen]
+pub struct Terminator {
+    subsolar_latitude: f64,
+    subsolar_longitude: f64,
+    points: Vec<f64>,
+}
+
+#[wasm_bindgen]
+impl Terminator {
+    // Planetocentric latitude of the subsolar point (degrees)
+    #[wasm_bindgen(getter)]
+    pub fn subsolar_latitude(&self) -> f64 {
+        self.subsolar_latitude
+    }
+
+    // Planetocentric longitude of the subsolar point, east of the prime
+    // meridian (degrees, -180 to 180)
+    #[wasm_bindgen(getter)]
+    pub fn subsolar_longitude(&self) -> f64 {
+        self.subsolar_longitude
+    }
+
+    // The terminator as a closed polyline of unit vectors packed [x, y, z, ...]
+    // in the planet's model space (x toward the prime meridian, y along the
+    // pole), matching the mesh rotated by planet_orientations
+    #[wasm_bindgen(getter)]
+    pub fn points(&self) -> Float64Array {
+        Float64Array::from(self.points.as_slice())
+    }
+
+    // The same polyline as packed [latitude, longitude, ...] (degrees)
+    pub fn coordinates(&self) -> Float64Array {
+        let coordinates: Vec<f64> = self
+            .points
+            .chunks_exact(3)
+            .flat_map(|p| {
+                // Model z points to 90 degrees west
+                [p[1].clamp(-1.0, 1.0).asin().to_degrees(), (-p[2]).atan2(p[0]).to_degrees()]
+            })
+            .collect();
+        Float64Array::from(coordinates.as_slice())
+    }
+}
+
+// Subsolar point and a terminator of segments + 1 points for a planet with a
+// rotation model at a Julian date
+#[wasm_bindgen]
+pub fn terminator(planet: &str, julian_date: f64, segments: u32) -> Result<Terminator, JsError> {
+    check_julian_date(julian_date)?;
+    let index = PLANET_ELEMENTS
+        .iter()
+        .position(|(name, _)| *name == planet)
+        .ok_or_else(|| SimulationError::unknown_body(planet))?;
+    let (ra, dec, w) = pole_and_meridian(planet, julian_date).ok_or_else(|| {
+        SimulationError::invalid_argument(&format!("{} has no rotation model", planet))
+    })?;
+
+    // Body-fixed axes in ICRF: prime meridian, 90 degrees east, and pole
+    let (ra, dec, w) = (deg_to_rad(ra), deg_to_rad(dec), deg_to_rad(w));
+    let pole = Vec3::new(dec.cos() * ra.cos(), dec.cos() * ra.sin(), dec.sin());
+    let node = Vec3::new(-ra.sin(), ra.cos(), 0.0);
+    let meridian = node.scale(w.cos()).add(&pole.cross(&node).scale(w.sin()));
+    let east = pole.cross(&meridian);
+
+    let to_sun = ecliptic_to_equatorial(&planet_state(index, julian_date).position.scale(-1.0), mean_obliquity(2451545.0))
+        .normalize();
+    // Sun direction in model space
+    let sun = Vec3::new(to_sun.dot(&meridian), to_sun.dot(&pole), -to_sun.dot(&east));
+
+    // Two unit vectors spanning the great circle facing the Sun edge-on
+    let reference = if sun.y.abs() < 0.9 { Vec3::new(0.0, 1.0, 0.0) } else { Vec3::new(1.0, 0.0, 0.0) };
+    let u = reference.cross(&sun).normalize();
+    let v = sun.cross(&u);
+    let segments = segments.max(3);
+    let mut points = Vec::with_capacity((segments as usize + 1) * 3);
+    for i in 0..=segments {
+        let angle = 2.0 * PI * i as f64 / segments as f64;
+        let point = u.scale(angle.cos()).add(&v.scale(angle.sin()));
+        points.extend_from_slice(&[point.x, point.y, point.z]);
+    }
+
+    Ok(Terminator {
+        subsolar_latitude: sun.y.clamp(-1.0, 1.0).asin().to_degrees(),
+        subsolar_longitude: (-sun.z).atan2(sun.x).to_degrees(),
+        points,
+    })
+}
@@ -0,0 +1,329 @@
+// Procedural terrain for the rocky bodies: equirectangular height fields with
+// displacement and normal map textures
+
+use wasm_bindgen::prelude::*;
+use js_sys::{Float32Array, Uint8Array};
+
+use crate::deg_to_rad;
+use crate::noise::SimplexNoise;
+use crate::random::Rng;
+
+// Largest texture edge generated
+const MAX_TEXTURE_SIZE: u32 = 4096;
+
+// Slopes are exaggerated in the normal map so relief reads at planet scale
+const NORMAL_MAP_EXAGGERATION: f64 = 20.0;
+
+// Crater rims rise this fraction of the crater's depth above the plain
+const CRATER_RIM_FRACTION: f64 = 0.15;
+
+// A linear trench: center longitude and latitude, half-length and half-width
+// (degrees), and depth (km)
+type Canyon = (f64, f64, f64, f64, f64);
+
+struct TerrainProfile {
+    body: &'static str,
+    radius_km: f64,
+    // Noise frequency on the unit sphere and octave count
+    frequency: f64,
+    octaves: u32,
+    // Peak-to-mean relief of the noise (km) and the share of ridged noise
+    relief: f64,
+    ridged: f64,
+    // Land/sea style asymmetry: heights below zero are scaled by this factor
+    basin_scale: f64,
+    craters: u32,
+    // Largest crater angular radius (degrees)
+    max_crater: f64,
+    canyon: Option<Canyon>,
+}
+
+static TERRAIN_PROFILES: &[TerrainProfile] = &[
+    TerrainProfile {
+        body: "Mercury",
+        radius_km: 2439.7,
+        frequency: 2.0,
+        octaves: 6,
+        relief: 1.5,
+        ridged: 0.2,
+        basin_scale: 1.0,
+        craters: 500,
+        max_crater: 8.0,
+        canyon: None,
+    },
+    TerrainProfile {
+        body: "Venus",
+        radius_km: 6051.8,
+        frequency: 1.5,
+        octaves: 6,
+        relief: 2.0,
+        ridged: 0.5,
+        basin_scale: 0.5,
+        craters: 30,
+        max_crater: 2.0,
+        canyon: None,
+    },
+    TerrainProfile {
+        body: "Earth",
+        radius_km: 6371.0,
+        frequency: 1.2,
+        octaves: 7,
+        relief: 3.0,
+        ridged: 0.3,
+        basin_scale: 1.6,
+        craters: 0,
+        max_crater: 0.0,
+        canyon: None,
+    },
+    TerrainProfile {
+        body: "Moon",
+        radius_km: 1737.4,
+        frequency: 2.0,
+        octaves: 6,
+        relief: 2.0,
+        ridged: 0.1,
+        basin_scale: 1.0,
+        craters: 500,
+        max_crater: 10.0,
+        canyon: None,
+    },
+    // Valles Marineris cuts along the equator east of Tharsis
+    TerrainProfile {
+        body: "Mars",
+        radius_km: 3389.5,
+        frequency: 1.5,
+        octaves: 7,
+        relief: 3.0,
+        ridged: 0.3,
+        basin_scale: 1.0,
+        craters: 200,
+        max_crater: 6.0,
+        canyon: Some((-70.0, -8.0, 30.0, 2.0, 7.0)),
+    },
+];
+
+// Height field with textures derived from it. Row 0 is the north pole and
+// column 0 is longitude -180°, as in standard planetary maps.
+#[derive(Debug, Clone)]
+#[wasm_bindgen]
+pub struct HeightMap {
+    width: u32,
+    height: u32,
+    heights: Vec<f32>,
+    min_height: f64,
+    max_height: f64,
+    radius: f64,
+}
+
+#[wasm_bindgen]
+impl HeightMap {
+    #[wasm_bindgen(getter)]
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    // Elevation of each pixel relative to the mean radius (km), row by row
+    #[wasm_bindgen(getter)]
+    pub fn heights(&self) -> Float32Array {
+        Float32Array::from(self.heights.as_slice())
+    }
+
+    // Lowest elevation (km), mapped to black in the displacement map
+    #[wasm_bindgen(getter)]
+    pub fn min_height(&self) -> f64 {
+        self.min_height
+    }
+
+    // Highest elevation (km), mapped to white in the displacement map
+    #[wasm_bindgen(getter)]
+    pub fn max_height(&self) -> f64 {
+        self.max_height
+    }
+
+    // Mean radius of the body (km)
+    #[wasm_bindgen(getter)]
+    pub fn radius(&self) -> f64 {
+        self.radius
+    }
+
+    // Grayscale RGBA displacement texture spanning min_height to max_height
+    pub fn displacement_map(&self) -> Uint8Array {
+        let span = (self.max_height - self.min_height).max(f64::EPSILON);
+        let mut pixels = Vec::with_capacity(self.heights.len() * 4);
+        for &h in &self.heights {
+            let level = ((h as f64 - self.min_height) / span * 255.0).round() as u8;
+            pixels.extend_from_slice(&[level, level, level, 255]);
+        }
+        Uint8Array::from(pixels.as_slice())
+    }
+
+    // Tangent-space RGBA normal map (+x east, +y north, +z out of the surface)
+    pub fn normal_map(&self) -> Uint8Array {
+        let (width, height) = (self.width as usize, self.height as usize);
+        let row_step = std::f64::consts::PI * self.radius / height as f64;
+        let equator_column_step = 2.0 * std::f64::consts::PI * self.radius / width as f64;
+        let mut pixels = Vec::with_capacity(self.heights.len() * 4);
+        for row in 0..height {
+            let latitude = pixel_latitude(row, height);
+            // Longitude spacing shrinks toward the poles
+            let column_step = (equator_column_step * latitude.cos()).max(row_step * 1e-3);
+            let (north, south) = (row.saturating_sub(1), (row + 1).min(height - 1));
+            for column in 0..width {
+                let (west, east) = ((column + width - 1) % width, (column + 1) % width);
+                let at = |r: usize, c: usize| self.heights[r * width + c] as f64;
+                let slope_east = (at(row, east) - at(row, west)) / (2.0 * column_step);
+                let slope_north = (at(north, column) - at(south, column)) / ((south - north).max(1) as f64 * row_step);
+
+                let (nx, ny) = (-slope_east * NORMAL_MAP_EXAGGERATION, -slope_north * NORMAL_MAP_EXAGGERATION);
+                let length = (nx * nx + ny * ny + 1.0).sqrt();
+                let encode = |n: f64| ((n / length * 0.5 + 0.5) * 255.0).round() as u8;
+                pixels.extend_from_slice(&[encode(nx), encode(ny), encode(1.0), 255]);
+            }
+        }
+        Uint8Array::from(pixels.as_slice())
+    }
+}
+
+// Latitude (radians) at the center of a pixel row
+pub(crate) fn pixel_latitude(row: usize, height: usize) -> f64 {
+    std::f64::consts::FRAC_PI_2 - (row as f64 + 0.5) * std::f64::consts::PI / height as f64
+}
+
+// Longitude (radians) at the center of a pixel column
+pub(crate) fn pixel_longitude(column: usize, width: usize) -> f64 {
+    -std::f64::consts::PI + (column as f64 + 0.5) * 2.0 * std::f64::consts::PI / width as f64
+}
+
+// Unit vector toward a longitude and latitude (radians)
+pub(crate) fn unit_vector(longitude: f64, latitude: f64) -> [f64; 3] {
+    [latitude.cos() * longitude.cos(), latitude.cos() * longitude.sin(), latitude.sin()]
+}
+
+// Noise-only elevation (km) at a point on the unit sphere
+fn base_height(profile: &TerrainProfile, noise: &SimplexNoise, p: [f64; 3]) -> f64 {
+    let [x, y, z] = p.map(|c| c * profile.frequency);
+    let smooth = noise.fbm(x, y, z, profile.octaves);
+    // Offset the ridged sample so the two layers are uncorrelated
+    let ridges = noise.ridged(x + 31.7, y - 12.9, z + 5.3, profile.octaves) * 2.0 - 1.0;
+    let h = profile.relief * ((1.0 - profile.ridged) * smooth + profile.ridged * ridges);
+    if h < 0.0 {
+        h * profile.basin_scale
+    } else {
+        h
+    }
+}
+
+// Depth of a canyon (km, positive down) at a longitude and latitude (degrees)
+fn canyon_depth(canyon: &Canyon, noise: &SimplexNoise, longitude: f64, latitude: f64, p: [f64; 3]) -> f64 {
+    let &(center_lon, center_lat, half_length, half_width, depth) = canyon;
+    let along = ((longitude - center_lon + 180.0).rem_euclid(360.0) - 180.0) / half_length;
+    if along.abs() >= 1.0 {
+        return 0.0;
+    }
+    // Wander the axis and vary the depth so the trench is not a straight groove
+    let wander = noise.sample(p[0] * 4.0, p[1] * 4.0, p[2] * 4.0) * half_width;
+    let across = (latitude - center_lat - wander) / half_width;
+    let variation = 0.7 + 0.3 * noise.ridged(p[0] * 8.0, p[1] * 8.0, p[2] * 8.0, 3);
+    depth * (1.0 - along * along) * (-across * across).exp() * variation
+}
+
+// Elevation (in units of crater depth) of a crater's bowl and rim at a distance
+// r in crater radii, fading to zero at two radii
+fn crater_profile(r: f64) -> f64 {
+    if r < 1.0 {
+        r * r - 1.0 + CRATER_RIM_FRACTION
+    } else {
+        CRATER_RIM_FRACTION * (2.0 - r).max(0.0).powi(3)
+    }
+}
+
+// Stamp seeded craters into the height field, visiting only the pixels each
+// crater can reach
+fn add_craters(profile: &TerrainProfile, seed: u32, heights: &mut [f64], width: usize, height: usize) {
+    let mut rng = Rng::new(seed as u64 ^ 0xC7A7E5);
+    // Angular radii follow a power law n(r) ~ r^-3, so small craters dominate
+    let min_crater = deg_to_rad(0.3).min(deg_to_rad(profile.max_crater));
+    let (low, high) = (min_crater.powi(-2), deg_to_rad(profile.max_crater).powi(-2));
+    let row_step = std::f64::consts::PI / height as f64;
+    let column_step = 2.0 * std::f64::consts::PI / width as f64;
+
+    for _ in 0..profile.craters {
+        let longitude = rng.range(-std::f64::consts::PI, std::f64::consts::PI);
+        let latitude = rng.range(-1.0, 1.0).asin();
+        let radius = (low + rng.next_f64() * (high - low)).powf(-0.5);
+        // Simple bowls are 1:5 deep; large complex craters are shallower
+        let diameter = 2.0 * radius * profile.radius_km;
+        let depth = (0.2 * diameter).min(diameter.powf(0.3));
+        let center = unit_vector(longitude, latitude);
+
+        let reach = 2.0 * radius;
+        let first_row = ((std::f64::consts::FRAC_PI_2 - latitude - reach) / row_step).floor().max(0.0) as usize;
+        let last_row = (((std::f64::consts::FRAC_PI_2 - latitude + reach) / row_step).ceil() as usize).min(height - 1);
+        let polar = latitude.abs() + reach >= std::f64::consts::FRAC_PI_2;
+        let half_columns = if polar {
+            width / 2
+        } else {
+            ((reach / (latitude.abs() + reach).cos() / column_step).ceil() as usize + 1).min(width / 2)
+        };
+        let center_column = ((longitude + std::f64::consts::PI) / column_step) as isize;
+
+        for row in first_row..=last_row {
+            let lat = pixel_latitude(row, height);
+            for offset in -(half_columns as isize)..=(half_columns as isize) {
+                let column = (center_column + offset).rem_euclid(width as isize) as usize;
+                let p = unit_vector(pixel_longitude(column, width), lat);
+                let cosine = p[0] * center[0] + p[1] * center[1] + p[2] * center[2];
+                let r = cosine.clamp(-1.0, 1.0).acos() / radius;
+                if r < 2.0 {
+                    heights[row * width + column] += depth * crater_profile(r);
+                }
+            }
+        }
+    }
+}
+
+// Generate a procedural height map for "Mercury", "Venus", "Earth", "Moon" or
+// "Mars" at the given texture size (an equirectangular 2:1 layout is expected,
+// e.g. 1024 x 512). Terrain is fBM and ridged simplex noise tuned per body, with
+// seeded impact craters and Valles Marineris on Mars. Returns None for an
+// unknown body or an empty size.
+#[wasm_bindgen]
+pub fn planet_height_map(body: &str, width: u32, height: u32, seed: u32) -> Option<HeightMap> {
+    let profile = TERRAIN_PROFILES.iter().find(|profile| profile.body == body)?;
+    if width == 0 || height == 0 {
+        return None;
+    }
+    let (width, height) = (width.min(MAX_TEXTURE_SIZE) as usize, height.min(MAX_TEXTURE_SIZE) as usize);
+    let noise = SimplexNoise::new(seed as u64);
+
+    let mut heights = Vec::with_capacity(width * height);
+    for row in 0..height {
+        let latitude = pixel_latitude(row, height);
+        for column in 0..width {
+            let longitude = pixel_longitude(column, width);
+            let p = unit_vector(longitude, latitude);
+            let mut h = base_height(profile, &noise, p);
+            if let Some(canyon) = &profile.canyon {
+                h -= canyon_depth(canyon, &noise, longitude.to_degrees(), latitude.to_degrees(), p);
+            }
+            heights.push(h);
+        }
+    }
+    add_craters(profile, seed, &mut heights, width, height);
+
+    let min_height = heights.iter().copied().fold(f64::INFINITY, f64::min);
+    let max_height = heights.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+    Some(HeightMap {
+        width: width as u32,
+        height: height as u32,
+        heights: heights.iter().map(|&h| h as f32).collect(),
+        min_height,
+        max_height,
+        radius: profile.radius_km,
+    })
+}
@@ -0,0 +1,155 @@
+// Solar irradiance and temperatures from a body's current distance to the
+// Sun, so eccentric orbits show seasonal warming and cooling
+
+use wasm_bindgen::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::errors::{check_julian_date, SimulationError};
+use crate::lagrange::body_state_and_mass;
+use crate::{PhysicalData, DWARF_PLANET_DATA, PLANET_DATA};
+
+// Total solar irradiance at 1 AU (W/m²)
+const SOLAR_CONSTANT: f64 = 1361.0;
+
+// Stefan-Boltzmann constant (W/m²/K⁴)
+const STEFAN_BOLTZMANN: f64 = 5.670374419e-8;
+
+// Bond albedos, the fraction of all incident sunlight reflected
+static BOND_ALBEDO: &[(&str, f64)] = &[
+    ("Mercury", 0.088),
+    ("Venus", 0.76),
+    ("Earth", 0.306),
+    ("Mars", 0.25),
+    ("Jupiter", 0.343),
+    ("Saturn", 0.342),
+    ("Uranus", 0.30),
+    ("Neptune", 0.29),
+    ("Ceres", 0.03),
+    ("Pluto", 0.72),
+    ("Haumea", 0.51),
+    ("Makemake", 0.74),
+    ("Eris", 0.90),
+];
+
+// Assumed for bodies missing from the table
+const DEFAULT_BOND_ALBEDO: f64 = 0.1;
+
+pub(crate) fn bond_albedo(name: &str) -> f64 {
+    BOND_ALBEDO
+        .iter()
+        .find(|(body, _)| *body == name)
+        .map_or(DEFAULT_BOND_ALBEDO, |(_, albedo)| *albedo)
+}
+
+// Solar irradiance (W/m²) at a distance from the Sun (AU)
+pub(crate) fn solar_flux(distance: f64) -> f64 {
+    SOLAR_CONSTANT / (distance * distance)
+}
+
+// Temperature (K) of a fast-rotating body in radiative balance: it absorbs
+// over its cross-section and radiates from its whole surface
+pub(crate) fn equilibrium_temperature(albedo: f64, distance: f64) -> f64 {
+    (solar_flux(distance) * (1.0 - albedo) / (4.0 * STEFAN_BOLTZMANN)).powf(0.25)
+}
+
+// Ratio of a body's tabulated mean temperature to its equilibrium temperature
+// at its mean distance, folding greenhouse warming, internal heat and uneven
+// day-night heating into one factor (Venus about 3.2, Earth 1.1)
+pub(crate) fn greenhouse_factor(name: &str, data: &PhysicalData) -> f64 {
+    let (mean_temperature, mean_distance) = (data.7, data.3);
+    if mean_distance <= 0.0 {
+        return 1.0;
+    }
+    mean_temperature / equilibrium_temperature(bond_albedo(name), mean_distance)
+}
+
+// Instantaneous temperature (K) of a body at a distance from the Sun (AU),
+// equal to the tabulated mean at its mean distance
+pub(crate) fn temperature(name: &str, data: &PhysicalData, distance: f64) -> f64 {
+    if distance <= 0.0 || data.3 <= 0.0 {
+        return data.7;
+    }
+    greenhouse_factor(name, data) * equilibrium_temperature(bond_albedo(name), distance)
+}
+
+// Irradiance and temperatures of a body at one epoch
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[wasm_bindgen]
+pub struct ThermalState {
+    name: String,
+    distance: f64,
+    albedo: f64,
+    greenhouse_factor: f64,
+}
+
+#[wasm_bindgen]
+impl ThermalState {
+    #[wasm_bindgen(getter)]
+    pub fn name(&self) -> String {
+        self.name.clone()
+    }
+
+    // Heliocentric distance (AU)
+    #[wasm_bindgen(getter)]
+    pub fn distance(&self) -> f64 {
+        self.distance
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn albedo(&self) -> f64 {
+        self.albedo
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn greenhouse_factor(&self) -> f64 {
+        self.greenhouse_factor
+    }
+
+    // Solar irradiance (W/m²)
+    #[wasm_bindgen(getter)]
+    pub fn solar_flux(&self) -> f64 {
+        solar_flux(self.distance)
+    }
+
+    // Irradiance absorbed, averaged over the whole surface (W/m²)
+    #[wasm_bindgen(getter)]
+    pub fn absorbed_flux(&self) -> f64 {
+        solar_flux(self.distance) * (1.0 - self.albedo) / 4.0
+    }
+
+    // Temperature without greenhouse warming or internal heat (K)
+    #[wasm_bindgen(getter)]
+    pub fn equilibrium_temperature(&self) -> f64 {
+        equilibrium_temperature(self.albedo, self.distance)
+    }
+
+    // Surface or cloud-top temperature (K)
+    #[wasm_bindgen(getter)]
+    pub fn temperature(&self) -> f64 {
+        self.greenhouse_factor * equilibrium_temperature(self.albedo, self.distance)
+    }
+}
+
+// Irradiance and temperatures of a planet or dwarf planet at a Julian date
+#[wasm_bindgen]
+pub fn thermal_state(body: &str, julian_date: f64) -> Result<ThermalState, JsError> {
+    check_julian_date(julian_date)?;
+    let data = PLANET_DATA
+        .iter()
+        .chain(DWARF_PLANET_DATA.iter())
+        .find(|data| data.0 == body)
+        .ok_or_else(|| SimulationError::unknown_body(body))?;
+    let (state, _) = body_state_and_mass(body, julian_date).ok_or_else(|| SimulationError::unknown_body(body))?;
+    Ok(ThermalState {
+        name: body.to_string(),
+        distance: state.position.length(),
+        albedo: bond_albedo(body),
+        greenhouse_factor: greenhouse_factor(body, data),
+    })
+}
+
+// Solar irradiance (W/m²) at a planet or dwarf planet on a Julian date
+#[wasm_bindgen]
+pub fn solar_flux_at(body: &str, julian_date: f64) -> Result<f64, JsError> {
+    Ok(thermal_state(body, julian_date)?.solar_flux())
+}
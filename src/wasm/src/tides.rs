@@ -0,0 +1,141 @@
+// Tidal stretching of moons by their planets and Roche limits, for showing
+// why rings lie inside the limit and flagging moons close to disruption
+
+use wasm_bindgen::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::f64::consts::PI;
+
+use crate::errors::{check_julian_date, SimulationError};
+use crate::moons::{moon_positions, PARENT_RADII};
+use crate::units::{EARTH_RADIUS_KM, GM_EARTH};
+use crate::{PhysicalData, DWARF_PLANET_DATA, PLANET_DATA};
+
+// Gravitational constant (km³/kg/s²) and kilograms per km³ at 1 g/cm³
+const GRAVITATIONAL_CONSTANT: f64 = 6.6743e-20;
+const DENSITY_TO_KG_PER_KM3: f64 = 1e12;
+
+// Mean densities (g/cm³) of the modelled moons
+static MOON_DENSITIES: &[(&str, f64)] = &[
+    ("Moon", 3.344),
+    ("Io", 3.528),
+    ("Europa", 3.013),
+    ("Ganymede", 1.942),
+    ("Callisto", 1.834),
+    ("Mimas", 1.149),
+    ("Enceladus", 1.609),
+    ("Tethys", 0.984),
+    ("Dione", 1.478),
+    ("Rhea", 1.237),
+    ("Titan", 1.880),
+    ("Iapetus", 1.088),
+];
+
+// Fluid Roche limit (km) of a primary of the given radius (km) and density
+// for a satellite of another density: inside it, tides tear apart a
+// satellite held together only by its own gravity
+pub(crate) fn fluid_roche_limit(primary_radius: f64, primary_density: f64, satellite_density: f64) -> f64 {
+    2.44 * primary_radius * (primary_density / satellite_density).cbrt()
+}
+
+fn physical_data(name: &str) -> Option<&'static PhysicalData> {
+    PLANET_DATA.iter().chain(DWARF_PLANET_DATA.iter()).find(|data| data.0 == name)
+}
+
+// Fluid Roche limit (km) of a planet or dwarf planet for a satellite of the
+// given density (g/cm³); about 0.9 for ring ice
+#[wasm_bindgen]
+pub fn roche_limit(primary: &str, satellite_density: f64) -> Result<f64, JsError> {
+    let data = physical_data(primary).ok_or_else(|| SimulationError::unknown_body(primary))?;
+    if satellite_density.is_nan() || satellite_density <= 0.0 {
+        return Err(SimulationError::invalid_argument(&format!(
+            "Satellite density must be positive, got {}",
+            satellite_density
+        ))
+        .into());
+    }
+    Ok(fluid_roche_limit(data.1 * EARTH_RADIUS_KM, data.10, satellite_density))
+}
+
+// Tidal state of a moon at one epoch
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[wasm_bindgen]
+pub struct MoonTide {
+    name: String,
+    distance: f64,
+    tidal_acceleration: f64,
+    surface_gravity: f64,
+    roche_limit: f64,
+}
+
+#[wasm_bindgen]
+impl MoonTide {
+    #[wasm_bindgen(getter)]
+    pub fn name(&self) -> String {
+        self.name.clone()
+    }
+
+    // Distance from the planet's center (km)
+    #[wasm_bindgen(getter)]
+    pub fn distance(&self) -> f64 {
+        self.distance
+    }
+
+    // Difference between the planet's pull at the moon's near side and at its
+    // center (m/s²)
+    #[wasm_bindgen(getter)]
+    pub fn tidal_acceleration(&self) -> f64 {
+        self.tidal_acceleration
+    }
+
+    // The moon's own surface gravity (m/s²)
+    #[wasm_bindgen(getter)]
+    pub fn surface_gravity(&self) -> f64 {
+        self.surface_gravity
+    }
+
+    // Tidal acceleration as a fraction of surface gravity; 1 or more would
+    // lift loose material off the surface
+    #[wasm_bindgen(getter)]
+    pub fn tidal_ratio(&self) -> f64 {
+        self.tidal_acceleration / self.surface_gravity
+    }
+
+    // Fluid Roche limit for the moon's density (km)
+    #[wasm_bindgen(getter)]
+    pub fn roche_limit(&self) -> f64 {
+        self.roche_limit
+    }
+
+    // Distance in Roche limits: below 1 the moon would be torn apart, and
+    // values near 1 are close to disruption
+    #[wasm_bindgen(getter)]
+    pub fn roche_distance(&self) -> f64 {
+        self.distance / self.roche_limit
+    }
+}
+
+// Tides raised on each modelled moon of a planet at a Julian date, in
+// moon_positions order
+#[wasm_bindgen]
+pub fn moon_tides(planet: &str, julian_date: f64) -> Result<Vec<MoonTide>, JsError> {
+    check_julian_date(julian_date)?;
+    let data = physical_data(planet).ok_or_else(|| SimulationError::unknown_body(planet))?;
+    let gm = GM_EARTH * data.9;
+    let parent_radius = PARENT_RADII.iter().find(|(name, _)| *name == planet).map_or(1.0, |(_, radius)| *radius);
+    Ok(moon_positions(planet, julian_date)?
+        .iter()
+        .map(|moon| {
+            let name = moon.name();
+            let density = MOON_DENSITIES.iter().find(|(body, _)| *body == name).map_or(2.0, |(_, density)| *density);
+            let distance = moon.position().length() * parent_radius;
+            let radius = moon.radius();
+            MoonTide {
+                tidal_acceleration: 2.0 * gm * radius / distance.powi(3) * 1000.0,
+                surface_gravity: 4.0 / 3.0 * PI * GRAVITATIONAL_CONSTANT * density * DENSITY_TO_KG_PER_KM3 * radius * 1000.0,
+                roche_limit: fluid_roche_limit(data.1 * EARTH_RADIUS_KM, data.10, density),
+                name,
+                distance,
+            }
+        })
+        .collect())
+}
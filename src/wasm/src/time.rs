@@ -0,0 +1,252 @@
+// Astronomical time scales: UTC with leap seconds, TAI, TT, TDB and UT1
+//
+// Ephemeris functions expect TDB (indistinguishable from TT at this model's
+// accuracy), while sidereal time and rise/set expect UT1. A Time converts a
+// wall-clock UTC epoch into whichever scale a calculation needs.
+
+use wasm_bindgen::prelude::*;
+
+use crate::calendar::calendar_to_julian_date;
+use crate::errors::check_finite_date;
+
+const SECONDS_PER_DAY: f64 = 86400.0;
+
+// TT runs ahead of TAI by a fixed offset
+const TT_MINUS_TAI: f64 = 32.184;
+
+// Unix epoch (1970-01-01T00:00:00 UTC) as a Julian date
+const UNIX_EPOCH: f64 = 2440587.5;
+
+// TAI - UTC (seconds) from the first day of each listed month (IERS Bulletin C)
+static LEAP_SECONDS: &[(i32, u32, f64)] = &[
+    (1972, 1, 10.0),
+    (1972, 7, 11.0),
+    (1973, 1, 12.0),
+    (1974, 1, 13.0),
+    (1975, 1, 14.0),
+    (1976, 1, 15.0),
+    (1977, 1, 16.0),
+    (1978, 1, 17.0),
+    (1979, 1, 18.0),
+    (1980, 1, 19.0),
+    (1981, 7, 20.0),
+    (1982, 7, 21.0),
+    (1983, 7, 22.0),
+    (1985, 7, 23.0),
+    (1988, 1, 24.0),
+    (1990, 1, 25.0),
+    (1991, 1, 26.0),
+    (1992, 7, 27.0),
+    (1993, 7, 28.0),
+    (1994, 7, 29.0),
+    (1996, 1, 30.0),
+    (1997, 7, 31.0),
+    (1999, 1, 32.0),
+    (2006, 1, 33.0),
+    (2009, 1, 34.0),
+    (2012, 7, 35.0),
+    (2015, 7, 36.0),
+    (2017, 1, 37.0),
+];
+
+// Measured Delta T follows the leap seconds until this year; beyond it the
+// long-term model takes over
+const DELTA_T_OBSERVED_UNTIL: f64 = 2026.0;
+
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimeScale {
+    Utc = 0,
+    Tai = 1,
+    Tt = 2,
+    Tdb = 3,
+    Ut1 = 4,
+}
+
+// TAI - UTC (seconds) at a UTC Julian date, or None before leap seconds began
+fn leap_seconds_at(utc: f64) -> Option<f64> {
+    LEAP_SECONDS
+        .iter()
+        .rev()
+        .find(|&&(year, month, _)| utc >= calendar_to_julian_date(year, month, 1.0))
+        .map(|&(_, _, seconds)| seconds)
+}
+
+// Decimal year of a Julian date
+fn decimal_year(julian_date: f64) -> f64 {
+    2000.0 + (julian_date - 2451545.0) / 365.25
+}
+
+// Delta T = TT - UT1 (seconds) from the Espenak & Meeus (2006) polynomials
+fn delta_t_model(year: f64) -> f64 {
+    let long_term = |y: f64| -20.0 + 32.0 * ((y - 1820.0) / 100.0).powi(2);
+    let poly = |t: f64, c: &[f64]| c.iter().rev().fold(0.0, |sum, &coefficient| sum * t + coefficient);
+    match year {
+        y if y < -500.0 => long_term(y),
+        y if y < 500.0 => poly(
+            y / 100.0,
+            &[10583.6, -1014.41, 33.78311, -5.952053, -0.1798452, 0.022174192, 0.0090316521],
+        ),
+        y if y < 1600.0 => poly(
+            (y - 1000.0) / 100.0,
+            &[1574.2, -556.01, 71.23472, 0.319781, -0.8503463, -0.005050998, 0.0083572073],
+        ),
+        y if y < 1700.0 => poly(y - 1600.0, &[120.0, -0.9808, -0.01532, 1.0 / 7129.0]),
+        y if y < 1800.0 => poly(y - 1700.0, &[8.83, 0.1603, -0.0059285, 0.00013336, -1.0 / 1174000.0]),
+        y if y < 1860.0 => poly(
+            y - 1800.0,
+            &[13.72, -0.332447, 0.0068612, 0.0041116, -0.00037436, 0.0000121272, -0.0000001699, 0.000000000875],
+        ),
+        y if y < 1900.0 => poly(y - 1860.0, &[7.62, 0.5737, -0.251754, 0.01680668, -0.0004473624, 1.0 / 233174.0]),
+        y if y < 1920.0 => poly(y - 1900.0, &[-2.79, 1.494119, -0.0598939, 0.0061966, -0.000197]),
+        y if y < 1941.0 => poly(y - 1920.0, &[21.20, 0.84493, -0.076100, 0.0020936]),
+        y if y < 1961.0 => poly(y - 1950.0, &[29.07, 0.407, -1.0 / 233.0, 1.0 / 2547.0]),
+        y if y < 1986.0 => poly(y - 1975.0, &[45.45, 1.067, -1.0 / 260.0, -1.0 / 718.0]),
+        y if y < 2005.0 => poly(
+            y - 2000.0,
+            &[63.86, 0.3345, -0.060374, 0.0017275, 0.000651814, 0.00002373599],
+        ),
+        y if y < 2050.0 => poly(y - 2000.0, &[62.92, 0.32217, 0.005589]),
+        y if y < 2150.0 => long_term(y) - 0.5628 * (2150.0 - y),
+        y => long_term(y),
+    }
+}
+
+// Delta T = TT - UT1 (seconds) at a Julian date. Between 1972 and recent years
+// it comes from the leap seconds (UT1 stays within 0.9 s of UTC); later dates
+// ease from the last measured value back onto the model by 2150.
+pub(crate) fn delta_t(julian_date: f64) -> f64 {
+    let year = decimal_year(julian_date);
+    let leap = leap_seconds_at(julian_date);
+    match leap {
+        Some(leap) if year < DELTA_T_OBSERVED_UNTIL => TT_MINUS_TAI + leap,
+        Some(leap) => {
+            let offset = TT_MINUS_TAI + leap - delta_t_model(DELTA_T_OBSERVED_UNTIL);
+            let fade = ((2150.0 - year) / (2150.0 - DELTA_T_OBSERVED_UNTIL)).clamp(0.0, 1.0);
+            delta_t_model(year) + offset * fade
+        }
+        None => delta_t_model(year),
+    }
+}
+
+// TDB - TT (seconds), dominated by Earth's eccentric orbit
+fn tdb_minus_tt(julian_date: f64) -> f64 {
+    let g = (357.53 + 0.98560028 * (julian_date - 2451545.0)).to_radians();
+    0.001657 * g.sin() + 0.000014 * (2.0 * g).sin()
+}
+
+// An instant, stored as a TT Julian date
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Time {
+    tt: f64,
+}
+
+#[wasm_bindgen]
+impl Time {
+    // An instant given as a Julian date in any scale
+    #[wasm_bindgen(constructor)]
+    pub fn new(julian_date: f64, scale: TimeScale) -> Result<Time, JsError> {
+        check_finite_date(julian_date)?;
+        Ok(Time::in_scale(julian_date, scale))
+    }
+
+    // The instant of a JavaScript timestamp (milliseconds since 1970, UTC)
+    pub fn from_unix_ms(milliseconds: f64) -> Result<Time, JsError> {
+        Time::new(UNIX_EPOCH + milliseconds / (SECONDS_PER_DAY * 1000.0), TimeScale::Utc)
+    }
+
+    // Julian date of this instant in a scale
+    pub fn to_scale(&self, scale: TimeScale) -> f64 {
+        let seconds = |s: f64| s / SECONDS_PER_DAY;
+        match scale {
+            TimeScale::Tt => self.tt,
+            TimeScale::Tai => self.tt - seconds(TT_MINUS_TAI),
+            TimeScale::Tdb => self.tt + seconds(tdb_minus_tt(self.tt)),
+            TimeScale::Ut1 => self.tt - seconds(delta_t(self.tt)),
+            TimeScale::Utc => {
+                let tai = self.tt - seconds(TT_MINUS_TAI);
+                // Look the offset up at the UTC date, which can differ near a leap second
+                match leap_seconds_at(tai).and_then(|leap| leap_seconds_at(tai - seconds(leap))) {
+                    Some(leap) => tai - seconds(leap),
+                    None => self.to_scale(TimeScale::Ut1),
+                }
+            }
+        }
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn utc(&self) -> f64 {
+        self.to_scale(TimeScale::Utc)
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn tai(&self) -> f64 {
+        self.to_scale(TimeScale::Tai)
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn tt(&self) -> f64 {
+        self.tt
+    }
+
+    // Barycentric dynamical time, the argument of the planetary ephemeris
+    #[wasm_bindgen(getter)]
+    pub fn tdb(&self) -> f64 {
+        self.to_scale(TimeScale::Tdb)
+    }
+
+    // Universal time, the argument of sidereal time and Earth's rotation
+    #[wasm_bindgen(getter)]
+    pub fn ut1(&self) -> f64 {
+        self.to_scale(TimeScale::Ut1)
+    }
+
+    // TT - UT1 (seconds)
+    #[wasm_bindgen(getter)]
+    pub fn delta_t(&self) -> f64 {
+        delta_t(self.tt)
+    }
+
+    // TAI - UTC (seconds); zero before 1972, when UTC had no leap seconds
+    #[wasm_bindgen(getter)]
+    pub fn leap_seconds(&self) -> f64 {
+        leap_seconds_at(self.utc()).unwrap_or(0.0)
+    }
+
+    // Milliseconds since 1970 UTC, for JavaScript Date
+    #[wasm_bindgen(getter)]
+    pub fn unix_ms(&self) -> f64 {
+        (self.utc() - UNIX_EPOCH) * SECONDS_PER_DAY * 1000.0
+    }
+
+    // This instant shifted by a number of TT days
+    pub fn add_days(&self, days: f64) -> Time {
+        Time { tt: self.tt + days }
+    }
+}
+
+impl Time {
+    // An instant at a Julian date already known to be valid
+    pub(crate) fn in_scale(julian_date: f64, scale: TimeScale) -> Time {
+        let seconds = |s: f64| s / SECONDS_PER_DAY;
+        let tt = match scale {
+            TimeScale::Tt => julian_date,
+            TimeScale::Tai => julian_date + seconds(TT_MINUS_TAI),
+            TimeScale::Tdb => julian_date - seconds(tdb_minus_tt(julian_date)),
+            TimeScale::Ut1 => julian_date + seconds(delta_t(julian_date)),
+            TimeScale::Utc => match leap_seconds_at(julian_date) {
+                Some(leap) => julian_date + seconds(leap + TT_MINUS_TAI),
+                // Before 1972 UTC is taken to be UT1
+                None => julian_date + seconds(delta_t(julian_date)),
+            },
+        };
+        Time { tt }
+    }
+}
+
+// Convert a Julian date from one time scale to another
+#[wasm_bindgen]
+pub fn convert_time(julian_date: f64, from: TimeScale, to: TimeScale) -> Result<f64, JsError> {
+    Ok(Time::new(julian_date, from)?.to_scale(to))
+}
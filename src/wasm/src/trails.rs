@@ -0,0 +1,127 @@
+// Motion trails kept in fixed ring buffers: positions are sampled at a set
+// interval of simulation time and the oldest points are overwritten, so a
+// long trail costs the same memory for the whole session
+
+use wasm_bindgen::prelude::*;
+use js_sys::Float32Array;
+
+use crate::errors::SimulationError;
+use crate::solar_system::SolarSystem;
+
+#[wasm_bindgen]
+pub struct TrailRecorder {
+    body_count: usize,
+    // Points kept per body, and simulation days between them
+    capacity: usize,
+    interval: f64,
+    // Ring of [x, y, z] scene positions, capacity slots per body
+    points: Vec<f32>,
+    // Slot the next sample goes into, and samples held
+    head: usize,
+    len: usize,
+    last_date: Option<f64>,
+}
+
+#[wasm_bindgen]
+impl TrailRecorder {
+    // Trails for body_count bodies, each up to capacity points sampled every
+    // interval_days of simulation time
+    #[wasm_bindgen(constructor)]
+    pub fn new(body_count: usize, capacity: usize, interval_days: f64) -> TrailRecorder {
+        let capacity = capacity.max(2);
+        TrailRecorder {
+            body_count,
+            capacity,
+            interval: interval_days.abs(),
+            points: vec![0.0; body_count * capacity * 3],
+            head: 0,
+            len: 0,
+            last_date: None,
+        }
+    }
+
+    // Trails for every body of a simulation, in its buffer order
+    pub fn for_solar_system(system: &SolarSystem, capacity: usize, interval_days: f64) -> TrailRecorder {
+        TrailRecorder::new(system.body_count(), capacity, interval_days)
+    }
+
+    // Sample a simulation's current positions; see record_positions
+    pub fn record(&mut self, system: &SolarSystem) -> Result<bool, JsError> {
+        self.record_positions(system.julian_date(), system.scene_positions())
+    }
+
+    // Append packed [x, y, z, ...] scene positions at a Julian date if at
+    // least one interval has passed since the last sample, returning whether
+    // they were stored. Stepping backwards past the last sample (scrubbing
+    // the date) restarts the trails, since the history no longer leads up to
+    // the bodies.
+    pub fn record_positions(&mut self, julian_date: f64, positions: &[f64]) -> Result<bool, JsError> {
+        if positions.len() != self.body_count * 3 {
+            return Err(SimulationError::invalid_argument(&format!(
+                "Expected {} packed positions, got {} values",
+                self.body_count,
+                positions.len()
+            ))
+            .into());
+        }
+        match self.last_date {
+            Some(last) if julian_date < last => self.clear(),
+            Some(last) if julian_date - last < self.interval => return Ok(false),
+            _ => {}
+        }
+        for (body, position) in positions.chunks_exact(3).enumerate() {
+            let slot = (body * self.capacity + self.head) * 3;
+            for (point, value) in self.points[slot..slot + 3].iter_mut().zip(position) {
+                *point = *value as f32;
+            }
+        }
+        self.head = (self.head + 1) % self.capacity;
+        self.len = (self.len + 1).min(self.capacity);
+        self.last_date = Some(julian_date);
+        Ok(true)
+    }
+
+    // A body's trail, oldest point first, packed [x, y, z, ...] for a line
+    // strip; None for an index out of range
+    pub fn vertices(&self, body: usize) -> Option<Float32Array> {
+        if body >= self.body_count {
+            return None;
+        }
+        let ring = &self.points[body * self.capacity * 3..(body + 1) * self.capacity * 3];
+        let start = (self.head + self.capacity - self.len) % self.capacity;
+        let mut vertices = Vec::with_capacity(self.len * 3);
+        for k in 0..self.len {
+            let slot = (start + k) % self.capacity * 3;
+            vertices.extend_from_slice(&ring[slot..slot + 3]);
+        }
+        Some(Float32Array::from(vertices.as_slice()))
+    }
+
+    // Forget every sample
+    pub fn clear(&mut self) {
+        self.head = 0;
+        self.len = 0;
+        self.last_date = None;
+    }
+
+    // Points currently held per body
+    #[wasm_bindgen(getter)]
+    pub fn point_count(&self) -> usize {
+        self.len
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn interval(&self) -> f64 {
+        self.interval
+    }
+
+    #[wasm_bindgen(setter)]
+    pub fn set_interval(&mut self, interval_days: f64) {
+        self.interval = interval_days.abs();
+    }
+}
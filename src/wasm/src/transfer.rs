@@ -0,0 +1,505 @@
+// Interplanetary transfer trajectories
+
+use wasm_bindgen::prelude::*;
+use js_sys::{Float32Array, Float64Array};
+use std::f64::consts::PI;
+
+use crate::errors::check_julian_date;
+use crate::lambert::{propagate_universal, solve_lambert, solve_lambert_revolutions};
+use crate::observer::planet_state;
+use crate::state::{osculating_elements, StateVector};
+use crate::{
+    calculate_planet_position, orbit_point, planet_elements, to_scene, OrbitalElements, Precision, Vec3, AU_KM,
+    GAUSSIAN_MEAN_MOTION, GM_SUN, PLANET_ELEMENTS,
+};
+
+// Kilometres per second in one AU/day
+pub(crate) const KM_S_PER_AU_DAY: f64 = AU_KM / 86400.0;
+
+// Polyline segments sampled along a transfer arc
+const TRAJECTORY_SEGMENTS: u32 = 128;
+
+// Largest porkchop grid edge
+const MAX_PORKCHOP_RESOLUTION: u32 = 512;
+
+// A Hohmann transfer between two planets
+#[wasm_bindgen]
+#[derive(Debug, Clone)]
+pub struct HohmannTransfer {
+    origin: String,
+    target: String,
+    departure_date: f64,
+    arrival_date: f64,
+    elements: OrbitalElements,
+    departure_delta_v: f64,
+    arrival_delta_v: f64,
+    phase_angle: f64,
+    current_phase_angle: f64,
+    // Eccentric anomaly at departure: 0 leaving from perihelion, π from aphelion
+    start_anomaly: f64,
+}
+
+#[wasm_bindgen]
+impl HohmannTransfer {
+    #[wasm_bindgen(getter)]
+    pub fn origin(&self) -> String {
+        self.origin.clone()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn target(&self) -> String {
+        self.target.clone()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn departure_date(&self) -> f64 {
+        self.departure_date
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn arrival_date(&self) -> f64 {
+        self.arrival_date
+    }
+
+    // Flight time (days), half the transfer orbit's period
+    #[wasm_bindgen(getter)]
+    pub fn time_of_flight(&self) -> f64 {
+        self.arrival_date - self.departure_date
+    }
+
+    // Heliocentric elements of the transfer ellipse
+    #[wasm_bindgen(getter)]
+    pub fn elements(&self) -> OrbitalElements {
+        self.elements
+    }
+
+    // Speed change (km/s) to leave the origin's orbit onto the transfer ellipse
+    #[wasm_bindgen(getter)]
+    pub fn departure_delta_v(&self) -> f64 {
+        self.departure_delta_v
+    }
+
+    // Speed change (km/s) to match the target's orbit on arrival
+    #[wasm_bindgen(getter)]
+    pub fn arrival_delta_v(&self) -> f64 {
+        self.arrival_delta_v
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn total_delta_v(&self) -> f64 {
+        self.departure_delta_v + self.arrival_delta_v
+    }
+
+    // Angle (degrees) the target must lead the origin by at departure to be at
+    // the arrival point on time
+    #[wasm_bindgen(getter)]
+    pub fn phase_angle(&self) -> f64 {
+        self.phase_angle
+    }
+
+    // Actual lead of the target over the origin (degrees, 0-360) at departure;
+    // the launch window is open when it matches phase_angle
+    #[wasm_bindgen(getter)]
+    pub fn current_phase_angle(&self) -> f64 {
+        self.current_phase_angle
+    }
+
+    // The transfer arc from departure to arrival as scene-space vertices packed
+    // [x, y, z, ...]
+    #[wasm_bindgen(getter)]
+    pub fn trajectory(&self) -> Float64Array {
+        let mut buffer = Vec::with_capacity((TRAJECTORY_SEGMENTS as usize + 1) * 3);
+        for i in 0..=TRAJECTORY_SEGMENTS {
+            let eccentric_anomaly = self.start_anomaly + PI * i as f64 / TRAJECTORY_SEGMENTS as f64;
+            let vertex = to_scene(orbit_point(&self.elements, eccentric_anomaly));
+            buffer.extend_from_slice(&[vertex.x, vertex.y, vertex.z]);
+        }
+        Float64Array::from(buffer.as_slice())
+    }
+
+    // Scene position of the spacecraft at a Julian date, held at the departure
+    // or arrival point outside the flight
+    pub fn position_at(&self, julian_date: f64) -> Result<Vec3, JsError> {
+        check_julian_date(julian_date)?;
+        Ok(calculate_planet_position(&self.elements, julian_date.clamp(self.departure_date, self.arrival_date)))
+    }
+}
+
+// Planet index by name
+pub(crate) fn planet_index(name: &str) -> Option<usize> {
+    PLANET_ELEMENTS.iter().position(|(n, _)| *n == name)
+}
+
+// Plan a Hohmann transfer between two planets leaving at a Julian date. The
+// ellipse starts at the origin's position, in the origin's orbital plane, and
+// reaches the target's mean distance half an orbit later; delta-v assumes
+// circular orbits. Returns None unless both names are distinct planets.
+#[wasm_bindgen]
+pub fn hohmann_transfer(origin: &str, target: &str, departure_jd: f64) -> Result<Option<HohmannTransfer>, JsError> {
+    check_julian_date(departure_jd)?;
+    Ok(plan_hohmann(origin, target, departure_jd))
+}
+
+// Hohmann transfer leaving at a date already known to be valid
+fn plan_hohmann(origin: &str, target: &str, departure_jd: f64) -> Option<HohmannTransfer> {
+    let (origin_index, target_index) = (planet_index(origin)?, planet_index(target)?);
+    if origin_index == target_index {
+        return None;
+    }
+    let departure = planet_state(origin_index, departure_jd);
+    let r1 = departure.position.length();
+    let r2 = planet_elements(target_index, departure_jd, Precision::High).a;
+    let outbound = r2 > r1;
+
+    let a = (r1 + r2) / 2.0;
+    let e = (r2 - r1).abs() / (r1 + r2);
+    let n = GAUSSIAN_MEAN_MOTION / a.powf(1.5);
+    let time_of_flight = 180.0 / n;
+
+    // Orient the ellipse's apse line through the departure point in the origin's plane
+    let normal = departure.position.cross(&departure.velocity).normalize();
+    let node = Vec3::new(-normal.y, normal.x, 0.0);
+    let node = if node.length() > 1e-12 { node.normalize() } else { Vec3::new(1.0, 0.0, 0.0) };
+    let argument_of_latitude = normal.dot(&node.cross(&departure.position)).atan2(node.dot(&departure.position));
+    let (start_anomaly, w) = if outbound { (0.0, argument_of_latitude) } else { (PI, argument_of_latitude + PI) };
+    let start_mean_anomaly = start_anomaly.to_degrees();
+    let elements = OrbitalElements {
+        a,
+        e,
+        i: normal.z.clamp(-1.0, 1.0).acos().to_degrees(),
+        omega: node.y.atan2(node.x).to_degrees().rem_euclid(360.0),
+        w: w.to_degrees().rem_euclid(360.0),
+        m0: (start_mean_anomaly - n * (departure_jd - 2451545.0)).rem_euclid(360.0),
+        n,
+    };
+
+    // Vis-viva speeds at each end against circular orbit speeds
+    let circular = |r: f64| (GM_SUN / r).sqrt();
+    let transfer = |r: f64| (GM_SUN * (2.0 / r - 1.0 / a)).sqrt();
+    let departure_delta_v = (transfer(r1) - circular(r1)).abs() * KM_S_PER_AU_DAY;
+    let arrival_delta_v = (circular(r2) - transfer(r2)).abs() * KM_S_PER_AU_DAY;
+
+    // The target covers n_target · tof while the craft sweeps 180°
+    let target_motion = GAUSSIAN_MEAN_MOTION / r2.powf(1.5);
+    let phase_angle = (180.0 - target_motion * time_of_flight).rem_euclid(360.0);
+    let longitude = |v: &Vec3| v.y.atan2(v.x).to_degrees();
+    let target_position = planet_state(target_index, departure_jd).position;
+    let current_phase_angle = (longitude(&target_position) - longitude(&departure.position)).rem_euclid(360.0);
+
+    Some(HohmannTransfer {
+        origin: origin.to_string(),
+        target: target.to_string(),
+        departure_date: departure_jd,
+        arrival_date: departure_jd + time_of_flight,
+        elements,
+        departure_delta_v,
+        arrival_delta_v,
+        phase_angle,
+        current_phase_angle,
+        start_anomaly,
+    })
+}
+
+// A transfer between two planets on given departure and arrival dates, from
+// the Lambert arc joining their positions
+#[wasm_bindgen]
+#[derive(Debug, Clone)]
+pub struct LambertTransfer {
+    origin: String,
+    target: String,
+    departure_date: f64,
+    arrival_date: f64,
+    // Spacecraft heliocentric state leaving the origin
+    departure: StateVector,
+    arrival_velocity: Vec3,
+    origin_velocity: Vec3,
+    target_velocity: Vec3,
+}
+
+#[wasm_bindgen]
+impl LambertTransfer {
+    #[wasm_bindgen(getter)]
+    pub fn origin(&self) -> String {
+        self.origin.clone()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn target(&self) -> String {
+        self.target.clone()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn departure_date(&self) -> f64 {
+        self.departure_date
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn arrival_date(&self) -> f64 {
+        self.arrival_date
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn time_of_flight(&self) -> f64 {
+        self.arrival_date - self.departure_date
+    }
+
+    // Spacecraft heliocentric velocity (AU/day) leaving the origin
+    #[wasm_bindgen(getter)]
+    pub fn departure_velocity(&self) -> Vec3 {
+        self.departure.velocity
+    }
+
+    // Spacecraft heliocentric velocity (AU/day) reaching the target
+    #[wasm_bindgen(getter)]
+    pub fn arrival_velocity(&self) -> Vec3 {
+        self.arrival_velocity
+    }
+
+    // Hyperbolic excess speed (km/s) relative to the origin at departure
+    #[wasm_bindgen(getter)]
+    pub fn departure_v_infinity(&self) -> f64 {
+        self.departure_excess().length() * KM_S_PER_AU_DAY
+    }
+
+    // Hyperbolic excess speed (km/s) relative to the target on arrival
+    #[wasm_bindgen(getter)]
+    pub fn arrival_v_infinity(&self) -> f64 {
+        self.arrival_excess().length() * KM_S_PER_AU_DAY
+    }
+
+    // Characteristic launch energy C3 (km²/s²)
+    #[wasm_bindgen(getter)]
+    pub fn c3(&self) -> f64 {
+        self.departure_v_infinity().powi(2)
+    }
+
+    // Sum of the departure and arrival excess speeds (km/s), the heliocentric
+    // cost of the transfer
+    #[wasm_bindgen(getter)]
+    pub fn total_delta_v(&self) -> f64 {
+        self.departure_v_infinity() + self.arrival_v_infinity()
+    }
+
+    // Heliocentric elements of the transfer orbit, or None if it escapes the Sun
+    #[wasm_bindgen(getter)]
+    pub fn elements(&self) -> Option<OrbitalElements> {
+        osculating_elements(&self.departure.position, &self.departure.velocity, GM_SUN, self.departure_date)
+    }
+
+    // The transfer arc from departure to arrival as scene-space vertices packed
+    // [x, y, z, ...], evenly spaced in time
+    #[wasm_bindgen(getter)]
+    pub fn trajectory(&self) -> Float64Array {
+        let mut buffer = Vec::with_capacity((TRAJECTORY_SEGMENTS as usize + 1) * 3);
+        for i in 0..=TRAJECTORY_SEGMENTS {
+            let dt = self.time_of_flight() * i as f64 / TRAJECTORY_SEGMENTS as f64;
+            let vertex = to_scene(propagate_universal(&self.departure, dt, GM_SUN).position);
+            buffer.extend_from_slice(&[vertex.x, vertex.y, vertex.z]);
+        }
+        Float64Array::from(buffer.as_slice())
+    }
+
+    // Scene position of the spacecraft at a Julian date, held at the departure
+    // or arrival point outside the flight
+    pub fn position_at(&self, julian_date: f64) -> Result<Vec3, JsError> {
+        check_julian_date(julian_date)?;
+        Ok(self.position_within(julian_date))
+    }
+}
+
+impl LambertTransfer {
+    // Scene position at a date already known to be valid
+    pub(crate) fn position_within(&self, julian_date: f64) -> Vec3 {
+        let dt = julian_date.clamp(self.departure_date, self.arrival_date) - self.departure_date;
+        to_scene(propagate_universal(&self.departure, dt, GM_SUN).position)
+    }
+
+    // Hyperbolic excess velocity (AU/day) relative to the origin at departure
+    pub(crate) fn departure_excess(&self) -> Vec3 {
+        self.departure.velocity.sub(&self.origin_velocity)
+    }
+
+    // Hyperbolic excess velocity (AU/day) relative to the target on arrival
+    pub(crate) fn arrival_excess(&self) -> Vec3 {
+        self.arrival_velocity.sub(&self.target_velocity)
+    }
+}
+
+// Plan a prograde transfer from one planet to another between two Julian
+// dates. Returns None unless both are distinct planets, arrival follows
+// departure and the Lambert arc exists.
+#[wasm_bindgen]
+pub fn lambert_transfer(origin: &str, target: &str, departure_jd: f64, arrival_jd: f64) -> Result<Option<LambertTransfer>, JsError> {
+    check_julian_date(departure_jd)?;
+    check_julian_date(arrival_jd)?;
+    Ok(plan_lambert(origin, target, departure_jd, arrival_jd))
+}
+
+// Lambert transfer between dates already known to be valid
+pub(crate) fn plan_lambert(origin: &str, target: &str, departure_jd: f64, arrival_jd: f64) -> Option<LambertTransfer> {
+    let (origin_index, target_index) = (planet_index(origin)?, planet_index(target)?);
+    if origin_index == target_index {
+        return None;
+    }
+    plan_lambert_revolutions(origin, target, departure_jd, arrival_jd, 0, false)
+}
+
+// Lambert transfer completing whole revolutions on the way (see
+// solve_lambert_revolutions), which may leave and return to the same planet
+pub(crate) fn plan_lambert_revolutions(
+    origin: &str,
+    target: &str,
+    departure_jd: f64,
+    arrival_jd: f64,
+    revolutions: u32,
+    upper: bool,
+) -> Option<LambertTransfer> {
+    let (origin_index, target_index) = (planet_index(origin)?, planet_index(target)?);
+    if arrival_jd <= departure_jd {
+        return None;
+    }
+    let start = planet_state(origin_index, departure_jd);
+    let end = planet_state(target_index, arrival_jd);
+    let tof = arrival_jd - departure_jd;
+    let (v1, v2) = solve_lambert_revolutions(&start.position, &end.position, tof, GM_SUN, revolutions, upper)?;
+    Some(LambertTransfer {
+        origin: origin.to_string(),
+        target: target.to_string(),
+        departure_date: departure_jd,
+        arrival_date: arrival_jd,
+        departure: StateVector::new(start.position, v1),
+        arrival_velocity: v2,
+        origin_velocity: start.velocity,
+        target_velocity: end.velocity,
+    })
+}
+
+// Launch energy and arrival speed over a grid of departure and arrival dates.
+// Grids are row-major with one row per arrival date, NaN where no transfer
+// exists (arrival before departure or a degenerate Lambert arc).
+#[wasm_bindgen]
+#[derive(Debug, Clone)]
+pub struct PorkchopPlot {
+    departure_dates: Vec<f64>,
+    arrival_dates: Vec<f64>,
+    c3: Vec<f32>,
+    arrival_v_infinity: Vec<f32>,
+}
+
+#[wasm_bindgen]
+impl PorkchopPlot {
+    // Departure Julian dates, one per column
+    #[wasm_bindgen(getter)]
+    pub fn departure_dates(&self) -> Float64Array {
+        Float64Array::from(self.departure_dates.as_slice())
+    }
+
+    // Arrival Julian dates, one per row
+    #[wasm_bindgen(getter)]
+    pub fn arrival_dates(&self) -> Float64Array {
+        Float64Array::from(self.arrival_dates.as_slice())
+    }
+
+    // Launch energy C3 (km²/s²)
+    #[wasm_bindgen(getter)]
+    pub fn c3(&self) -> Float32Array {
+        Float32Array::from(self.c3.as_slice())
+    }
+
+    // Hyperbolic excess speed at the target (km/s)
+    #[wasm_bindgen(getter)]
+    pub fn arrival_v_infinity(&self) -> Float32Array {
+        Float32Array::from(self.arrival_v_infinity.as_slice())
+    }
+
+    // Sum of departure and arrival excess speeds (km/s)
+    #[wasm_bindgen(getter)]
+    pub fn total_delta_v(&self) -> Float32Array {
+        let total: Vec<f32> = self.c3.iter().zip(&self.arrival_v_infinity).map(|(c3, v)| c3.sqrt() + v).collect();
+        Float32Array::from(total.as_slice())
+    }
+
+    // Departure and arrival dates of the lowest-C3 transfer, as
+    // [departure, arrival, c3], or empty if no cell is feasible
+    #[wasm_bindgen(getter)]
+    pub fn minimum_c3(&self) -> Float64Array {
+        let columns = self.departure_dates.len();
+        let best = self
+            .c3
+            .iter()
+            .enumerate()
+            .filter(|(_, c3)| !c3.is_nan())
+            .min_by(|a, b| a.1.total_cmp(b.1));
+        match best {
+            Some((cell, &c3)) => Float64Array::from(
+                &[self.departure_dates[cell % columns], self.arrival_dates[cell / columns], c3 as f64][..],
+            ),
+            None => Float64Array::new_with_length(0),
+        }
+    }
+}
+
+// Evenly spaced dates across a [start, end] range
+fn date_grid(range: &[f64], resolution: u32) -> Option<Vec<f64>> {
+    let &[start, end] = range else { return None };
+    let step = if resolution > 1 { (end - start) / (resolution - 1) as f64 } else { 0.0 };
+    Some((0..resolution).map(|i| start + step * i as f64).collect())
+}
+
+// Evaluate transfers from origin to target over a resolution × resolution grid
+// of departure and arrival dates, each range given as [start, end] Julian
+// dates, for rendering the classic porkchop heatmap. Returns None unless both
+// are distinct planets and both ranges have two entries.
+#[wasm_bindgen]
+pub fn porkchop(
+    origin: &str,
+    target: &str,
+    departure_range: &[f64],
+    arrival_range: &[f64],
+    resolution: u32,
+) -> Result<Option<PorkchopPlot>, JsError> {
+    for &julian_date in departure_range.iter().chain(arrival_range) {
+        check_julian_date(julian_date)?;
+    }
+    Ok(plan_porkchop(origin, target, departure_range, arrival_range, resolution))
+}
+
+// Porkchop grid over date ranges already known to be valid
+fn plan_porkchop(
+    origin: &str,
+    target: &str,
+    departure_range: &[f64],
+    arrival_range: &[f64],
+    resolution: u32,
+) -> Option<PorkchopPlot> {
+    let (origin_index, target_index) = (planet_index(origin)?, planet_index(target)?);
+    if origin_index == target_index {
+        return None;
+    }
+    let resolution = resolution.clamp(1, MAX_PORKCHOP_RESOLUTION);
+    let departure_dates = date_grid(departure_range, resolution)?;
+    let arrival_dates = date_grid(arrival_range, resolution)?;
+    let departures: Vec<StateVector> = departure_dates.iter().map(|&jd| planet_state(origin_index, jd)).collect();
+    let arrivals: Vec<StateVector> = arrival_dates.iter().map(|&jd| planet_state(target_index, jd)).collect();
+
+    let cells = departure_dates.len() * arrival_dates.len();
+    let mut c3 = Vec::with_capacity(cells);
+    let mut arrival_v_infinity = Vec::with_capacity(cells);
+    for (arrival_jd, end) in arrival_dates.iter().zip(&arrivals) {
+        for (departure_jd, start) in departure_dates.iter().zip(&departures) {
+            let solution = (arrival_jd > departure_jd)
+                .then(|| solve_lambert(&start.position, &end.position, arrival_jd - departure_jd, GM_SUN))
+                .flatten();
+            let (departure_excess, arrival_excess) = match solution {
+                Some((v1, v2)) => (
+                    v1.sub(&start.velocity).length() * KM_S_PER_AU_DAY,
+                    v2.sub(&end.velocity).length() * KM_S_PER_AU_DAY,
+                ),
+                None => (f64::NAN, f64::NAN),
+            };
+            c3.push((departure_excess * departure_excess) as f32);
+            arrival_v_infinity.push(arrival_excess as f32);
+        }
+    }
+    Some(PorkchopPlot { departure_dates, arrival_dates, c3, arrival_v_infinity })
+}
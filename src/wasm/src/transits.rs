@@ -0,0 +1,206 @@
+// Transits of Mercury and Venus across the Sun as seen from Earth's center:
+// contact times, greatest transit and the planet's path across the disk
+
+use wasm_bindgen::prelude::*;
+use serde::{Deserialize, Serialize};
+use js_sys::Float64Array;
+
+use crate::errors::check_date_range;
+use crate::observer::geocentric_ecliptic;
+use crate::search::{bisect, find_minima};
+use crate::units::{EARTH_RADIUS_KM, SOLAR_RADIUS_KM};
+use crate::{Vec3, AU_KM, PLANET_DATA, PLANET_ELEMENTS};
+
+// Planets that can pass in front of the Sun
+static INFERIOR_PLANETS: &[&str] = &["Mercury", "Venus"];
+
+// Sampling interval (days) for conjunctions, refinement tolerance (days) and
+// how far either side of greatest transit contacts are sought
+const SAMPLE_STEP: f64 = 1.0;
+const TIME_TOLERANCE: f64 = 1e-6;
+const CONTACT_WINDOW: f64 = 0.75;
+
+// Longest range (days) searched in one call, a century
+const MAX_SEARCH_SPAN: f64 = 36525.0;
+
+// Time step (days) between chord samples
+const CHORD_STEP: f64 = 5.0 / 1440.0;
+
+// A transit of a planet across the Sun
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[wasm_bindgen]
+pub struct Transit {
+    body: String,
+    first_contact: f64,
+    second_contact: Option<f64>,
+    maximum: f64,
+    third_contact: Option<f64>,
+    fourth_contact: f64,
+    separation: f64,
+    sun_radius: f64,
+    chord: Vec<f64>,
+}
+
+#[wasm_bindgen]
+impl Transit {
+    #[wasm_bindgen(getter)]
+    pub fn body(&self) -> String {
+        self.body.clone()
+    }
+
+    // Julian date the planet's disk first touches the Sun's limb (exterior
+    // ingress)
+    #[wasm_bindgen(getter)]
+    pub fn first_contact(&self) -> f64 {
+        self.first_contact
+    }
+
+    // Julian date the planet's disk is wholly inside the limb (interior
+    // ingress); None for a grazing transit
+    #[wasm_bindgen(getter)]
+    pub fn second_contact(&self) -> Option<f64> {
+        self.second_contact
+    }
+
+    // Julian date of least separation of the centers
+    #[wasm_bindgen(getter)]
+    pub fn maximum(&self) -> f64 {
+        self.maximum
+    }
+
+    // Interior egress; None for a grazing transit
+    #[wasm_bindgen(getter)]
+    pub fn third_contact(&self) -> Option<f64> {
+        self.third_contact
+    }
+
+    // Exterior egress
+    #[wasm_bindgen(getter)]
+    pub fn fourth_contact(&self) -> f64 {
+        self.fourth_contact
+    }
+
+    // Least angular separation of the planet from the Sun's center (arcseconds)
+    #[wasm_bindgen(getter)]
+    pub fn separation(&self) -> f64 {
+        self.separation
+    }
+
+    // Apparent radius of the Sun at greatest transit (arcseconds)
+    #[wasm_bindgen(getter)]
+    pub fn sun_radius(&self) -> f64 {
+        self.sun_radius
+    }
+
+    // The planet's path across the disk from first to fourth contact, packed
+    // [julian_date, x, y, ...] in solar radii from the Sun's center, x toward
+    // increasing ecliptic longitude and y toward ecliptic north
+    #[wasm_bindgen(getter)]
+    pub fn chord(&self) -> Float64Array {
+        Float64Array::from(self.chord.as_slice())
+    }
+}
+
+// Geocentric directions (AU) of the Sun and a planet
+fn sun_and_planet(body: &str, julian_date: f64) -> (Vec3, Vec3) {
+    let sun = geocentric_ecliptic("Sun", julian_date).unwrap_or(Vec3::new(1.0, 0.0, 0.0));
+    let planet = geocentric_ecliptic(body, julian_date).unwrap_or(sun.scale(-1.0));
+    (sun, planet)
+}
+
+// Offset of a planet from the Sun's center on the sky (radians), toward
+// increasing ecliptic longitude and toward ecliptic north
+fn sky_offset(sun: &Vec3, planet: &Vec3) -> (f64, f64) {
+    let toward_sun = sun.normalize();
+    let east = Vec3::new(0.0, 0.0, 1.0).cross(&toward_sun).normalize();
+    let north = toward_sun.cross(&east);
+    let along = planet.dot(&toward_sun);
+    (planet.dot(&east).atan2(along), planet.dot(&north).atan2(along))
+}
+
+// Angular separation of the centers less the sum (exterior) or difference
+// (interior) of the apparent radii (radians); negative while in contact
+fn contact_margin(body: &str, planet_radius: f64, exterior: bool, julian_date: f64) -> f64 {
+    let (sun, planet) = sun_and_planet(body, julian_date);
+    let separation = (sun.dot(&planet) / (sun.length() * planet.length())).clamp(-1.0, 1.0).acos();
+    let sun_radius = (SOLAR_RADIUS_KM / (sun.length() * AU_KM)).asin();
+    let planet_radius = (planet_radius / (planet.length() * AU_KM)).asin();
+    if exterior {
+        separation - sun_radius - planet_radius
+    } else {
+        separation - sun_radius + planet_radius
+    }
+}
+
+// The transit, if any, at a conjunction of a planet with the Sun
+fn transit(body: &str, planet_radius: f64, conjunction: f64) -> Option<Transit> {
+    let (sun, planet) = sun_and_planet(body, conjunction);
+    if planet.length() >= sun.length() || contact_margin(body, planet_radius, true, conjunction) >= 0.0 {
+        return None;
+    }
+
+    // Contacts are where the margins change sign either side of greatest transit
+    let (start, end) = (conjunction - CONTACT_WINDOW, conjunction + CONTACT_WINDOW);
+    let exterior = |jd: f64| contact_margin(body, planet_radius, true, jd);
+    let interior = |jd: f64| contact_margin(body, planet_radius, false, jd);
+    let interior_contacts = (interior(conjunction) < 0.0).then(|| {
+        (bisect(interior, start, conjunction, TIME_TOLERANCE), bisect(interior, conjunction, end, TIME_TOLERANCE))
+    });
+    let first_contact = bisect(exterior, start, conjunction, TIME_TOLERANCE);
+    let fourth_contact = bisect(exterior, conjunction, end, TIME_TOLERANCE);
+
+    let sun_radius = (SOLAR_RADIUS_KM / (sun.length() * AU_KM)).asin();
+    let mut chord = Vec::new();
+    let mut jd = first_contact;
+    while jd <= fourth_contact {
+        let (sun, planet) = sun_and_planet(body, jd);
+        let (x, y) = sky_offset(&sun, &planet);
+        let radius = (SOLAR_RADIUS_KM / (sun.length() * AU_KM)).asin();
+        chord.extend_from_slice(&[jd, x / radius, y / radius]);
+        jd += CHORD_STEP;
+    }
+
+    let (x, y) = sky_offset(&sun, &planet);
+    Some(Transit {
+        body: body.to_string(),
+        first_contact,
+        second_contact: interior_contacts.map(|(ingress, _)| ingress),
+        maximum: conjunction,
+        third_contact: interior_contacts.map(|(_, egress)| egress),
+        fourth_contact,
+        separation: x.hypot(y).to_degrees() * 3600.0,
+        sun_radius: sun_radius.to_degrees() * 3600.0,
+        chord,
+    })
+}
+
+// Search for transits of Mercury and Venus between two Julian dates, in time
+// order
+#[wasm_bindgen]
+pub fn transits_between(jd_start: f64, jd_end: f64) -> Result<Vec<Transit>, JsError> {
+    check_date_range(jd_start, jd_end, MAX_SEARCH_SPAN)?;
+    let mut transits: Vec<Transit> = INFERIOR_PLANETS
+        .iter()
+        .flat_map(|&body| {
+            let index = PLANET_ELEMENTS.iter().position(|(name, _)| *name == body).unwrap_or(0);
+            let planet_radius = PLANET_DATA[index].1 * EARTH_RADIUS_KM;
+            // Conjunctions are the minima of the planet's separation from the Sun
+            find_minima(
+                |jd| {
+                    let (sun, planet) = sun_and_planet(body, jd);
+                    let (x, y) = sky_offset(&sun, &planet);
+                    x.hypot(y)
+                },
+                jd_start,
+                jd_end,
+                SAMPLE_STEP,
+                TIME_TOLERANCE,
+            )
+            .into_iter()
+            .filter_map(move |conjunction| transit(body, planet_radius, conjunction))
+        })
+        .filter(|transit| transit.maximum >= jd_start && transit.maximum <= jd_end)
+        .collect();
+    transits.sort_by(|a, b| a.maximum.total_cmp(&b.maximum));
+    Ok(transits)
+}
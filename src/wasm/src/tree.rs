@@ -0,0 +1,76 @@
+// Scene-graph output: every body with its parent and parent-relative position
+
+use wasm_bindgen::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::moons::{moon_positions, PARENT_RADII};
+use crate::scene::scene_length;
+use crate::{dwarf_planet_positions, planet_positions_in_frame, sun_position, Precision, ReferenceFrame, Vec3, AU_KM};
+
+const BARYCENTER: &str = "Solar System Barycenter";
+const SUN: &str = "Sun";
+
+// A node of the body hierarchy. Roots have no parent.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[wasm_bindgen]
+pub struct BodyNode {
+    name: String,
+    parent: Option<String>,
+    position: Vec3,
+}
+
+#[wasm_bindgen]
+impl BodyNode {
+    #[wasm_bindgen(getter)]
+    pub fn name(&self) -> String {
+        self.name.clone()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn parent(&self) -> Option<String> {
+        self.parent.clone()
+    }
+
+    // Position relative to the parent, in scene units
+    #[wasm_bindgen(getter)]
+    pub fn position(&self) -> Vec3 {
+        self.position
+    }
+}
+
+fn node(name: &str, parent: Option<&str>, position: Vec3) -> BodyNode {
+    BodyNode { name: name.to_string(), parent: parent.map(str::to_string), position }
+}
+
+// Build the body hierarchy at a Julian date, parents before children: the
+// barycenter (barycentric frame only), the Sun, planets and dwarf planets, then
+// moons. Positions are true-scale scene units relative to each node's parent,
+// so transforms can be nested directly.
+#[wasm_bindgen]
+pub fn body_tree(julian_date: f64, precision: Precision, frame: ReferenceFrame) -> Result<Vec<BodyNode>, JsError> {
+    let mut nodes = Vec::new();
+    if frame == ReferenceFrame::Barycentric {
+        nodes.push(node(BARYCENTER, None, Vec3::new(0.0, 0.0, 0.0)));
+        nodes.push(node(SUN, Some(BARYCENTER), sun_position(julian_date, precision, frame)?));
+    } else {
+        nodes.push(node(SUN, None, Vec3::new(0.0, 0.0, 0.0)));
+    }
+
+    // Planet output is already relative to the frame origin; re-reference it to the Sun
+    let sun = nodes.last().map_or(Vec3::new(0.0, 0.0, 0.0), |sun| sun.position);
+    for planet in planet_positions_in_frame(julian_date, precision, frame)? {
+        nodes.push(node(&planet.name, Some(SUN), planet.position.sub(&sun)));
+    }
+    for dwarf in dwarf_planet_positions(julian_date)? {
+        nodes.push(node(&dwarf.name, Some(SUN), dwarf.position));
+    }
+
+    // Moon positions come in parent radii; convert to scene units
+    for (parent, radius_km) in PARENT_RADII {
+        let scale = scene_length(radius_km / AU_KM);
+        for moon in moon_positions(parent, julian_date)? {
+            nodes.push(node(&moon.name(), Some(parent), moon.position().scale(scale)));
+        }
+    }
+    Ok(nodes)
+}
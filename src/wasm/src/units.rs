@@ -0,0 +1,89 @@
+// Unit conversions for distances and masses, and the physical constants
+// behind them, so every module and readout converts with the same values
+
+use wasm_bindgen::prelude::*;
+
+use crate::{AU_KM, SUN_EARTH_MASS_RATIO};
+
+// Distance light travels in one minute (km)
+const LIGHT_MINUTE_KM: f64 = 299792.458 * 60.0;
+
+// Earth's equatorial radius (km), the unit of the planet radius column
+pub(crate) const EARTH_RADIUS_KM: f64 = 6378.137;
+
+// IAU nominal solar radius (km)
+pub(crate) const SOLAR_RADIUS_KM: f64 = 695700.0;
+
+// Earth's mass (kg), the unit of the planet mass column
+pub(crate) const EARTH_MASS_KG: f64 = 5.9722e24;
+
+// Earth's gravitational parameter (km³/s²), one mass column unit times G
+pub(crate) const GM_EARTH: f64 = 398600.4418;
+
+// Jupiter's mass in Earth masses
+const JUPITER_EARTH_MASS_RATIO: f64 = 317.8284;
+
+// Distance units
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DistanceUnit {
+    Au = 0,
+    MillionKm = 1,
+    LightMinutes = 2,
+    Km = 3,
+    EarthRadii = 4,
+    SolarRadii = 5,
+}
+
+impl DistanceUnit {
+    // Size of one unit (km)
+    fn km(self) -> f64 {
+        match self {
+            DistanceUnit::Au => AU_KM,
+            DistanceUnit::MillionKm => 1e6,
+            DistanceUnit::LightMinutes => LIGHT_MINUTE_KM,
+            DistanceUnit::Km => 1.0,
+            DistanceUnit::EarthRadii => EARTH_RADIUS_KM,
+            DistanceUnit::SolarRadii => SOLAR_RADIUS_KM,
+        }
+    }
+
+    // Size of 1 AU in this unit
+    pub(crate) fn per_au(self) -> f64 {
+        AU_KM / self.km()
+    }
+}
+
+// Mass units
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MassUnit {
+    EarthMasses = 0,
+    Kg = 1,
+    SolarMasses = 2,
+    JupiterMasses = 3,
+}
+
+impl MassUnit {
+    // Size of one unit (Earth masses)
+    fn earth_masses(self) -> f64 {
+        match self {
+            MassUnit::EarthMasses => 1.0,
+            MassUnit::Kg => 1.0 / EARTH_MASS_KG,
+            MassUnit::SolarMasses => SUN_EARTH_MASS_RATIO,
+            MassUnit::JupiterMasses => JUPITER_EARTH_MASS_RATIO,
+        }
+    }
+}
+
+// Convert a distance between units, e.g. AU to light-minutes
+#[wasm_bindgen]
+pub fn convert_distance(value: f64, from: DistanceUnit, to: DistanceUnit) -> f64 {
+    value * from.km() / to.km()
+}
+
+// Convert a mass between units, e.g. Earth masses to kilograms
+#[wasm_bindgen]
+pub fn convert_mass(value: f64, from: MassUnit, to: MassUnit) -> f64 {
+    value * from.earth_masses() / to.earth_masses()
+}
@@ -0,0 +1,57 @@
+// How far the ephemeris can be trusted at a date, so a date scrubber can grey
+// out unreliable spans before the user lands in them
+
+use wasm_bindgen::prelude::*;
+use js_sys::Float64Array;
+
+use crate::errors::{MAX_JULIAN_DATE, MIN_JULIAN_DATE};
+use crate::Precision;
+
+// Span where fixed J2000.0 elements stay within a few tenths of a degree of the
+// secular model, AD 1900 to 2100: beyond it the unmodelled perihelion and node
+// drift dominate
+const STANDARD_RELIABLE_START: f64 = 2415020.5;
+const STANDARD_RELIABLE_END: f64 = 2488069.5;
+
+// Quality of computed positions at a date
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EphemerisValidity {
+    // Within the span the elements were fitted or checked against
+    Reliable = 0,
+    // Computed, but drifting from the true positions; fine for a sketch of the
+    // sky, not for timing events
+    Approximate = 1,
+    // Outside 3000 BC to AD 3000, where position calls fail with EPOCH_OUT_OF_RANGE
+    Unsupported = 2,
+}
+
+// Reliable span of a precision level as (start, end) Julian dates
+fn reliable_range(precision: Precision) -> (f64, f64) {
+    match precision {
+        Precision::Standard => (STANDARD_RELIABLE_START, STANDARD_RELIABLE_END),
+        // The secular elements are fitted over their whole supported span
+        Precision::High => (MIN_JULIAN_DATE, MAX_JULIAN_DATE),
+    }
+}
+
+// Classify a Julian date for an ephemeris precision
+#[wasm_bindgen]
+pub fn ephemeris_validity(julian_date: f64, precision: Precision) -> EphemerisValidity {
+    let (start, end) = reliable_range(precision);
+    if !(MIN_JULIAN_DATE..=MAX_JULIAN_DATE).contains(&julian_date) {
+        EphemerisValidity::Unsupported
+    } else if (start..=end).contains(&julian_date) {
+        EphemerisValidity::Reliable
+    } else {
+        EphemerisValidity::Approximate
+    }
+}
+
+// Julian date bounds [reliable start, reliable end, supported start, supported
+// end] for an ephemeris precision, for shading a date scrubber
+#[wasm_bindgen]
+pub fn ephemeris_validity_range(precision: Precision) -> Float64Array {
+    let (start, end) = reliable_range(precision);
+    Float64Array::from(&[start, end, MIN_JULIAN_DATE, MAX_JULIAN_DATE][..])
+}